@@ -0,0 +1,37 @@
+//! Fixtures shared by the `bevy_seedling` benchmarks.
+
+use bevy::prelude::*;
+use bevy_seedling::{
+    SeedlingCorePlugin, platform::null::NullBackendPlugin, prelude::*, sample::AudioSample,
+};
+use std::num::{NonZeroU32, NonZeroUsize};
+
+/// Build an app with the core plugin, a null backend, and an empty graph.
+pub fn prepare_app() -> App {
+    let mut app = App::new();
+
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        SeedlingCorePlugin,
+        NullBackendPlugin,
+    ))
+    .insert_resource(AudioGraphTemplate::Empty);
+
+    app.finish();
+    app.cleanup();
+    app.update();
+
+    app
+}
+
+/// Add a short, silent mono sample.
+pub fn add_sample(app: &mut App) -> Handle<AudioSample> {
+    app.world_mut()
+        .resource_mut::<Assets<AudioSample>>()
+        .add(AudioSample::from_pcm(
+            vec![0.0; 48],
+            NonZeroUsize::new(1).unwrap(),
+            NonZeroU32::new(48000).unwrap(),
+        ))
+}
@@ -0,0 +1,56 @@
+//! Benchmarks sampler assignment for many queued samples across pools.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_seedling::prelude::*;
+use common::{add_sample, prepare_app};
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+
+const TOTAL_SAMPLES: usize = 10_000;
+const POOL_SIZE: usize = 32;
+
+#[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct BenchPool(usize);
+
+fn prepare_pools(pools: usize) -> App {
+    let mut app = prepare_app();
+
+    for i in 0..pools {
+        app.world_mut()
+            .spawn((SamplerPool(BenchPool(i)), PoolSize(POOL_SIZE..=POOL_SIZE)));
+    }
+
+    // spawn the pools' samplers
+    app.update();
+    app.update();
+
+    let sample = add_sample(&mut app);
+
+    for i in 0..TOTAL_SAMPLES {
+        app.world_mut()
+            .spawn((SamplePlayer::new(sample.clone()), BenchPool(i % pools)));
+    }
+
+    app
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("assign 10k queued samples");
+    group.sample_size(10);
+
+    for pools in [1, 4, 16] {
+        group.bench_with_input(BenchmarkId::from_parameter(pools), &pools, |b, &pools| {
+            b.iter_batched(
+                || prepare_pools(pools),
+                |mut app| app.update(),
+                BatchSize::PerIteration,
+            );
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+//! Benchmarks the cost of spawning samples and assigning them to samplers.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_seedling::{prelude::*, sample::AudioSample};
+use common::{add_sample, prepare_app};
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+
+const POOL_SIZE: usize = 256;
+
+#[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct BenchPool;
+
+fn prepare_pool() -> (App, Handle<AudioSample>) {
+    let mut app = prepare_app();
+
+    app.world_mut()
+        .spawn((SamplerPool(BenchPool), PoolSize(POOL_SIZE..=POOL_SIZE)));
+
+    // spawn the pool's samplers
+    app.update();
+    app.update();
+
+    let sample = add_sample(&mut app);
+
+    (app, sample)
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spawn and assign samples");
+
+    for spawns in [16, 64, POOL_SIZE] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(spawns),
+            &spawns,
+            |b, &spawns| {
+                b.iter_batched(
+                    prepare_pool,
+                    |(mut app, sample)| {
+                        for _ in 0..spawns {
+                            app.world_mut()
+                                .spawn((SamplePlayer::new(sample.clone()), BenchPool));
+                        }
+
+                        app.update();
+                    },
+                    BatchSize::PerIteration,
+                );
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,153 @@
+//! Benchmarks the per-frame cost of `bevy_seedling`'s core systems.
+//!
+//! With the `profiling` feature enabled, each system's spans and
+//! per-frame counters are emitted to any installed `tracing` subscriber.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_seedling::prelude::*;
+use common::{add_sample, prepare_app};
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+
+const SAMPLE_SPAWNS: usize = 1_000;
+const SPATIAL_NODES: usize = 10_000;
+const GROWTH_SAMPLES: usize = 1_000;
+const RECONFIGURED_NODES: usize = 1_000;
+
+#[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct BenchPool;
+
+fn sample_spawns(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spawn 1k samples in a frame");
+    group.sample_size(10);
+
+    group.bench_function("fixed pool", |b| {
+        b.iter_batched(
+            || {
+                let mut app = prepare_app();
+                app.world_mut()
+                    .spawn((SamplerPool(BenchPool), PoolSize(64..=64)));
+
+                // spawn the pool's samplers
+                app.update();
+                app.update();
+
+                let sample = add_sample(&mut app);
+                (app, sample)
+            },
+            |(mut app, sample)| {
+                app.world_mut().spawn_batch(
+                    (0..SAMPLE_SPAWNS).map(move |_| (SamplePlayer::new(sample.clone()), BenchPool)),
+                );
+                app.update();
+            },
+            BatchSize::PerIteration,
+        );
+    });
+
+    group.finish();
+}
+
+fn spatial_diffing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("diff 10k changed spatial nodes");
+    group.sample_size(10);
+
+    let mut app = prepare_app();
+    app.world_mut()
+        .spawn((SpatialListener3D, Transform::default()));
+    app.world_mut().spawn_batch((0..SPATIAL_NODES).map(|i| {
+        (
+            SpatialBasicNode::default(),
+            Transform::from_xyz(i as f32, 0.0, 0.0),
+        )
+    }));
+
+    // acquire the nodes and settle the baselines
+    app.update();
+    app.update();
+
+    group.bench_function("spatial basic", |b| {
+        b.iter(|| {
+            let mut transforms = app
+                .world_mut()
+                .query_filtered::<&mut Transform, With<SpatialBasicNode>>();
+            for mut transform in transforms.iter_mut(app.world_mut()) {
+                transform.translation.z += 1.0;
+            }
+
+            app.update();
+        });
+    });
+
+    group.finish();
+}
+
+fn pool_growth(c: &mut Criterion) {
+    let mut group = c.benchmark_group("grow a pool for 1k queued samples");
+    group.sample_size(10);
+
+    group.bench_function("1 to 1024 samplers", |b| {
+        b.iter_batched(
+            || {
+                let mut app = prepare_app();
+                app.world_mut()
+                    .spawn((SamplerPool(BenchPool), PoolSize(1..=1024)));
+                app.update();
+
+                let sample = add_sample(&mut app);
+                app.world_mut().spawn_batch(
+                    (0..GROWTH_SAMPLES)
+                        .map(move |_| (SamplePlayer::new(sample.clone()), BenchPool)),
+                );
+
+                app
+            },
+            |mut app| {
+                app.update();
+                app.update();
+            },
+            BatchSize::PerIteration,
+        );
+    });
+
+    group.finish();
+}
+
+fn config_rebuild(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rebuild 1k nodes on configuration change");
+    group.sample_size(10);
+
+    let mut app = prepare_app();
+    app.world_mut().spawn_batch(
+        (0..RECONFIGURED_NODES).map(|_| (VolumeNode::default(), VolumeNodeConfig::default())),
+    );
+    app.update();
+
+    group.bench_function("volume", |b| {
+        let mut mono = false;
+
+        b.iter(|| {
+            mono = !mono;
+            let channels = NonZeroChannelCount::new(if mono { 1 } else { 2 }).unwrap();
+
+            let mut configs = app.world_mut().query::<&mut VolumeNodeConfig>();
+            for mut config in configs.iter_mut(app.world_mut()) {
+                config.channels = channels;
+            }
+
+            app.update();
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    sample_spawns,
+    spatial_diffing,
+    pool_growth,
+    config_rebuild
+);
+criterion_main!(benches);
@@ -0,0 +1,134 @@
+//! Second-order filters.
+
+use core::num::NonZeroU32;
+
+/// The Butterworth quality factor.
+///
+/// Cascading two Butterworth filters produces a Linkwitz-Riley filter.
+pub const BUTTERWORTH_Q: f32 = core::f32::consts::FRAC_1_SQRT_2;
+
+/// Normalized biquad coefficients.
+///
+/// The design functions use a Butterworth [`BUTTERWORTH_Q`],
+/// clamping the cutoff to the range `[10.0, nyquist * 0.99]`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct BiquadCoeffs {
+    /// The current input's feedforward coefficient.
+    pub b0: f32,
+    /// The previous input's feedforward coefficient.
+    pub b1: f32,
+    /// The second previous input's feedforward coefficient.
+    pub b2: f32,
+    /// The previous output's feedback coefficient.
+    pub a1: f32,
+    /// The second previous output's feedback coefficient.
+    pub a2: f32,
+}
+
+impl BiquadCoeffs {
+    /// Design a Butterworth low-pass filter.
+    pub fn lowpass(cutoff_hz: f32, sample_rate: NonZeroU32) -> Self {
+        let (cos, alpha) = Self::prewarp(cutoff_hz, sample_rate);
+        let b1 = 1.0 - cos;
+        Self::normalize(b1 * 0.5, b1, b1 * 0.5, cos, alpha)
+    }
+
+    /// Design a Butterworth high-pass filter.
+    pub fn highpass(cutoff_hz: f32, sample_rate: NonZeroU32) -> Self {
+        let (cos, alpha) = Self::prewarp(cutoff_hz, sample_rate);
+        let b1 = -(1.0 + cos);
+        Self::normalize(-b1 * 0.5, b1, -b1 * 0.5, cos, alpha)
+    }
+
+    /// Design an all-pass filter centered on `cutoff_hz`.
+    pub fn allpass(cutoff_hz: f32, sample_rate: NonZeroU32) -> Self {
+        let (cos, alpha) = Self::prewarp(cutoff_hz, sample_rate);
+        Self::normalize(1.0 - alpha, -2.0 * cos, 1.0 + alpha, cos, alpha)
+    }
+
+    fn prewarp(cutoff_hz: f32, sample_rate: NonZeroU32) -> (f32, f32) {
+        let nyquist = sample_rate.get() as f32 * 0.5;
+        let cutoff_hz = cutoff_hz.clamp(10.0, nyquist * 0.99);
+        let omega = core::f32::consts::TAU * cutoff_hz / sample_rate.get() as f32;
+
+        (libm::cosf(omega), libm::sinf(omega) / (2.0 * BUTTERWORTH_Q))
+    }
+
+    fn normalize(b0: f32, b1: f32, b2: f32, cos: f32, alpha: f32) -> Self {
+        let a0 = 1.0 + alpha;
+
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: -2.0 * cos / a0,
+            a2: (1.0 - alpha) / a0,
+        }
+    }
+}
+
+/// A transposed direct form II biquad.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Biquad {
+    z1: f32,
+    z2: f32,
+}
+
+impl Biquad {
+    /// Process a single sample.
+    #[inline]
+    pub fn process(&mut self, input: f32, c: &BiquadCoeffs) -> f32 {
+        let output = c.b0 * input + self.z1;
+        self.z1 = c.b1 * input - c.a1 * output + self.z2;
+        self.z2 = c.b2 * input - c.a2 * output;
+        output
+    }
+}
+
+/// A fourth-order Linkwitz-Riley filter.
+///
+/// This cascades two [`Biquad`]s sharing the same Butterworth coefficients.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LinkwitzRiley([Biquad; 2]);
+
+impl LinkwitzRiley {
+    /// Process a single sample.
+    #[inline]
+    pub fn process(&mut self, input: f32, c: &BiquadCoeffs) -> f32 {
+        let first = self.0[0].process(input, c);
+        self.0[1].process(first, c)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_RATE: NonZeroU32 = NonZeroU32::new(48000).unwrap();
+
+    #[test]
+    fn test_lowpass_passes_dc() {
+        let coeffs = BiquadCoeffs::lowpass(1000.0, SAMPLE_RATE);
+        let mut filter = LinkwitzRiley::default();
+
+        let mut output = 0.0;
+        for _ in 0..48000 {
+            output = filter.process(1.0, &coeffs);
+        }
+
+        assert!((output - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_highpass_blocks_dc() {
+        let coeffs = BiquadCoeffs::highpass(1000.0, SAMPLE_RATE);
+        let mut filter = LinkwitzRiley::default();
+
+        let mut output = 1.0;
+        for _ in 0..48000 {
+            output = filter.process(1.0, &coeffs);
+        }
+
+        assert!(output.abs() < 1e-3);
+    }
+}
@@ -0,0 +1,127 @@
+//! A fractional delay line.
+
+use crate::interpolate::lerp;
+use alloc::{vec, vec::Vec};
+
+/// A circular delay line with a linearly interpolated read head.
+#[derive(Debug, Clone)]
+pub struct DelayLine {
+    buffer: Vec<f32>,
+    write_head: usize,
+
+    /// The read head is a fractional offset from the write head.
+    ///
+    /// The larger this value, the further back in time we read.
+    read_head: f32,
+}
+
+impl DelayLine {
+    /// Create a new [`DelayLine`] holding `size` samples.
+    ///
+    /// The buffer always holds at least one sample.
+    pub fn new(size: usize) -> Self {
+        Self {
+            buffer: vec![0.0; size.max(1)],
+            write_head: 0,
+            read_head: 0.0,
+        }
+    }
+
+    /// The number of samples the buffer holds.
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns `true` if the buffer holds no samples.
+    ///
+    /// Since the buffer always holds at least one sample,
+    /// this is always `false`.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Resize the buffer, keeping the heads in bounds.
+    pub fn resize(&mut self, new_size: usize) {
+        self.buffer.resize(new_size.max(1), 0.0);
+        self.write_head %= self.buffer.len();
+        self.read_head %= self.buffer.len() as f32;
+    }
+
+    /// Write a sample, advancing the write head.
+    pub fn write(&mut self, sample: f32) {
+        self.buffer[self.write_head] = sample;
+        self.write_head = (self.write_head + 1) % self.buffer.len();
+    }
+
+    /// Set the sample offset for the read head.
+    ///
+    /// The larger this value, the further back in time we read.
+    /// `delay` is epxressed as a ratio in the range [0, 1].
+    pub fn set_read_head(&mut self, delay: f32) {
+        let max = self.len().saturating_sub(1) as f32;
+        self.read_head = delay.clamp(0.0, 1.0) * max;
+    }
+
+    /// Read from the buffer at the read head, performing linear interpolation.
+    pub fn read(&self) -> f32 {
+        self.read_at(self.read_head)
+    }
+
+    /// Read `delay` samples behind the most recent write,
+    /// performing linear interpolation.
+    ///
+    /// Unlike [`DelayLine::set_read_head`], `delay` is expressed in samples,
+    /// which is convenient for modulated effects like chorus and flanging.
+    /// It's clamped to the buffer's length.
+    pub fn read_at(&self, delay: f32) -> f32 {
+        let max = self.len().saturating_sub(1) as f32;
+        let delay = delay.clamp(0.0, max);
+
+        let float_len = self.buffer.len() as f32;
+        let read_position = float_len + self.write_head as f32 - 1.0 - delay;
+
+        let index_a = read_position as usize % self.buffer.len();
+        let index_b = (index_a + 1) % self.buffer.len();
+
+        let fract = read_position - libm::truncf(read_position);
+
+        lerp(self.buffer[index_a], self.buffer[index_b], fract)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_oob() {
+        let mut delay = DelayLine::new(31);
+        delay.set_read_head(1.2271447e-13);
+
+        delay.write(0.5);
+        delay.read();
+    }
+
+    #[test]
+    fn test_delay() {
+        let mut delay = DelayLine::new(4);
+        delay.set_read_head(1.0);
+
+        let mut output = [0.0; 6];
+        for (i, out) in output.iter_mut().enumerate() {
+            delay.write(i as f32 + 1.0);
+            *out = delay.read();
+        }
+
+        assert_eq!(output, [0.0, 0.0, 0.0, 1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_fractional_read() {
+        let mut delay = DelayLine::new(4);
+        delay.write(1.0);
+        delay.write(2.0);
+
+        assert_eq!(delay.read_at(0.5), 1.5);
+    }
+}
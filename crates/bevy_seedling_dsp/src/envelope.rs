@@ -0,0 +1,170 @@
+//! Peak envelope following.
+
+use core::num::NonZeroU32;
+
+/// Calculate a smoothing pole for a one-pole envelope segment
+/// that takes roughly `seconds` to settle.
+///
+/// A time of zero or less produces a pole of `0.0`,
+/// which makes the segment respond instantly.
+pub fn envelope_pole(seconds: f32, sample_rate: NonZeroU32) -> f32 {
+    if seconds <= 0.0 {
+        return 0.0;
+    }
+
+    libm::expf(-1.0 / (seconds * sample_rate.get() as f32))
+}
+
+/// Attack and release poles for an [`EnvelopeFollower`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnvelopeCoeffs {
+    /// The pole used while the envelope is rising.
+    pub attack: f32,
+    /// The pole used while the envelope is falling.
+    pub release: f32,
+}
+
+impl EnvelopeCoeffs {
+    /// Calculate coefficients for the given attack and release times in seconds.
+    pub fn new(attack: f32, release: f32, sample_rate: NonZeroU32) -> Self {
+        Self {
+            attack: envelope_pole(attack, sample_rate),
+            release: envelope_pole(release, sample_rate),
+        }
+    }
+}
+
+/// A peak envelope follower with separate attack and release times.
+///
+/// Multichannel input is followed by its loudest channel.
+///
+/// ```
+/// # use bevy_seedling_dsp::envelope::{EnvelopeCoeffs, EnvelopeFollower};
+/// # use core::num::NonZeroU32;
+/// let coeffs = EnvelopeCoeffs::new(0.005, 0.15, NonZeroU32::new(48000).unwrap());
+/// let mut follower = EnvelopeFollower::default();
+///
+/// let left = [0.5; 256];
+/// let right = [-0.25; 256];
+/// let envelope = follower.process_block(&[&left, &right], 256, &coeffs);
+/// assert!(envelope > 0.0 && envelope <= 0.5);
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EnvelopeFollower {
+    envelope: f32,
+}
+
+impl EnvelopeFollower {
+    /// Process a single amplitude, returning the new envelope.
+    ///
+    /// Non-finite inputs are ignored.
+    #[inline]
+    pub fn process(&mut self, input: f32, coeffs: &EnvelopeCoeffs) -> f32 {
+        let amplitude = if input.is_finite() {
+            libm::fabsf(input)
+        } else {
+            0.0
+        };
+
+        let pole = if amplitude > self.envelope {
+            coeffs.attack
+        } else {
+            coeffs.release
+        };
+
+        self.envelope = amplitude + pole * (self.envelope - amplitude);
+        self.envelope
+    }
+
+    /// Follow the loudest of `inputs` for `frames` frames,
+    /// returning the final envelope.
+    pub fn process_block(
+        &mut self,
+        inputs: &[&[f32]],
+        frames: usize,
+        coeffs: &EnvelopeCoeffs,
+    ) -> f32 {
+        for i in 0..frames {
+            let peak = inputs
+                .iter()
+                .map(|input| input[i])
+                .filter(|x| x.is_finite())
+                .fold(0f32, |peak, x| peak.max(libm::fabsf(x)));
+
+            self.process(peak, coeffs);
+        }
+
+        self.envelope
+    }
+
+    /// Release the envelope over `frames` frames of silence,
+    /// returning the final envelope.
+    pub fn process_silence(&mut self, frames: usize, coeffs: &EnvelopeCoeffs) -> f32 {
+        self.envelope *= libm::powf(coeffs.release, frames as f32);
+        self.envelope
+    }
+
+    /// The current envelope as a linear amplitude.
+    pub fn envelope(&self) -> f32 {
+        self.envelope
+    }
+
+    /// Reset the envelope to silence.
+    pub fn reset(&mut self) {
+        self.envelope = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_RATE: NonZeroU32 = NonZeroU32::new(48000).unwrap();
+
+    #[test]
+    fn test_instant_segments() {
+        let coeffs = EnvelopeCoeffs::new(0.0, 0.0, SAMPLE_RATE);
+        let mut follower = EnvelopeFollower::default();
+
+        assert_eq!(follower.process(-0.5, &coeffs), 0.5);
+        assert_eq!(follower.process(0.25, &coeffs), 0.25);
+    }
+
+    #[test]
+    fn test_follows_loudest_channel() {
+        let coeffs = EnvelopeCoeffs::new(0.001, 0.1, SAMPLE_RATE);
+        let mut follower = EnvelopeFollower::default();
+
+        let quiet = [0.1; 4800];
+        let loud = [-0.8; 4800];
+        let envelope = follower.process_block(&[&quiet, &loud], 4800, &coeffs);
+
+        assert!((envelope - 0.8).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_silence_matches_zeros() {
+        let coeffs = EnvelopeCoeffs::new(0.001, 0.05, SAMPLE_RATE);
+        let mut a = EnvelopeFollower::default();
+        let mut b = EnvelopeFollower::default();
+
+        a.process(1.0, &coeffs);
+        b.process(1.0, &coeffs);
+
+        let zeros = [0.0; 512];
+        let processed = a.process_block(&[&zeros], 512, &coeffs);
+        let released = b.process_silence(512, &coeffs);
+
+        assert!((processed - released).abs() < 1e-4);
+        assert!(released < 1.0);
+    }
+
+    #[test]
+    fn test_ignores_non_finite() {
+        let coeffs = EnvelopeCoeffs::new(0.0, 0.0, SAMPLE_RATE);
+        let mut follower = EnvelopeFollower::default();
+
+        assert_eq!(follower.process(f32::NAN, &coeffs), 0.0);
+        assert_eq!(follower.process(f32::INFINITY, &coeffs), 0.0);
+    }
+}
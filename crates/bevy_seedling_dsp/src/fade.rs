@@ -0,0 +1,104 @@
+//! Crossfade and declicking helpers.
+
+/// Calculate equal-power crossfade gains for position `t` in the range `[0, 1]`.
+///
+/// Returns the gains for the outgoing and incoming signals, respectively.
+/// Equal-power fades keep the perceived loudness constant when
+/// crossfading uncorrelated signals.
+#[inline]
+pub fn equal_power(t: f32) -> (f32, f32) {
+    let angle = t.clamp(0.0, 1.0) * core::f32::consts::FRAC_PI_2;
+    (libm::cosf(angle), libm::sinf(angle))
+}
+
+/// Calculate linear crossfade gains for position `t` in the range `[0, 1]`.
+///
+/// Returns the gains for the outgoing and incoming signals, respectively.
+/// Linear fades keep the amplitude constant when crossfading
+/// correlated signals, such as two reads from the same buffer.
+#[inline]
+pub fn linear(t: f32) -> (f32, f32) {
+    let t = t.clamp(0.0, 1.0);
+    (1.0 - t, t)
+}
+
+/// A linear gain ramp for removing clicks from sudden changes.
+///
+/// Abruptly starting, stopping, or muting a signal produces an
+/// audible click. Multiplying the signal by a [`Declicker`]'s
+/// gain spreads the change over a short number of frames.
+///
+/// ```
+/// # use bevy_seedling_dsp::fade::Declicker;
+/// let mut declicker = Declicker::new(0.0);
+/// declicker.fade_to(1.0, 4);
+///
+/// let gains: Vec<f32> = (0..5).map(|_| declicker.next_gain()).collect();
+/// assert_eq!(gains, [0.25, 0.5, 0.75, 1.0, 1.0]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Declicker {
+    gain: f32,
+    target: f32,
+    step: f32,
+    remaining: u32,
+}
+
+impl Default for Declicker {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl Declicker {
+    /// Create a new [`Declicker`] resting at `gain`.
+    pub fn new(gain: f32) -> Self {
+        Self {
+            gain,
+            target: gain,
+            step: 0.0,
+            remaining: 0,
+        }
+    }
+
+    /// Begin ramping toward `target` over `frames` frames.
+    ///
+    /// If `frames` is zero, the gain jumps immediately.
+    pub fn fade_to(&mut self, target: f32, frames: u32) {
+        self.target = target;
+
+        if frames == 0 {
+            self.gain = target;
+            self.remaining = 0;
+            return;
+        }
+
+        self.step = (target - self.gain) / frames as f32;
+        self.remaining = frames;
+    }
+
+    /// Advance the ramp by one frame, returning the new gain.
+    #[inline]
+    pub fn next_gain(&mut self) -> f32 {
+        if self.remaining > 0 {
+            self.remaining -= 1;
+            self.gain = if self.remaining == 0 {
+                self.target
+            } else {
+                self.gain + self.step
+            };
+        }
+
+        self.gain
+    }
+
+    /// The current gain.
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Returns `true` if the ramp has reached its target.
+    pub fn is_settled(&self) -> bool {
+        self.remaining == 0
+    }
+}
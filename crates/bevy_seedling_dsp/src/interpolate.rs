@@ -0,0 +1,43 @@
+//! Fractional sample interpolation.
+
+/// Linearly interpolate between `a` and `b`.
+///
+/// `t` is expected to be in the range `[0, 1]`.
+#[inline]
+pub fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + t * (b - a)
+}
+
+/// Interpolate between `x1` and `x2` with a four-point,
+/// third-order Hermite spline.
+///
+/// `x0` and `x3` are the samples on either side. This is smoother
+/// than [`lerp`], which can matter for modulated delays where
+/// linear interpolation audibly dulls the high end.
+#[inline]
+pub fn hermite(x0: f32, x1: f32, x2: f32, x3: f32, t: f32) -> f32 {
+    let c1 = 0.5 * (x2 - x0);
+    let c2 = x0 - 2.5 * x1 + 2.0 * x2 - 0.5 * x3;
+    let c3 = 0.5 * (x3 - x0) + 1.5 * (x1 - x2);
+
+    ((c3 * t + c2) * t + c1) * t + x1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_endpoints() {
+        assert_eq!(lerp(1.0, 3.0, 0.0), 1.0);
+        assert_eq!(lerp(1.0, 3.0, 1.0), 3.0);
+        assert_eq!(hermite(0.0, 1.0, 3.0, 4.0, 0.0), 1.0);
+        assert_eq!(hermite(0.0, 1.0, 3.0, 4.0, 1.0), 3.0);
+    }
+
+    #[test]
+    fn test_hermite_line() {
+        // A straight line should be reproduced exactly.
+        assert!((hermite(0.0, 1.0, 2.0, 3.0, 0.25) - 1.25).abs() < 1e-6);
+    }
+}
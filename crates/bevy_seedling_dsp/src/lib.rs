@@ -0,0 +1,57 @@
+//! `no_std` DSP utilities shared by
+//! [bevy_seedling](https://github.com/corvusprudens/bevy_seedling)'s
+//! built-in audio nodes.
+//!
+//! This crate depends on neither Bevy nor the standard library,
+//! so custom processors can reuse these building blocks
+//! and unit test them without spinning up an `App`.
+//! `bevy_seedling` re-exports it as `bevy_seedling::dsp`.
+//!
+//! All utilities process one sample at a time, keeping their state
+//! separate from their coefficients. Coefficients should be calculated
+//! when parameters or the sample rate change, not in the processing loop.
+//!
+//! ```
+//! use bevy_seedling_dsp::{DelayLine, fade::Declicker};
+//!
+//! // A simple feedback echo that fades in when started.
+//! let mut delay = DelayLine::new(4800);
+//! let mut declicker = Declicker::new(0.0);
+//! declicker.fade_to(1.0, 64);
+//!
+//! let mut process = |input: f32| {
+//!     let echo = delay.read_at(4799.0);
+//!     delay.write(input + echo * 0.5);
+//!     (input + echo) * declicker.next_gain()
+//! };
+//!
+//! process(1.0);
+//! ```
+//!
+//! ## Modules
+//!
+//! - [`delay_line`]: a circular buffer with fractional reads.
+//! - [`interpolate`]: linear and Hermite sample interpolation.
+//! - [`one_pole`]: cheap first-order filters, including a DC blocker.
+//! - [`biquad`]: second-order filters and Linkwitz-Riley crossovers.
+//! - [`fade`]: crossfade curves and a declicking gain ramp.
+//! - [`envelope`]: a peak envelope follower with attack and release.
+
+#![no_std]
+#![warn(missing_debug_implementations)]
+#![warn(missing_docs)]
+
+extern crate alloc;
+
+pub mod biquad;
+pub mod delay_line;
+pub mod envelope;
+pub mod fade;
+pub mod interpolate;
+pub mod one_pole;
+
+pub use biquad::{Biquad, BiquadCoeffs, LinkwitzRiley};
+pub use delay_line::DelayLine;
+pub use envelope::EnvelopeFollower;
+pub use fade::Declicker;
+pub use one_pole::{DcBlocker, OnePoleHighpass, OnePoleLowpass};
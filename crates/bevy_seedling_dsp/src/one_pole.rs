@@ -0,0 +1,124 @@
+//! One-pole filters.
+
+use core::num::NonZeroU32;
+
+/// Calculate a one-pole filter's pole position for `cutoff_hz`.
+///
+/// The cutoff is clamped to the Nyquist frequency.
+pub fn pole(cutoff_hz: f32, sample_rate: NonZeroU32) -> f32 {
+    let cutoff_hz = cutoff_hz.clamp(0.0, sample_rate.get() as f32 * 0.5);
+    libm::expf(-core::f32::consts::TAU * cutoff_hz / sample_rate.get() as f32)
+}
+
+/// A one-pole high-pass filter that removes DC offsets.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DcBlocker {
+    x1: f32,
+    y1: f32,
+}
+
+impl DcBlocker {
+    /// Process a single sample with the given [`pole`].
+    #[inline]
+    pub fn process(&mut self, input: f32, pole: f32) -> f32 {
+        let output = input - self.x1 + pole * self.y1;
+        self.x1 = input;
+        self.y1 = output;
+        output
+    }
+
+    /// Returns `true` if the filter's output has decayed to silence.
+    pub fn is_settled(&self) -> bool {
+        libm::fabsf(self.y1) < f32::EPSILON
+    }
+}
+
+/// Calculate a one-pole low-pass coefficient for `cutoff_hz`.
+///
+/// The cutoff is clamped to the Nyquist frequency. A cutoff of
+/// zero or less produces a coefficient of `0.0`, which holds
+/// the filter's state in place.
+pub fn lowpass_coeff(cutoff_hz: f32, sample_rate: NonZeroU32) -> f32 {
+    if cutoff_hz <= 0.0 {
+        return 0.0;
+    }
+
+    1.0 - pole(cutoff_hz, sample_rate)
+}
+
+/// A one-pole low-pass filter.
+///
+/// ```
+/// # use bevy_seedling_dsp::one_pole::{OnePoleLowpass, lowpass_coeff};
+/// # use core::num::NonZeroU32;
+/// let coeff = lowpass_coeff(200.0, NonZeroU32::new(48000).unwrap());
+/// let mut filter = OnePoleLowpass::default();
+///
+/// let output = filter.process(1.0, coeff);
+/// assert!(output > 0.0 && output < 1.0);
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OnePoleLowpass {
+    z1: f32,
+}
+
+impl OnePoleLowpass {
+    /// Process a single sample with the given [`lowpass_coeff`].
+    #[inline]
+    pub fn process(&mut self, input: f32, coeff: f32) -> f32 {
+        self.z1 += coeff * (input - self.z1);
+        self.z1
+    }
+
+    /// Reset the filter's state.
+    pub fn reset(&mut self) {
+        self.z1 = 0.0;
+    }
+}
+
+/// A one-pole high-pass filter.
+///
+/// This subtracts a [`OnePoleLowpass`] from the input,
+/// so it shares the same [`lowpass_coeff`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OnePoleHighpass {
+    lowpass: OnePoleLowpass,
+}
+
+impl OnePoleHighpass {
+    /// Process a single sample with the given [`lowpass_coeff`].
+    #[inline]
+    pub fn process(&mut self, input: f32, coeff: f32) -> f32 {
+        input - self.lowpass.process(input, coeff)
+    }
+
+    /// Reset the filter's state.
+    pub fn reset(&mut self) {
+        self.lowpass.reset();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_RATE: NonZeroU32 = NonZeroU32::new(48000).unwrap();
+
+    #[test]
+    fn test_disabled_lowpass() {
+        assert_eq!(lowpass_coeff(0.0, SAMPLE_RATE), 0.0);
+    }
+
+    #[test]
+    fn test_highpass_blocks_dc() {
+        let coeff = lowpass_coeff(100.0, SAMPLE_RATE);
+        let mut filter = OnePoleHighpass::default();
+
+        let mut output = 1.0;
+        for _ in 0..48000 {
+            output = filter.process(1.0, coeff);
+        }
+
+        assert!(output.abs() < 1e-3);
+    }
+}
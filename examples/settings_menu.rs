@@ -1,16 +1,15 @@
 //! This example demonstrates a simple master, music, and sfx setup.
 //!
-//! With the default [`GraphConfiguration`], we have everything we need
-//! to create the typical audio settings menu.
+//! With the default [`AudioGraphTemplate`], we have everything we need
+//! to create the typical audio settings menu. The [`AudioSettingsPlugin`]
+//! provides the backend, so this example only supplies the UI.
 //!
 //! The `Music` pool, `Sfx` pool, and `DefaultPool` are all routed to the `MainBus` node.
-//! Since each pool has a `VolumeNode`, we can control them all individually. And,
-//! since they're all routed to the `MainBus`, we can also set the volume of all three
-//! at once.
+//! The plugin maps each [`VolumeChannel`] onto the matching node, so we can
+//! control them all individually, or all three at once with the master volume.
 //!
-//! You can see this in action in the knob observers: to set the master volume,
-//! we adjust the `MainBus` node, and to set the individual volumes, we adjust the
-//! pool nodes.
+//! You can see this in action in the knob observers: each button simply
+//! triggers [`AdjustVolume`] for its channel.
 
 #![allow(clippy::type_complexity)]
 use bevy::{
@@ -22,23 +21,17 @@ use bevy_seedling::prelude::*;
 
 fn main() {
     let mut app = App::new();
-    app.add_plugins((DefaultPlugins, SeedlingPlugins))
+    app.add_plugins((DefaultPlugins, SeedlingPlugins, AudioSettingsPlugin))
         .add_systems(Startup, setup)
-        .add_systems(
-            Update,
-            (
-                update_music_volume_label,
-                update_master_volume_label,
-                update_sfx_volume_label,
-                button_hover,
-            ),
-        )
+        .add_systems(Update, (update_volume_labels, button_hover))
         .run();
 }
 
-fn setup(mut master: Single<&mut VolumeNode, With<MainBus>>, mut commands: Commands) {
-    // Let's reduce the master volume a bit.
-    master.volume = CONVERTER.perceptual_to_volume(0.7);
+fn setup(mut settings: ResMut<AudioSettings>, mut commands: Commands) {
+    // Let's reduce the master volume a bit, and allow
+    // each channel to be boosted up to 200%.
+    settings.max_perceptual = MAX_VOLUME;
+    settings.set_perceptual(VolumeChannel::Master, 0.7);
 
     commands.spawn(Camera2d);
 
@@ -102,82 +95,55 @@ fn play_sfx(_: On<Pointer<Click>>, mut commands: Commands, server: Res<AssetServ
 
 //  ============================ Control Knob Observers ============================ //
 
-const CONVERTER: PerceptualVolume = PerceptualVolume::new();
-
-const MIN_VOLUME: f32 = 0.0;
 const MAX_VOLUME: f32 = 2.0;
 const STEP: f32 = 0.1;
 
-fn increment_volume(volume: Volume) -> Volume {
-    let perceptual = CONVERTER.volume_to_perceptual(volume);
-    let new_perceptual = (perceptual + STEP).min(MAX_VOLUME);
-    CONVERTER.perceptual_to_volume(new_perceptual)
-}
-
-fn decrement_volume(volume: Volume) -> Volume {
-    let perceptual = CONVERTER.volume_to_perceptual(volume);
-    let new_perceptual = (perceptual - STEP).max(MIN_VOLUME);
-    CONVERTER.perceptual_to_volume(new_perceptual)
+fn lower_master(_: On<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(AdjustVolume::new(VolumeChannel::Master, -STEP));
 }
 
-// Master
-fn lower_master(_: On<Pointer<Click>>, mut master: Single<&mut VolumeNode, With<MainBus>>) {
-    master.volume = decrement_volume(master.volume);
+fn raise_master(_: On<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(AdjustVolume::new(VolumeChannel::Master, STEP));
 }
 
-fn raise_master(_: On<Pointer<Click>>, mut master: Single<&mut VolumeNode, With<MainBus>>) {
-    master.volume = increment_volume(master.volume);
+fn lower_music(_: On<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(AdjustVolume::new(VolumeChannel::Music, -STEP));
 }
 
-fn update_master_volume_label(
-    mut label: Single<&mut Text, With<MasterVolumeLabel>>,
-    master: Single<&VolumeNode, (With<MainBus>, Changed<VolumeNode>)>,
-) {
-    let percent = CONVERTER.volume_to_perceptual(master.volume) * 100.0;
-    let text = format!("{}%", percent.round());
-    label.0 = text;
+fn raise_music(_: On<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(AdjustVolume::new(VolumeChannel::Music, STEP));
 }
 
-// Music
-fn lower_music(
-    _: On<Pointer<Click>>,
-    mut music: Single<&mut VolumeNode, With<SamplerPool<MusicPool>>>,
-) {
-    music.volume = decrement_volume(music.volume);
+fn lower_sfx(_: On<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(AdjustVolume::new(VolumeChannel::Sfx, -STEP));
 }
 
-fn raise_music(
-    _: On<Pointer<Click>>,
-    mut music: Single<&mut VolumeNode, With<SamplerPool<MusicPool>>>,
-) {
-    music.volume = increment_volume(music.volume);
+fn raise_sfx(_: On<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(AdjustVolume::new(VolumeChannel::Sfx, STEP));
 }
 
-fn update_music_volume_label(
-    mut label: Single<&mut Text, With<MusicVolumeLabel>>,
-    music: Single<&VolumeNode, (With<SamplerPool<MusicPool>>, Changed<VolumeNode>)>,
+fn update_volume_labels(
+    settings: Res<AudioSettings>,
+    mut master: Single<&mut Text, With<MasterVolumeLabel>>,
+    mut music: Single<&mut Text, (With<MusicVolumeLabel>, Without<MasterVolumeLabel>)>,
+    mut sfx: Single<
+        &mut Text,
+        (
+            With<SfxVolumeLabel>,
+            Without<MasterVolumeLabel>,
+            Without<MusicVolumeLabel>,
+        ),
+    >,
 ) {
-    let percent = CONVERTER.volume_to_perceptual(music.volume) * 100.0;
-    let text = format!("{}%", percent.round());
-    label.0 = text;
-}
-
-// SFX
-fn lower_sfx(_: On<Pointer<Click>>, mut sfx: Single<&mut VolumeNode, With<SoundEffectsBus>>) {
-    sfx.volume = decrement_volume(sfx.volume);
-}
+    if !settings.is_changed() {
+        return;
+    }
 
-fn raise_sfx(_: On<Pointer<Click>>, mut sfx: Single<&mut VolumeNode, With<SoundEffectsBus>>) {
-    sfx.volume = increment_volume(sfx.volume);
-}
+    let percent = |channel| format!("{}%", (settings.perceptual(channel) * 100.0).round());
 
-fn update_sfx_volume_label(
-    mut label: Single<&mut Text, With<SfxVolumeLabel>>,
-    sfx: Single<&VolumeNode, (With<SoundEffectsBus>, Changed<VolumeNode>)>,
-) {
-    let percent = CONVERTER.volume_to_perceptual(sfx.volume) * 100.0;
-    let text = format!("{}%", percent.round());
-    label.0 = text;
+    master.0 = percent(VolumeChannel::Master);
+    music.0 = percent(VolumeChannel::Music);
+    sfx.0 = percent(VolumeChannel::Sfx);
 }
 
 //  ============================ UI Code ============================ //
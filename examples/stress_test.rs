@@ -0,0 +1,210 @@
+//! This example hammers `bevy_seedling` with random operations:
+//! spawning and despawning spatial samples, churning the output
+//! device, suspending and resuming the stream, and changing node
+//! configurations.
+//!
+//! It runs headless on the null backend, asserting that nothing
+//! panics and that entities and graph edges don't accumulate.
+//!
+//! ```sh
+//! cargo run --example stress_test -- <iterations> <seed>
+//! ```
+
+use bevy::{log::LogPlugin, prelude::*};
+use bevy_seedling::{
+    SeedlingCorePlugin,
+    platform::{
+        AudioStreamConfig,
+        null::{NullBackendPlugin, NullConfig},
+    },
+    prelude::*,
+    sample::AudioSample,
+};
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    time::{Duration, Instant},
+};
+
+/// The maximum number of samples we keep alive at once.
+const MAX_LIVE: usize = 64;
+
+/// Entities that may linger after cleanup, like the orphan container.
+const ENTITY_SLACK: usize = 8;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let iterations: usize = args.next().and_then(|a| a.parse().ok()).unwrap_or(5_000);
+    let seed: u64 = args.next().and_then(|a| a.parse().ok()).unwrap_or(0x5eed);
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        LogPlugin::default(),
+        AssetPlugin::default(),
+        TransformPlugin,
+        SeedlingCorePlugin,
+        NullBackendPlugin,
+    ));
+
+    app.finish();
+    app.cleanup();
+    app.update();
+
+    let sample = app
+        .world_mut()
+        .resource_mut::<Assets<AudioSample>>()
+        .add(AudioSample::from_pcm(
+            vec![0.0; 4800],
+            NonZeroUsize::new(1).unwrap(),
+            NonZeroU32::new(48000).unwrap(),
+        ));
+    app.world_mut()
+        .spawn((SpatialListener3D, Transform::default()));
+
+    app.update();
+    app.update();
+
+    let baseline_entities = app.world().entities().len() as usize;
+    let baseline_edges = edge_count(&mut app);
+
+    let mut rng = Rng(seed.max(1));
+    let mut live = Vec::new();
+
+    for i in 0..iterations {
+        let world = app.world_mut();
+
+        match rng.below(10) {
+            // Most operations spawn or despawn samples.
+            0..=3 if live.len() < MAX_LIVE => {
+                let position = Vec3::new(rng.unit(), rng.unit(), rng.unit()) * 20.0;
+                let entity = world
+                    .spawn((
+                        SamplePlayer::new(sample.clone()),
+                        Transform::from_translation(position),
+                        sample_effects![SpatialBasicNode::default()],
+                    ))
+                    .id();
+                live.push(entity);
+            }
+            4 | 5 if !live.is_empty() => {
+                let entity = live.swap_remove(rng.below(live.len()));
+                if let Ok(entity) = world.get_entity_mut(entity) {
+                    entity.despawn();
+                }
+            }
+            // Parents despawning mid-playback.
+            6 => {
+                let parent = world
+                    .spawn(Transform::default())
+                    .with_child((
+                        SamplePlayer::new(sample.clone()),
+                        DetachOnParentDespawn::default(),
+                    ))
+                    .id();
+                world.despawn(parent);
+            }
+            // Device churn.
+            7 => {
+                let mut config = world.resource_mut::<AudioStreamConfig<NullConfig>>();
+                config.0.num_output_channels = if rng.chance(0.5) { 1 } else { 2 };
+                config.0.sample_rate = if rng.chance(0.5) {
+                    NonZeroU32::new(44100).unwrap()
+                } else {
+                    NonZeroU32::new(48000).unwrap()
+                };
+            }
+            8 => {
+                if world.resource::<AudioContext>().is_suspended() {
+                    world.trigger(ResumeAudio);
+                } else {
+                    world.trigger(SuspendAudio);
+                }
+            }
+            // Configuration changes.
+            _ => {
+                let end = 1 + rng.below(32);
+                let mut pools =
+                    world.query_filtered::<&mut PoolSize, With<SamplerPool<DefaultPool>>>();
+                for mut size in pools.iter_mut(world) {
+                    size.0 = 1..=end;
+                }
+
+                let channels = if rng.chance(0.5) { 1 } else { 2 };
+                let mut buses = world.query_filtered::<&mut VolumeNodeConfig, With<MainBus>>();
+                for mut config in buses.iter_mut(world) {
+                    config.channels = NonZeroChannelCount::new(channels).unwrap();
+                }
+            }
+        }
+
+        app.update();
+
+        if i % 1000 == 0 {
+            info!(
+                "iteration {i}: {} entities, {} live samples",
+                app.world().entities().len(),
+                live.len()
+            );
+        }
+    }
+
+    // Clean up and let everything settle.
+    app.world_mut().trigger(ResumeAudio);
+    for entity in live.drain(..) {
+        if let Ok(entity) = app.world_mut().get_entity_mut(entity) {
+            entity.despawn();
+        }
+    }
+
+    // Detached and queued samples need a moment to finish or expire.
+    let settle = Instant::now();
+    while settle.elapsed() < Duration::from_millis(500) {
+        app.update();
+        std::thread::sleep(Duration::from_millis(5));
+    }
+
+    let entities = app.world().entities().len() as usize;
+    assert!(
+        entities <= baseline_entities + ENTITY_SLACK,
+        "entities grew from {baseline_entities} to {entities}"
+    );
+
+    let edges = edge_count(&mut app);
+    assert!(
+        edges <= baseline_edges,
+        "graph edges grew from {baseline_edges} to {edges}"
+    );
+
+    info!("completed {iterations} iterations with seed {seed}");
+}
+
+fn edge_count(app: &mut App) -> usize {
+    app.world_mut()
+        .resource_mut::<AudioContext>()
+        .with(|context| context.edges().count())
+}
+
+/// A tiny xorshift generator, so runs can be reproduced from a seed.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    fn chance(&mut self, p: f32) -> bool {
+        self.unit().abs() < p
+    }
+
+    /// A value in `-1.0..1.0`.
+    fn unit(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+    }
+}
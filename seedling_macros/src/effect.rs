@@ -0,0 +1,45 @@
+use bevy_macro_utils::BevyManifest;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+pub fn derive_audio_effect_inner(input: TokenStream) -> syn::Result<TokenStream2> {
+    let input: syn::DeriveInput = syn::parse(input)?;
+
+    let bevy_ecs = BevyManifest::shared(|manifest| manifest.get_path("bevy_ecs"));
+    let firewheel = quote! { ::bevy_seedling::firewheel };
+    let effect = quote! { ::bevy_seedling::node::effect };
+
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #bevy_ecs::component::Component for #ident #ty_generics #where_clause {
+            const STORAGE_TYPE: #bevy_ecs::component::StorageType = #bevy_ecs::component::StorageType::Table;
+            type Mutability = #bevy_ecs::component::Mutable;
+        }
+
+        impl #impl_generics #firewheel::node::AudioNode for #ident #ty_generics #where_clause {
+            type Configuration = #effect::AudioEffectConfig;
+
+            fn info(
+                &self,
+                config: &Self::Configuration,
+            ) -> ::core::result::Result<#firewheel::node::AudioNodeInfo, #firewheel::node::NodeError> {
+                ::core::result::Result::Ok(#effect::effect_info(#name, config))
+            }
+
+            fn construct_processor(
+                &self,
+                _: &Self::Configuration,
+                cx: #firewheel::node::ConstructProcessorContext,
+            ) -> ::core::result::Result<impl #firewheel::node::AudioNodeProcessor, #firewheel::node::NodeError> {
+                ::core::result::Result::Ok(#effect::AudioEffectProcessor::new(
+                    ::core::clone::Clone::clone(self),
+                    cx,
+                ))
+            }
+        }
+    })
+}
@@ -19,6 +19,10 @@ pub fn derive_node_label_inner(input: TokenStream) -> syn::Result<TokenStream2>
             fn on_insert() -> Option<#bevy_ecs::lifecycle::ComponentHook> {
                 Some(::bevy_seedling::node::label::insert_node_label::<Self>)
             }
+
+            fn on_remove() -> Option<#bevy_ecs::lifecycle::ComponentHook> {
+                Some(::bevy_seedling::node::label::remove_node_label::<Self>)
+            }
         }
     };
 
@@ -2,6 +2,7 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 
+mod effect;
 mod label;
 
 #[proc_macro_derive(NodeLabel)]
@@ -17,3 +18,10 @@ pub fn derive_pool_label(input: TokenStream) -> TokenStream {
         .unwrap_or_else(syn::Error::into_compile_error)
         .into()
 }
+
+#[proc_macro_derive(AudioEffect)]
+pub fn derive_audio_effect(input: TokenStream) -> TokenStream {
+    effect::derive_audio_effect_inner(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,243 @@
+//! Run conditions driven by the state of the audio graph.
+//!
+//! These conditions make it easy to gate gameplay systems
+//! on audio activity without writing bespoke queries.
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use bevy_seedling::prelude::*;
+//! fn plugin(app: &mut App) {
+//!     app.add_systems(
+//!         Update,
+//!         (
+//!             start_next_track.run_if(not(any_sample_playing_in::<MusicPool>())),
+//!             play_footstep.run_if(pool_has_capacity::<DefaultPool>()),
+//!         ),
+//!     );
+//! }
+//! # fn start_next_track() {}
+//! # fn play_footstep() {}
+//! ```
+
+use crate::{
+    edge::{GraphEdges, NodeMap},
+    node::{FirewheelNode, label::NodeLabel},
+    pool::{PoolSamplers, PoolSize, Sampler, SamplerOf, SamplerPool, label::PoolLabel},
+    sample::Queued,
+};
+use bevy_ecs::prelude::*;
+use bevy_platform::collections::HashSet;
+use firewheel::node::NodeID;
+
+/// A run condition that returns `true` if any sample in the
+/// pool labeled `L` is currently playing.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn plugin(app: &mut App) {
+///     app.add_systems(
+///         Update,
+///         duck_ambience.run_if(any_sample_playing_in::<MusicPool>()),
+///     );
+/// }
+/// # fn duck_ambience() {}
+/// ```
+pub fn any_sample_playing_in<L: PoolLabel + Component>()
+-> impl FnMut(Query<&Sampler, With<L>>) -> bool + Clone {
+    |samplers: Query<&Sampler, With<L>>| samplers.iter().any(Sampler::is_playing)
+}
+
+/// A run condition that returns `true` if the pool labeled `L`
+/// can accept another sample without skipping or stealing.
+///
+/// A pool has capacity when its idle samplers outnumber the samples
+/// waiting in its queue, or when it can still grow according to its
+/// [`PoolSize`]. If the pool doesn't exist, this returns `false`.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn plugin(app: &mut App) {
+///     app.add_systems(
+///         Update,
+///         play_footstep.run_if(pool_has_capacity::<DefaultPool>()),
+///     );
+/// }
+/// # fn play_footstep() {}
+/// ```
+pub fn pool_has_capacity<L: PoolLabel + Component + Clone>() -> impl FnMut(
+    Query<(&PoolSamplers, &PoolSize), With<SamplerPool<L>>>,
+    Query<Has<SamplerOf>>,
+    Query<(), (With<L>, Queued)>,
+) -> bool
++ Clone {
+    |pools: Query<(&PoolSamplers, &PoolSize), With<SamplerPool<L>>>,
+     samplers: Query<Has<SamplerOf>>,
+     queued: Query<(), (With<L>, Queued)>| {
+        let queued = queued.iter().count();
+
+        pools.iter().any(|(pool_samplers, size)| {
+            let idle = samplers
+                .iter_many(pool_samplers.iter())
+                .filter(|assigned| !assigned)
+                .count();
+            let growth = size.0.end().saturating_sub(pool_samplers.len());
+
+            idle + growth > queued
+        })
+    }
+}
+
+/// A run condition that returns `true` if no playing samples
+/// feed into the node labeled `label`.
+///
+/// This traces the audio graph upstream from the labeled node,
+/// so samples routed through any number of effects or
+/// intermediate buses are accounted for. Note that only sampler
+/// activity is considered; other sources, like procedural nodes,
+/// are not detected. If the label isn't present in the graph,
+/// this returns `true`.
+///
+/// The graph is read from a snapshot refreshed once per frame,
+/// so this never locks the audio context. Edges changed directly
+/// through [`AudioContext::with`][crate::prelude::AudioContext::with]
+/// may not be seen until the next ECS-driven graph change.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn plugin(app: &mut App) {
+///     app.add_systems(
+///         Update,
+///         play_stinger.run_if(bus_is_silent(SoundEffectsBus)),
+///     );
+/// }
+/// # fn play_stinger() {}
+/// ```
+pub fn bus_is_silent<L: NodeLabel>(
+    label: L,
+) -> impl FnMut(
+    Res<NodeMap>,
+    Res<GraphEdges>,
+    Query<&FirewheelNode>,
+    Query<&Sampler>,
+    Local<Option<(NodeID, HashSet<NodeID>)>>,
+) -> bool
++ Clone {
+    let label = label.intern();
+
+    move |map: Res<NodeMap>,
+          edges: Res<GraphEdges>,
+          nodes: Query<&FirewheelNode>,
+          samplers: Query<&Sampler>,
+          mut upstream: Local<Option<(NodeID, HashSet<NodeID>)>>| {
+        let Some(bus) = map.get(&label).and_then(|bus| nodes.get(*bus).ok()) else {
+            return true;
+        };
+
+        // The upstream set only needs to be traced again
+        // when the graph's edges or the bus itself change.
+        let stale = match upstream.as_ref() {
+            Some((cached, _)) => *cached != bus.0 || edges.is_changed(),
+            None => true,
+        };
+        if stale {
+            *upstream = Some((bus.0, edges.upstream_of(bus.0)));
+        }
+
+        let Some((_, upstream)) = upstream.as_ref() else {
+            return true;
+        };
+
+        !samplers
+            .iter()
+            .filter(|s| s.is_playing())
+            .filter_map(|s| nodes.get(s.sampler()).ok())
+            .any(|node| upstream.contains(&node.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        prelude::*,
+        test::{prepare_app, run},
+    };
+    use bevy::prelude::*;
+
+    #[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    struct TestPool;
+
+    #[derive(NodeLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    struct TestBus;
+
+    #[derive(NodeLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    struct OtherBus;
+
+    #[test]
+    fn test_pool_capacity() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((SamplerPool(TestPool), PoolSize(1..=1)));
+        });
+
+        let has_capacity = run(&mut app, pool_has_capacity::<TestPool>());
+        assert!(has_capacity);
+
+        run(
+            &mut app,
+            |mut commands: Commands, server: Res<AssetServer>| {
+                commands.spawn((
+                    TestPool,
+                    SamplePlayer::new(server.load("caw.ogg")).looping(),
+                ));
+            },
+        );
+
+        let has_capacity = run(&mut app, pool_has_capacity::<TestPool>());
+        assert!(!has_capacity);
+    }
+
+    #[test]
+    fn test_missing_pool() {
+        let mut app = prepare_app(|| {});
+
+        let has_capacity = run(&mut app, pool_has_capacity::<TestPool>());
+        assert!(!has_capacity);
+    }
+
+    #[test]
+    fn test_bus_is_silent() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((TestBus, VolumeNode::default()));
+            commands.spawn((OtherBus, VolumeNode::default()));
+            commands.spawn(SamplerPool(TestPool)).connect(TestBus);
+        });
+
+        assert!(run(&mut app, bus_is_silent(TestBus)));
+
+        run(
+            &mut app,
+            |mut commands: Commands, server: Res<AssetServer>| {
+                commands.spawn((
+                    TestPool,
+                    SamplePlayer::new(server.load("caw.ogg")).looping(),
+                ));
+            },
+        );
+
+        let start = std::time::Instant::now();
+        while !run(&mut app, any_sample_playing_in::<TestPool>()) {
+            if start.elapsed() > std::time::Duration::from_secs(5) {
+                panic!("test exceeded timeout");
+            }
+            app.update();
+        }
+        app.update();
+
+        assert!(!run(&mut app, bus_is_silent(TestBus)));
+        assert!(!run(&mut app, bus_is_silent(MainBus)));
+        assert!(run(&mut app, bus_is_silent(OtherBus)));
+    }
+}
@@ -30,6 +30,8 @@ pub(super) struct GraphPlugin;
 impl Plugin for GraphPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<AudioGraphTemplate>()
+            .init_resource::<MainBusDcBlock>()
+            .init_resource::<PreWarmPools>()
             .add_systems(
                 PreStartup,
                 (crate::context::initialize_context, insert_io, set_up_graph)
@@ -244,6 +246,31 @@ pub enum AudioGraphTemplate {
     Empty,
 }
 
+/// Insert a [`DcBlockNode`][crate::prelude::DcBlockNode] directly
+/// after the [`MainBus`][crate::prelude::MainBus] in the
+/// [`AudioGraphTemplate`].
+///
+/// This protects the output from DC offsets introduced by procedural
+/// or custom nodes. It has no effect with [`AudioGraphTemplate::Empty`]
+/// or without the `filters` feature.
+///
+/// Like [`AudioGraphTemplate`], this should be inserted before
+/// [`SeedlingStartupSystems::GraphSetup`] runs.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// App::new()
+///     .add_plugins((DefaultPlugins, SeedlingPlugins))
+///     .insert_resource(MainBusDcBlock(true))
+///     .run();
+/// ```
+///
+/// Defaults to `false`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct MainBusDcBlock(pub bool);
+
 /// Insert the I/O markers, facilitating the graph setup.
 ///
 /// We have to defer adding [`FirewheelNode`] because the audio context
@@ -260,7 +287,7 @@ fn connect_io<E: Event>(
     mut commands: Commands,
     mut context: ResMut<AudioContext>,
 ) -> Result {
-    context.with(|ctx| {
+    context.try_with(|ctx| {
         let node_id = ctx.graph_in_node_id();
         let info = FirewheelNodeInfo::new(ctx.node_info(node_id).unwrap());
         commands
@@ -276,20 +303,96 @@ fn connect_io<E: Event>(
             .insert_if_new(FirewheelNode(node_id));
 
         Ok(())
-    })
+    })?
+}
+
+/// Point the graph's I/O entities at the current context's I/O nodes.
+pub(super) fn reacquire_io(world: &mut World) {
+    // A dead audio thread is reported by the context plugin.
+    let Ok([input, output]) = world.resource_mut::<AudioContext>().try_with(|ctx| {
+        [ctx.graph_in_node_id(), ctx.graph_out_node_id()].map(|node_id| {
+            let info = FirewheelNodeInfo::new(ctx.node_info(node_id).unwrap());
+            (FirewheelNode(node_id), info)
+        })
+    }) else {
+        return;
+    };
+
+    if let Ok(entity) = world
+        .query_filtered::<Entity, With<AudioGraphInput>>()
+        .single(world)
+    {
+        world.entity_mut(entity).insert(input);
+    }
+
+    if let Ok(entity) = world
+        .query_filtered::<Entity, With<AudioGraphOutput>>()
+        .single(world)
+    {
+        world.entity_mut(entity).insert(output);
+    }
+}
+
+/// Insert [`PreWarm`][crate::prelude::PreWarm] on the sampler
+/// pools spawned by the [`AudioGraphTemplate`].
+///
+/// Like [`AudioGraphTemplate`], this should be inserted before
+/// [`SeedlingStartupSystems::GraphSetup`] runs.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// App::new()
+///     .add_plugins((DefaultPlugins, SeedlingPlugins))
+///     .insert_resource(PreWarmPools(true))
+///     .run();
+/// ```
+///
+/// Defaults to `false`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct PreWarmPools(pub bool);
+
+/// Spawn the [`MainBus`][crate::prelude::MainBus], returning the tail of its chain.
+fn spawn_main_bus(commands: &mut Commands, dc_block: MainBusDcBlock) -> Entity {
+    use crate::prelude::*;
+
+    let bus = commands.spawn((MainBus, VolumeNode::default(), Name::new("Main Bus")));
+
+    #[cfg(feature = "filters")]
+    if dc_block.0 {
+        return bus.chain_node(DcBlockNode::default()).tail();
+    }
+
+    #[cfg(not(feature = "filters"))]
+    let _ = dc_block;
+
+    bus.id()
 }
 
 /// Set up the graph according to the initial configuration.
-fn set_up_graph(mut commands: Commands, config: Res<AudioGraphTemplate>) {
+fn set_up_graph(
+    mut commands: Commands,
+    config: Res<AudioGraphTemplate>,
+    dc_block: Res<MainBusDcBlock>,
+    pre_warm: Res<PreWarmPools>,
+) {
     use crate::prelude::*;
 
+    let mut pools = Vec::new();
+
     match *config {
         AudioGraphTemplate::Game => {
             // Buses
-            commands
-                .spawn((MainBus, VolumeNode::default(), Name::new("Main Bus")))
+            let main_bus = spawn_main_bus(&mut commands, *dc_block);
+
+            #[cfg(feature = "limiter")]
+            let main_bus = commands
+                .entity(main_bus)
                 .chain_node(LimiterNode::new(0.003, 0.15))
-                .connect(AudioGraphOutput);
+                .tail();
+
+            commands.entity(main_bus).connect(AudioGraphOutput);
 
             commands.spawn((
                 SoundEffectsBus,
@@ -306,33 +409,38 @@ fn set_up_graph(mut commands: Commands, config: Res<AudioGraphTemplate>) {
                 .connect(SoundEffectsBus);
 
             // Pools
-            commands
+            let default_pool = commands
                 .spawn((
                     SamplerPool(DefaultPool),
                     Name::new("Default Sampler Pool"),
                     sample_effects![VolumeNode::default()],
                 ))
-                .connect(SoundEffectsBus);
+                .connect(SoundEffectsBus)
+                .head();
 
-            commands
+            let spatial_pool = commands
                 .spawn((
                     SamplerPool(SpatialPool),
                     Name::new("Spatial Sampler Pool"),
                     sample_effects![VolumeNode::default(), SpatialBasicNode::default()],
                 ))
-                .connect(SoundEffectsBus);
+                .connect(SoundEffectsBus)
+                .head();
 
-            commands.spawn((
-                SamplerPool(MusicPool),
-                Name::new("Music Sampler Pool"),
-                sample_effects![VolumeNode::default()],
-            ));
+            let music_pool = commands
+                .spawn((
+                    SamplerPool(MusicPool),
+                    Name::new("Music Sampler Pool"),
+                    sample_effects![VolumeNode::default()],
+                ))
+                .id();
+
+            pools.extend([default_pool, spatial_pool, music_pool]);
         }
         AudioGraphTemplate::Minimal => {
             // Buses
-            commands
-                .spawn((MainBus, VolumeNode::default(), Name::new("Main Bus")))
-                .connect(AudioGraphOutput);
+            let main_bus = spawn_main_bus(&mut commands, *dc_block);
+            commands.entity(main_bus).connect(AudioGraphOutput);
 
             commands.spawn((
                 crate::pool::dynamic::DynamicBus,
@@ -341,12 +449,22 @@ fn set_up_graph(mut commands: Commands, config: Res<AudioGraphTemplate>) {
             ));
 
             // Pools
-            commands.spawn((
-                SamplerPool(DefaultPool),
-                Name::new("Default Sampler Pool"),
-                sample_effects![VolumeNode::default()],
-            ));
+            let default_pool = commands
+                .spawn((
+                    SamplerPool(DefaultPool),
+                    Name::new("Default Sampler Pool"),
+                    sample_effects![VolumeNode::default()],
+                ))
+                .id();
+
+            pools.push(default_pool);
         }
         AudioGraphTemplate::Empty => {}
     }
+
+    if pre_warm.0 {
+        for pool in pools {
+            commands.entity(pool).insert(PreWarm);
+        }
+    }
 }
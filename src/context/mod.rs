@@ -1,8 +1,10 @@
 //! Glue code for interfacing with the underlying audio context.
 
+use crate::{SeedlingSystems, error::SeedlingError, platform::RestartAudioStream};
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
-use bevy_platform::sync;
+use bevy_log::prelude::*;
+use bevy_platform::{cell::SyncCell, sync};
 use firewheel::{FirewheelConfig, FirewheelContext, clock::AudioClock};
 use std::{
     any::{Any, TypeId},
@@ -11,14 +13,37 @@ use std::{
 };
 
 pub mod graph;
+#[cfg(feature = "envelopes")]
+pub mod monitor;
+pub mod tee;
+mod transaction;
+
+pub use transaction::{GraphTransaction, TransactionError};
 
 pub(crate) struct ContextPlugin;
 
 impl Plugin for ContextPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<AudioContextConfig>()
-            .add_plugins(graph::GraphPlugin)
-            .add_systems(PreStartup, initialize_context);
+            .init_resource::<DspLoad>()
+            .init_resource::<AudioThreadRecovery>()
+            .add_plugins((
+                graph::GraphPlugin,
+                #[cfg(feature = "envelopes")]
+                monitor::MonitorPlugin,
+            ))
+            .add_systems(PreStartup, initialize_context)
+            .add_systems(
+                Last,
+                (
+                    update_dsp_load.in_set(SeedlingSystems::Flush),
+                    report_thread_death
+                        .run_if(resource_exists::<AudioContext>)
+                        .after(SeedlingSystems::Flush),
+                ),
+            )
+            .add_observer(observe_suspend)
+            .add_observer(observe_resume);
     }
 }
 
@@ -45,15 +70,83 @@ use os::InnerContext;
 ///     });
 /// }
 /// ```
-#[derive(Debug, Resource)]
-pub struct AudioContext(InnerContext);
+#[derive(Resource)]
+pub struct AudioContext {
+    inner: InnerContext,
+    deferred: SyncCell<Vec<DeferredCall>>,
+    suspended: bool,
+    dead: bool,
+    unreported_deaths: u32,
+}
+
+type DeferredCall = Box<dyn FnOnce(&mut FirewheelContext) + Send + 'static>;
+
+impl core::fmt::Debug for AudioContext {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AudioContext")
+            .field("inner", &self.inner)
+            .field("suspended", &self.suspended)
+            .field("dead", &self.dead)
+            .finish_non_exhaustive()
+    }
+}
 
 impl AudioContext {
     /// Create the audio context.
     ///
     /// This will not start a stream.
     pub fn new(settings: FirewheelConfig) -> Self {
-        AudioContext(InnerContext::new(settings))
+        AudioContext {
+            inner: InnerContext::new(settings),
+            deferred: SyncCell::new(Vec::new()),
+            suspended: false,
+            dead: false,
+            unreported_deaths: 0,
+        }
+    }
+
+    /// Stop the audio stream while keeping the audio graph intact.
+    ///
+    /// While suspended, the graph can be freely modified, and all
+    /// changes are applied once the stream resumes. This is useful
+    /// for mobile background transitions or a "disable audio" setting.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn toggle_audio(mut context: ResMut<AudioContext>) {
+    ///     if context.is_suspended() {
+    ///         context.resume();
+    ///     } else {
+    ///         context.suspend();
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// The [`SuspendAudio`] and [`ResumeAudio`] events provide
+    /// the same functionality.
+    pub fn suspend(&mut self) {
+        if self.suspended {
+            return;
+        }
+
+        self.suspended = true;
+        // A dead context has no stream to stop.
+        let _ = self.try_with_store(|_, store| store.remove_stream());
+    }
+
+    /// Resume a suspended audio stream.
+    ///
+    /// The stream is restarted by the active backend with its current
+    /// [`AudioStreamConfig`][crate::platform::AudioStreamConfig]
+    /// during the next update.
+    pub fn resume(&mut self) {
+        self.suspended = false;
+    }
+
+    /// Returns whether the audio stream is suspended.
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
     }
 
     /// Get an absolute timestamp from the audio thread of the current time.
@@ -95,6 +188,15 @@ impl AudioContext {
     ///     let stream_info = context.with(|context| context.stream_info().cloned());
     /// }
     /// ```
+    ///
+    /// If the control thread panicked during an earlier call, a fresh
+    /// audio context is created before running `f`. See
+    /// [`AudioThreadDied`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the control thread dies while running `f`. Use
+    /// [`AudioContext::try_with`] to handle this case instead.
     pub fn with<F, O>(&mut self, f: F) -> O
     where
         F: FnOnce(&mut FirewheelContext) -> O + Send,
@@ -103,12 +205,148 @@ impl AudioContext {
         self.with_store(|context, _| f(context))
     }
 
+    /// Operate on the underlying audio context, returning
+    /// [`SeedlingError::AudioThreadDead`] if the control thread
+    /// dies while running `f`.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn system(mut context: ResMut<AudioContext>) -> Result {
+    ///     let stream_info = context.try_with(|context| context.stream_info().cloned())?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// Like [`AudioContext::with`], a fresh audio context is created first
+    /// if the control thread died during an earlier call.
+    pub fn try_with<F, O>(&mut self, f: F) -> Result<O, SeedlingError>
+    where
+        F: FnOnce(&mut FirewheelContext) -> O + Send,
+        O: Send + 'static,
+    {
+        self.try_with_store(|context, _| f(context))
+    }
+
+    /// Queue an operation on the underlying audio context without blocking.
+    ///
+    /// Unlike [`AudioContext::with`], this returns immediately. Deferred
+    /// operations are batched and applied in order at the start of the
+    /// next blocking call, which happens at least once per frame in
+    /// [`SeedlingSystems::Flush`][crate::SeedlingSystems::Flush].
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn system(mut context: ResMut<AudioContext>) {
+    ///     context.defer(|context| {
+    ///         let _ = context.update();
+    ///     });
+    /// }
+    /// ```
+    ///
+    /// Since many systems can defer their work, this avoids a
+    /// round-trip to the audio context's thread for each one.
+    /// `bevy_seedling`'s own connection systems are deferred
+    /// this way, so they share the flush's round-trip.
+    pub fn defer<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut FirewheelContext) + Send + 'static,
+    {
+        self.deferred.get().push(Box::new(f));
+    }
+
     pub(crate) fn with_store<F, O>(&mut self, f: F) -> O
     where
         F: FnOnce(&mut FirewheelContext, &mut LocalStore) -> O + Send,
         O: Send + 'static,
     {
-        self.0.with_store(f)
+        self.try_with_store(f).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    pub(crate) fn try_with_store<F, O>(&mut self, f: F) -> Result<O, SeedlingError>
+    where
+        F: FnOnce(&mut FirewheelContext, &mut LocalStore) -> O + Send,
+        O: Send + 'static,
+    {
+        if self.dead {
+            self.inner.restart();
+            self.dead = false;
+        }
+
+        let deferred = core::mem::take(self.deferred.get());
+        let result = if deferred.is_empty() {
+            self.inner.with_store(f)
+        } else {
+            self.inner.with_store(move |context, store| {
+                for call in deferred {
+                    call(context);
+                }
+
+                f(context, store)
+            })
+        };
+
+        if result.is_err() {
+            self.dead = true;
+            self.unreported_deaths += 1;
+        }
+
+        result
+    }
+}
+
+/// Scoped access to the [`AudioContext`] alongside the [`World`].
+///
+/// In exclusive systems and queued commands, this lets graph edits be
+/// batched into a single round-trip to the audio context, interleaved
+/// with other world mutations, rather than acquiring the context across
+/// several systems.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn exclusive(world: &mut World) {
+///     world.audio_context_scope(|world, context| {
+///         let stream_info = context.with(|context| context.stream_info().cloned());
+///
+///         if stream_info.is_some() {
+///             world.spawn(VolumeNode::default());
+///         }
+///     });
+/// }
+///
+/// // The same applies to the command queue.
+/// fn deferred(mut commands: Commands) {
+///     commands.queue(|world: &mut World| {
+///         world.audio_context_scope(|_, context| {
+///             context.defer(|context| {
+///                 let _ = context.update();
+///             });
+///         });
+///     });
+/// }
+/// ```
+pub trait AudioContextScope {
+    /// Temporarily remove the [`AudioContext`], calling `f` with
+    /// access to both the context and the rest of the world.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`AudioContext`] resource doesn't exist, which
+    /// is the case before [`PreStartup`] has run.
+    fn audio_context_scope<F, O>(&mut self, f: F) -> O
+    where
+        F: FnOnce(&mut World, &mut AudioContext) -> O;
+}
+
+impl AudioContextScope for World {
+    fn audio_context_scope<F, O>(&mut self, f: F) -> O
+    where
+        F: FnOnce(&mut World, &mut AudioContext) -> O,
+    {
+        self.resource_scope(|world, mut context: Mut<AudioContext>| f(world, &mut context))
     }
 }
 
@@ -128,12 +366,16 @@ impl AudioThreadState {
 
 #[allow(dead_code)]
 #[derive(Default)]
-pub(crate) struct LocalStore(HashMap<TypeId, Box<dyn Any>>);
+pub(crate) struct LocalStore {
+    values: HashMap<TypeId, Box<dyn Any>>,
+    /// The type of the active backend's output stream.
+    stream: Option<TypeId>,
+}
 
 #[allow(dead_code)]
 impl LocalStore {
     pub(crate) fn insert<T: 'static>(&mut self, value: T) -> Option<T> {
-        self.0
+        self.values
             .insert(TypeId::of::<T>(), Box::new(value))
             .map(|value| {
                 *value
@@ -142,16 +384,32 @@ impl LocalStore {
             })
     }
 
+    /// Insert the active backend's output stream.
+    ///
+    /// Unlike other values, the stream is dropped when the
+    /// context is [suspended][AudioContext::suspend].
+    pub(crate) fn insert_stream<T: 'static>(&mut self, stream: T) -> Option<T> {
+        self.stream = Some(TypeId::of::<T>());
+        self.insert(stream)
+    }
+
     pub(crate) fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
-        self.0.get_mut(&TypeId::of::<T>()).map(|value| {
+        self.values.get_mut(&TypeId::of::<T>()).map(|value| {
             value
                 .downcast_mut()
                 .expect("stored type should match its `TypeId`")
         })
     }
 
+    /// Drop the active backend's output stream, if any.
+    pub(crate) fn remove_stream(&mut self) {
+        if let Some(stream) = self.stream.take() {
+            self.values.remove(&stream);
+        }
+    }
+
     pub(crate) fn remove<T: 'static>(&mut self) -> Option<T> {
-        self.0.remove(&TypeId::of::<T>()).map(|value| {
+        self.values.remove(&TypeId::of::<T>()).map(|value| {
             *value
                 .downcast()
                 .expect("stored type should match its `TypeId`")
@@ -195,6 +453,149 @@ impl SampleRate {
     }
 }
 
+/// The audio thread's processing load.
+///
+/// This is updated every frame from Firewheel's profiling data,
+/// making it a convenient signal for dynamically reducing audio
+/// work, like limiting the number of active voices, before
+/// the audio thread is overloaded.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn degrade(load: Res<DspLoad>, mut pools: Query<&mut PoolSize>) {
+///     if load.usage() > 0.8 || load.new_underruns() > 0 {
+///         for mut size in &mut pools {
+///             let end = (*size.0.end() / 2).max(*size.0.start());
+///             size.0 = *size.0.start()..=end;
+///         }
+///     }
+/// }
+/// ```
+///
+/// Underruns are only reported by backends that detect them.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct DspLoad {
+    usage: f64,
+    underruns: u64,
+    new_underruns: u32,
+}
+
+impl DspLoad {
+    /// The most recent processing time as a fraction of the
+    /// block's time budget.
+    ///
+    /// Values approaching `1.0` indicate the audio thread is
+    /// close to overloading.
+    pub fn usage(&self) -> f64 {
+        self.usage
+    }
+
+    /// The total number of underruns since startup.
+    pub fn underruns(&self) -> u64 {
+        self.underruns
+    }
+
+    /// The number of underruns reported in the previous frame.
+    pub fn new_underruns(&self) -> u32 {
+        self.new_underruns
+    }
+
+    /// Returns `true` if the usage exceeds `threshold` or
+    /// any underruns were reported in the previous frame.
+    pub fn is_overloaded(&self, threshold: f64) -> bool {
+        self.usage > threshold || self.new_underruns > 0
+    }
+
+    /// Record an underrun reported by the audio stream.
+    pub(crate) fn record_underrun(&mut self) {
+        self.underruns += 1;
+        self.new_underruns += 1;
+    }
+}
+
+fn update_dsp_load(mut load: ResMut<DspLoad>, mut context: ResMut<AudioContext>) {
+    load.new_underruns = 0;
+
+    // A dead audio thread is reported in `report_thread_death`.
+    if let Ok(usage) = context.try_with(|context| context.profiling_data().overall_cpu_usage) {
+        load.usage = usage;
+    }
+}
+
+/// Triggered globally when the audio context's control thread panics.
+///
+/// Rather than propagating the panic to the main thread,
+/// `bevy_seedling` drops the dead context, silencing all audio.
+/// The next call to [`AudioContext::with`] creates a fresh context.
+///
+/// The audio graph is then rebuilt on the fresh context. Every
+/// [`FirewheelNode`][crate::prelude::FirewheelNode] is acquired again,
+/// and the edges from the last [`GraphEdges`][crate::edge::GraphEdges]
+/// snapshot are reconnected. State held only on the audio thread,
+/// like a sampler's playhead, is lost.
+///
+/// Whether a stream is started on the fresh context is
+/// determined by [`AudioThreadRecovery`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn notify_player(_: On<AudioThreadDied>) {
+///     warn!("audio has stopped due to an internal error");
+/// }
+/// ```
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AudioThreadDied;
+
+/// Determines how `bevy_seedling` responds when the
+/// audio context's control thread panics.
+///
+/// In either case, the audio graph is rebuilt on the fresh context,
+/// as described in [`AudioThreadDied`].
+///
+/// Defaults to [`AudioThreadRecovery::Silence`].
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum AudioThreadRecovery {
+    /// Leave the audio stream stopped.
+    #[default]
+    Silence,
+    /// Trigger [`RestartAudioStream`] to start a
+    /// stream on the fresh context.
+    Restart,
+}
+
+fn report_thread_death(
+    mut context: ResMut<AudioContext>,
+    recovery: Res<AudioThreadRecovery>,
+    mut commands: Commands,
+) {
+    if context.unreported_deaths == 0 {
+        return;
+    }
+
+    context.unreported_deaths = 0;
+    error!("{}", SeedlingError::AudioThreadDead);
+    commands.queue(rebuild_graph);
+    commands.trigger(AudioThreadDied);
+
+    if *recovery == AudioThreadRecovery::Restart {
+        commands.trigger(RestartAudioStream);
+    }
+}
+
+/// Rebuild the audio graph on a fresh context.
+///
+/// Every node is acquired again, and the edges from the last
+/// [`GraphEdges`][crate::edge::GraphEdges] snapshot are reconnected
+/// between the same entities.
+fn rebuild_graph(world: &mut World) {
+    crate::edge::requeue_edges(world);
+    crate::node::release_nodes(world);
+    graph::reacquire_io(world);
+}
+
 fn initialize_context(firewheel_config: Res<AudioContextConfig>, mut commands: Commands) -> Result {
     let context = AudioContext::new(firewheel_config.0);
     commands.insert_resource(context);
@@ -233,3 +634,190 @@ pub struct StreamRestartEvent {
     /// The current sample rate following the restart.
     pub current_rate: NonZeroU32,
 }
+
+/// When triggered globally, this suspends the audio stream.
+///
+/// See [`AudioContext::suspend`] for more details.
+#[derive(Event, Debug)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct SuspendAudio;
+
+/// When triggered globally, this resumes a suspended audio stream.
+///
+/// See [`AudioContext::resume`] for more details.
+#[derive(Event, Debug)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct ResumeAudio;
+
+fn observe_suspend(_: On<SuspendAudio>, mut context: ResMut<AudioContext>) {
+    context.suspend();
+}
+
+fn observe_resume(_: On<ResumeAudio>, mut context: ResMut<AudioContext>) {
+    context.resume();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::{prepare_app, run};
+
+    #[derive(Resource, Default)]
+    struct Restarted(bool);
+
+    #[test]
+    fn test_suspend_resume() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.init_resource::<Restarted>();
+        });
+
+        app.add_observer(
+            |_: On<StreamRestartEvent>, mut restarted: ResMut<Restarted>| {
+                restarted.0 = true;
+            },
+        );
+
+        app.world_mut().trigger(SuspendAudio);
+        app.update();
+
+        assert!(run(&mut app, |context: Res<AudioContext>| context
+            .is_suspended()));
+        assert!(!app.world().resource::<Restarted>().0);
+
+        app.world_mut().trigger(ResumeAudio);
+        app.update();
+        app.update();
+
+        assert!(!run(&mut app, |context: Res<AudioContext>| context
+            .is_suspended()));
+        assert!(app.world().resource::<Restarted>().0);
+    }
+
+    #[test]
+    fn test_dsp_load() {
+        let mut app = prepare_app(|| ());
+
+        app.world_mut().resource_mut::<DspLoad>().record_underrun();
+        assert!(app.world().resource::<DspLoad>().is_overloaded(1.0));
+
+        app.update();
+
+        let load = app.world().resource::<DspLoad>();
+        assert_eq!(load.underruns(), 1);
+        assert_eq!(load.new_underruns(), 0);
+        assert!(!load.is_overloaded(1.0));
+    }
+
+    #[test]
+    fn test_audio_context_scope() {
+        let mut app = prepare_app(|| ());
+
+        let suspended = app.world_mut().audio_context_scope(|world, context| {
+            assert!(!world.contains_resource::<AudioContext>());
+
+            context.suspend();
+            context.is_suspended()
+        });
+
+        assert!(suspended);
+        assert!(app.world().resource::<AudioContext>().is_suspended());
+    }
+
+    #[test]
+    fn test_defer() {
+        let mut context = AudioContext::new(FirewheelConfig::default());
+        let calls = sync::Arc::new(sync::atomic::AtomicUsize::new(0));
+
+        for _ in 0..3 {
+            let calls = calls.clone();
+            context.defer(move |_| {
+                calls.fetch_add(1, sync::atomic::Ordering::Relaxed);
+            });
+        }
+
+        assert_eq!(calls.load(sync::atomic::Ordering::Relaxed), 0);
+
+        let seen = context.with({
+            let calls = calls.clone();
+            move |_| calls.load(sync::atomic::Ordering::Relaxed)
+        });
+        assert_eq!(seen, 3);
+    }
+
+    #[test]
+    fn test_remove_stream() {
+        let mut store = LocalStore::default();
+        store.insert(1u32);
+        store.insert_stream("stream");
+
+        store.remove_stream();
+
+        // Only the stream is dropped.
+        assert!(store.get_mut::<&str>().is_none());
+        assert_eq!(store.get_mut::<u32>().copied(), Some(1));
+    }
+
+    #[derive(Resource, Default)]
+    struct Deaths(usize);
+
+    #[test]
+    fn test_thread_death() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.init_resource::<Deaths>();
+        });
+
+        app.add_observer(|_: On<AudioThreadDied>, mut deaths: ResMut<Deaths>| {
+            deaths.0 += 1;
+        });
+
+        run(&mut app, |mut context: ResMut<AudioContext>| {
+            let result = context.try_with::<_, ()>(|_| panic!("simulated DSP bug"));
+            assert!(matches!(result, Err(SeedlingError::AudioThreadDead)));
+
+            // The next call runs on a fresh, streamless context.
+            assert!(context.with(|context| context.stream_info().is_none()));
+        });
+
+        app.update();
+        app.update();
+
+        assert_eq!(app.world().resource::<Deaths>().0, 1);
+    }
+
+    #[test]
+    fn test_graph_rebuilt() {
+        use crate::prelude::*;
+
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands
+                .spawn(VolumeNode::default())
+                .connect(AudioGraphOutput);
+        });
+
+        app.update();
+
+        run(&mut app, |mut context: ResMut<AudioContext>| {
+            let _ = context.try_with::<_, ()>(|_| panic!("simulated DSP bug"));
+        });
+
+        for _ in 0..3 {
+            app.update();
+        }
+
+        run(
+            &mut app,
+            |node: Single<&FirewheelNode, With<VolumeNode>>, mut context: ResMut<AudioContext>| {
+                let node = node.0;
+                context.with(|context| {
+                    let out = context.graph_out_node_id();
+                    assert!(context.node_info(node).is_some());
+                    assert!(
+                        context
+                            .edges()
+                            .any(|e| e.src_node == node && e.dst_node == out)
+                    );
+                });
+            },
+        );
+    }
+}
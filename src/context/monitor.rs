@@ -0,0 +1,206 @@
+//! Live monitoring of the audio graph's input.
+
+use crate::{
+    SeedlingSystems,
+    edge::{AudioGraphInput, Connect},
+    node::AudioState,
+    nodes::amplitude_tracker::{AmplitudeTrackerNode, AmplitudeTrackerState},
+};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use firewheel::{Volume, nodes::volume::VolumeNode};
+
+pub(super) struct MonitorPlugin;
+
+impl Plugin for MonitorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Last,
+            (
+                spawn_monitor_guards.before(SeedlingSystems::Acquire),
+                update_monitors
+                    .after(SeedlingSystems::Connect)
+                    .before(SeedlingSystems::Queue),
+            ),
+        );
+    }
+}
+
+/// Routes the audio graph's input through a gain stage for live monitoring.
+///
+/// An entity with [`InputMonitor`] is automatically connected to the
+/// [`AudioGraphInput`]. Its [`VolumeNode`] acts as the input gain, and
+/// like any other node, it can be chained through effects and routed
+/// to any bus. If no connections are provided, the monitor is routed
+/// according to [`AutoConnect`][crate::edge::AutoConnect].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn monitor_guitar(mut commands: Commands) {
+///     commands
+///         .spawn(InputMonitor::default())
+///         .chain_node(LimiterNode::default())
+///         .connect(MainBus);
+/// }
+///
+/// fn toggle_monitor(mut monitor: Single<&mut InputMonitor>) {
+///     monitor.muted = !monitor.muted;
+/// }
+/// ```
+///
+/// ## Feedback protection
+///
+/// Monitoring a microphone through speakers can quickly produce runaway
+/// feedback. When the input's smoothed level exceeds
+/// [`feedback_threshold`][InputMonitor::feedback_threshold], the monitor
+/// is muted and [`InputMonitorMuted`] is triggered on its entity.
+/// The monitor stays muted until [`muted`][InputMonitor::muted] is cleared.
+///
+/// By default, Firewheel's graph will have no inputs. Make sure your
+/// selected backend and [`FirewheelConfig`][firewheel::FirewheelConfig] are
+/// configured for input.
+#[derive(Component, Debug, Clone, PartialEq)]
+#[require(VolumeNode)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct InputMonitor {
+    /// The gain applied to the input.
+    ///
+    /// Defaults to [`Volume::UNITY_GAIN`].
+    pub gain: Volume,
+    /// Whether the monitor is muted.
+    ///
+    /// Defaults to `false`.
+    pub muted: bool,
+    /// The input level at which the monitor is automatically muted.
+    ///
+    /// When `None`, feedback protection is disabled.
+    /// Defaults to -3 dB.
+    pub feedback_threshold: Option<Volume>,
+}
+
+impl Default for InputMonitor {
+    fn default() -> Self {
+        Self {
+            gain: Volume::UNITY_GAIN,
+            muted: false,
+            feedback_threshold: Some(Volume::Decibels(-3.0)),
+        }
+    }
+}
+
+/// An event triggered on [`InputMonitor`] entities when
+/// feedback protection mutes the monitor.
+#[derive(Debug, EntityEvent)]
+pub struct InputMonitorMuted {
+    /// The [`InputMonitor`] entity.
+    pub entity: Entity,
+    /// The input level that triggered the mute.
+    pub level: Volume,
+}
+
+/// Points from an input level tracker to its [`InputMonitor`].
+#[derive(Debug, Component)]
+#[relationship(relationship_target = MonitorGuard)]
+struct MonitorGuardOf(Entity);
+
+/// The input level tracker for an [`InputMonitor`].
+#[derive(Debug, Component)]
+#[relationship_target(relationship = MonitorGuardOf, linked_spawn)]
+struct MonitorGuard(Entity);
+
+fn spawn_monitor_guards(
+    monitors: Query<Entity, (With<InputMonitor>, Without<MonitorGuard>)>,
+    input: Query<Entity, With<AudioGraphInput>>,
+    mut commands: Commands,
+) {
+    let Ok(input) = input.single() else {
+        return;
+    };
+
+    for monitor in &monitors {
+        let guard = commands
+            .spawn((
+                AmplitudeTrackerNode {
+                    attack: 0.005,
+                    release: 0.25,
+                    ..Default::default()
+                },
+                MonitorGuardOf(monitor),
+            ))
+            .connect(monitor)
+            .id();
+
+        commands.entity(input).connect(guard);
+    }
+}
+
+fn update_monitors(
+    mut monitors: Query<(
+        Entity,
+        &mut InputMonitor,
+        &mut VolumeNode,
+        Option<&MonitorGuard>,
+    )>,
+    guards: Query<&AudioState<AmplitudeTrackerState>>,
+    mut commands: Commands,
+) {
+    for (entity, mut monitor, mut volume, guard) in &mut monitors {
+        let level = guard
+            .and_then(|g| guards.get(g.0).ok())
+            .map(|state| state.0.amplitude())
+            .unwrap_or_default();
+
+        if !monitor.muted
+            && let Some(threshold) = monitor.feedback_threshold
+            && level > threshold.linear()
+        {
+            monitor.muted = true;
+            commands.trigger(InputMonitorMuted {
+                entity,
+                level: Volume::Linear(level),
+            });
+        }
+
+        let target = if monitor.muted {
+            Volume::SILENT
+        } else {
+            monitor.gain
+        };
+
+        if volume.volume != target {
+            volume.volume = target;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::{prepare_app, run};
+
+    #[test]
+    fn test_monitor_mute() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn(InputMonitor {
+                gain: Volume::Decibels(-6.0),
+                ..Default::default()
+            });
+        });
+
+        let volume = run(&mut app, |node: Single<&VolumeNode, With<InputMonitor>>| {
+            node.volume
+        });
+        assert_eq!(volume, Volume::Decibels(-6.0));
+
+        run(&mut app, |mut monitor: Single<&mut InputMonitor>| {
+            monitor.muted = true;
+        });
+        app.update();
+
+        let volume = run(&mut app, |node: Single<&VolumeNode, With<InputMonitor>>| {
+            node.volume
+        });
+        assert_eq!(volume, Volume::SILENT);
+    }
+}
@@ -2,10 +2,14 @@ use firewheel::{FirewheelConfig, FirewheelContext};
 use std::sync::mpsc;
 
 use super::{AudioThreadState, LocalStore};
+use crate::error::SeedlingError;
 
 /// A thread-safe wrapper around the underlying Firewheel audio context.
 #[derive(Debug)]
-pub struct InnerContext(mpsc::Sender<ThreadLocalCall>);
+pub struct InnerContext {
+    sender: mpsc::Sender<ThreadLocalCall>,
+    settings: FirewheelConfig,
+}
 
 type ThreadLocalCall = Box<dyn FnOnce(&mut AudioThreadState) + Send + 'static>;
 
@@ -22,7 +26,17 @@ impl InnerContext {
             }
         });
 
-        InnerContext(bev_to_audio_tx)
+        InnerContext {
+            sender: bev_to_audio_tx,
+            settings,
+        }
+    }
+
+    // Replace a dead control thread with a fresh one.
+    //
+    // The new audio context has an empty graph and no stream.
+    pub fn restart(&mut self) {
+        *self = Self::new(self.settings);
     }
 
     // Send `f` to the underlying control thread to operate on the audio context.
@@ -33,9 +47,12 @@ impl InnerContext {
     // This API can't completely prevent them in the general case: calling
     // [AudioContext::with] within itself will deadlock.
     //
+    // If the control thread has panicked, this returns
+    // [SeedlingError::AudioThreadDead].
+    //
     // This API is based on [this PR](https://github.com/bevyengine/bevy/pull/9122).
     #[inline(always)]
-    pub fn with_store<F, O>(&mut self, f: F) -> O
+    pub fn with_store<F, O>(&mut self, f: F) -> Result<O, SeedlingError>
     where
         F: FnOnce(&mut FirewheelContext, &mut LocalStore) -> O + Send,
         O: Send + 'static,
@@ -44,13 +61,15 @@ impl InnerContext {
         let func: Box<dyn FnOnce(&mut AudioThreadState) + Send> = Box::new(move |state| {
             let AudioThreadState { context, store } = state;
             let result = f(context, store);
-            send.send(result).unwrap();
+            let _ = send.send(result);
         });
 
         // # SAFETY
         //
         // This thread will block until the function returns,
-        // so we can pretend it has a static lifetime.
+        // so we can pretend it has a static lifetime. If the control
+        // thread panics, `send` is dropped only after `f` has unwound,
+        // and a failed `send` hands the function back to be dropped here.
         let func = unsafe {
             core::mem::transmute::<
                 Box<dyn FnOnce(&mut AudioThreadState) + Send>,
@@ -58,10 +77,10 @@ impl InnerContext {
             >(func)
         };
 
-        // If the audio communication thread fails to send or receive
-        // messages, like in the event of a panic, a panic will be
-        // propagated to the calling thread .
-        self.0.send(func).unwrap();
-        receive.recv().unwrap()
+        // The channels only disconnect if the control thread has panicked.
+        self.sender
+            .send(func)
+            .map_err(|_| SeedlingError::AudioThreadDead)?;
+        receive.recv().map_err(|_| SeedlingError::AudioThreadDead)
     }
 }
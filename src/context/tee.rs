@@ -0,0 +1,444 @@
+//! Duplicating the final mix into a buffer readable by the app.
+//!
+//! Streaming and recording software often wants the game's audio
+//! separately from the rest of the system's output. [`OutputTeePlugin`]
+//! mirrors every connection into the [`AudioGraphOutput`] to a capture
+//! node, which writes the mix into a ring buffer. The primary output
+//! path is left untouched.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_seedling::prelude::*;
+//! fn main() {
+//!     App::new()
+//!         .add_plugins((
+//!             DefaultPlugins,
+//!             SeedlingPlugins,
+//!             OutputTeePlugin::default(),
+//!         ))
+//!         .add_systems(Update, forward_mix)
+//!         .run();
+//! }
+//!
+//! fn forward_mix(tee: Option<ResMut<OutputTee>>, mut buffer: Local<Vec<f32>>) {
+//!     let Some(mut tee) = tee else {
+//!         return;
+//!     };
+//!
+//!     buffer.clear();
+//!     tee.read(&mut buffer);
+//!
+//!     // Hand the interleaved samples off to an encoder,
+//!     // a virtual device, or anywhere else.
+//! }
+//! ```
+//!
+//! Opening a second output stream isn't handled here, since that
+//! depends heavily on the backend. Instead, [`OutputTee`] can be
+//! drained into whatever sink the app needs.
+
+use super::{AudioContext, SampleRate};
+use crate::{
+    SeedlingSystems,
+    edge::AudioGraphOutput,
+    node::{AudioState, FirewheelNode, FirewheelNodeInfo, RegisterNode},
+};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_log::prelude::*;
+use core::{
+    num::NonZeroU32,
+    sync::atomic::{AtomicU32, AtomicU64, Ordering, fence},
+};
+use firewheel::{
+    channel_config::{ChannelConfig, ChannelCount, NonZeroChannelCount},
+    clock::DurationSeconds,
+    collector::ArcGc,
+    event::ProcEvents,
+    node::{
+        AudioNode, AudioNodeInfo, AudioNodeProcessor, ConstructProcessorContext, NodeError,
+        ProcBuffers, ProcExtra, ProcInfo, ProcessStatus,
+    },
+};
+
+/// Tee the final mix into an [`OutputTee`] ring buffer.
+///
+/// This isn't included in [`SeedlingPlugins`][crate::SeedlingPlugins],
+/// since most apps don't need it.
+///
+/// See the [module docs][self] for an example.
+#[derive(Debug, Clone)]
+pub struct OutputTeePlugin {
+    /// The length of audio the ring buffer can hold.
+    ///
+    /// If the buffer isn't read at least this often, the oldest
+    /// frames are overwritten. Defaults to one second.
+    pub capacity: DurationSeconds,
+}
+
+impl Default for OutputTeePlugin {
+    fn default() -> Self {
+        Self {
+            capacity: DurationSeconds(1.0),
+        }
+    }
+}
+
+impl Plugin for OutputTeePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TeeCapacity(self.capacity))
+            .register_simple_node::<OutputTeeNode>()
+            .register_node_state::<OutputTeeNode, OutputTeeState>()
+            .add_systems(
+                Last,
+                (
+                    spawn_tee
+                        .run_if(resource_exists::<SampleRate>)
+                        .before(SeedlingSystems::Acquire),
+                    (
+                        publish_tee.run_if(resource_exists::<SampleRate>),
+                        mirror_output,
+                    )
+                        .after(SeedlingSystems::Connect)
+                        .before(SeedlingSystems::Queue),
+                ),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct TeeCapacity(DurationSeconds);
+
+/// A node that continuously records its input into a ring buffer.
+///
+/// This is the capture node spawned by [`OutputTeePlugin`].
+#[derive(Debug, Default, Clone, Component)]
+pub struct OutputTeeNode;
+
+/// Configuration for [`OutputTeeNode`].
+#[derive(Debug, Clone, Component, PartialEq)]
+pub struct OutputTeeConfig {
+    /// The number of input channels.
+    ///
+    /// By default, this is stereo.
+    pub channels: NonZeroChannelCount,
+    /// The number of frames the ring buffer holds.
+    ///
+    /// This is clamped to at least one frame.
+    pub frames: usize,
+}
+
+impl Default for OutputTeeConfig {
+    fn default() -> Self {
+        Self {
+            channels: NonZeroChannelCount::STEREO,
+            frames: 48000,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TeeBuffer {
+    /// Interleaved samples, stored as bits.
+    samples: Box<[AtomicU32]>,
+    channels: usize,
+    frames: usize,
+    /// The total number of frames written.
+    written: AtomicU64,
+    /// The total number of frames written or being written.
+    ///
+    /// This runs ahead of `written` while the processor is
+    /// writing, letting readers detect when they've been lapped.
+    claimed: AtomicU64,
+}
+
+/// The shared ring buffer [`OutputTeeNode`] records into.
+#[derive(Debug, Clone)]
+pub struct OutputTeeState(ArcGc<TeeBuffer>);
+
+impl AudioNode for OutputTeeNode {
+    type Configuration = OutputTeeConfig;
+
+    fn info(&self, config: &Self::Configuration) -> Result<AudioNodeInfo, NodeError> {
+        let channels = config.channels.get().get() as usize;
+        let frames = config.frames.max(1);
+        let samples = (0..frames * channels).map(|_| AtomicU32::new(0)).collect();
+
+        Ok(AudioNodeInfo::new()
+            .debug_name("output tee")
+            .channel_config(ChannelConfig {
+                num_inputs: config.channels.get(),
+                num_outputs: ChannelCount::ZERO,
+            })
+            .custom_state(OutputTeeState(ArcGc::new(TeeBuffer {
+                samples,
+                channels,
+                frames,
+                written: AtomicU64::new(0),
+                claimed: AtomicU64::new(0),
+            }))))
+    }
+
+    fn construct_processor(
+        &self,
+        _: &Self::Configuration,
+        cx: ConstructProcessorContext,
+    ) -> Result<impl AudioNodeProcessor, NodeError> {
+        Ok(OutputTeeProcessor {
+            state: cx.custom_state().cloned().unwrap(),
+        })
+    }
+}
+
+struct OutputTeeProcessor {
+    state: OutputTeeState,
+}
+
+impl AudioNodeProcessor for OutputTeeProcessor {
+    fn events(&mut self, _info: &ProcInfo, _events: &mut ProcEvents, _extra: &mut ProcExtra) {}
+
+    fn process(
+        &mut self,
+        proc_info: &ProcInfo,
+        buffers: ProcBuffers,
+        _: &mut ProcExtra,
+    ) -> ProcessStatus {
+        let buffer = &self.state.0;
+        let channels = buffer.channels;
+        let written = buffer.written.load(Ordering::Relaxed);
+        let frames = proc_info.frames;
+
+        buffer
+            .claimed
+            .store(written + frames as u64, Ordering::Relaxed);
+        fence(Ordering::Release);
+
+        for (channel, input) in buffers.inputs.iter().enumerate().take(channels) {
+            for (frame, sample) in input[..frames].iter().enumerate() {
+                let slot = ((written + frame as u64) % buffer.frames as u64) as usize;
+                buffer.samples[slot * channels + channel]
+                    .store(sample.to_bits(), Ordering::Relaxed);
+            }
+        }
+
+        buffer
+            .written
+            .store(written + frames as u64, Ordering::Release);
+
+        ProcessStatus::Bypass
+    }
+}
+
+/// A reader for the final mix, provided by [`OutputTeePlugin`].
+///
+/// This resource is inserted once the tee is in the audio graph,
+/// and replaced if the graph's output channel count changes.
+#[derive(Resource, Debug)]
+pub struct OutputTee {
+    state: OutputTeeState,
+    sample_rate: NonZeroU32,
+    read: u64,
+    dropped: u64,
+}
+
+impl OutputTee {
+    /// The number of interleaved channels per frame.
+    pub fn channels(&self) -> usize {
+        self.state.0.channels
+    }
+
+    /// The sample rate of the mix.
+    pub fn sample_rate(&self) -> NonZeroU32 {
+        self.sample_rate
+    }
+
+    /// The total number of frames lost because the
+    /// ring buffer wasn't read quickly enough.
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Append every frame recorded since the last read to
+    /// `out` as interleaved samples, returning the number of frames.
+    ///
+    /// If more than the buffer's capacity has been recorded since
+    /// the last read, only the most recent frames are returned.
+    pub fn read(&mut self, out: &mut Vec<f32>) -> usize {
+        let buffer = &self.state.0;
+        let channels = buffer.channels;
+        let capacity = buffer.frames as u64;
+        let written = buffer.written.load(Ordering::Acquire);
+        let start = self.read.max(written.saturating_sub(capacity));
+        let offset = out.len();
+
+        for frame in start..written {
+            let slot = (frame % capacity) as usize * channels;
+            out.extend(
+                buffer.samples[slot..slot + channels]
+                    .iter()
+                    .map(|s| f32::from_bits(s.load(Ordering::Relaxed))),
+            );
+        }
+
+        // The processor may have lapped us while copying,
+        // in which case the oldest frames are unreliable.
+        fence(Ordering::Acquire);
+        let claimed = buffer.claimed.load(Ordering::Relaxed);
+        let valid = start.max(claimed.saturating_sub(capacity)).min(written);
+        out.drain(offset..offset + (valid - start) as usize * channels);
+
+        self.dropped += valid - self.read;
+        self.read = written;
+
+        (written - valid) as usize
+    }
+}
+
+fn spawn_tee(
+    output: Query<&FirewheelNodeInfo, With<AudioGraphOutput>>,
+    tee: Query<(Entity, &OutputTeeConfig), With<OutputTeeNode>>,
+    capacity: Res<TeeCapacity>,
+    sample_rate: Res<SampleRate>,
+    mut commands: Commands,
+) {
+    let Ok(output) = output.single() else {
+        return;
+    };
+
+    let Some(channels) = NonZeroChannelCount::new(output.channel_config.num_inputs.get()) else {
+        return;
+    };
+
+    if let Ok((entity, config)) = tee.single() {
+        if config.channels == channels {
+            return;
+        }
+
+        commands.entity(entity).despawn();
+    }
+
+    let frames = (capacity.0.0 * sample_rate.get().get() as f64).round() as usize;
+    commands.spawn((
+        OutputTeeNode,
+        OutputTeeConfig { channels, frames },
+        Name::new("Output Tee"),
+    ));
+}
+
+fn publish_tee(
+    tee: Query<&AudioState<OutputTeeState>, Changed<AudioState<OutputTeeState>>>,
+    sample_rate: Res<SampleRate>,
+    mut commands: Commands,
+) {
+    for state in &tee {
+        commands.insert_resource(OutputTee {
+            state: state.0.clone(),
+            sample_rate: sample_rate.get(),
+            read: 0,
+            dropped: 0,
+        });
+    }
+}
+
+/// Keep the tee's inputs in sync with the graph output's inputs.
+fn mirror_output(
+    output: Query<&FirewheelNode, With<AudioGraphOutput>>,
+    tee: Query<(&FirewheelNode, &OutputTeeConfig), With<OutputTeeNode>>,
+    mut context: ResMut<AudioContext>,
+) {
+    let (Ok(output), Ok((tee, config))) = (output.single(), tee.single()) else {
+        return;
+    };
+    let channels = config.channels.get().get();
+    let (output, tee) = (*output, *tee);
+
+    // This runs every frame but never needs a result, so
+    // it's batched with the frame's other deferred operations.
+    context.defer(move |context| {
+        let edges = |node| {
+            context
+                .edges()
+                .filter(|e| e.dst_node == node && e.dst_port < channels)
+                .map(|e| (e.src_node, e.src_port, e.dst_port))
+                .collect::<Vec<_>>()
+        };
+        let wanted = edges(output.0);
+        let existing = edges(tee.0);
+
+        for &(source, src_port, dst_port) in &existing {
+            if !wanted.contains(&(source, src_port, dst_port)) {
+                context.disconnect(source, tee.0, &[(src_port, dst_port)]);
+            }
+        }
+
+        for &(source, src_port, dst_port) in &wanted {
+            if existing.contains(&(source, src_port, dst_port)) {
+                continue;
+            }
+
+            if let Err(e) = context.connect(source, tee.0, &[(src_port, dst_port)], false) {
+                error!("failed to mirror output connection to tee: {e:?}");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        prelude::*,
+        test::{prepare_app_with, run},
+    };
+    use std::time::Instant;
+
+    #[derive(Component)]
+    struct Source;
+
+    #[test]
+    fn test_output_tee() {
+        let mut app = prepare_app_with(OutputTeePlugin::default(), |mut commands: Commands| {
+            commands
+                .spawn((VolumeNode::default(), Source))
+                .connect(AudioGraphOutput);
+        });
+
+        let start = Instant::now();
+        loop {
+            let frames = app
+                .world_mut()
+                .get_resource_mut::<OutputTee>()
+                .map(|mut tee| tee.read(&mut Vec::new()))
+                .unwrap_or_default();
+
+            if frames > 0 {
+                break;
+            }
+
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+
+        run(
+            &mut app,
+            |source: Single<&FirewheelNode, With<Source>>,
+             tee: Single<&FirewheelNode, With<OutputTeeNode>>,
+             output: Single<&FirewheelNode, With<AudioGraphOutput>>,
+             mut context: ResMut<AudioContext>| {
+                context.with(|context| {
+                    let feeds = |node| {
+                        context
+                            .edges()
+                            .any(|e| e.src_node == source.0 && e.dst_node == node)
+                    };
+
+                    assert!(feeds(tee.0));
+                    assert!(feeds(output.0));
+                });
+            },
+        );
+    }
+}
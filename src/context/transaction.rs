@@ -0,0 +1,404 @@
+//! All-or-nothing audio graph edits.
+
+use super::AudioContext;
+use bevy_platform::collections::HashSet;
+use firewheel::{FirewheelContext, node::NodeID};
+
+/// A batch of audio graph edits.
+///
+/// See [`AudioContext::transaction`].
+#[derive(Debug, Default)]
+pub struct GraphTransaction {
+    edits: Vec<Edit>,
+}
+
+#[derive(Debug, Clone)]
+enum Edit {
+    Connect {
+        source: NodeID,
+        dest: NodeID,
+        ports: Vec<(u32, u32)>,
+    },
+    Disconnect {
+        source: NodeID,
+        dest: NodeID,
+        ports: Vec<(u32, u32)>,
+    },
+    Remove(NodeID),
+}
+
+impl GraphTransaction {
+    /// Connect `source` to `dest` with the given `(output, input)` port pairs.
+    pub fn connect(&mut self, source: NodeID, dest: NodeID, ports: &[(u32, u32)]) -> &mut Self {
+        self.edits.push(Edit::Connect {
+            source,
+            dest,
+            ports: ports.to_vec(),
+        });
+        self
+    }
+
+    /// Disconnect the given `(output, input)` port pairs between `source` and `dest`.
+    pub fn disconnect(&mut self, source: NodeID, dest: NodeID, ports: &[(u32, u32)]) -> &mut Self {
+        self.edits.push(Edit::Disconnect {
+            source,
+            dest,
+            ports: ports.to_vec(),
+        });
+        self
+    }
+
+    /// Remove `node` and all of its edges.
+    ///
+    /// Removals are applied after every connection succeeds.
+    pub fn remove(&mut self, node: NodeID) -> &mut Self {
+        self.edits.push(Edit::Remove(node));
+        self
+    }
+
+    /// The number of queued edits.
+    pub fn len(&self) -> usize {
+        self.edits.len()
+    }
+
+    /// Returns `true` if no edits are queued.
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Ensure every edit refers to a node that will exist when it's applied,
+    /// and that every removal is allowed.
+    fn validate(&self, context: &FirewheelContext) -> Result<(), TransactionError> {
+        let mut removed = HashSet::new();
+        let check = |index, node: NodeID, removed: &HashSet<NodeID>| {
+            if removed.contains(&node) || context.node_info(node).is_none() {
+                Err(TransactionError::MissingNode { index, node })
+            } else {
+                Ok(())
+            }
+        };
+
+        for (index, edit) in self.edits.iter().enumerate() {
+            match edit {
+                Edit::Connect { source, dest, .. } | Edit::Disconnect { source, dest, .. } => {
+                    check(index, *source, &removed)?;
+                    check(index, *dest, &removed)?;
+                }
+                Edit::Remove(node) => {
+                    check(index, *node, &removed)?;
+
+                    if *node == context.graph_in_node_id() || *node == context.graph_out_node_id() {
+                        return Err(TransactionError::Removal {
+                            index,
+                            node: *node,
+                            error: "the graph's input and output nodes can't be removed".into(),
+                        });
+                    }
+
+                    removed.insert(*node);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn apply(self, context: &mut FirewheelContext) -> Result<(), TransactionError> {
+        self.validate(context)?;
+
+        let exists = |context: &FirewheelContext, source, dest, (src_port, dst_port)| {
+            context.edges().any(|e| {
+                e.src_node == source
+                    && e.dst_node == dest
+                    && e.src_port == src_port
+                    && e.dst_port == dst_port
+            })
+        };
+
+        // Only edges that actually changed are recorded,
+        // so rolling back leaves the graph as we found it.
+        let mut undo = Vec::new();
+        let mut removals = Vec::new();
+        for (index, edit) in self.edits.into_iter().enumerate() {
+            match edit {
+                Edit::Connect {
+                    source,
+                    dest,
+                    ports,
+                } => {
+                    let ports: Vec<_> = ports
+                        .into_iter()
+                        .filter(|p| !exists(context, source, dest, *p))
+                        .collect();
+
+                    if ports.is_empty() {
+                        continue;
+                    }
+
+                    if let Err(e) = context.connect(source, dest, &ports, true) {
+                        rollback(context, undo);
+                        return Err(TransactionError::Connection {
+                            index,
+                            source,
+                            dest,
+                            error: e.to_string(),
+                        });
+                    }
+
+                    undo.push(Edit::Disconnect {
+                        source,
+                        dest,
+                        ports,
+                    });
+                }
+                Edit::Disconnect {
+                    source,
+                    dest,
+                    ports,
+                } => {
+                    let ports: Vec<_> = ports
+                        .into_iter()
+                        .filter(|p| exists(context, source, dest, *p))
+                        .collect();
+
+                    context.disconnect(source, dest, &ports);
+
+                    undo.push(Edit::Connect {
+                        source,
+                        dest,
+                        ports,
+                    });
+                }
+                Edit::Remove(node) => removals.push(node),
+            }
+        }
+
+        // Removals were validated up front, so they can't fail here.
+        for node in removals {
+            let removed = context.remove_node(node);
+            debug_assert!(removed.is_ok(), "validated removal of {node:?} failed");
+        }
+
+        Ok(())
+    }
+}
+
+/// Revert applied edge edits in reverse order.
+fn rollback(context: &mut FirewheelContext, undo: Vec<Edit>) {
+    for edit in undo.into_iter().rev() {
+        match edit {
+            Edit::Connect {
+                source,
+                dest,
+                ports,
+            } => {
+                let _ = context.connect(source, dest, &ports, false);
+            }
+            Edit::Disconnect {
+                source,
+                dest,
+                ports,
+            } => {
+                context.disconnect(source, dest, &ports);
+            }
+            Edit::Remove(_) => {}
+        }
+    }
+}
+
+/// An error that caused a [`GraphTransaction`] to be rolled back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionError {
+    /// An edit referred to a node that doesn't exist
+    /// or was removed earlier in the transaction.
+    MissingNode {
+        /// The index of the failing edit.
+        index: usize,
+        /// The missing node.
+        node: NodeID,
+    },
+    /// A connection was rejected, for example because it would form a cycle.
+    Connection {
+        /// The index of the failing edit.
+        index: usize,
+        /// The source node.
+        source: NodeID,
+        /// The destination node.
+        dest: NodeID,
+        /// The underlying Firewheel error.
+        error: String,
+    },
+    /// A node can't be removed, such as the graph's input or output.
+    ///
+    /// Removals are checked before any edit is applied,
+    /// so the graph is left untouched.
+    Removal {
+        /// The index of the failing edit.
+        index: usize,
+        /// The node that couldn't be removed.
+        node: NodeID,
+        /// Why the node can't be removed.
+        error: String,
+    },
+}
+
+impl core::fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingNode { index, node } => {
+                write!(
+                    f,
+                    "graph transaction edit {index} refers to missing node {node:?}"
+                )
+            }
+            Self::Connection { index, error, .. } => {
+                write!(
+                    f,
+                    "graph transaction edit {index} failed to connect: {error}"
+                )
+            }
+            Self::Removal { index, error, .. } => {
+                write!(
+                    f,
+                    "graph transaction edit {index} failed to remove node: {error}"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for TransactionError {}
+
+impl AudioContext {
+    /// Apply a batch of graph edits atomically.
+    ///
+    /// Either every edit is applied, taking effect on the next
+    /// update, or the graph is left untouched and an error is returned.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn reroute(
+    ///     node: Single<&FirewheelNode, With<SamplerPool<MusicPool>>>,
+    ///     main_bus: Single<&FirewheelNode, With<MainBus>>,
+    ///     sfx_bus: Single<&FirewheelNode, With<SoundEffectsBus>>,
+    ///     mut context: ResMut<AudioContext>,
+    /// ) -> Result {
+    ///     let (node, main_bus, sfx_bus) = (node.0, main_bus.0, sfx_bus.0);
+    ///
+    ///     context.transaction(|tx| {
+    ///         tx.disconnect(node, main_bus, &[(0, 0), (1, 1)])
+    ///             .connect(node, sfx_bus, &[(0, 0), (1, 1)]);
+    ///     })?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// Edits are validated before any are applied. Connections and
+    /// disconnections are applied in order, while removals are applied
+    /// last. Note that edits made this way bypass the ECS, so
+    /// [`Connect`][crate::prelude::Connect] and friends won't know about them.
+    ///
+    /// # Panics
+    ///
+    /// Like [`AudioContext::with`], this panics if the
+    /// control thread dies while applying the edits.
+    pub fn transaction<F>(&mut self, f: F) -> Result<(), TransactionError>
+    where
+        F: FnOnce(&mut GraphTransaction),
+    {
+        let mut transaction = GraphTransaction::default();
+        f(&mut transaction);
+
+        if transaction.is_empty() {
+            return Ok(());
+        }
+
+        self.with(move |context| transaction.apply(context))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TransactionError;
+    use crate::{
+        prelude::*,
+        test::{prepare_app, run},
+    };
+
+    #[test]
+    fn test_transaction_rollback() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn(VolumeNode::default());
+            commands.spawn(VolumeNode::default());
+        });
+
+        run(
+            &mut app,
+            |nodes: Query<&FirewheelNode, With<VolumeNode>>, mut context: ResMut<AudioContext>| {
+                let nodes: Vec<_> = nodes.iter().map(|n| n.0).collect();
+                let (a, b) = (nodes[0], nodes[1]);
+                let ports = [(0, 0), (1, 1)];
+
+                let result = context.transaction(|tx| {
+                    tx.connect(a, b, &ports).connect(b, a, &ports);
+                });
+
+                assert!(matches!(
+                    result,
+                    Err(TransactionError::Connection { index: 1, .. })
+                ));
+
+                let edges = context.with(move |c| c.edges().filter(|e| e.src_node == a).count());
+                assert_eq!(edges, 0);
+
+                context
+                    .transaction(|tx| {
+                        tx.connect(a, b, &ports);
+                    })
+                    .unwrap();
+
+                let edges = context.with(move |c| c.edges().filter(|e| e.src_node == a).count());
+                assert_eq!(edges, 2);
+            },
+        );
+    }
+
+    #[test]
+    fn test_transaction_invalid_removal() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn(VolumeNode::default());
+            commands.spawn(VolumeNode::default());
+        });
+
+        run(
+            &mut app,
+            |nodes: Query<&FirewheelNode, With<VolumeNode>>, mut context: ResMut<AudioContext>| {
+                let nodes: Vec<_> = nodes.iter().map(|n| n.0).collect();
+                let (a, b) = (nodes[0], nodes[1]);
+                let output = context.with(|c| c.graph_out_node_id());
+                let ports = [(0, 0), (1, 1)];
+
+                let result = context.transaction(|tx| {
+                    tx.connect(a, b, &ports).remove(b).remove(output);
+                });
+
+                assert!(matches!(
+                    result,
+                    Err(TransactionError::Removal { index: 2, .. })
+                ));
+
+                // Neither the connection nor the earlier removal was applied.
+                let (edges, has_b) = context.with(move |c| {
+                    (
+                        c.edges().filter(|e| e.src_node == a).count(),
+                        c.node_info(b).is_some(),
+                    )
+                });
+                assert_eq!(edges, 0);
+                assert!(has_b);
+            },
+        );
+    }
+}
@@ -2,6 +2,7 @@ use core::cell::RefCell;
 use firewheel::{FirewheelConfig, FirewheelContext};
 
 use super::{AudioThreadState, LocalStore};
+use crate::error::SeedlingError;
 
 thread_local! {
     static CONTEXT: RefCell<AudioThreadState> = panic!("audio context should be initialized");
@@ -9,7 +10,7 @@ thread_local! {
 
 /// A simple, single-threaded context wrapper.
 #[derive(Debug)]
-pub struct InnerContext(());
+pub struct InnerContext(FirewheelConfig);
 
 impl InnerContext {
     /// Spawn the audio process and control thread.
@@ -17,12 +18,21 @@ impl InnerContext {
     pub fn new(settings: FirewheelConfig) -> Self {
         CONTEXT.set(AudioThreadState::new(settings));
 
-        Self(())
+        Self(settings)
+    }
+
+    /// Replace the audio context with a fresh one.
+    #[inline(always)]
+    pub fn restart(&mut self) {
+        CONTEXT.set(AudioThreadState::new(self.0));
     }
 
     /// Operate on the underlying context.
+    ///
+    /// Panics can't be recovered on this platform,
+    /// so this always succeeds.
     #[inline(always)]
-    pub fn with_store<F, O>(&mut self, f: F) -> O
+    pub fn with_store<F, O>(&mut self, f: F) -> Result<O, SeedlingError>
     where
         F: FnOnce(&mut FirewheelContext, &mut LocalStore) -> O + Send,
         O: Send + 'static,
@@ -30,7 +40,7 @@ impl InnerContext {
         CONTEXT.with(|state| {
             let mut state = state.borrow_mut();
             let AudioThreadState { context, store } = &mut *state;
-            f(context, store)
+            Ok(f(context, store))
         })
     }
 }
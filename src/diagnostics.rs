@@ -41,7 +41,8 @@ fn diagnostic_system(
     mut data: ResMut<AudioProfilingData>,
     mut context: ResMut<AudioContext>,
 ) {
-    context.with(|context| {
+    // A dead audio thread is reported by the context plugin.
+    let _ = context.try_with(|context| {
         let new_data = context.profiling_data();
 
         if new_data.version != data.0.version {
@@ -1,20 +1,23 @@
-use super::{EdgeTarget, NodeMap, PendingEdge};
+use super::{ChannelRange, EdgeTarget, NodeMap, OutputPort, PendingEdge};
 use crate::{
     context::AudioContext,
     edge::ChannelMapping,
     node::{FirewheelNode, FirewheelNodeInfo},
+    profiling::audio_span,
 };
 use bevy_ecs::prelude::*;
 use bevy_log::prelude::*;
+use firewheel::node::NodeID;
 
 #[cfg(feature = "track_location")]
 use core::panic::Location;
 
 /// The set of all pending connections for an entity.
 ///
-/// These connections are drained and synchronized with the
-/// audio graph in the [`SeedlingSystems::Connect`][crate::SeedlingSystems::Connect]
-/// set.
+/// These connections are drained in the
+/// [`SeedlingSystems::Connect`][crate::SeedlingSystems::Connect] set
+/// and applied to the audio graph along with the frame's other
+/// [deferred operations][AudioContext::defer].
 #[derive(Debug, Default, Component)]
 pub struct PendingConnections(Vec<PendingEdge>);
 
@@ -212,6 +215,30 @@ pub trait Connect<'a>: Sized {
         ports: &[(u32, u32)],
     ) -> ConnectCommands<'a>;
 
+    /// Queue a connection from a single [`OutputPort`] of this entity to the target.
+    ///
+    /// This is useful for nodes with several groups of outputs.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// # fn system(mut commands: Commands) {
+    /// let config = CrossoverConfig::default();
+    /// let lows = commands.spawn(VolumeNode::default()).id();
+    ///
+    /// commands
+    ///     .spawn((CrossoverNode::default(), config.clone()))
+    ///     .connect_port(lows, config.port(CrossoverBand::Low));
+    /// # }
+    /// ```
+    ///
+    /// The connection is deferred, finalizing in the
+    /// [`SeedlingSystems::Connect`][crate::SeedlingSystems::Connect] set.
+    #[cfg_attr(feature = "track_location", track_caller)]
+    fn connect_port(self, target: impl Into<EdgeTarget>, port: OutputPort) -> ConnectCommands<'a> {
+        self.connect_with(target, &port.connections())
+    }
+
     /// Chain a node's output into this node's input.
     ///
     /// This allows you to easily build up effects chains.
@@ -447,9 +474,11 @@ pub(crate) fn process_connections(
         &FirewheelNode,
         &FirewheelNodeInfo,
         &ChannelMapping,
+        Option<&ChannelRange>,
     )>,
     targets: Query<(&FirewheelNode, &FirewheelNodeInfo)>,
     node_map: Res<NodeMap>,
+    mut edges: ResMut<super::GraphEdges>,
     mut context: ResMut<AudioContext>,
 ) {
     let connections = connections
@@ -461,43 +490,68 @@ pub(crate) fn process_connections(
         return;
     }
 
-    context.with(|context| {
-        for (mut pending, source_node, source_info, source_mapping) in connections.into_iter() {
-            pending.0.retain(|connection| {
-                let Some((target_node, target_info)) =
-                    super::fetch_target(connection, &node_map, &targets, context)
-                else {
-                    return false;
-                };
-
-                let inferred_ports;
-                let ports = match connection.ports.as_deref() {
-                    Some(ports) => ports,
-                    None => {
-                        let outputs = source_info.channel_config.num_outputs.get();
-                        let inputs = target_info.channel_config.num_inputs.get();
-
-                        inferred_ports = source_mapping.map_channels(outputs, inputs);
-
-                        inferred_ports.as_slice()
-                    }
-                };
+    edges.mark_dirty();
+
+    audio_span!("seedling_connect");
+    let mut planned = Vec::new();
+    for (mut pending, source_node, source_info, source_mapping, source_range) in connections {
+        for connection in pending.0.drain(..) {
+            let Some(target) = super::resolve_target(&connection, &node_map, &targets) else {
+                continue;
+            };
+
+            planned.push(PlannedConnection {
+                source: source_node.0,
+                outputs: source_info.channel_config.num_outputs.get(),
+                mapping: source_mapping.clone(),
+                range: source_range.copied(),
+                target,
+                ports: connection.ports,
+            });
+        }
+    }
 
-                if let Err(e) = context.connect(source_node.0, target_node, ports, false) {
-                    error_once!("failed to connect audio node to target: {e}");
-                }
+    // Connections don't produce anything the ECS needs, so
+    // they're batched with the frame's other deferred operations.
+    context.defer(move |context| {
+        for connection in planned {
+            let Some((target_node, target_info)) = connection.target.fetch(context) else {
+                continue;
+            };
 
-                false
+            let ports = connection.ports.unwrap_or_else(|| {
+                let inputs = target_info.channel_config.num_inputs.get();
+
+                match connection.range {
+                    Some(range) => {
+                        range.map_channels(&connection.mapping, connection.outputs, inputs)
+                    }
+                    None => connection.mapping.map_channels(connection.outputs, inputs),
+                }
             });
+
+            if let Err(e) = context.connect(connection.source, target_node, &ports, false) {
+                error_once!("failed to connect audio node to target: {e}");
+            }
         }
     });
 }
 
+/// A connection resolved against the ECS, awaiting the audio context.
+struct PlannedConnection {
+    source: NodeID,
+    outputs: u32,
+    mapping: ChannelMapping,
+    range: Option<ChannelRange>,
+    target: super::ResolvedTarget,
+    ports: Option<Vec<(u32, u32)>>,
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
         context::AudioContext,
-        edge::AudioGraphOutput,
+        edge::{AudioGraphOutput, AutoConnect, DefaultRoute},
         prelude::MainBus,
         test::{prepare_app, run},
     };
@@ -654,6 +708,38 @@ mod test {
         assert!(connected);
     }
 
+    #[test]
+    fn test_default_route() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.insert_resource(AutoConnect::Disabled);
+
+            let two = commands.spawn((VolumeNode::default(), Two)).id();
+            commands.entity(two).connect(AudioGraphOutput);
+            commands.spawn((VolumeNode::default(), One, DefaultRoute::new(two)));
+            commands.spawn((VolumeNode::default(), Three));
+        });
+
+        let (routed, unrouted) = run(
+            &mut app,
+            |one: Single<&FirewheelNode, With<One>>,
+             two: Single<&FirewheelNode, With<Two>>,
+             three: Single<&FirewheelNode, With<Three>>,
+             mut context: ResMut<AudioContext>| {
+                context.with(|context| {
+                    let routed = context
+                        .edges()
+                        .any(|e| e.src_node == one.0 && e.dst_node == two.0);
+                    let unrouted = context.edges().all(|e| e.src_node != three.0);
+
+                    (routed, unrouted)
+                })
+            },
+        );
+
+        assert!(routed);
+        assert!(unrouted);
+    }
+
     #[test]
     fn test_downmix() {
         let mut app = prepare_app(|mut commands: Commands| {
@@ -710,4 +796,52 @@ mod test {
 
         assert!(connected);
     }
+
+    #[test]
+    fn test_channel_range() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            let interface = commands
+                .spawn((
+                    VolumeNode::default(),
+                    VolumeNodeConfig {
+                        channels: NonZeroChannelCount::new(6).unwrap(),
+                    },
+                    Two,
+                ))
+                .id();
+
+            commands
+                .spawn((VolumeNode::default(), ChannelRange::new(2, 2), One))
+                .connect(interface);
+
+            // The range is clipped to the target's inputs.
+            commands
+                .spawn((VolumeNode::default(), ChannelRange::new(5, 2), Three))
+                .connect(interface);
+        });
+
+        let ports = run(
+            &mut app,
+            |one: Single<&FirewheelNode, With<One>>,
+             three: Single<&FirewheelNode, With<Three>>,
+             mut context: ResMut<AudioContext>| {
+                let (one, three) = (one.0, three.0);
+                context.with(move |context| {
+                    let ports = |source| {
+                        let mut ports: Vec<_> = context
+                            .edges()
+                            .filter(|e| e.src_node == source)
+                            .map(|e| (e.src_port, e.dst_port))
+                            .collect();
+                        ports.sort();
+                        ports
+                    };
+
+                    (ports(one), ports(three))
+                })
+            },
+        );
+
+        assert_eq!(ports, (vec![(0, 2), (1, 3)], vec![(0, 5)]));
+    }
 }
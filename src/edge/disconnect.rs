@@ -10,9 +10,10 @@ use core::panic::Location;
 
 /// The set of all pending disconnections for an entity.
 ///
-/// These disconnections are drained and synchronized with the
-/// audio graph in the [`SeedlingSystems::Connect`][crate::SeedlingSystems::Connect]
-/// set.
+/// These disconnections are drained in the
+/// [`SeedlingSystems::Connect`][crate::SeedlingSystems::Connect] set
+/// and applied to the audio graph along with the frame's other
+/// [deferred operations][AudioContext::defer].
 #[derive(Debug, Default, Component)]
 pub struct PendingDisconnections(Vec<PendingEdge>);
 
@@ -142,6 +143,7 @@ pub(crate) fn process_disconnections(
     mut disconnections: Query<(&mut PendingDisconnections, &FirewheelNode)>,
     targets: Query<(&FirewheelNode, &FirewheelNodeInfo)>,
     node_map: Res<NodeMap>,
+    mut edges: ResMut<super::GraphEdges>,
     mut context: ResMut<AudioContext>,
 ) {
     let disconnections = disconnections
@@ -153,33 +155,34 @@ pub(crate) fn process_disconnections(
         return;
     }
 
-    context.with(|context| {
-        for (mut pending, source_node) in disconnections.into_iter() {
-            pending.0.retain(|disconnections| {
-                let Some((target_node, _target_info)) =
-                    super::fetch_target(disconnections, &node_map, &targets, context)
-                else {
-                    return false;
-                };
-
-                let existing_connections;
-                let ports = match disconnections.ports.as_deref() {
-                    Some(ports) => ports,
-                    None => {
-                        existing_connections = context
-                            .edges()
-                            .filter(|e| e.src_node == source_node.0 && e.dst_node == target_node)
-                            .map(|e| (e.src_port, e.dst_port))
-                            .collect::<Vec<_>>();
-
-                        existing_connections.as_slice()
-                    }
-                };
-
-                context.disconnect(source_node.0, target_node, ports);
-
-                false
+    edges.mark_dirty();
+
+    let mut planned = Vec::new();
+    for (mut pending, source_node) in disconnections {
+        for disconnection in pending.0.drain(..) {
+            let Some(target) = super::resolve_target(&disconnection, &node_map, &targets) else {
+                continue;
+            };
+
+            planned.push((source_node.0, target, disconnection.ports));
+        }
+    }
+
+    context.defer(move |context| {
+        for (source, target, ports) in planned {
+            let Some((target_node, _)) = target.fetch(context) else {
+                continue;
+            };
+
+            let ports = ports.unwrap_or_else(|| {
+                context
+                    .edges()
+                    .filter(|e| e.src_node == source && e.dst_node == target_node)
+                    .map(|e| (e.src_port, e.dst_port))
+                    .collect()
             });
+
+            context.disconnect(source, target_node, &ports);
         }
     });
 }
@@ -1,15 +1,15 @@
 //! Node connection and disconnection utilities.
 
 use crate::SeedlingSystems;
-use crate::context::AudioContext;
 use crate::node::FirewheelNodeInfo;
 use crate::node::label::InternedNodeLabel;
 use crate::prelude::{FirewheelNode, MainBus, NodeLabel};
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 use bevy_log::error_once;
-use bevy_platform::collections::HashMap;
+use bevy_platform::collections::{HashMap, HashSet};
 use firewheel::FirewheelContext;
+use firewheel::graph::Edge;
 use firewheel::node::NodeID;
 
 #[cfg(feature = "track_location")]
@@ -18,27 +18,32 @@ use core::panic::Location;
 #[allow(clippy::module_inception)]
 mod connect;
 mod disconnect;
+mod route;
 
 pub use connect::*;
 pub use disconnect::*;
+pub use route::BusQuery;
 
 pub(super) struct EdgePlugin;
 
 impl Plugin for EdgePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<NodeMap>().add_systems(
-            Last,
-            (
-                auto_connect
-                    .before(SeedlingSystems::Connect)
-                    .after(SeedlingSystems::Acquire),
-                // we process disconnections before connections to allow
-                // same-frame disconnect-then-reconnect functionality
-                (process_disconnections, process_connections)
-                    .chain()
-                    .in_set(SeedlingSystems::Connect),
-            ),
-        );
+        app.init_resource::<NodeMap>()
+            .init_resource::<AutoConnect>()
+            .init_resource::<GraphEdges>()
+            .add_systems(
+                Last,
+                (
+                    auto_connect
+                        .before(SeedlingSystems::Connect)
+                        .after(SeedlingSystems::Acquire),
+                    // we process disconnections before connections to allow
+                    // same-frame disconnect-then-reconnect functionality
+                    (process_disconnections, process_connections)
+                        .chain()
+                        .in_set(SeedlingSystems::Connect),
+                ),
+            );
     }
 }
 
@@ -247,6 +252,104 @@ impl From<Entity> for EdgeTarget {
     }
 }
 
+/// A contiguous group of a node's output channels.
+///
+/// Most nodes have a single group of outputs, such as a stereo pair.
+/// Some nodes, like [`CrossoverNode`][crate::prelude::CrossoverNode], expose
+/// several groups laid out one after another. For example, a stereo
+/// node with three ports has the outputs `[0, 1]`, `[2, 3]`, and `[4, 5]`.
+///
+/// [`Connect::connect_port`] connects a single port to a target's inputs,
+/// starting at the target's first input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct OutputPort {
+    /// The index of the port's first output channel.
+    pub start: u32,
+    /// The number of channels in the port.
+    pub channels: u32,
+}
+
+impl OutputPort {
+    /// Construct a new [`OutputPort`].
+    pub const fn new(start: u32, channels: u32) -> Self {
+        Self { start, channels }
+    }
+
+    /// Get the port mapping that connects this port to
+    /// a target's inputs, beginning at input `0`.
+    ///
+    /// ```
+    /// # use bevy_seedling::edge::OutputPort;
+    /// assert_eq!(OutputPort::new(2, 2).connections(), [(2, 0), (3, 1)]);
+    /// ```
+    pub fn connections(&self) -> Vec<(u32, u32)> {
+        (0..self.channels).map(|i| (self.start + i, i)).collect()
+    }
+}
+
+/// Routes a node's outputs to a range of its targets' inputs.
+///
+/// This is useful for multi-channel interfaces, where a bus should
+/// be sent to specific output channels instead of the first few.
+/// Channels are zero-indexed, so sending music to a stereo pair
+/// on channels 3 and 4 looks like:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::{prelude::*, edge::AudioGraphOutput};
+/// fn route_music(mut commands: Commands) {
+///     commands
+///         .spawn((VolumeNode::default(), ChannelRange::new(2, 2)))
+///         .connect(AudioGraphOutput);
+/// }
+/// ```
+///
+/// The node's outputs are mapped onto the range according to its
+/// [`ChannelMapping`], and inputs beyond the target's channel count
+/// are dropped. Connections with explicit port mappings, such as
+/// those made with [`Connect::connect_with`], ignore this component.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct ChannelRange {
+    /// The index of the first input channel.
+    pub start: u32,
+    /// The number of input channels.
+    pub channels: u32,
+}
+
+impl ChannelRange {
+    /// Construct a new [`ChannelRange`].
+    pub const fn new(start: u32, channels: u32) -> Self {
+        Self { start, channels }
+    }
+
+    /// Get the port mapping that connects `outputs` channels to
+    /// this range of a target's `inputs`.
+    ///
+    /// ```
+    /// # use bevy_seedling::edge::{ChannelMapping, ChannelRange};
+    /// let range = ChannelRange::new(2, 2);
+    /// assert_eq!(
+    ///     range.map_channels(&ChannelMapping::Speakers, 2, 8),
+    ///     [(0, 2), (1, 3)]
+    /// );
+    /// ```
+    pub fn map_channels(
+        &self,
+        mapping: &ChannelMapping,
+        outputs: u32,
+        inputs: u32,
+    ) -> Vec<(u32, u32)> {
+        mapping
+            .map_channels(outputs, self.channels)
+            .into_iter()
+            .map(|(output, input)| (output, input + self.start))
+            .filter(|(_, input)| *input < inputs)
+            .collect()
+    }
+}
+
 /// A map that associates [`NodeLabel`]s with audio
 /// graph nodes.
 ///
@@ -270,33 +373,225 @@ impl core::ops::DerefMut for NodeMap {
     }
 }
 
-/// Automatically connect nodes without manual connections to the main bus.
+/// A snapshot of the audio graph's edges, keyed by destination.
+///
+/// Systems that modify the graph's edges mark this as dirty,
+/// and it's rebuilt during the next event flush. This lets
+/// readers trace the graph without locking the context.
+///
+/// Edges changed directly through
+/// [`AudioContext::with`][crate::prelude::AudioContext::with]
+/// aren't tracked until the next ECS-driven change.
+///
+/// The snapshot is also used to reconnect the graph
+/// after the audio thread dies. See
+/// [`AudioThreadDied`][crate::context::AudioThreadDied].
+#[derive(Debug, Resource)]
+pub struct GraphEdges {
+    edges: Vec<Edge>,
+    upstream: HashMap<NodeID, Vec<NodeID>>,
+    dirty: bool,
+}
+
+impl Default for GraphEdges {
+    fn default() -> Self {
+        Self {
+            edges: Vec::new(),
+            upstream: HashMap::default(),
+            dirty: true,
+        }
+    }
+}
+
+impl GraphEdges {
+    /// Request a rebuild during the next flush.
+    pub(crate) fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Returns `true` if the snapshot is awaiting a rebuild.
+    pub(crate) fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Rebuild the snapshot from the context's current edges.
+    pub(crate) fn refresh(&mut self, context: &FirewheelContext) {
+        self.edges.clear();
+        self.upstream.clear();
+        for edge in context.edges() {
+            let sources = self.upstream.entry(edge.dst_node).or_default();
+            if !sources.contains(&edge.src_node) {
+                sources.push(edge.src_node);
+            }
+
+            self.edges.push(edge.clone());
+        }
+
+        self.dirty = false;
+    }
+
+    /// Every edge in the snapshot.
+    pub(crate) fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    /// Collect every node that feeds into `node`, including `node` itself.
+    pub fn upstream_of(&self, node: NodeID) -> HashSet<NodeID> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![node];
+
+        while let Some(node) = stack.pop() {
+            if !visited.insert(node) {
+                continue;
+            }
+
+            if let Some(sources) = self.upstream.get(&node) {
+                stack.extend(sources.iter().copied());
+            }
+        }
+
+        visited
+    }
+}
+
+/// Queue the edges from the last [`GraphEdges`] snapshot as pending connections.
+///
+/// This lets a fresh graph be reconnected between the same entities
+/// once their nodes are acquired again. Pending disconnections refer
+/// to the old graph, so they're dropped.
+pub(crate) fn requeue_edges(world: &mut World) {
+    let entities: HashMap<NodeID, Entity> = world
+        .query::<(Entity, &FirewheelNode)>()
+        .iter(world)
+        .map(|(entity, node)| (node.0, entity))
+        .collect();
+
+    let mut graph_edges = world.resource_mut::<GraphEdges>();
+    let snapshot = core::mem::take(&mut graph_edges.edges);
+    graph_edges.upstream.clear();
+    graph_edges.mark_dirty();
+
+    let mut grouped: HashMap<(Entity, Entity), Vec<(u32, u32)>> = HashMap::default();
+    for edge in snapshot {
+        let (Some(source), Some(target)) =
+            (entities.get(&edge.src_node), entities.get(&edge.dst_node))
+        else {
+            continue;
+        };
+
+        grouped
+            .entry((*source, *target))
+            .or_default()
+            .push((edge.src_port, edge.dst_port));
+    }
+
+    for entity in entities.values() {
+        world.entity_mut(*entity).remove::<PendingDisconnections>();
+    }
+
+    for ((source, target), ports) in grouped {
+        let edge = PendingEdge::new(target, Some(ports));
+        let mut source = world.entity_mut(source);
+
+        match source.get_mut::<PendingConnections>() {
+            Some(mut pending) => pending.push(edge),
+            None => {
+                let mut pending = PendingConnections::default();
+                pending.push(edge);
+                source.insert(pending);
+            }
+        }
+    }
+}
+
+/// Determines where nodes without explicit connections are routed.
+///
+/// By default, any node with outputs that isn't explicitly connected is
+/// routed to the [`MainBus`]. This resource can redirect that fallback,
+/// or disable it entirely for strictly explicit routing.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn strict_routing(app: &mut App) {
+///     app.insert_resource(AutoConnect::Disabled);
+/// }
+/// ```
+///
+/// Individual nodes can override this with [`DefaultRoute`].
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub enum AutoConnect {
+    /// Connect unrouted nodes to the given target.
+    ///
+    /// This defaults to the [`MainBus`].
+    Enabled(EdgeTarget),
+    /// Leave unrouted nodes disconnected.
+    Disabled,
+}
+
+impl Default for AutoConnect {
+    fn default() -> Self {
+        Self::Enabled(MainBus.into())
+    }
+}
+
+/// Overrides the [`AutoConnect`] target for a single node.
+///
+/// If the node has no explicit connections, it'll be routed to this
+/// target, even when [`AutoConnect::Disabled`] is set.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn spawn_ambience(mut commands: Commands) {
+///     commands.spawn((VolumeNode::default(), DefaultRoute::new(SoundEffectsBus)));
+/// }
+/// ```
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct DefaultRoute(pub EdgeTarget);
+
+impl DefaultRoute {
+    /// Construct a new [`DefaultRoute`].
+    pub fn new(target: impl Into<EdgeTarget>) -> Self {
+        Self(target.into())
+    }
+}
+
+/// Automatically connect nodes without manual connections
+/// according to [`DefaultRoute`] and [`AutoConnect`].
 ///
 /// Importantly, this should _only_ apply connections to nodes that have
 /// outputs.
 pub(crate) fn auto_connect(
-    nodes: Query<(Entity, &FirewheelNode), Without<PendingConnections>>,
-    mut context: ResMut<AudioContext>,
+    nodes: Query<
+        (Entity, &FirewheelNodeInfo, Option<&DefaultRoute>),
+        (With<FirewheelNode>, Without<PendingConnections>),
+    >,
+    auto_connect: Res<AutoConnect>,
     mut commands: Commands,
 ) {
-    if nodes.iter().len() == 0 {
-        return;
-    }
-
-    context.with(|context| {
-        for (entity, node) in nodes.iter() {
-            let Some(info) = context.node_info(node.0) else {
-                continue;
-            };
+    // The node info mirrors the graph, so there's
+    // no need to query the audio context here.
+    for (entity, info, route) in nodes.iter() {
+        let outputs = info.channel_config.num_outputs.get();
+        if outputs == 0 {
+            continue;
+        }
 
-            let outputs = info.info.channel_config.num_outputs.get();
-            if outputs == 0 {
+        let target = match (route, auto_connect.as_ref()) {
+            (Some(route), _) => route.0.clone(),
+            (None, AutoConnect::Enabled(target)) => target.clone(),
+            (None, AutoConnect::Disabled) => {
+                // Mark the node as handled so it's not revisited.
+                commands
+                    .entity(entity)
+                    .insert(PendingConnections::default());
                 continue;
             }
+        };
 
-            commands.entity(entity).connect(MainBus);
-        }
-    });
+        commands.entity(entity).connect(target);
+    }
 }
 
 fn lookup_node<'a>(
@@ -327,16 +622,42 @@ fn lookup_node<'a>(
     }
 }
 
-fn fetch_target(
+/// A connection target resolved against the ECS.
+///
+/// Raw [`NodeID`] targets can only be checked against the
+/// audio context, so they're validated when the edge is applied.
+#[derive(Debug, Clone, Copy)]
+enum ResolvedTarget {
+    Node(NodeID, FirewheelNodeInfo),
+    Unchecked(NodeID),
+}
+
+impl ResolvedTarget {
+    fn fetch(self, context: &FirewheelContext) -> Option<(NodeID, FirewheelNodeInfo)> {
+        match self {
+            Self::Node(node, info) => Some((node, info)),
+            Self::Unchecked(node) => {
+                let Some(info) = context.node_info(node) else {
+                    error_once!(
+                        "failed to connect audio node to target: the target `NodeID` doesn't exist"
+                    );
+                    return None;
+                };
+
+                Some((node, FirewheelNodeInfo::new(info)))
+            }
+        }
+    }
+}
+
+fn resolve_target(
     connection: &PendingEdge,
     node_map: &NodeMap,
     targets: &Query<(&FirewheelNode, &FirewheelNodeInfo)>,
-    context: &FirewheelContext,
-) -> Option<(NodeID, FirewheelNodeInfo)> {
+) -> Option<ResolvedTarget> {
     match connection.target {
-        EdgeTarget::Entity(entity) => {
-            lookup_node(entity, connection, targets).map(|(node, info)| (node.0, *info))
-        }
+        EdgeTarget::Entity(entity) => lookup_node(entity, connection, targets)
+            .map(|(node, info)| ResolvedTarget::Node(node.0, *info)),
         EdgeTarget::Label(label) => {
             let Some(entity) = node_map.get(&label) else {
                 #[cfg(feature = "track_location")]
@@ -354,19 +675,10 @@ fn fetch_target(
                 return None;
             };
 
-            lookup_node(*entity, connection, targets).map(|(node, info)| (node.0, *info))
-        }
-        EdgeTarget::Node(dest_node) => {
-            let Some(info) = context.node_info(dest_node) else {
-                error_once!(
-                    "failed to connect audio node to target: the target `NodeID` doesn't exist"
-                );
-                return None;
-            };
-            let info = FirewheelNodeInfo::new(info);
-
-            Some((dest_node, info))
+            lookup_node(*entity, connection, targets)
+                .map(|(node, info)| ResolvedTarget::Node(node.0, *info))
         }
+        EdgeTarget::Node(dest_node) => Some(ResolvedTarget::Unchecked(dest_node)),
     }
 }
 
@@ -0,0 +1,111 @@
+use super::{EdgeTarget, GraphEdges, NodeMap};
+use crate::{node::FirewheelNode, pool::Sampler, prelude::SamplePlayer};
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_platform::collections::HashSet;
+use firewheel::node::NodeID;
+
+/// A system parameter for querying the routing of the audio graph.
+///
+/// Since connections live in the audio graph rather than the ECS,
+/// finding everything that feeds into a bus requires walking the
+/// graph's edges. [`BusQuery`] does this for you.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// #[derive(NodeLabel, PartialEq, Eq, Debug, Hash, Clone)]
+/// struct SfxBus;
+///
+/// fn pause_sfx(buses: BusQuery, mut players: Query<&mut PlaybackSettings>) {
+///     for player in buses.samples_of(SfxBus).collect::<Vec<_>>() {
+///         if let Ok(mut settings) = players.get_mut(player) {
+///             settings.pause();
+///         }
+///     }
+/// }
+/// ```
+///
+/// Routing is read from the [`GraphEdges`] snapshot, so this doesn't
+/// lock the audio context. Connections made this frame are visible
+/// once they've been flushed to the graph.
+#[derive(SystemParam)]
+pub struct BusQuery<'w, 's> {
+    edges: Res<'w, GraphEdges>,
+    node_map: Res<'w, NodeMap>,
+    nodes: Query<'w, 's, (Entity, &'static FirewheelNode)>,
+    samplers: Query<'w, 's, (Entity, &'static Sampler), With<SamplePlayer>>,
+}
+
+impl BusQuery<'_, '_> {
+    /// Iterate over every node entity that feeds into `bus`,
+    /// directly or indirectly.
+    ///
+    /// This does not include `bus` itself.
+    pub fn upstream_of(&self, bus: impl Into<EdgeTarget>) -> impl Iterator<Item = Entity> {
+        let upstream = self.upstream_nodes(bus.into());
+
+        self.nodes
+            .iter()
+            .filter(move |(_, node)| upstream.contains(&node.0))
+            .map(|(entity, _)| entity)
+    }
+
+    /// Iterate over every [`SamplePlayer`] whose assigned sampler
+    /// feeds into `bus`, directly or indirectly.
+    ///
+    /// Only samples that are currently playing or paused in a pool
+    /// have a sampler, so queued samples aren't included.
+    pub fn samples_of(&self, bus: impl Into<EdgeTarget>) -> impl Iterator<Item = Entity> {
+        let upstream = self.upstream_nodes(bus.into());
+        let nodes = &self.nodes;
+
+        self.samplers
+            .iter()
+            .filter(move |(_, sampler)| {
+                nodes
+                    .get(sampler.sampler())
+                    .is_ok_and(|(_, node)| upstream.contains(&node.0))
+            })
+            .map(|(entity, _)| entity)
+    }
+
+    /// Collect the port mappings from `node` to each node it feeds.
+    pub(crate) fn outputs_of(&self, node: Entity) -> Vec<(NodeID, Vec<(u32, u32)>)> {
+        let Some(node) = self.resolve(&node.into()) else {
+            return Vec::new();
+        };
+
+        let mut outputs: Vec<(NodeID, Vec<(u32, u32)>)> = Vec::new();
+        for edge in self.edges.edges().iter().filter(|e| e.src_node == node) {
+            let ports = (edge.src_port, edge.dst_port);
+            match outputs.iter_mut().find(|(node, _)| *node == edge.dst_node) {
+                Some((_, existing)) => existing.push(ports),
+                None => outputs.push((edge.dst_node, vec![ports])),
+            }
+        }
+
+        outputs
+    }
+
+    /// Resolve an [`EdgeTarget`] to its node in the audio graph.
+    fn resolve(&self, target: &EdgeTarget) -> Option<NodeID> {
+        let entity = match target {
+            EdgeTarget::Node(node) => return Some(*node),
+            EdgeTarget::Entity(entity) => *entity,
+            EdgeTarget::Label(label) => *self.node_map.get(label)?,
+        };
+
+        self.nodes.get(entity).ok().map(|(_, node)| node.0)
+    }
+
+    /// Collect the nodes feeding into `bus`.
+    fn upstream_nodes(&self, bus: EdgeTarget) -> HashSet<NodeID> {
+        let Some(bus) = self.resolve(&bus) else {
+            return HashSet::new();
+        };
+
+        let mut upstream = self.edges.upstream_of(bus);
+        upstream.remove(&bus);
+        upstream
+    }
+}
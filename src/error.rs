@@ -45,6 +45,10 @@ pub enum SeedlingError {
     },
     /// Encountered an error when flushing the audio context.
     Update(UpdateError),
+    /// The audio context's control thread panicked.
+    ///
+    /// See [`AudioThreadDied`][crate::context::AudioThreadDied].
+    AudioThreadDead,
 }
 
 impl core::fmt::Display for SeedlingError {
@@ -71,6 +75,9 @@ impl core::fmt::Display for SeedlingError {
             Self::Update(e) => {
                 write!(f, "{e}")
             }
+            Self::AudioThreadDead => {
+                write!(f, "The audio context's control thread panicked")
+            }
         }
     }
 }
@@ -121,6 +121,7 @@
 //! ### Custom nodes
 //! - [Creating and registering nodes][prelude::RegisterNode#creating-and-registering-nodes]
 //! - [Synchronizing ECS and audio types][prelude::RegisterNode#synchronizing-ecs-and-audio-types]
+//! - [Shared DSP utilities, like delay lines and filters][dsp]
 //!
 //! ## Feature flags
 //!
@@ -142,10 +143,20 @@
 //! | `hrtf_subjects`   | Enable all HRTF embedded data.             | No      |
 //! | `loudness`        | Enable LUFS analyzer node.                 | No      |
 //! | `effects`         | Enable extra effects and analyzers.        | No      |
+//! | `itd`             | Enable the ITD spatialization node.        | Yes     |
+//! | `limiter`         | Enable the limiter node.                   | Yes     |
+//! | `envelopes`       | Enable envelope and amplitude followers.   | Yes     |
+//! | `filters`         | Enable crossover and DC blocking nodes.    | Yes     |
+//! | `freeverb`        | Enable the Freeverb reverb node.           | No      |
+//! | `ui`              | Enable screen-space panning for UI sounds. | No      |
+//! | `midi`            | Enable MIDI file playback.                 | No      |
+//! | `sprites`         | Enable audio sprite sheets.                | No      |
+//! | `nodes-all`       | Enable all built-in node families.         | No      |
 //! | `resample_inputs` | Enable audio input resampling.             | No      |
 //! | `dev`             | Enable helpful features for development.   | No      |
 //! | `entity_names`    | Add [`Name`]s to node and sample entities. | No      |
 //! | `track_location`  | Track caller locations in diagnostics.     | No      |
+//! | `validation`      | Warn about problems in loaded samples.     | No      |
 //!
 //! [`RandomPitch`]: crate::prelude::RandomPitch
 //! [`Name`]: bevy_ecs::prelude::Name
@@ -349,8 +360,10 @@ use bevy_asset::prelude::AssetApp;
 use bevy_ecs::prelude::*;
 
 // We re-export Firewheel here for convenience.
+pub use bevy_seedling_dsp as dsp;
 pub use firewheel;
 
+pub mod conditions;
 pub mod context;
 pub mod edge;
 pub mod error;
@@ -358,54 +371,121 @@ pub mod node;
 pub mod nodes;
 pub mod platform;
 pub mod pool;
+mod profiling;
 pub mod sample;
+pub mod settings;
 pub mod spatial;
 pub mod time;
 pub mod utils;
 
 #[cfg(feature = "diagnostics")]
 pub mod diagnostics;
+#[cfg(feature = "midi")]
+pub mod midi;
 
 pub mod prelude {
     //! All `bevy_seedlings`'s important types and traits.
 
-    pub use crate::context::AudioContext;
+    pub use crate::conditions::{any_sample_playing_in, bus_is_silent, pool_has_capacity};
     pub use crate::context::graph::{
-        AudioGraphTemplate, MusicPool, SeedlingStartupSystems, SoundEffectsBus, SpatialPool,
+        AudioGraphTemplate, MainBusDcBlock, MusicPool, PreWarmPools, SeedlingStartupSystems,
+        SoundEffectsBus, SpatialPool,
+    };
+    #[cfg(feature = "envelopes")]
+    pub use crate::context::monitor::{InputMonitor, InputMonitorMuted};
+    pub use crate::context::tee::{OutputTee, OutputTeePlugin};
+    pub use crate::context::{
+        AudioContext, AudioContextScope, AudioThreadDied, AudioThreadRecovery, DspLoad,
+        ResumeAudio, SuspendAudio,
     };
     pub use crate::edge::{
-        AudioGraphInput, AudioGraphOutput, ChannelMapping, Connect, Disconnect, EdgeTarget,
+        AudioGraphInput, AudioGraphOutput, AutoConnect, BusQuery, ChannelMapping, ChannelRange,
+        Connect, DefaultRoute, Disconnect, EdgeTarget, OutputPort,
     };
     pub use crate::node::{
-        AudioBypass, FirewheelNode, RegisterNode,
-        events::{AudioEvents, VolumeFade},
-        label::{MainBus, NodeLabel},
+        AudioBypass, AudioState, FirewheelNode, RegisterNode,
+        effect::{AudioEffect, AudioEffectConfig},
+        events::{
+            AudioEvents, AudioLerp, ParamFade, SpatialFade, TimelineError, TimelineLimit,
+            TimelineOverflow, TimelineOverflowed, TimelinePriority, VolumeFade,
+        },
+        label::{LabelCommands, MainBus, NodeLabel, NodeLabelRegistry},
+        mute::AudioMute,
+        snapshot::{ParamSnapshot, ParamSnapshots, RecallParams, SnapshotCommands, SnapshotParams},
     };
-    #[cfg(feature = "effects")]
+    #[cfg(any(feature = "effects", feature = "freeverb"))]
     pub use crate::nodes::effects::*;
+    #[cfg(feature = "itd")]
+    pub use crate::nodes::itd::{ItdConfig, ItdNode};
+    #[cfg(feature = "limiter")]
+    pub use crate::nodes::limiter::{LimiterConfig, LimiterNode};
     #[cfg(feature = "loudness")]
     pub use crate::nodes::loudness::{LoudnessConfig, LoudnessNode, LoudnessState};
+    #[cfg(feature = "envelopes")]
+    pub use crate::nodes::{
+        amplitude_tracker::{
+            AmplitudeTrackerConfig, AmplitudeTrackerNode, AmplitudeTrackerState, SampleAmplitude,
+        },
+        envelope_follower::{
+            EnvelopeDriven, EnvelopeDriverPlugin, EnvelopeFollowerConfig, EnvelopeFollowerNode,
+            EnvelopeFollowerState,
+        },
+    };
     pub use crate::nodes::{
+        bitcrusher::{BitCrusherConfig, BitCrusherNode},
+        bounce::{BounceBus, BounceComplete},
         core::*,
-        itd::{ItdConfig, ItdNode},
-        limiter::{LimiterConfig, LimiterNode},
+        gate::{GateConfig, GateNode},
+        granular::{GranularConfig, GranularNode, GranularSample},
+        humanize::{HumanizeConfig, HumanizeNode},
+        pan::{PanConfig, PanLaw, PanNode},
         send::{SendConfig, SendNode},
+        stereo_width::StereoWidthNode,
+        synth::{SynthConfig, SynthNode, SynthNote, Waveform},
+    };
+    #[cfg(feature = "filters")]
+    pub use crate::nodes::{
+        crossover::{CrossoverBand, CrossoverConfig, CrossoverNode},
+        dc_block::{DcBlockConfig, DcBlockNode},
     };
     pub use crate::platform::AudioStreamConfig;
     pub use crate::pool::{
-        DefaultPoolSize, PlaybackCompletion, PoolCommands, PoolDespawn, PoolSize, SamplerPool,
-        dynamic::DynamicBus,
+        AssignmentStrategy, DefaultPoolSize, DefaultSamplerConfig, MoveToPool, PlaybackCompletion,
+        PoolBus, PoolBusOf, PoolCommands, PoolDespawn, PoolEntityCommands, PoolSize,
+        PoolSizePolicy, PreWarm, ResolvedPoolSize, Restart, SamplerPool, SetRepeatMode,
+        SetStaticVolume, StealFade, StopAll, StopWhere, TargetPlatform,
+        chain::{ChainEnds, SpawnChain},
+        dynamic::{AcceptDynamic, DynamicBus, DynamicPoolLimits, DynamicPoolStats},
         label::{DefaultPool, PoolLabel},
-        sample_effects::{EffectOf, EffectsQuery, SampleEffects},
+        sample_effects::{
+            EffectChain, EffectLabel, EffectOf, EffectsBuilder, EffectsQuery, RetainSharedEffects,
+            SampleEffects, SharedEffects, SharedEffectsUsers,
+        },
+        scaling::{Virtualized, VoiceBudget, VoiceScaling},
     };
     pub use crate::sample::{
-        AudioSample, OnComplete, PlaybackSettings, SamplePlayer, SamplePriority,
+        AudioSample, CompletionTrigger, DetachOnParentDespawn, EnvelopeFollowsPlayback, OnComplete,
+        OrphanSounds, PlayCommands, PlaybackDirection, PlaybackSettings, SamplePlayer,
+        SamplePriority, SampleQueue, SampleQueueItemComplete, SmoothSeek, StartDelay, TapeStart,
+        TapeStop,
     };
     pub use crate::sample_effects;
+    #[cfg(feature = "cpal")]
+    pub use crate::settings::SelectOutputDevice;
+    pub use crate::settings::{
+        AdjustVolume, AudioSettings, AudioSettingsPlugin, OutputDevices, RefreshOutputDevices,
+        SetVolume, StreamLatency, StreamLatencyChanged, VolumeChannel,
+    };
+    #[cfg(feature = "effects")]
+    pub use crate::spatial::AirAbsorption;
     pub use crate::spatial::{
-        DefaultSpatialScale, SpatialListener2D, SpatialListener3D, SpatialScale,
+        DefaultSpatialScale, HeadLocked, ListenerBlend, ListenerBus, ListenerBusOf, ListenerMask,
+        ListenerOutput, ListenerPool, ListenerRelative, ListenerTarget, NearField, PerListener,
+        SpatialListener2D, SpatialListener3D, SpatialScale,
     };
-    pub use crate::time::{Audio, AudioTime};
+    #[cfg(feature = "ui")]
+    pub use crate::spatial::{ScreenSource, ScreenSpacePan};
+    pub use crate::time::{Audio, AudioClockResync, AudioTime, Tempo};
     pub use crate::utils::perceptual_volume::PerceptualVolume;
     pub use crate::{SeedlingPlugins, SeedlingSystems};
 
@@ -429,7 +509,13 @@ pub mod prelude {
     pub use firewheel_ircam_hrtf::{self as hrtf, HrtfConfig, HrtfNode};
 
     #[cfg(feature = "rand")]
-    pub use crate::sample::RandomPitch;
+    pub use crate::sample::{PitchRngSource, RandomPitch, RandomSeed};
+
+    #[cfg(feature = "midi")]
+    pub use crate::midi::{MidiPlayer, MidiSequence, MidiTarget};
+
+    #[cfg(feature = "sprites")]
+    pub use crate::sample::AudioSpriteSheet;
 }
 
 /// Sets for all `bevy_seedling` systems.
@@ -482,6 +568,9 @@ plugin_group! {
 
         #[cfg(feature = "diagnostics")]
         diagnostics:::AudioDiagnosticsPlugin,
+
+        #[cfg(feature = "validation")]
+        sample:::SampleValidationPlugin,
     }
 }
 
@@ -499,6 +588,7 @@ impl Plugin for SeedlingCorePlugin {
         use prelude::*;
 
         app.init_resource::<pool::DefaultPoolSize>()
+            .init_resource::<pool::DefaultSamplerConfig>()
             .init_asset::<sample::AudioSample>();
 
         app.configure_sets(
@@ -515,18 +605,31 @@ impl Plugin for SeedlingCorePlugin {
 
         app.add_plugins((
             context::ContextPlugin,
+            platform::PlatformPlugin,
             node::NodePlugin,
             edge::EdgePlugin,
             pool::SamplePoolPlugin,
             nodes::SeedlingNodesPlugin,
             spatial::SpatialPlugin,
             time::TimePlugin,
+            sample::DetachPlugin,
+            sample::EnvelopePlugin,
+            sample::TapePlugin,
+            #[cfg(feature = "profiling")]
+            profiling::ProfilingPlugin,
             #[cfg(feature = "rand")]
             sample::RandomPlugin,
             #[cfg(feature = "symphonia")]
             sample::SymphoniumLoaderPlugin,
+            #[cfg(feature = "midi")]
+            midi::MidiPlugin,
         ));
 
+        app.add_plugins(settings::StreamLatencyPlugin);
+
+        #[cfg(feature = "sprites")]
+        app.add_plugins(sample::AudioSpritePlugin);
+
         #[cfg(feature = "reflect")]
         app.register_type::<SamplerPool<MusicPool>>()
             .register_type::<SamplerPool<DefaultPool>>()
@@ -536,20 +639,28 @@ impl Plugin for SeedlingCorePlugin {
 
 #[cfg(test)]
 mod test {
-    use crate::{node::DiffRate, platform::mock::MockBackendPlugin, prelude::*};
+    use crate::{node::DiffRate, platform::null::NullBackendPlugin, prelude::*};
     use bevy::{ecs::system::RunSystemOnce, prelude::*};
     use firewheel::nodes::fast_filters::lowpass::FastLowpassNode;
 
     pub fn prepare_app<F: IntoSystem<(), (), M>, M>(startup: F) -> App {
+        prepare_app_with((), startup)
+    }
+
+    pub fn prepare_app_with<P: bevy::app::Plugins<PM>, PM, F: IntoSystem<(), (), M>, M>(
+        plugins: P,
+        startup: F,
+    ) -> App {
         let mut app = App::new();
 
         app.add_plugins((
             MinimalPlugins,
             AssetPlugin::default(),
             crate::SeedlingCorePlugin,
-            MockBackendPlugin,
+            NullBackendPlugin,
             TransformPlugin,
         ))
+        .add_plugins(plugins)
         .insert_resource(DiffRate(std::time::Duration::from_secs_f32(0f32)))
         .insert_resource(AudioGraphTemplate::Empty)
         .register_node::<FastLowpassNode>()
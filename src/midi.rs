@@ -0,0 +1,496 @@
+//! MIDI file playback.
+//!
+//! A [`MidiSequence`] can be loaded from standard MIDI files (`.mid` or `.midi`)
+//! and played with a [`MidiPlayer`], which schedules each note on the audio
+//! clock. Notes can drive a [`SynthNode`][crate::prelude::SynthNode] or spawn
+//! pitched [`SamplePlayer`]s, making tracker-style music possible without
+//! pre-rendered stems.
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use bevy_seedling::prelude::*;
+//! fn play_song(mut commands: Commands, server: Res<AssetServer>) {
+//!     commands.spawn((
+//!         SynthNode {
+//!             waveform: Waveform::Square,
+//!             ..Default::default()
+//!         },
+//!         MidiPlayer::new(server.load("songs/overworld.mid")).looping(),
+//!     ));
+//! }
+//! ```
+
+use crate::{
+    node::SchedulingConfig,
+    nodes::synth::SynthNote,
+    prelude::{Audio, AudioEvents, PlaybackSettings, SamplePlayer, SeedlingSystems},
+    time::AudioTime,
+};
+use bevy_app::prelude::*;
+use bevy_asset::{Asset, AssetApp, AssetLoader, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_reflect::TypePath;
+use firewheel::{
+    Volume,
+    clock::{DurationSeconds, InstantSeconds},
+};
+
+pub(crate) struct MidiPlugin;
+
+impl Plugin for MidiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<MidiSequence>()
+            .init_asset_loader::<MidiLoader>()
+            .add_systems(
+                Last,
+                sequence_midi
+                    .after(SeedlingSystems::Acquire)
+                    .before(SeedlingSystems::Queue),
+            )
+            .add_observer(release_notes);
+    }
+}
+
+/// A single note event in a [`MidiSequence`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MidiNote {
+    /// The note's offset from the start of the sequence.
+    pub time: DurationSeconds,
+    /// The MIDI channel, from `0` to `15`.
+    pub channel: u8,
+    /// The note on or off event.
+    pub event: SynthNote,
+}
+
+/// A sequence of timed notes, typically loaded from a MIDI file.
+///
+/// Tempo changes are resolved during loading, so each
+/// note's time is expressed directly in seconds.
+#[derive(Asset, TypePath, Debug, Clone, Default)]
+pub struct MidiSequence {
+    notes: Vec<MidiNote>,
+    duration: DurationSeconds,
+}
+
+impl MidiSequence {
+    /// Construct a new [`MidiSequence`] from a set of notes.
+    ///
+    /// The notes are sorted by time. The sequence lasts until its final note,
+    /// or until `duration` if that's longer.
+    pub fn new(notes: impl IntoIterator<Item = MidiNote>, duration: DurationSeconds) -> Self {
+        let mut notes: Vec<_> = notes.into_iter().collect();
+        notes.sort_by(|a, b| a.time.0.total_cmp(&b.time.0));
+
+        let last = notes.last().map(|n| n.time.0).unwrap_or_default();
+
+        Self {
+            notes,
+            duration: DurationSeconds(duration.0.max(last)),
+        }
+    }
+
+    /// The sequence's notes, sorted by time.
+    pub fn notes(&self) -> &[MidiNote] {
+        &self.notes
+    }
+
+    /// The sequence's total duration.
+    pub fn duration(&self) -> DurationSeconds {
+        self.duration
+    }
+
+    /// Parse a standard MIDI file.
+    pub fn from_smf(bytes: &[u8]) -> Result<Self, MidiLoaderError> {
+        use midly::{MetaMessage, MidiMessage, Timing, TrackEventKind};
+
+        let smf = midly::Smf::parse(bytes)?;
+
+        // Gather every track's events on a single tick timeline so
+        // tempo changes apply across tracks.
+        let mut events = Vec::new();
+        for track in &smf.tracks {
+            let mut tick = 0u64;
+            for event in track {
+                tick += event.delta.as_int() as u64;
+                events.push((tick, event.kind));
+            }
+        }
+        events.sort_by_key(|(tick, _)| *tick);
+
+        // Microseconds per quarter note, defaulting to 120 BPM.
+        let mut tempo = 500_000.0;
+        let seconds_per_tick = |tempo: f64| match smf.header.timing {
+            Timing::Metrical(ticks) => tempo / 1_000_000.0 / ticks.as_int().max(1) as f64,
+            Timing::Timecode(fps, subframes) => 1.0 / (fps.as_f32() as f64 * subframes as f64),
+        };
+
+        let mut notes = Vec::new();
+        let mut seconds = 0.0;
+        let mut last_tick = 0;
+        for (tick, kind) in events {
+            seconds += (tick - last_tick) as f64 * seconds_per_tick(tempo);
+            last_tick = tick;
+
+            let (channel, event) = match kind {
+                TrackEventKind::Meta(MetaMessage::Tempo(t)) => {
+                    tempo = t.as_int() as f64;
+                    continue;
+                }
+                TrackEventKind::Midi { channel, message } => match message {
+                    MidiMessage::NoteOn { key, vel } if vel.as_int() > 0 => (
+                        channel,
+                        SynthNote::On {
+                            note: key.as_int(),
+                            velocity: vel.as_int() as f32 / 127.0,
+                        },
+                    ),
+                    MidiMessage::NoteOn { key, .. } | MidiMessage::NoteOff { key, .. } => {
+                        (channel, SynthNote::Off { note: key.as_int() })
+                    }
+                    _ => continue,
+                },
+                _ => continue,
+            };
+
+            notes.push(MidiNote {
+                time: DurationSeconds(seconds),
+                channel: channel.as_int(),
+                event,
+            });
+        }
+
+        Ok(Self::new(notes, DurationSeconds(seconds)))
+    }
+}
+
+/// Errors produced while loading a [`MidiSequence`].
+#[derive(Debug)]
+pub enum MidiLoaderError {
+    /// An error directly from the standard library.
+    StdIo(std::io::Error),
+    /// An error directly from `midly`.
+    Midly(String),
+}
+
+impl From<std::io::Error> for MidiLoaderError {
+    fn from(value: std::io::Error) -> Self {
+        Self::StdIo(value)
+    }
+}
+
+impl From<midly::Error> for MidiLoaderError {
+    fn from(value: midly::Error) -> Self {
+        Self::Midly(value.to_string())
+    }
+}
+
+impl std::error::Error for MidiLoaderError {}
+
+impl std::fmt::Display for MidiLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::StdIo(stdio) => stdio.fmt(f),
+            Self::Midly(midly) => f.write_str(midly),
+        }
+    }
+}
+
+/// A simple loader for standard MIDI files.
+#[derive(Debug, Default, TypePath)]
+pub struct MidiLoader;
+
+impl AssetLoader for MidiLoader {
+    type Asset = MidiSequence;
+    type Settings = ();
+    type Error = MidiLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn bevy_asset::io::Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut bevy_asset::LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        MidiSequence::from_smf(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["mid", "midi"]
+    }
+}
+
+/// Where a [`MidiPlayer`] sends its notes.
+#[derive(Debug, Clone, Default)]
+pub enum MidiTarget {
+    /// Queue [`SynthNote`] events on the player's own entity,
+    /// which should be a [`SynthNode`][crate::prelude::SynthNode].
+    #[default]
+    Synth,
+    /// Spawn a pitched copy of `player` for each note.
+    ///
+    /// The sample plays at its original pitch for `root_note`.
+    /// Each note's velocity scales the player's volume,
+    /// and note off events are ignored.
+    Sampler {
+        /// The template sample player.
+        player: SamplePlayer,
+        /// The MIDI note number of the unpitched sample.
+        root_note: u8,
+    },
+}
+
+/// Plays a [`MidiSequence`] on the audio clock.
+///
+/// Notes are queued slightly ahead of time according to the
+/// [`SchedulingConfig`], so playback stays sample-accurate
+/// regardless of the frame rate.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn play_drums(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn(
+///         MidiPlayer::new(server.load("songs/beat.mid"))
+///             .on_channel(9)
+///             .with_target(MidiTarget::Sampler {
+///                 player: SamplePlayer::new(server.load("kick.wav")),
+///                 root_note: 36,
+///             }),
+///     );
+/// }
+/// ```
+///
+/// Removing this component releases any
+/// notes still playing on a [`MidiTarget::Synth`].
+#[derive(Debug, Clone, Component)]
+pub struct MidiPlayer {
+    /// The sequence to play.
+    pub sequence: Handle<MidiSequence>,
+    /// Where the notes are sent.
+    pub target: MidiTarget,
+    /// Only play notes on this MIDI channel.
+    ///
+    /// When `None`, notes from every channel play.
+    pub channel: Option<u8>,
+    /// Semitones added to every note.
+    pub transpose: i8,
+    /// Whether the sequence restarts once it completes.
+    pub looping: bool,
+    /// When the current repetition started.
+    pub(crate) start: Option<InstantSeconds>,
+    /// The index of the next note to schedule.
+    pub(crate) cursor: usize,
+}
+
+impl MidiPlayer {
+    /// Construct a new [`MidiPlayer`] targeting a
+    /// [`SynthNode`][crate::prelude::SynthNode] on the same entity.
+    pub fn new(sequence: Handle<MidiSequence>) -> Self {
+        Self {
+            sequence,
+            target: MidiTarget::default(),
+            channel: None,
+            transpose: 0,
+            looping: false,
+            start: None,
+            cursor: 0,
+        }
+    }
+
+    /// Loop the sequence indefinitely.
+    pub fn looping(self) -> Self {
+        Self {
+            looping: true,
+            ..self
+        }
+    }
+
+    /// Set the player's [`MidiTarget`].
+    pub fn with_target(self, target: MidiTarget) -> Self {
+        Self { target, ..self }
+    }
+
+    /// Only play notes on `channel`.
+    pub fn on_channel(self, channel: u8) -> Self {
+        Self {
+            channel: Some(channel),
+            ..self
+        }
+    }
+
+    /// Shift every note by `semitones`.
+    pub fn with_transpose(self, semitones: i8) -> Self {
+        Self {
+            transpose: semitones,
+            ..self
+        }
+    }
+
+    /// Returns whether the sequence has finished playing.
+    ///
+    /// Looping sequences never finish.
+    pub fn is_finished(&self, sequence: &MidiSequence) -> bool {
+        !self.looping && self.start.is_some() && self.cursor >= sequence.notes().len()
+    }
+
+    /// Collect the notes due before `until`, advancing the cursor.
+    fn advance(
+        &mut self,
+        sequence: &MidiSequence,
+        now: InstantSeconds,
+        until: InstantSeconds,
+    ) -> Vec<(InstantSeconds, MidiNote)> {
+        let mut due = Vec::new();
+        let mut start = *self.start.get_or_insert(now);
+
+        loop {
+            let Some(note) = sequence.notes().get(self.cursor) else {
+                // Avoid spinning on empty or instantaneous sequences.
+                if !self.looping || sequence.duration().0 <= 0.0 {
+                    break;
+                }
+
+                start = start + sequence.duration();
+                self.cursor = 0;
+                continue;
+            };
+
+            let time = start + note.time;
+            if time.0 >= until.0 {
+                break;
+            }
+
+            self.cursor += 1;
+            if self.channel.is_none_or(|c| c == note.channel) {
+                due.push((time, *note));
+            }
+        }
+
+        self.start = Some(start);
+        due
+    }
+}
+
+fn transpose(note: u8, semitones: i8) -> u8 {
+    (note as i16 + semitones as i16).clamp(0, 127) as u8
+}
+
+fn sequence_midi(
+    mut players: Query<(&mut MidiPlayer, Option<&mut AudioEvents>)>,
+    sequences: Res<Assets<MidiSequence>>,
+    time: Res<bevy_time::Time<Audio>>,
+    config: Res<SchedulingConfig>,
+    mut commands: Commands,
+) {
+    let now = time.now();
+    let until = now + config.max_queue_ahead;
+
+    for (mut player, mut events) in &mut players {
+        let Some(sequence) = sequences.get(&player.sequence) else {
+            continue;
+        };
+
+        if player.is_finished(sequence) {
+            continue;
+        }
+
+        let semitones = player.transpose;
+        for (at, note) in player.advance(sequence, now, until) {
+            match (&player.target, note.event) {
+                (MidiTarget::Synth, event) => {
+                    let event = match event {
+                        SynthNote::On { note, velocity } => SynthNote::On {
+                            note: transpose(note, semitones),
+                            velocity,
+                        },
+                        SynthNote::Off { note } => SynthNote::Off {
+                            note: transpose(note, semitones),
+                        },
+                        SynthNote::AllOff => SynthNote::AllOff,
+                    };
+
+                    if let Some(events) = events.as_mut() {
+                        events.push_custom_at(at, event);
+                    }
+                }
+                (
+                    MidiTarget::Sampler {
+                        player: template,
+                        root_note,
+                    },
+                    SynthNote::On { note, velocity },
+                ) => {
+                    let semitones = transpose(note, semitones) as f64 - *root_note as f64;
+                    let settings = PlaybackSettings::default()
+                        .with_playback(false)
+                        .with_speed(2f64.powf(semitones / 12.0));
+
+                    let mut events = AudioEvents::new(&time);
+                    settings.play_at(None, at, &mut events);
+
+                    let volume = Volume::Linear(template.volume.linear() * velocity);
+                    commands.spawn((template.clone().with_volume(volume), settings, events));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Release a synth's notes when its [`MidiPlayer`] is removed.
+fn release_notes(trigger: On<Remove, MidiPlayer>, mut events: Query<&mut AudioEvents>) {
+    if let Ok(mut events) = events.get_mut(trigger.entity) {
+        events.push_custom(SynthNote::AllOff);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn note(time: f64, channel: u8, note: u8) -> MidiNote {
+        MidiNote {
+            time: DurationSeconds(time),
+            channel,
+            event: SynthNote::On {
+                note,
+                velocity: 1.0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_sequence_lookahead() {
+        let sequence = MidiSequence::new(
+            [note(0.5, 0, 62), note(0.0, 0, 60), note(1.0, 1, 64)],
+            DurationSeconds(0.0),
+        );
+        assert_eq!(sequence.duration(), DurationSeconds(1.0));
+
+        let mut player = MidiPlayer::new(Handle::default());
+        let start = InstantSeconds(10.0);
+
+        let due = player.advance(&sequence, start, InstantSeconds(10.6));
+        let times: Vec<_> = due.iter().map(|(t, _)| t.0).collect();
+        assert_eq!(times, [10.0, 10.5]);
+
+        let due = player.advance(&sequence, InstantSeconds(10.6), InstantSeconds(12.0));
+        assert_eq!(due.len(), 1);
+        assert!(player.is_finished(&sequence));
+    }
+
+    #[test]
+    fn test_looping_channel_filter() {
+        let sequence =
+            MidiSequence::new([note(0.0, 0, 60), note(0.5, 1, 64)], DurationSeconds(1.0));
+
+        let mut player = MidiPlayer::new(Handle::default()).looping().on_channel(0);
+
+        let due = player.advance(&sequence, InstantSeconds(0.0), InstantSeconds(2.5));
+        let times: Vec<_> = due.iter().map(|(t, _)| t.0).collect();
+        assert_eq!(times, [0.0, 1.0, 2.0]);
+        assert!(!player.is_finished(&sequence));
+    }
+}
@@ -0,0 +1,138 @@
+//! Bypassing for arbitrary audio nodes.
+
+use super::{events::AudioEvents, follower::FollowerOf};
+use crate::pool::sample_effects::EffectOf;
+use bevy_ecs::prelude::*;
+use firewheel::event::NodeEventType;
+
+/// Bypass an audio node.
+///
+/// While this component is present, the node passes its input
+/// through unchanged. The node stays connected and keeps its
+/// parameters and internal state, so effects can be A/B-ed at
+/// runtime without rebuilding the chain. Firewheel applies the
+/// bypass on the audio thread, declicking the transition.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn toggle_low_pass(
+///     low_pass: Single<(Entity, Has<AudioBypass>), With<FastLowpassNode>>,
+///     mut commands: Commands,
+/// ) {
+///     let (entity, bypassed) = *low_pass;
+///
+///     if bypassed {
+///         commands.entity(entity).remove::<AudioBypass>();
+///     } else {
+///         commands.entity(entity).insert(AudioBypass);
+///     }
+/// }
+/// ```
+///
+/// This works for any node, including effects in
+/// [`SampleEffects`][crate::prelude::SampleEffects]. Bypassing an
+/// effect bypasses the node on whichever sampler the sample is
+/// assigned to, even when the sample is assigned later.
+#[derive(Component, Clone, Debug)]
+pub struct AudioBypass;
+
+impl AudioBypass {
+    pub(super) fn remove_bypass(
+        trigger: On<Remove, AudioBypass>,
+        mut node: Query<&mut AudioEvents, Without<EffectOf>>,
+    ) {
+        // Effects have no audio node of their own.
+        if let Ok(mut events) = node.get_mut(trigger.entity) {
+            events.queue.push(NodeEventType::SetBypassed(false));
+        }
+    }
+
+    pub(super) fn update_bypassed(
+        bypassed: Query<&mut AudioEvents, (Changed<AudioBypass>, Without<EffectOf>)>,
+    ) {
+        for mut events in bypassed {
+            events.queue.push(NodeEventType::SetBypassed(true));
+        }
+    }
+
+    /// Mirror each effect's bypass onto the nodes following it.
+    pub(super) fn sync_followers(
+        followers: Query<(Entity, Ref<FollowerOf>, Has<AudioBypass>)>,
+        sources: Query<Ref<AudioBypass>, With<EffectOf>>,
+        mut removed: RemovedComponents<AudioBypass>,
+        effects: Query<(), With<EffectOf>>,
+        mut commands: Commands,
+    ) {
+        let mut unbypassed: Vec<_> = removed.read().filter(|e| effects.contains(*e)).collect();
+        unbypassed.sort_unstable();
+
+        for (follower, source, bypassed) in &followers {
+            let source_bypass = sources.get(source.0).ok();
+            let source_changed = source_bypass.as_ref().is_some_and(|b| b.is_changed())
+                || unbypassed.binary_search(&source.0).is_ok();
+
+            if !source.is_changed() && !source_changed {
+                continue;
+            }
+
+            match (source_bypass.is_some(), bypassed) {
+                (true, false) => {
+                    commands.entity(follower).insert(AudioBypass);
+                }
+                (false, true) => {
+                    commands.entity(follower).remove::<AudioBypass>();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        prelude::*,
+        test::{prepare_app, run},
+    };
+
+    #[test]
+    fn test_follower_bypass() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            let effect = commands
+                .spawn(sample_effects![(VolumeNode::default(), AudioBypass)])
+                .id();
+
+            commands.queue(move |world: &mut World| {
+                let effect = world.get::<SampleEffects>(effect).unwrap()[0];
+                world.spawn((VolumeNode::default(), FollowerOf(effect)));
+            });
+        });
+
+        app.update();
+
+        run(
+            &mut app,
+            |follower: Single<Has<AudioBypass>, With<FollowerOf>>| {
+                assert!(*follower);
+            },
+        );
+
+        run(
+            &mut app,
+            |effect: Single<Entity, With<EffectOf>>, mut commands: Commands| {
+                commands.entity(*effect).remove::<AudioBypass>();
+            },
+        );
+
+        app.update();
+
+        run(
+            &mut app,
+            |follower: Single<Has<AudioBypass>, With<FollowerOf>>| {
+                assert!(!*follower);
+            },
+        );
+    }
+}
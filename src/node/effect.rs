@@ -0,0 +1,211 @@
+//! Simple, derivable audio effects.
+
+use bevy_ecs::component::Component;
+use firewheel::{
+    channel_config::{ChannelConfig, NonZeroChannelCount},
+    diff::Patch,
+    event::ProcEvents,
+    node::{
+        AudioNodeInfo, AudioNodeProcessor, ConstructProcessorContext, ProcBuffers, ProcExtra,
+        ProcInfo, ProcStreamCtx, ProcessStatus,
+    },
+};
+use std::num::NonZeroU32;
+
+/// Derive [`AudioNode`][firewheel::node::AudioNode] and [`Component`] for an [`AudioEffect`].
+///
+/// See [`AudioEffect`] for usage.
+pub use bevy_seedling_macros::AudioEffect;
+
+/// A simple, in-place audio effect.
+///
+/// Writing a Firewheel node by hand requires a node type,
+/// a configuration, and a separate processor. For effects whose
+/// parameters and state can live in a single struct, deriving
+/// [`AudioEffect`][derive@AudioEffect] generates the rest.
+///
+/// The effect's fields are diffed like any other node, so mutating
+/// the component in the ECS is automatically synchronized with the
+/// audio thread.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// use bevy_seedling::firewheel::diff::{Diff, Patch};
+///
+/// #[derive(AudioEffect, Diff, Patch, Debug, Clone)]
+/// struct Drive {
+///     amount: f32,
+/// }
+///
+/// impl AudioEffect for Drive {
+///     fn process(&mut self, inputs: &[&[f32]], outputs: &mut [&mut [f32]], frames: usize) {
+///         for (input, output) in inputs.iter().zip(outputs.iter_mut()) {
+///             for i in 0..frames {
+///                 output[i] = (input[i] * self.amount).tanh();
+///             }
+///         }
+///     }
+/// }
+///
+/// fn plugin(app: &mut App) {
+///     app.register_node::<Drive>();
+/// }
+/// ```
+///
+/// Effects use [`AudioEffectConfig`] as their configuration, which
+/// sets the number of input and output channels.
+pub trait AudioEffect: Patch + Clone + Send + Sync + 'static {
+    /// Process a block of audio.
+    ///
+    /// `inputs` and `outputs` have the same number of channels,
+    /// each with at least `frames` samples.
+    fn process(&mut self, inputs: &[&[f32]], outputs: &mut [&mut [f32]], frames: usize);
+
+    /// Called when the audio stream is (re)started.
+    ///
+    /// Effects that depend on the sample rate should
+    /// recalculate their coefficients here.
+    fn new_stream(&mut self, sample_rate: NonZeroU32) {
+        let _ = sample_rate;
+    }
+}
+
+/// The configuration for derived [`AudioEffect`] nodes.
+#[derive(Debug, Clone, Component, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct AudioEffectConfig {
+    /// The number of input and output channels.
+    ///
+    /// By default, this is stereo.
+    pub channels: NonZeroChannelCount,
+}
+
+impl Default for AudioEffectConfig {
+    fn default() -> Self {
+        Self {
+            channels: NonZeroChannelCount::STEREO,
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn effect_info(name: &'static str, config: &AudioEffectConfig) -> AudioNodeInfo {
+    AudioNodeInfo::new()
+        .debug_name(name)
+        .channel_config(ChannelConfig {
+            num_inputs: config.channels.get(),
+            num_outputs: config.channels.get(),
+        })
+}
+
+#[doc(hidden)]
+pub struct AudioEffectProcessor<T> {
+    effect: T,
+}
+
+impl<T: AudioEffect> AudioEffectProcessor<T> {
+    #[doc(hidden)]
+    pub fn new(mut effect: T, cx: ConstructProcessorContext) -> Self {
+        effect.new_stream(cx.stream_info.sample_rate);
+
+        Self { effect }
+    }
+}
+
+impl<T: AudioEffect> AudioNodeProcessor for AudioEffectProcessor<T> {
+    fn events(&mut self, _info: &ProcInfo, events: &mut ProcEvents, _extra: &mut ProcExtra) {
+        for patch in events.drain_patches::<T>() {
+            self.effect.apply(patch);
+        }
+    }
+
+    fn process(
+        &mut self,
+        proc_info: &ProcInfo,
+        buffers: ProcBuffers,
+        _: &mut ProcExtra,
+    ) -> ProcessStatus {
+        self.effect
+            .process(buffers.inputs, buffers.outputs, proc_info.frames);
+
+        ProcessStatus::OutputsModified
+    }
+
+    fn new_stream(&mut self, stream_info: &firewheel::StreamInfo, _: &mut ProcStreamCtx) {
+        self.effect.new_stream(stream_info.sample_rate);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        node::FirewheelNodeInfo,
+        prelude::*,
+        test::{prepare_app_with, run},
+    };
+    use bevy::prelude::*;
+    use firewheel::diff::Diff;
+
+    #[derive(AudioEffect, Diff, Patch, Debug, Clone)]
+    struct Gain {
+        gain: f32,
+    }
+
+    impl AudioEffect for Gain {
+        fn process(&mut self, inputs: &[&[f32]], outputs: &mut [&mut [f32]], frames: usize) {
+            for (input, output) in inputs.iter().zip(outputs.iter_mut()) {
+                for i in 0..frames {
+                    output[i] = input[i] * self.gain;
+                }
+            }
+        }
+    }
+
+    fn register(app: &mut App) {
+        app.register_node::<Gain>();
+    }
+
+    #[test]
+    fn test_derived_channels() {
+        let mut app = prepare_app_with(register, |mut commands: Commands| {
+            commands.spawn(Gain { gain: 0.5 });
+            commands.spawn((
+                Gain { gain: 0.5 },
+                AudioEffectConfig {
+                    channels: NonZeroChannelCount::MONO,
+                },
+            ));
+        });
+        app.update();
+
+        run(
+            &mut app,
+            |nodes: Query<(&FirewheelNodeInfo, &AudioEffectConfig)>| {
+                assert_eq!(nodes.iter().len(), 2);
+
+                for (info, config) in &nodes {
+                    assert_eq!(info.channel_config.num_inputs, config.channels.get());
+                    assert_eq!(info.channel_config.num_outputs, config.channels.get());
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn test_derived_component_is_mutable() {
+        let mut app = prepare_app_with(register, |mut commands: Commands| {
+            commands.spawn(Gain { gain: 0.5 });
+        });
+
+        run(&mut app, |mut gain: Single<&mut Gain>| {
+            gain.gain = 0.25;
+        });
+        app.update();
+
+        run(&mut app, |gain: Single<(&Gain, &FirewheelNode)>| {
+            assert_eq!(gain.0.gain, 0.25);
+        });
+    }
+}
@@ -2,25 +2,35 @@
 
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
-use bevy_math::FloatExt;
+use bevy_math::{DVec2, DVec3, FloatExt, Quat, Vec2, Vec3, Vec4};
 use bevy_time::{Time, TimeSystems};
 use bevy_utils::prelude::DebugName;
 use firewheel::{
     Volume,
     clock::{DurationSeconds, InstantSeconds},
-    diff::{Diff, EventQueue, ParamPath, Patch, PatchError, PathBuilder},
+    diff::{Diff, EventQueue, ParamPath, Patch, PathBuilder},
     event::{NodeEventType, ParamData},
-    nodes::volume::VolumeNode,
+    nodes::{spatial_basic::SpatialBasicNode, volume::VolumeNode},
 };
 use std::sync::Arc;
 
-use crate::{error::SeedlingError, time::Audio};
+use crate::{
+    error::SeedlingError,
+    time::{Audio, Tempo},
+};
 
 pub(crate) struct EventsPlugin;
 
 impl Plugin for EventsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(First, update_events_instant.after(TimeSystems));
+        app.add_systems(
+            First,
+            (
+                update_events_instant.after(TimeSystems),
+                sync_timeline_limits,
+            ),
+        )
+        .add_systems(Last, report_timeline_overflows);
     }
 }
 
@@ -93,6 +103,8 @@ impl Plugin for EventsPlugin {
 #[derive(Component)]
 pub struct AudioEvents {
     pub(super) queue: Vec<NodeEventType>,
+    /// Non-parameter events with an explicit time on the audio clock.
+    pub(super) timed: Vec<(InstantSeconds, NodeEventType)>,
     /// We keep a timeline like this because a simple queue of rendered events is not sufficient.
     ///
     /// Since we'll send out the scheduled events a little bit in advance, there will be some
@@ -103,6 +115,17 @@ pub struct AudioEvents {
     /// It's also much easier to detect overlapping events.
     pub(super) timeline: Vec<EventTimeline>,
     now: InstantSeconds,
+    /// The stream's play latency, used for compensation.
+    latency: DurationSeconds,
+    /// Whether scheduled events are shifted earlier by `latency`.
+    compensate: bool,
+    limit: TimelineLimit,
+    overflows: u64,
+    reported_overflows: u64,
+    priority: TimelinePriority,
+    /// The active repetition period and group.
+    repeat: Option<(DurationSeconds, u64)>,
+    repeat_groups: u64,
 }
 
 impl AudioEvents {
@@ -111,9 +134,327 @@ impl AudioEvents {
     pub fn new(now: &Time<Audio>) -> Self {
         Self {
             queue: Default::default(),
-            timeline: Default::default(),
+            timed: Default::default(),
+            timeline: Vec::with_capacity(TimelineLimit::default().capacity),
             now: now.context().instant(),
+            latency: now.context().play_latency(),
+            compensate: false,
+            limit: TimelineLimit::default(),
+            overflows: 0,
+            reported_overflows: 0,
+            priority: TimelinePriority::default(),
+            repeat: None,
+            repeat_groups: 0,
+        }
+    }
+
+    /// Set this node's [`TimelineLimit`], returning `self`.
+    pub fn with_limit(mut self, limit: TimelineLimit) -> Self {
+        self.set_limit(limit);
+        self
+    }
+
+    /// Set this node's [`TimelineLimit`].
+    ///
+    /// Scheduled events already beyond the new limit are retained.
+    /// Prefer inserting a [`TimelineLimit`] component, which
+    /// is synchronized automatically.
+    pub fn set_limit(&mut self, limit: TimelineLimit) {
+        self.timeline
+            .reserve(limit.capacity.saturating_sub(self.timeline.len()));
+        self.limit = limit;
+    }
+
+    /// This node's current [`TimelineLimit`].
+    pub fn limit(&self) -> TimelineLimit {
+        self.limit
+    }
+
+    /// The total number of scheduled events that were dropped
+    /// or rejected because the timeline was full.
+    pub fn overflow_count(&self) -> u64 {
+        self.overflows
+    }
+
+    /// Schedule events within `f` with the given [`TimelinePriority`].
+    ///
+    /// Where scheduled events for the same parameter overlap,
+    /// the higher priority events win. Events with equal priority
+    /// resolve with last-writer-wins, so the most recently
+    /// scheduled event takes over the parameter from its start onward.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn duck(
+    ///     main: Single<(&VolumeNode, &mut AudioEvents), With<MainBus>>,
+    ///     time: Res<Time<Audio>>,
+    /// ) {
+    ///     let (volume, mut events) = main.into_inner();
+    ///     let start = time.now();
+    ///     let end = time.delay(DurationSeconds(0.25));
+    ///
+    ///     // Any other system's fades won't interrupt the duck.
+    ///     events.with_priority(TimelinePriority::HIGH, |events| {
+    ///         volume.fade_at(Volume::Decibels(-12.0), start, end, events);
+    ///     });
+    /// }
+    /// ```
+    ///
+    /// Conflicts are only resolved for events that haven't been
+    /// sent to the audio thread yet.
+    pub fn with_priority<F>(&mut self, priority: TimelinePriority, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let previous = core::mem::replace(&mut self.priority, priority);
+        f(self);
+        self.priority = previous;
+    }
+
+    /// Compensate for the stream's play latency within `f`.
+    ///
+    /// Events scheduled within `f` are shifted earlier by
+    /// [`AudioTime::play_latency`], so they're _heard_ at the requested
+    /// time rather than sent to the device then. This helps sounds triggered
+    /// from gameplay line up with visuals on high-latency devices, like
+    /// bluetooth headphones.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn footstep(time: Res<Time<Audio>>, server: Res<AssetServer>, mut commands: Commands) {
+    ///     let mut events = AudioEvents::new(&time);
+    ///     let settings = PlaybackSettings::default().with_playback(false);
+    ///
+    ///     // Land exactly with the animation's contact frame.
+    ///     let contact = time.delay(DurationSeconds(0.25));
+    ///     events.with_latency_compensation(|events| {
+    ///         settings.play_at(None, contact, events);
+    ///     });
+    ///
+    ///     commands.spawn((
+    ///         events,
+    ///         settings,
+    ///         SamplePlayer::new(server.load("step.wav")),
+    ///     ));
+    /// }
+    /// ```
+    ///
+    /// Events can't be sent into the past, so compensated
+    /// times are never earlier than [`AudioTime::now`].
+    ///
+    /// [`AudioTime::play_latency`]: crate::time::AudioTime::play_latency
+    /// [`AudioTime::now`]: crate::time::AudioTime::now
+    pub fn with_latency_compensation<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let previous = core::mem::replace(&mut self.compensate, true);
+        f(self);
+        self.compensate = previous;
+    }
+
+    /// Shift `time` earlier by the play latency if compensation is active.
+    fn compensated(&self, time: InstantSeconds) -> InstantSeconds {
+        if !self.compensate {
+            return time;
+        }
+
+        InstantSeconds((time.0 - self.latency.0).max(self.now.0).min(time.0))
+    }
+
+    /// Repeat all events scheduled within `f` every `period`.
+    ///
+    /// Repeating events loop indefinitely without any further
+    /// scheduling, making them useful for LFO-style automation.
+    /// The repetitions are rendered ahead of time like any other
+    /// scheduled events, so they line up seamlessly.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn swell(
+    ///     main: Single<(&VolumeNode, &mut AudioEvents), With<MainBus>>,
+    ///     time: Res<Time<Audio>>,
+    /// ) {
+    ///     let (volume, mut events) = main.into_inner();
+    ///     let start = time.now();
+    ///     let peak = start + DurationSeconds(2.0);
+    ///     let end = start + DurationSeconds(4.0);
+    ///
+    ///     // A four-second swell, repeating forever.
+    ///     events.repeating(DurationSeconds(4.0), |events| {
+    ///         volume.fade_at(Volume::Decibels(-12.0), start, peak, events);
+    ///         volume.fade_at(Volume::UNITY_GAIN, peak, end, events);
+    ///     });
+    /// }
+    /// ```
+    ///
+    /// `period` is measured from the start of each event, and is
+    /// extended to the event's duration if shorter. To end the
+    /// repetition, call [`AudioEvents::stop_repeating`].
+    pub fn repeating<F>(&mut self, period: DurationSeconds, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.repeat_groups += 1;
+        let previous = self.repeat.replace((period, self.repeat_groups));
+        f(self);
+        self.repeat = previous;
+    }
+
+    /// Stop all repeating events at `at`.
+    ///
+    /// Repetitions scheduled after `at` are discarded, while
+    /// any repetition in progress is cut short.
+    pub fn stop_repeating(&mut self, at: InstantSeconds) {
+        for timeline in &mut self.timeline {
+            if let Some(repeat) = &mut timeline.repeat {
+                repeat.until = InstantSeconds(repeat.until.0.min(at.0));
+                timeline.render_progress.range.end = timeline.time_range().end;
+            }
+        }
+    }
+
+    /// Check the timeline for scheduled events that overlap
+    /// on the same parameter, logging a warning for each.
+    ///
+    /// Overlaps are resolved according to [`TimelinePriority`], but
+    /// they often indicate two systems are fighting over a node.
+    /// This returns the first overlap as an error, if any.
+    pub fn debug_validate(&self) -> Result<(), TimelineError> {
+        let mut result = Ok(());
+
+        for (i, first) in self.timeline.iter().enumerate() {
+            for second in &self.timeline[i + 1..] {
+                if !first.overlaps(second) {
+                    continue;
+                }
+
+                for path in first.paths().filter(|p| second.paths().any(|o| o == *p)) {
+                    let error = TimelineError::OverlappingRanges {
+                        path: path.clone(),
+                        first: first.time_range(),
+                        second: second.time_range(),
+                    };
+
+                    bevy_log::warn!("{error}");
+
+                    if result.is_ok() {
+                        result = Err(error);
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Describe every scheduled event in the timeline.
+    ///
+    /// This is intended for tooling, like an editor's timeline view,
+    /// that needs to show upcoming fades and scheduled plays.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn list_upcoming(nodes: Query<(Entity, &AudioEvents)>) {
+    ///     for (entity, events) in &nodes {
+    ///         for event in events.describe().events {
+    ///             info!(
+    ///                 "{entity}: {} from {:.2}s to {:.2}s",
+    ///                 event.target, event.start.0, event.end.0
+    ///             );
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Events are ordered by their start time. Parameters claimed by
+    /// higher-priority or later events are omitted, so each event only
+    /// lists the values it will actually apply. Repeating events
+    /// list their first repetition.
+    pub fn describe(&self) -> TimelineDescription {
+        let mut events: Vec<_> = self
+            .timeline
+            .iter()
+            .map(|event| {
+                let range = event.time_range();
+
+                ScheduledEvent {
+                    target: event.target.to_string(),
+                    start: range.start,
+                    end: range.end,
+                    priority: event.priority,
+                    repeat_period: event.repeat.map(|r| DurationSeconds(r.period)),
+                    sent: event.render_progress.complete,
+                    params: event
+                        .tween
+                        .iter()
+                        .filter(|p| !event.is_masked(p, p.time))
+                        .map(|p| ScheduledParam {
+                            path: p.path.to_vec(),
+                            time: p.time,
+                            value: format!("{:?}", p.data),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+        events.sort_by(|a, b| a.start.0.total_cmp(&b.start.0));
+
+        TimelineDescription {
+            now: self.now,
+            events,
+        }
+    }
+
+    /// Push a scheduled event onto the timeline, respecting the [`TimelineLimit`].
+    fn push_timeline(&mut self, mut timeline: EventTimeline) {
+        let max = match self.limit.overflow {
+            TimelineOverflow::Grow { max } => max.max(self.limit.capacity),
+            TimelineOverflow::DropOldest | TimelineOverflow::Reject => self.limit.capacity,
+        };
+
+        if self.timeline.len() >= max {
+            self.overflows += 1;
+
+            match self.limit.overflow {
+                TimelineOverflow::DropOldest if !self.timeline.is_empty() => {
+                    self.timeline.remove(0);
+                }
+                _ => return,
+            }
+        }
+
+        timeline.priority = self.priority;
+        if let Some((period, group)) = self.repeat {
+            timeline.set_repeat(period, group);
         }
+
+        for existing in &mut self.timeline {
+            if !existing.overlaps(&timeline) {
+                continue;
+            }
+
+            if timeline.priority >= existing.priority {
+                // Equal priorities take over the parameter for the rest of the
+                // existing event, while higher priorities only claim their own span.
+                let end = if timeline.priority == existing.priority {
+                    existing.time_range().end
+                } else {
+                    timeline.time_range().end
+                };
+
+                existing.mask(&timeline, timeline.time_range().start..=end);
+            } else {
+                let range = existing.time_range();
+                timeline.mask(existing, range.start..=range.end);
+            }
+        }
+
+        self.timeline.push(timeline);
     }
 
     /// Essentially a duplicate of [`AudioTime::now`][crate::time::AudioTime::now].
@@ -124,6 +465,64 @@ impl AudioEvents {
         self.now
     }
 
+    /// Queue an arbitrary event for this node.
+    ///
+    /// Queued events are sent to the audio thread immediately
+    /// in the [`SeedlingSystems::Flush`] set, bypassing the timeline.
+    /// This is useful for custom nodes that accept events other than
+    /// parameter patches.
+    ///
+    /// [`SeedlingSystems::Flush`]: crate::prelude::SeedlingSystems::Flush
+    pub fn push_event(&mut self, event: NodeEventType) {
+        self.queue.push(event);
+    }
+
+    /// Queue a custom, type-erased event for this node.
+    ///
+    /// The node's processor receives this as a [`NodeEventType::Custom`] event,
+    /// which it can downcast back to `T`.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn play_note(mut synth: Single<&mut AudioEvents, With<SynthNode>>) {
+    ///     synth.push_custom(SynthNote::On {
+    ///         note: 60,
+    ///         velocity: 0.8,
+    ///     });
+    /// }
+    /// ```
+    pub fn push_custom<T: Send + Sync + 'static>(&mut self, event: T) {
+        self.queue.push(NodeEventType::custom(event));
+    }
+
+    /// Queue a custom, type-erased event for this node at `time`.
+    ///
+    /// Like [`AudioEvents::push_custom`], these bypass the timeline and
+    /// are sent in the next [`SeedlingSystems::Flush`], but the audio thread
+    /// holds them until `time`. To avoid flooding the audio thread's
+    /// event queue, prefer pushing events shortly before they're due.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn arpeggio(
+    ///     mut synth: Single<&mut AudioEvents, With<SynthNode>>,
+    ///     time: Res<Time<Audio>>,
+    /// ) {
+    ///     for (i, note) in [60, 64, 67].into_iter().enumerate() {
+    ///         let start = time.delay(DurationSeconds(i as f64 * 0.1));
+    ///         synth.push_custom_at(start, SynthNote::On { note, velocity: 0.8 });
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`SeedlingSystems::Flush`]: crate::prelude::SeedlingSystems::Flush
+    pub fn push_custom_at<T: Send + Sync + 'static>(&mut self, time: InstantSeconds, event: T) {
+        let time = self.compensated(time);
+        self.timed.push((time, NodeEventType::custom(event)));
+    }
+
     /// Clone any timeline events from `other` that aren't present in `self`.
     pub fn merge_timelines(&mut self, other: &Self) {
         for event in &other.timeline {
@@ -170,6 +569,8 @@ impl AudioEvents {
         T: Diff + Patch + Send + Sync + Clone + 'static,
         F: FnOnce(&mut T),
     {
+        let time = self.compensated(time);
+
         // let's make sure to apply any patches that may exist in this queue before the start time
         let initial_value = self.get_value_at(time, value);
 
@@ -193,7 +594,24 @@ impl AudioEvents {
             return;
         }
 
-        self.timeline.push(EventTimeline::new(events));
+        self.push_timeline(EventTimeline::new(events, DebugName::type_name::<T>()));
+    }
+
+    /// Schedule an event at `beat` within `bar`, according to `tempo`.
+    ///
+    /// This is equivalent to [`AudioEvents::schedule`] at [`Tempo::at`].
+    pub fn schedule_at_beat<T, F>(
+        &mut self,
+        tempo: &Tempo,
+        bar: u32,
+        beat: f64,
+        value: &T,
+        change: F,
+    ) where
+        T: Diff + Patch + Send + Sync + Clone + 'static,
+        F: FnOnce(&mut T),
+    {
+        self.schedule(tempo.at(bar, beat), value, change);
     }
 
     /// Schedule a tween with a custom interpolator.
@@ -209,6 +627,8 @@ impl AudioEvents {
         T: Diff + Patch + Send + Sync + Clone + 'static,
         F: Fn(&T, &T, f32) -> T,
     {
+        let (start, end) = (self.compensated(start), self.compensated(end));
+
         let mut events = Vec::new();
         let mut func = |ev, time| match ev {
             NodeEventType::Param { data, path } => {
@@ -235,7 +655,7 @@ impl AudioEvents {
             return;
         }
 
-        self.timeline.push(EventTimeline::new(events));
+        self.push_timeline(EventTimeline::new(events, DebugName::type_name::<T>()));
     }
 
     pub(crate) fn active_within(&self, start: InstantSeconds, end: InstantSeconds) -> bool {
@@ -258,13 +678,21 @@ impl AudioEvents {
     where
         T: Diff + Patch + Clone,
     {
-        for event in &self.timeline {
-            event
-                .apply(start..=end, value)
-                .map_err(|e| SeedlingError::Patch {
-                    ty: DebugName::type_name::<T>(),
-                    error: e,
-                })?;
+        // Repeating events interleave with one another, so we
+        // need to apply all events in order.
+        let mut params: Vec<_> = self
+            .timeline
+            .iter()
+            .flat_map(|event| event.apply_range(start..=end))
+            .collect();
+        params.sort_by(|a, b| a.1.0.total_cmp(&b.1.0));
+
+        for (TimelineParam { data, path, .. }, _) in params {
+            let patch = T::patch(data, path).map_err(|e| SeedlingError::Patch {
+                ty: DebugName::type_name::<T>(),
+                error: e,
+            })?;
+            value.apply(patch);
         }
 
         Ok(())
@@ -282,6 +710,213 @@ impl AudioEvents {
     }
 }
 
+/// The priority of scheduled events, set with [`AudioEvents::with_priority`].
+///
+/// When scheduled events for the same parameter overlap, the
+/// higher priority events win. Equal priorities resolve with
+/// last-writer-wins.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct TimelinePriority(pub i32);
+
+impl TimelinePriority {
+    /// A priority below the default.
+    pub const LOW: Self = Self(-100);
+    /// The default priority.
+    pub const NORMAL: Self = Self(0);
+    /// A priority above the default.
+    pub const HIGH: Self = Self(100);
+}
+
+/// A snapshot of an [`AudioEvents`] timeline, produced by [`AudioEvents::describe`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct TimelineDescription {
+    /// The audio context time when the description was made.
+    pub now: InstantSeconds,
+    /// The scheduled events, ordered by start time.
+    pub events: Vec<ScheduledEvent>,
+}
+
+impl TimelineDescription {
+    /// Iterate over the events that haven't finished by [`TimelineDescription::now`].
+    pub fn upcoming(&self) -> impl Iterator<Item = &ScheduledEvent> {
+        self.events.iter().filter(|e| e.end >= self.now)
+    }
+}
+
+/// A single scheduled event within a [`TimelineDescription`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct ScheduledEvent {
+    /// The name of the type this event patches, like a node or [`PlaybackSettings`].
+    ///
+    /// [`PlaybackSettings`]: crate::prelude::PlaybackSettings
+    pub target: String,
+    /// When the event begins.
+    pub start: InstantSeconds,
+    /// When the event ends.
+    ///
+    /// This is equal to `start` for single changes. For repeating
+    /// events, this is when the repetition stops, which may be infinite.
+    pub end: InstantSeconds,
+    /// The event's priority.
+    pub priority: TimelinePriority,
+    /// The repetition period, if the event repeats.
+    pub repeat_period: Option<DurationSeconds>,
+    /// Whether the event has been fully sent to the audio thread.
+    pub sent: bool,
+    /// The parameter changes this event applies, in order.
+    pub params: Vec<ScheduledParam>,
+}
+
+/// A single parameter change within a [`ScheduledEvent`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct ScheduledParam {
+    /// The parameter's path within the target type.
+    pub path: Vec<u32>,
+    /// When the change is applied.
+    pub time: InstantSeconds,
+    /// A preview of the new value.
+    pub value: String,
+}
+
+/// Errors reported by [`AudioEvents::debug_validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimelineError {
+    /// Two scheduled events for the same parameter overlap in time.
+    OverlappingRanges {
+        /// The parameter's path.
+        path: ParamPath,
+        /// The time range of the earlier scheduled event.
+        first: core::ops::Range<InstantSeconds>,
+        /// The time range of the later scheduled event.
+        second: core::ops::Range<InstantSeconds>,
+    },
+}
+
+impl core::fmt::Display for TimelineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OverlappingRanges {
+                path,
+                first,
+                second,
+            } => write!(
+                f,
+                "Scheduled audio events for parameter {path:?} overlap: {:?}..{:?} and {:?}..{:?}",
+                first.start.0, first.end.0, second.start.0, second.end.0,
+            ),
+        }
+    }
+}
+
+impl core::error::Error for TimelineError {}
+
+/// How [`AudioEvents`] handles scheduled events beyond its [`TimelineLimit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum TimelineOverflow {
+    /// Drop the oldest scheduled event to make room for the new one.
+    ///
+    /// The dropped event may have already been partially sent
+    /// to the audio thread, so its value in the ECS will stop animating.
+    DropOldest,
+    /// Reject the new event, leaving the timeline untouched.
+    Reject,
+    /// Grow beyond [`TimelineLimit::capacity`] until `max`
+    /// scheduled events are held, then reject new events.
+    Grow {
+        /// The maximum number of scheduled events.
+        max: usize,
+    },
+}
+
+/// Limits the number of scheduled events an entity's [`AudioEvents`] holds.
+///
+/// Each call to [`AudioEvents::schedule`] or [`AudioEvents::schedule_tween`],
+/// including fades like [`VolumeFade::fade_to`], occupies one slot until it
+/// has elapsed. When the timeline is full, the [`TimelineOverflow`] policy
+/// decides what happens, and [`AudioEvents::overflow_count`] is incremented.
+/// A [`TimelineOverflowed`] event is also triggered on the entity in the
+/// [`Last`] schedule.
+///
+/// By default, timelines preallocate `32` slots and grow up to `1024`.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn spawn_automated(mut commands: Commands) {
+///     commands
+///         .spawn((
+///             VolumeNode::default(),
+///             TimelineLimit {
+///                 capacity: 8,
+///                 overflow: TimelineOverflow::DropOldest,
+///             },
+///         ))
+///         .observe(|overflow: On<TimelineOverflowed>| {
+///             warn!("lost {} automation events", overflow.count);
+///         });
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct TimelineLimit {
+    /// The number of scheduled events to allocate up front.
+    ///
+    /// For [`TimelineOverflow::DropOldest`] and [`TimelineOverflow::Reject`],
+    /// this is also the maximum number of scheduled events.
+    pub capacity: usize,
+    /// How to handle events beyond the limit.
+    pub overflow: TimelineOverflow,
+}
+
+impl Default for TimelineLimit {
+    fn default() -> Self {
+        Self {
+            capacity: 32,
+            overflow: TimelineOverflow::Grow { max: 1024 },
+        }
+    }
+}
+
+/// An event triggered on entities whose [`AudioEvents`] timeline
+/// overflowed its [`TimelineLimit`].
+#[derive(Debug, EntityEvent)]
+pub struct TimelineOverflowed {
+    /// The entity holding the [`AudioEvents`].
+    pub entity: Entity,
+    /// The number of scheduled events lost since the last report.
+    pub count: u64,
+}
+
+fn sync_timeline_limits(
+    mut nodes: Query<
+        (&TimelineLimit, &mut AudioEvents),
+        Or<(Changed<TimelineLimit>, Added<AudioEvents>)>,
+    >,
+) {
+    for (limit, mut events) in &mut nodes {
+        events.set_limit(*limit);
+    }
+}
+
+fn report_timeline_overflows(mut nodes: Query<(Entity, &mut AudioEvents)>, mut commands: Commands) {
+    for (entity, mut events) in &mut nodes {
+        if events.overflows == events.reported_overflows {
+            continue;
+        }
+
+        let events = events.bypass_change_detection();
+        let count = events.overflows - events.reported_overflows;
+        events.reported_overflows = events.overflows;
+
+        commands.trigger(TimelineOverflowed { entity, count });
+    }
+}
+
 impl EventQueue for AudioEvents {
     fn push(&mut self, data: firewheel::event::NodeEventType) {
         self.queue.push(data);
@@ -294,6 +929,8 @@ impl core::fmt::Debug for AudioEvents {
             .field("queue", &())
             .field("timeline", &self.timeline)
             .field("now", &self.now)
+            .field("limit", &self.limit)
+            .field("overflows", &self.overflows)
             .finish()
     }
 }
@@ -336,8 +973,36 @@ impl EventQueue for TimelineQueue<'_> {
 #[derive(Clone, Debug)]
 pub(super) struct EventTimeline {
     tween: Arc<[TimelineParam]>,
+    /// The type whose parameters this event patches.
+    target: DebugName,
     /// The current render progress.
     pub render_progress: RenderProgress,
+    priority: TimelinePriority,
+    /// Parameters claimed by conflicting events.
+    masks: Vec<TimelineMask>,
+    repeat: Option<TimelineRepeat>,
+}
+
+/// The maximum number of repetitions rendered at once.
+///
+/// This prevents a stalled app from flooding the
+/// audio thread with stale repetitions.
+const MAX_RENDERED_REPEATS: u64 = 16;
+
+/// The repetition of a looping event.
+#[derive(Clone, Copy, Debug)]
+struct TimelineRepeat {
+    period: f64,
+    /// Events repeated together never conflict with each other.
+    group: u64,
+    until: InstantSeconds,
+}
+
+/// A span of a parameter claimed by another, conflicting event.
+#[derive(Clone, Debug)]
+struct TimelineMask {
+    path: ParamPath,
+    range: core::ops::RangeInclusive<InstantSeconds>,
 }
 
 #[derive(Clone, Debug)]
@@ -393,14 +1058,77 @@ fn time_range(events: &[TimelineParam]) -> core::ops::Range<InstantSeconds> {
 
 impl EventTimeline {
     /// Construct a new [`EventTimeline`] from a collection of params.
-    fn new(tween: Vec<TimelineParam>) -> Self {
+    fn new(tween: Vec<TimelineParam>, target: DebugName) -> Self {
         assert!(!tween.is_empty(), "an event timeline should never be empty");
         let render_progress = RenderProgress::new(time_range(&tween));
 
         EventTimeline {
             tween: tween.into(),
+            target,
             render_progress,
+            priority: TimelinePriority::default(),
+            masks: Vec::new(),
+            repeat: None,
+        }
+    }
+
+    /// Repeat this event every `period`, indefinitely.
+    fn set_repeat(&mut self, period: DurationSeconds, group: u64) {
+        let span = time_range(&self.tween);
+        let period = period.0.max(span.end.0 - span.start.0);
+
+        if period <= 0.0 || !period.is_finite() {
+            return;
         }
+
+        self.repeat = Some(TimelineRepeat {
+            period,
+            group,
+            until: InstantSeconds(f64::INFINITY),
+        });
+        self.render_progress.range.end = self.time_range().end;
+    }
+
+    /// Iterate over the unique parameter paths in this event.
+    fn paths(&self) -> impl Iterator<Item = &ParamPath> {
+        self.tween
+            .iter()
+            .enumerate()
+            .filter(|(i, p)| !self.tween[..*i].iter().any(|o| o.path == p.path))
+            .map(|(_, p)| &p.path)
+    }
+
+    /// Returns `true` if the two events' time ranges intersect.
+    ///
+    /// Events that merely touch, like consecutive fades, don't overlap.
+    fn overlaps(&self, other: &Self) -> bool {
+        if let (Some(a), Some(b)) = (self.repeat, other.repeat)
+            && a.group == b.group
+        {
+            return false;
+        }
+
+        let a = self.time_range();
+        let b = other.time_range();
+
+        (a.start < b.end && b.start < a.end) || a == b
+    }
+
+    /// Mask out any of `other`'s parameters within `range`.
+    fn mask(&mut self, other: &Self, range: core::ops::RangeInclusive<InstantSeconds>) {
+        let masks = other.paths().map(|path| TimelineMask {
+            path: path.clone(),
+            range: range.clone(),
+        });
+
+        self.masks.extend(masks);
+    }
+
+    /// Returns `true` if a conflicting event has claimed this parameter.
+    fn is_masked(&self, param: &TimelineParam, time: InstantSeconds) -> bool {
+        self.masks
+            .iter()
+            .any(|m| m.path == param.path && m.range.contains(&time))
     }
 
     /// Report whether this event has completely elapsed by `now`.
@@ -434,8 +1162,15 @@ impl EventTimeline {
     }
 
     /// Get the full span of this event's timeline.
+    ///
+    /// For repeating events, this extends until the repetition is stopped.
     pub fn time_range(&self) -> core::ops::Range<InstantSeconds> {
-        time_range(&self.tween)
+        let range = time_range(&self.tween);
+
+        match self.repeat {
+            Some(repeat) => range.start..repeat.until,
+            None => range,
+        }
     }
 
     /// Indicates whether the timeline contains any events within `probe`.
@@ -445,26 +1180,54 @@ impl EventTimeline {
         range.start <= *probe.end() && range.end >= *probe.start()
     }
 
-    /// Returns an iterator over the parameters contained within `range`.
+    /// Returns an iterator over the parameters contained within `range`,
+    /// along with their times.
+    ///
+    /// For repeating events, only the `max_repeats` most recent repetitions
+    /// within `range` are provided, ordered from oldest to newest.
     fn params_in(
         &self,
         range: core::ops::RangeInclusive<InstantSeconds>,
-    ) -> impl Iterator<Item = &TimelineParam> {
-        self.tween.iter().filter(move |p| range.contains(&p.time))
+        max_repeats: u64,
+    ) -> impl Iterator<Item = (&TimelineParam, InstantSeconds)> {
+        let span = time_range(&self.tween);
+        let cycles = match self.repeat {
+            Some(repeat) => {
+                let end = range.end().0.min(repeat.until.0);
+                let last = ((end - span.start.0) / repeat.period).floor();
+                let first = ((range.start().0 - span.end.0) / repeat.period)
+                    .ceil()
+                    .max(last + 1.0 - max_repeats as f64)
+                    .max(0.0);
+
+                first as u64..(last + 1.0) as u64
+            }
+            None => 0..1,
+        };
+
+        cycles
+            .flat_map(move |cycle| {
+                let offset = self.repeat.map_or(0.0, |r| r.period * cycle as f64);
+                self.tween
+                    .iter()
+                    .map(move |p| (p, InstantSeconds(p.time.0 + offset)))
+            })
+            .filter(move |(p, time)| {
+                range.contains(time)
+                    && self.repeat.is_none_or(|r| *time <= r.until)
+                    && !self.is_masked(p, *time)
+            })
     }
 
-    /// Apply the events within `range` to `value`.
-    pub fn apply<T: Patch>(
+    /// Provides the events within `range` that should be applied to reconstruct
+    /// a value at the end of `range`.
+    fn apply_range(
         &self,
         range: core::ops::RangeInclusive<InstantSeconds>,
-        value: &mut T,
-    ) -> Result<(), PatchError> {
-        for TimelineParam { data, path, .. } in self.params_in(range) {
-            let patch = T::patch(data, path)?;
-            value.apply(patch);
-        }
-
-        Ok(())
+    ) -> impl Iterator<Item = (&TimelineParam, InstantSeconds)> {
+        // Each repetition rewrites the same parameters, so the two most recent
+        // are sufficient to reconstruct the value at the end of `range`.
+        self.params_in(range, 2)
     }
 
     /// Render out this event's steps, advancing the progress.
@@ -481,13 +1244,17 @@ impl EventTimeline {
             return Ok(());
         };
 
-        for param in self.params_in(render_range.start..=render_range.end) {
+        // Repetitions are rendered in order, so events on either side
+        // of a repetition's seam arrive in sequence.
+        for (param, time) in
+            self.params_in(render_range.start..=render_range.end, MAX_RENDERED_REPEATS)
+        {
             let event = NodeEventType::Param {
                 data: param.data.clone(),
                 path: param.path.clone(),
             };
 
-            buffer(event, param.time);
+            buffer(event, time);
         }
 
         self.render_progress.range.start = render_range.end;
@@ -502,11 +1269,28 @@ impl EventTimeline {
 fn update_events_instant(mut q: Query<&mut AudioEvents>, time: Res<Time<crate::time::Audio>>) {
     for mut event in &mut q {
         event.now = time.context().instant();
+        event.latency = time.context().play_latency();
     }
 }
 
-trait AudioLerp: Default + Clone + Send + Sync + 'static {
+/// Interpolation for animatable audio parameters.
+///
+/// This is used by [`ParamFade`] to tween individual
+/// fields of audio nodes.
+pub trait AudioLerp: Clone + Send + Sync + 'static {
+    /// Interpolate between `self` and `other` by `amount`, where `amount` is
+    /// in the range `[0.0, 1.0]`.
     fn audio_lerp(&self, other: Self, amount: f32) -> Self;
+
+    /// The number of events required for a tween between
+    /// `self` and `other` over `duration` seconds to sound smooth.
+    ///
+    /// By default, this produces one event every five milliseconds.
+    /// Most nodes smooth their parameters, so this is generally sufficient.
+    fn tween_events(&self, other: &Self, duration: f64) -> usize {
+        let _ = other;
+        max_event_rate(duration, 0.005)
+    }
 }
 
 fn clamp(db: f32) -> f32 {
@@ -522,6 +1306,62 @@ impl AudioLerp for Volume {
             (a, Self::Decibels(b)) => Self::Decibels(clamp(a.decibels()).lerp(b, amount)),
         }
     }
+
+    fn tween_events(&self, other: &Self, duration: f64) -> usize {
+        // Here, we use the just noticeable difference, around 1 dB, to roughly calculate
+        // how many total steps we need. We give a bit of margin just in case.
+        let db_span = (clamp(self.decibels()) - clamp(other.decibels())).abs();
+        let total_events = (db_span * 1.25).max(1.0) as usize;
+        max_event_rate(duration, 0.001).min(total_events)
+    }
+}
+
+impl AudioLerp for f32 {
+    fn audio_lerp(&self, other: Self, amount: f32) -> Self {
+        self.lerp(other, amount)
+    }
+}
+
+impl AudioLerp for f64 {
+    fn audio_lerp(&self, other: Self, amount: f32) -> Self {
+        self.lerp(other, amount as f64)
+    }
+}
+
+impl AudioLerp for Vec2 {
+    fn audio_lerp(&self, other: Self, amount: f32) -> Self {
+        self.lerp(other, amount)
+    }
+}
+
+impl AudioLerp for Vec3 {
+    fn audio_lerp(&self, other: Self, amount: f32) -> Self {
+        self.lerp(other, amount)
+    }
+}
+
+impl AudioLerp for Vec4 {
+    fn audio_lerp(&self, other: Self, amount: f32) -> Self {
+        self.lerp(other, amount)
+    }
+}
+
+impl AudioLerp for DVec2 {
+    fn audio_lerp(&self, other: Self, amount: f32) -> Self {
+        self.lerp(other, amount as f64)
+    }
+}
+
+impl AudioLerp for DVec3 {
+    fn audio_lerp(&self, other: Self, amount: f32) -> Self {
+        self.lerp(other, amount as f64)
+    }
+}
+
+impl AudioLerp for Quat {
+    fn audio_lerp(&self, other: Self, amount: f32) -> Self {
+        self.slerp(other, amount)
+    }
 }
 
 /// An extension trait that provides convenience methods for volume animation.
@@ -588,6 +1428,22 @@ pub trait VolumeFade {
         end: InstantSeconds,
         events: &mut AudioEvents,
     );
+
+    /// Linearly interpolate a [`VolumeNode`]'s volume to `volume` over `beats`,
+    /// starting at a `(bar, beat)` position.
+    ///
+    /// See [`Tempo`] for details on musical positions.
+    fn fade_over_beats(
+        &self,
+        volume: Volume,
+        tempo: &Tempo,
+        (bar, beat): (u32, f64),
+        beats: f64,
+        events: &mut AudioEvents,
+    ) {
+        let start = tempo.at(bar, beat);
+        self.fade_at(volume, start, start + tempo.beats(beats), events);
+    }
 }
 
 // Limit events to one per time step in seconds.
@@ -603,11 +1459,7 @@ impl VolumeFade for VolumeNode {
         let mut end_value = start_value;
         end_value.volume = target;
 
-        // Here, we use the just noticeable difference, around 1 dB, to roughly calculate
-        // how many total steps we need. We give a bit of margin just in case.
-        let db_span = (clamp(start_value.volume.decibels()) - clamp(target.decibels())).abs();
-        let total_events = (db_span * 1.25).max(1.0) as usize;
-        let total_events = max_event_rate(duration.0, 0.001).min(total_events);
+        let total_events = start_value.volume.tween_events(&target, duration.0);
 
         events.schedule_tween(
             start,
@@ -634,11 +1486,7 @@ impl VolumeFade for VolumeNode {
         let mut end_value = start_value;
         end_value.volume = target;
 
-        // Here, we use the just noticeable difference, around 1 dB, to roughly calculate
-        // how many total steps we need. We give a bit of margin just in case.
-        let db_span = (clamp(start_value.volume.decibels()) - clamp(target.decibels())).abs();
-        let total_events = (db_span * 1.25).max(1.0) as usize;
-        let total_events = max_event_rate(end.0 - start.0, 0.001).min(total_events);
+        let total_events = start_value.volume.tween_events(&target, end.0 - start.0);
 
         events.schedule_tween(
             start,
@@ -654,3 +1502,180 @@ impl VolumeFade for VolumeNode {
         );
     }
 }
+
+/// An extension trait that provides fades for arbitrary node parameters.
+///
+/// This is implemented for all types that can be scheduled with [`AudioEvents`],
+/// so any parameter implementing [`AudioLerp`] can be smoothly animated,
+/// not just a [`VolumeNode`]'s volume.
+///
+/// The parameter is selected with a closure that returns a mutable
+/// reference to the field.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn sweep(filter: Single<(&FastLowpassNode, &mut AudioEvents)>) {
+///     let (filter, mut events) = filter.into_inner();
+///
+///     // Sweep the cutoff frequency down to 250 Hz over two seconds.
+///     filter.fade_param_to(250.0, DurationSeconds(2.0), &mut events, |f| {
+///         &mut f.cutoff_hz
+///     });
+/// }
+/// ```
+pub trait ParamFade: Diff + Patch + Clone + Send + Sync + 'static {
+    /// Interpolate a parameter from its current value to `target`.
+    ///
+    /// Any events already scheduled at the current time are
+    /// applied before interpolating.
+    fn fade_param_to<P, F>(
+        &self,
+        target: P,
+        duration: DurationSeconds,
+        events: &mut AudioEvents,
+        param: F,
+    ) where
+        P: AudioLerp,
+        F: Fn(&mut Self) -> &mut P,
+    {
+        let start = events.now;
+        let end = events.now + duration;
+
+        self.fade_param_at(target, start, end, events, param);
+    }
+
+    /// Interpolate a parameter from its value at `start` to `target`.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn swell(
+    ///     reverb: Single<(&FreeverbNode, &mut AudioEvents)>,
+    ///     time: Res<Time<Audio>>,
+    /// ) {
+    ///     let (reverb, mut events) = reverb.into_inner();
+    ///
+    ///     // Grow the room starting one second from now.
+    ///     reverb.fade_param_at(
+    ///         1.0,
+    ///         time.now() + DurationSeconds(1.0),
+    ///         time.now() + DurationSeconds(4.0),
+    ///         &mut events,
+    ///         |r| &mut r.room_size,
+    ///     );
+    /// }
+    /// ```
+    fn fade_param_at<P, F>(
+        &self,
+        target: P,
+        start: InstantSeconds,
+        end: InstantSeconds,
+        events: &mut AudioEvents,
+        param: F,
+    ) where
+        P: AudioLerp,
+        F: Fn(&mut Self) -> &mut P,
+    {
+        let mut start_value = events.get_value_at(start, self);
+        let mut end_value = start_value.clone();
+        *param(&mut end_value) = target.clone();
+
+        let total_events = param(&mut start_value).tween_events(&target, end.0 - start.0);
+
+        events.schedule_tween(
+            start,
+            end,
+            start_value,
+            end_value,
+            total_events.max(1),
+            |a, b, t| {
+                let mut a = a.clone();
+                let mut b = b.clone();
+                let to = param(&mut b).clone();
+                let from = param(&mut a);
+                *from = from.audio_lerp(to, t);
+                a
+            },
+        );
+    }
+
+    /// Linearly interpolate a parameter to `target` over `beats`,
+    /// starting at a `(bar, beat)` position.
+    ///
+    /// See [`Tempo`] for details on musical positions.
+    fn fade_param_over_beats<P, F>(
+        &self,
+        target: P,
+        tempo: &Tempo,
+        (bar, beat): (u32, f64),
+        beats: f64,
+        events: &mut AudioEvents,
+        param: F,
+    ) where
+        P: AudioLerp,
+        F: Fn(&mut Self) -> &mut P,
+    {
+        let start = tempo.at(bar, beat);
+        self.fade_param_at(target, start, start + tempo.beats(beats), events, param);
+    }
+}
+
+impl<T> ParamFade for T where T: Diff + Patch + Clone + Send + Sync + 'static {}
+
+/// An extension trait that provides convenience methods for spatial animation.
+pub trait SpatialFade {
+    /// Linearly interpolate a [`SpatialBasicNode`]'s offset from its current value to `offset`.
+    ///
+    /// Note that offsets are overwritten each frame for emitters with transforms.
+    /// This is only useful for nodes whose positions are managed manually.
+    fn fade_offset_to(&self, offset: Vec3, duration: DurationSeconds, events: &mut AudioEvents);
+
+    /// Linearly interpolate a [`SpatialBasicNode`]'s offset from its value at `start` to `offset`.
+    fn fade_offset_at(
+        &self,
+        offset: Vec3,
+        start: InstantSeconds,
+        end: InstantSeconds,
+        events: &mut AudioEvents,
+    );
+}
+
+impl SpatialFade for SpatialBasicNode {
+    fn fade_offset_to(&self, offset: Vec3, duration: DurationSeconds, events: &mut AudioEvents) {
+        let start = events.now;
+        let end = events.now + duration;
+
+        self.fade_offset_at(offset, start, end, events);
+    }
+
+    fn fade_offset_at(
+        &self,
+        offset: Vec3,
+        start: InstantSeconds,
+        end: InstantSeconds,
+        events: &mut AudioEvents,
+    ) {
+        let start_value = events.get_value_at(start, self);
+        let mut end_value = start_value.clone();
+        end_value.offset = offset.into();
+
+        let total_events = max_event_rate(end.0 - start.0, 0.005).max(1);
+
+        events.schedule_tween(
+            start,
+            end,
+            start_value,
+            end_value,
+            total_events,
+            |a, b, t| {
+                let from: Vec3 = a.offset.into();
+                let to: Vec3 = b.offset.into();
+
+                let mut output = a.clone();
+                output.offset = from.lerp(to, t).into();
+                output
+            },
+        );
+    }
+}
@@ -88,6 +88,7 @@ pub(crate) fn param_follower<T: Diff + Patch + Component<Mutability = Mutable> +
                 events.push(event);
             }
         }
+        events.timed.append(&mut source_events.timed);
 
         // TODO: this will remove the timestamp too eagerly if there
         // are multiple followers.
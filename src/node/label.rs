@@ -7,9 +7,13 @@
 //! Any node that doesn't provide an explicit connection when spawned
 //! will be automatically connected to [MainBus].
 
+use super::FirewheelNode;
 use crate::edge::NodeMap;
-use bevy_ecs::{intern::Interned, lifecycle::HookContext, prelude::*, world::DeferredWorld};
+use bevy_ecs::{
+    intern::Interned, lifecycle::HookContext, prelude::*, system::SystemParam, world::DeferredWorld,
+};
 use bevy_log::prelude::*;
+use firewheel::node::NodeID;
 use smallvec::SmallVec;
 
 /// Node label derive macro.
@@ -85,7 +89,8 @@ bevy_ecs::define_label!(
 ///
 /// If no connections are specified for an entity
 /// with a [`FirewheelNode`][crate::prelude::FirewheelNode] component, the
-/// node will automatically be routed to this bus.
+/// node will automatically be routed to this bus. This fallback can be
+/// changed with [`AutoConnect`][crate::prelude::AutoConnect].
 /// For example, if you spawn a [`VolumeNode`][crate::prelude::VolumeNode]:
 ///
 /// ```
@@ -137,6 +142,15 @@ pub type InternedNodeLabel = Interned<dyn NodeLabel>;
 ///
 /// commands.spawn((VolumeNode { volume: Volume::Linear(0.25), ..Default::default() }, MyLabel));
 /// # }
+/// ```
+///
+/// An entity can carry any number of labels, and each is
+/// tracked independently. Removing a label component removes
+/// only that label from the collection.
+///
+/// A label should only be applied to one entity at a time.
+/// To move a label to another entity at runtime, use
+/// [`LabelCommands::take_label`].
 #[derive(Debug, Default, Component, Clone)]
 #[component(immutable)]
 pub struct NodeLabels(SmallVec<[InternedNodeLabel; 1]>);
@@ -165,10 +179,15 @@ impl NodeLabels {
         labels: Query<&NodeLabels>,
         mut map: ResMut<NodeMap>,
     ) -> Result {
-        let labels = labels.get(trigger.event_target())?;
+        let entity = trigger.event_target();
+        let labels = labels.get(entity)?;
 
+        // If a label has since been applied to another entity,
+        // that mapping should be left alone.
         for label in labels.iter() {
-            map.remove(label);
+            if map.get(label) == Some(&entity) {
+                map.remove(label);
+            }
         }
 
         Ok(())
@@ -212,6 +231,56 @@ impl NodeLabels {
     }
 }
 
+/// Read-only lookup of the entities and audio graph nodes
+/// associated with [`NodeLabel`]s.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn inspect(labels: NodeLabelRegistry) {
+///     if let Some(node) = labels.node_id_of(MainBus) {
+///         info!("the main bus is {node:?}");
+///     }
+///
+///     for (label, entity) in labels.iter() {
+///         info!("{label:?} -> {entity}");
+///     }
+/// }
+/// ```
+///
+/// Labels are registered when an entity with a label
+/// is spawned, while an entity's [`FirewheelNode`] is only
+/// available once the node has been acquired in the
+/// [`SeedlingSystems::Acquire`][crate::prelude::SeedlingSystems::Acquire] set.
+#[derive(SystemParam)]
+pub struct NodeLabelRegistry<'w, 's> {
+    map: Res<'w, NodeMap>,
+    nodes: Query<'w, 's, &'static FirewheelNode>,
+}
+
+impl NodeLabelRegistry<'_, '_> {
+    /// Get the entity labeled with `label`.
+    pub fn entity_of(&self, label: impl NodeLabel) -> Option<Entity> {
+        self.map.get(&label.intern()).copied()
+    }
+
+    /// Get the audio graph node labeled with `label`.
+    pub fn node_id_of(&self, label: impl NodeLabel) -> Option<NodeID> {
+        let entity = self.entity_of(label)?;
+        self.nodes.get(entity).ok().map(|node| node.0)
+    }
+
+    /// Returns `true` if an entity is labeled with `label`.
+    pub fn contains(&self, label: impl NodeLabel) -> bool {
+        self.map.contains_key(&label.intern())
+    }
+
+    /// Iterate over all registered labels and their entities.
+    pub fn iter(&self) -> impl Iterator<Item = (InternedNodeLabel, Entity)> {
+        self.map.iter().map(|(label, entity)| (*label, *entity))
+    }
+}
+
 /// Update an entity's node labels collection.
 #[doc(hidden)]
 pub fn insert_node_label<L: Component + NodeLabel>(mut world: DeferredWorld, context: HookContext) {
@@ -228,9 +297,81 @@ pub fn insert_node_label<L: Component + NodeLabel>(mut world: DeferredWorld, con
     world.commands().entity(context.entity).insert(labels);
 }
 
+/// Remove a label from an entity's node labels collection.
+#[doc(hidden)]
+pub fn remove_node_label<L: Component + NodeLabel>(mut world: DeferredWorld, context: HookContext) {
+    let value = world.get::<L>(context.entity).unwrap();
+    let interned = <L as NodeLabel>::intern(value);
+
+    world.commands().queue(move |world: &mut World| {
+        let Ok(mut entity) = world.get_entity_mut(context.entity) else {
+            return;
+        };
+        let Some(mut labels) = entity.get::<NodeLabels>().cloned() else {
+            return;
+        };
+
+        if labels.remove(interned) {
+            entity.insert(labels);
+        }
+    });
+}
+
+/// An [`EntityCommands`] extension trait for managing node labels.
+pub trait LabelCommands {
+    /// Move `label` to this entity, removing it from
+    /// whichever entity currently holds it.
+    ///
+    /// The [`NodeMap`] is updated immediately, so any connections
+    /// that are still pending will resolve to this entity. Connections
+    /// that have already been made are left untouched.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// #[derive(NodeLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    /// struct MusicBus;
+    ///
+    /// fn transition(mut commands: Commands) {
+    ///     let next_bus = commands.spawn(VolumeNode::default()).id();
+    ///
+    ///     // New music will now be routed to `next_bus`.
+    ///     commands.entity(next_bus).take_label(MusicBus);
+    /// }
+    /// ```
+    fn take_label<L: NodeLabel + Component + Clone>(&mut self, label: L) -> &mut Self;
+}
+
+impl LabelCommands for EntityCommands<'_> {
+    fn take_label<L: NodeLabel + Component + Clone>(&mut self, label: L) -> &mut Self {
+        let target = self.id();
+
+        self.commands().queue(move |world: &mut World| {
+            let interned = label.intern();
+            let owner = world.resource::<NodeMap>().get(&interned).copied();
+
+            if let Some(owner) = owner
+                && owner != target
+                && let Ok(mut owner) = world.get_entity_mut(owner)
+            {
+                owner.remove::<L>();
+                world.flush();
+            }
+
+            if let Ok(mut target) = world.get_entity_mut(target) {
+                target.insert(label);
+            }
+        });
+
+        self
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use super::NodeLabels;
     use crate::{
+        context::AudioContext,
         edge::NodeMap,
         prelude::*,
         test::{prepare_app, run},
@@ -289,4 +430,122 @@ mod test {
             assert!(!map.contains_key(&interned_two));
         });
     }
+
+    #[test]
+    fn test_label_registry() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((TestLabel, VolumeNode::default()));
+        });
+
+        run(
+            &mut app,
+            |registry: NodeLabelRegistry,
+             node: Single<(Entity, &FirewheelNode), With<TestLabel>>| {
+                let (entity, node) = *node;
+
+                assert_eq!(registry.entity_of(TestLabel), Some(entity));
+                assert_eq!(registry.node_id_of(TestLabel), Some(node.0));
+                assert!(!registry.contains(TestLabelTwo));
+                assert!(
+                    registry
+                        .iter()
+                        .any(|(label, e)| label == TestLabel.intern() && e == entity)
+                );
+            },
+        );
+    }
+
+    #[derive(Component)]
+    struct First;
+
+    #[derive(Component)]
+    struct Second;
+
+    #[derive(Component)]
+    struct Source;
+
+    #[test]
+    fn test_label_removal() {
+        let interned_one = TestLabel.intern();
+        let interned_two = TestLabelTwo.intern();
+
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((TestLabel, TestLabelTwo, VolumeNode::default()));
+        });
+
+        run(
+            &mut app,
+            |node: Single<Entity, With<TestLabel>>, mut commands: Commands| {
+                commands.entity(*node).remove::<TestLabel>();
+            },
+        );
+
+        run(
+            &mut app,
+            move |node: Single<(Entity, &NodeLabels), With<TestLabelTwo>>, map: Res<NodeMap>| {
+                let (entity, labels) = *node;
+
+                assert_eq!(&**labels, &[interned_two]);
+                assert!(!map.contains_key(&interned_one));
+                assert_eq!(map[&interned_two], entity);
+            },
+        );
+    }
+
+    #[test]
+    fn test_take_label() {
+        let interned = TestLabel.intern();
+
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((TestLabel, VolumeNode::default(), First));
+            commands.spawn((VolumeNode::default(), Second));
+        });
+
+        run(
+            &mut app,
+            |second: Single<Entity, With<Second>>, mut commands: Commands| {
+                commands
+                    .spawn((VolumeNode::default(), Source))
+                    .connect(TestLabel);
+                commands.entity(*second).take_label(TestLabel);
+            },
+        );
+
+        app.update();
+
+        run(
+            &mut app,
+            move |first: Single<(Entity, &FirewheelNode, Has<TestLabel>), With<First>>,
+                  second: Single<(Entity, &FirewheelNode), (With<Second>, With<TestLabel>)>,
+                  source: Single<&FirewheelNode, With<Source>>,
+                  map: Res<NodeMap>,
+                  mut context: ResMut<AudioContext>,
+                  mut commands: Commands| {
+                let (first, first_node, first_labeled) = *first;
+                let (second, second_node) = *second;
+                let source = source.0;
+
+                assert!(!first_labeled);
+                assert_eq!(map[&interned], second);
+
+                // The pending connection should resolve to the new owner.
+                context.with(|context| {
+                    let edges: Vec<_> = context.edges().filter(|e| e.src_node == source).collect();
+
+                    assert!(!edges.is_empty());
+                    assert!(edges.iter().all(|e| e.dst_node == second_node.0));
+                    assert!(edges.iter().all(|e| e.dst_node != first_node.0));
+                });
+
+                commands.entity(first).despawn();
+            },
+        );
+
+        run(
+            &mut app,
+            move |second: Single<Entity, With<Second>>, map: Res<NodeMap>| {
+                assert_eq!(map[&interned], *second);
+            },
+        );
+    }
 }
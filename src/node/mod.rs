@@ -2,10 +2,11 @@
 
 use crate::error::{SeedlingError, render_errors};
 use crate::pool::sample_effects::EffectOf;
+use crate::profiling::{self, audio_span};
 use crate::time::{Audio, AudioTime};
 use crate::{
     SeedlingSystems,
-    edge::{ChannelMapping, NodeMap},
+    edge::{ChannelMapping, GraphEdges, NodeMap},
     prelude::AudioContext,
 };
 use bevy_app::prelude::*;
@@ -25,16 +26,23 @@ use core::{any::TypeId, time::Duration};
 use firewheel::channel_config::ChannelConfig;
 use firewheel::clock::{DurationSeconds, EventInstant, InstantSeconds};
 use firewheel::graph::NodeEntry;
+use firewheel::nodes::{volume::VolumeNode, volume_pan::VolumePanNode};
 use firewheel::{
     diff::{Diff, Patch},
     event::{NodeEvent, NodeEventType},
     node::{AudioNode, NodeID},
 };
+use std::collections::VecDeque;
 
+pub mod bypass;
+pub mod effect;
 pub mod events;
 pub mod follower;
 pub mod label;
+pub mod mute;
+pub mod snapshot;
 
+pub use bypass::AudioBypass;
 use events::AudioEvents;
 use label::NodeLabels;
 
@@ -44,10 +52,13 @@ impl Plugin for NodePlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(events::EventsPlugin)
             .init_resource::<ScheduleDiffing>()
-            .init_resource::<AudioScheduleLookahead>()
+            .init_resource::<SchedulingConfig>()
+            .init_resource::<LateEventLog>()
             .init_resource::<PendingRemovals>()
             .init_resource::<DiffRate>()
             .init_resource::<DiffStopwatch>()
+            .init_resource::<snapshot::ParamSnapshots>()
+            .init_resource::<snapshot::PendingRecalls>()
             .add_systems(
                 Last,
                 (
@@ -55,10 +66,40 @@ impl Plugin for NodePlugin {
                     DiffStopwatch::post_diff.in_set(SeedlingSystems::PollStream),
                 ),
             )
-            .add_systems(Last, flush_events.in_set(SeedlingSystems::Flush))
             .add_systems(
                 Last,
-                AudioBypass::update_bypassed.in_set(SeedlingSystems::Queue),
+                (sync_deprecated_lookahead, flush_events)
+                    .chain()
+                    .in_set(SeedlingSystems::Flush),
+            )
+            .add_systems(
+                Last,
+                (
+                    AudioBypass::sync_followers
+                        .after(SeedlingSystems::Pool)
+                        .before(SeedlingSystems::Queue),
+                    AudioBypass::update_bypassed.in_set(SeedlingSystems::Queue),
+                    snapshot::apply_pending_recalls.before(SeedlingSystems::Queue),
+                ),
+            )
+            .add_systems(
+                Last,
+                (
+                    mute::AudioMute::sync_followers
+                        .after(SeedlingSystems::Pool)
+                        .before(SeedlingSystems::Queue),
+                    // Muting must land after followers are patched
+                    // and before their parameters are diffed.
+                    (
+                        mute::update_muted::<VolumeNode>
+                            .after(follower::param_follower::<VolumeNode>)
+                            .before(generate_param_events::<VolumeNode>),
+                        mute::update_muted::<VolumePanNode>
+                            .after(follower::param_follower::<VolumePanNode>)
+                            .before(generate_param_events::<VolumePanNode>),
+                    )
+                        .in_set(SeedlingSystems::Queue),
+                ),
             )
             .add_observer(label::NodeLabels::on_add_observer)
             .add_observer(label::NodeLabels::on_discard_observer)
@@ -66,28 +107,6 @@ impl Plugin for NodePlugin {
     }
 }
 
-/// Bypass an audio node.
-#[derive(Component, Clone, Debug)]
-pub struct AudioBypass;
-
-impl AudioBypass {
-    fn remove_bypass(
-        trigger: On<Remove, AudioBypass>,
-        mut node: Query<&mut AudioEvents>,
-    ) -> Result {
-        let mut events = node.get_mut(trigger.entity)?;
-        events.queue.push(NodeEventType::SetBypassed(false));
-
-        Ok(())
-    }
-
-    fn update_bypassed(bypassed: Query<&mut AudioEvents, Changed<AudioBypass>>) {
-        for mut events in bypassed {
-            events.queue.push(NodeEventType::SetBypassed(true));
-        }
-    }
-}
-
 /// Sets the maximum rate at which parameters will be
 /// diffed and flushed to the audio graph.
 ///
@@ -226,7 +245,7 @@ impl FirewheelNodeInfo {
 }
 
 /// A resource that determines how soon scheduled events are sent to the
-/// audio thread.
+/// audio thread, and how events that arrive too late are handled.
 ///
 /// `bevy_seedling` does not eagerly send all scheduled events to the audio thread.
 /// This could easily overwhelm the audio thread's event queue, especially when
@@ -236,21 +255,200 @@ impl FirewheelNodeInfo {
 /// to the target time. To account for potential hitches or framerate-to-audio-processing-rate
 /// mismatches, "close enough" should generally be at least a few frames in advance.
 ///
-/// [`AudioScheduleLookahead`] determines this buffer period. That is for each frame, any remaining
-/// events scheduled between the start of the app and `now` + [`AudioScheduleLookahead`]
-/// are sent.
+/// [`SchedulingConfig::max_queue_ahead`] determines this buffer period. That is for each frame,
+/// any remaining events scheduled between the start of the app and `now` +
+/// [`SchedulingConfig::max_queue_ahead`] are sent.
+///
+/// Events that would be sent with less than [`SchedulingConfig::min_lead`] to spare
+/// are snapped to `now` + [`SchedulingConfig::min_lead`]. This is normal for events
+/// scheduled in the recent past, like fades started from the previous frame's time.
+/// Events that miss the deadline by more than [`SchedulingConfig::late_tolerance`]
+/// are considered late, and are handled according to [`SchedulingConfig::late_events`].
+/// Each late event is recorded in the [`LateEventLog`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::{prelude::*, node::{SchedulingConfig, LateEventPolicy}};
+/// fn configure(mut config: ResMut<SchedulingConfig>) {
+///     // Guarantee the audio thread at least 20ms of notice.
+///     config.min_lead = DurationSeconds(0.02);
+///     config.late_events = LateEventPolicy::Drop;
+/// }
+/// ```
+#[derive(Resource, Debug, Clone)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct SchedulingConfig {
+    /// The minimum notice an event should be sent with.
+    ///
+    /// Events scheduled earlier than `now` + `min_lead` are late.
+    ///
+    /// Defaults to `DurationSeconds(0.0)`.
+    pub min_lead: DurationSeconds,
+    /// How far ahead of `now` scheduled events are sent to the audio thread.
+    ///
+    /// Defaults to `DurationSeconds(0.1)` (100ms).
+    pub max_queue_ahead: DurationSeconds,
+    /// How far past `now` + `min_lead` an event can be before it's late.
+    ///
+    /// Events within the tolerance are snapped to `now` + `min_lead`
+    /// without being logged or dropped.
+    ///
+    /// Defaults to `DurationSeconds(0.1)` (100ms), which covers
+    /// a few frames of staleness even at low frame rates.
+    pub late_tolerance: DurationSeconds,
+    /// How late events are handled.
+    ///
+    /// Defaults to [`LateEventPolicy::SnapToNow`].
+    pub late_events: LateEventPolicy,
+}
+
+impl Default for SchedulingConfig {
+    fn default() -> Self {
+        Self {
+            min_lead: DurationSeconds(0.0),
+            max_queue_ahead: DurationSeconds(0.1),
+            late_tolerance: DurationSeconds(0.1),
+            late_events: LateEventPolicy::SnapToNow,
+        }
+    }
+}
+
+impl SchedulingConfig {
+    /// Determine when an event scheduled for `scheduled` should be sent.
+    fn send_time(&self, now: InstantSeconds, scheduled: InstantSeconds) -> SendTime {
+        let deadline = now + self.min_lead;
+
+        if scheduled >= deadline {
+            SendTime::OnTime(scheduled)
+        } else if scheduled.0 >= deadline.0 - self.late_tolerance.0 {
+            SendTime::OnTime(deadline)
+        } else {
+            SendTime::Late(match self.late_events {
+                LateEventPolicy::SnapToNow => Some(deadline),
+                LateEventPolicy::Drop => None,
+            })
+        }
+    }
+}
+
+/// When a scheduled event should be sent to the audio thread.
+#[derive(Debug, PartialEq)]
+enum SendTime {
+    /// Send the event at the given time.
+    OnTime(InstantSeconds),
+    /// The event is late, and should be sent at the given time, if any.
+    Late(Option<InstantSeconds>),
+}
+
+/// The previous, lookahead-only scheduling configuration.
 ///
-/// Defaults to `DurationSeconds(0.1)` (100ms).
+/// If inserted, this overrides [`SchedulingConfig::max_queue_ahead`]
+/// whenever it changes.
+#[deprecated(
+    since = "0.8.0",
+    note = "use `SchedulingConfig::max_queue_ahead` instead"
+)]
 #[derive(Resource, Debug)]
 #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct AudioScheduleLookahead(pub DurationSeconds);
 
+#[allow(deprecated)]
 impl Default for AudioScheduleLookahead {
     fn default() -> Self {
         Self(DurationSeconds(0.1))
     }
 }
 
+#[allow(deprecated)]
+fn sync_deprecated_lookahead(
+    lookahead: Option<Res<AudioScheduleLookahead>>,
+    mut config: ResMut<SchedulingConfig>,
+) {
+    if let Some(lookahead) = lookahead.filter(|l| l.is_changed()) {
+        config.max_queue_ahead = lookahead.0;
+    }
+}
+
+/// Determines how events that miss [`SchedulingConfig::min_lead`] are handled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum LateEventPolicy {
+    /// Reschedule the event to `now` + [`SchedulingConfig::min_lead`].
+    ///
+    /// This ensures every event is applied, at the cost of timing accuracy.
+    #[default]
+    SnapToNow,
+    /// Discard the event entirely.
+    ///
+    /// Only events later than [`SchedulingConfig::late_tolerance`] are dropped.
+    /// Note that dropping an event from a tween may leave the parameter
+    /// short of its target value on the audio thread.
+    Drop,
+}
+
+/// A late event, recorded in the [`LateEventLog`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LateEvent {
+    /// The node entity the event targets.
+    pub entity: Entity,
+    /// The time the event was originally scheduled for.
+    pub scheduled: InstantSeconds,
+    /// The time the event was sent with, or `None` if it was dropped.
+    pub sent: Option<InstantSeconds>,
+}
+
+/// A bounded log of events that missed [`SchedulingConfig::min_lead`].
+///
+/// Only the most recent [`LateEventLog::CAPACITY`] events are retained,
+/// while [`LateEventLog::total`] counts all late events.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::node::LateEventLog;
+/// fn report(log: Res<LateEventLog>) {
+///     for late in log.iter() {
+///         warn!("late event for {}: {:?}", late.entity, late);
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct LateEventLog {
+    events: VecDeque<LateEvent>,
+    total: u64,
+}
+
+impl LateEventLog {
+    /// The maximum number of events retained in the log.
+    pub const CAPACITY: usize = 64;
+
+    fn push(&mut self, event: LateEvent) {
+        if self.events.len() >= Self::CAPACITY {
+            self.events.pop_front();
+        }
+
+        self.events.push_back(event);
+        self.total += 1;
+    }
+
+    /// Iterate over the most recent late events, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &LateEvent> {
+        self.events.iter()
+    }
+
+    /// The total number of late events encountered, including
+    /// those no longer retained.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Clear the retained events.
+    ///
+    /// This does not reset [`LateEventLog::total`].
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
+
 /// A component that communicates an effect is present on an entity.
 ///
 /// This is used for sample pool bookkeeping.
@@ -272,7 +470,7 @@ fn apply_patch<T: Patch>(value: &mut T, event: &NodeEventType) -> Result {
     Ok(())
 }
 
-fn generate_param_events<T: Diff + Patch + Component<Mutability = Mutable> + Clone>(
+pub(crate) fn generate_param_events<T: Diff + Patch + Component<Mutability = Mutable> + Clone>(
     mut nodes: Query<(
         Entity,
         Mut<T>,
@@ -285,6 +483,7 @@ fn generate_param_events<T: Diff + Patch + Component<Mutability = Mutable> + Clo
     diff_timer: DiffTimer,
     mut commands: Commands,
 ) -> Result {
+    audio_span!("seedling_diff", node = core::any::type_name::<T>());
     let render_range = time.render_range();
 
     for (entity, mut params, mut baseline, mut events, effect, ignore_timer) in nodes.iter_mut() {
@@ -333,6 +532,7 @@ fn handle_configuration_changes<
         ),
         Changed<T::Configuration>,
     >,
+    mut edges: ResMut<GraphEdges>,
     mut context: ResMut<AudioContext>,
     mut commands: Commands,
 ) -> Result {
@@ -341,9 +541,12 @@ fn handle_configuration_changes<
         return Ok(());
     }
 
+    edges.mark_dirty();
+
     let mut errors = Vec::new();
 
-    context.with(|context| {
+    // A dead audio thread is reported by the context plugin.
+    let _ = context.try_with(|context| {
         for (entity, node, node_id, config, mut baseline) in changes {
             // we have to get them every time, which is kind of annoying
             let existing_inputs = context
@@ -365,6 +568,7 @@ fn handle_configuration_changes<
                     continue;
                 }
             };
+            profiling::count_nodes_added(1);
 
             let info = FirewheelNodeInfo::new(context.node_info(new_node).unwrap());
 
@@ -431,9 +635,11 @@ where
         return Ok(());
     }
 
+    audio_span!("seedling_acquire", node = core::any::type_name::<T>());
     let mut errors = Vec::new();
 
-    context.with(|context| {
+    // A dead audio thread is reported by the context plugin.
+    let _ = context.try_with(|context| {
         for (entity, container, config, labels) in q.iter() {
             let node = context.add_node(container.clone(), config.cloned());
             let node = match node {
@@ -443,6 +649,7 @@ where
                     continue;
                 }
             };
+            profiling::count_nodes_added(1);
 
             let info = FirewheelNodeInfo::new(context.node_info(node).unwrap());
 
@@ -492,7 +699,8 @@ where
 
     let mut errors = Vec::new();
 
-    context.with(|context| {
+    // A dead audio thread is reported by the context plugin.
+    let _ = context.try_with(|context| {
         for (entity, node) in q.iter() {
             match context.node_state::<S>(node.0) {
                 Some(state) => {
@@ -676,6 +884,9 @@ impl RegisterNode for App {
         if nodes.insert::<T>() {
             world.add_observer(observe_node_insertion::<T>);
             world.register_required_components::<T, T::Configuration>();
+            world
+                .get_resource_or_init::<snapshot::SnapshotRegistry>()
+                .register::<T>();
         } else {
             // TODO: we'll need to be more careful about getting type names
             // for upstreaming.
@@ -704,6 +915,8 @@ impl RegisterNode for App {
             world.add_observer(insert_baseline::<T::Configuration>);
         }
 
+        // With many registered nodes, most will have no instances in a given
+        // frame, so we skip them entirely rather than running empty queries.
         self.add_systems(
             Last,
             (
@@ -713,7 +926,8 @@ impl RegisterNode for App {
                 (follower::param_follower::<T>, generate_param_events::<T>)
                     .chain()
                     .in_set(SeedlingSystems::Queue),
-            ),
+            )
+                .run_if(any_with_component::<T>),
         )
     }
 
@@ -758,7 +972,8 @@ impl RegisterNode for App {
             Last,
             (acquire_id::<T>, handle_configuration_changes::<T>)
                 .chain()
-                .in_set(SeedlingSystems::Acquire),
+                .in_set(SeedlingSystems::Acquire)
+                .run_if(any_with_component::<T>),
         )
     }
 
@@ -796,7 +1011,8 @@ impl RegisterNode for App {
             Last,
             fetch_state::<T, S>
                 .after(SeedlingSystems::Acquire)
-                .before(SeedlingSystems::Connect),
+                .before(SeedlingSystems::Connect)
+                .run_if(any_with_component::<T>),
         )
     }
 }
@@ -874,6 +1090,25 @@ impl FirewheelNode {
     }
 }
 
+/// Detach every entity from its audio node so it's acquired again.
+///
+/// This is used when the audio context is replaced, so the old node IDs
+/// must not be removed from the new graph.
+pub(crate) fn release_nodes(world: &mut World) {
+    let entities: Vec<_> = world
+        .query_filtered::<Entity, With<FirewheelNode>>()
+        .iter(world)
+        .collect();
+
+    for entity in entities {
+        world
+            .entity_mut(entity)
+            .remove::<(FirewheelNode, FirewheelNodeInfo)>();
+    }
+
+    world.resource_mut::<PendingRemovals>().0.clear();
+}
+
 /// Queued audio node removals.
 ///
 /// This resource allows us to defer audio node removals
@@ -895,37 +1130,57 @@ fn flush_events(
         Option<&DiffTimestamp>,
     )>,
     mut removals: ResMut<PendingRemovals>,
+    mut edges: ResMut<GraphEdges>,
     mut context: ResMut<AudioContext>,
     time: Res<bevy_time::Time<Audio>>,
     should_schedule: Res<ScheduleDiffing>,
-    lookahead: Res<AudioScheduleLookahead>,
+    config: Res<SchedulingConfig>,
+    mut late_events: ResMut<LateEventLog>,
     mut commands: Commands,
 ) -> Result {
+    audio_span!("seedling_flush");
     let mut errors = Vec::new();
+    let mut events_queued = 0;
 
-    context.with(|context| {
+    // A dead audio thread is reported by the context plugin,
+    // so the error itself can be ignored here.
+    let _ = context.try_with(|context| {
         for node in removals.0.drain(..) {
+            edges.mark_dirty();
             if let Err(e) = context.remove_node(node) {
                 error!("{e}");
             }
         }
 
+        // Removals and earlier connection changes are reflected
+        // here so run conditions can trace the graph without locking.
+        if edges.is_dirty() {
+            edges.refresh(context);
+        }
+
         // We use the start-of-frame time here to ensure these events
         // line up with the overall frame, even if it has already fallen
         // behind the audio thread at this point in the frame.
         let now = time.now();
-        let range_to_render = InstantSeconds(0.0)..now + lookahead.0;
+        let range_to_render = InstantSeconds(0.0)..now + config.max_queue_ahead;
+        let deadline = now + config.min_lead;
+        // Instants are translated to the context's clock, which may
+        // be offset from `Time<Audio>` after a stream restart.
+        let clock = time.context();
         for (node_entity, node, mut events, timestamp) in nodes.iter_mut() {
             for event in events.queue.drain(..) {
                 let time = match timestamp {
                     Some(t) => {
                         commands.entity(node_entity).remove::<DiffTimestamp>();
-                        Some(EventInstant::AtClockSeconds(t.0))
+                        Some(EventInstant::AtClockSeconds(clock.to_context(t.0)))
+                    }
+                    None if should_schedule.0 => {
+                        Some(EventInstant::AtClockSeconds(clock.to_context(now)))
                     }
-                    None if should_schedule.0 => Some(EventInstant::AtClockSeconds(now)),
                     _ => None,
                 };
 
+                events_queued += 1;
                 context.queue_event(NodeEvent {
                     node_id: node.0,
                     event,
@@ -933,13 +1188,41 @@ fn flush_events(
                 });
             }
 
+            for (time, event) in events.timed.drain(..) {
+                events_queued += 1;
+                context.queue_event(NodeEvent {
+                    node_id: node.0,
+                    event,
+                    time: Some(EventInstant::AtClockSeconds(
+                        clock.to_context(InstantSeconds(time.0.max(deadline.0))),
+                    )),
+                });
+            }
+
             for event in &mut events.timeline {
                 if let Err(e) =
                     event.render(range_to_render.start, range_to_render.end, |event, time| {
+                        let time = match config.send_time(now, time) {
+                            SendTime::OnTime(time) => time,
+                            SendTime::Late(sent) => {
+                                late_events.push(LateEvent {
+                                    entity: node_entity,
+                                    scheduled: time,
+                                    sent,
+                                });
+
+                                match sent {
+                                    Some(time) => time,
+                                    None => return,
+                                }
+                            }
+                        };
+
+                        events_queued += 1;
                         context.queue_event(NodeEvent {
                             node_id: node.0,
                             event,
-                            time: Some(EventInstant::AtClockSeconds(time)),
+                            time: Some(EventInstant::AtClockSeconds(clock.to_context(time))),
                         })
                     })
                 {
@@ -953,6 +1236,7 @@ fn flush_events(
         }
     });
 
+    profiling::count_events_queued(events_queued);
     render_errors("Failed to flush all events", errors)
 }
 
@@ -963,6 +1247,7 @@ mod test {
         prelude::*,
         test::{prepare_app, run},
     };
+    use bevy_math::{Quat, Vec3};
 
     #[derive(Component)]
     struct TestMarker;
@@ -1034,4 +1319,354 @@ mod test {
             },
         );
     }
+
+    #[test]
+    fn test_timeline_overflow() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((
+                VolumeNode::default(),
+                TimelineLimit {
+                    capacity: 1,
+                    overflow: TimelineOverflow::Reject,
+                },
+                TestMarker,
+            ));
+        });
+
+        #[derive(Resource, Default)]
+        struct Overflowed(u64);
+
+        app.init_resource::<Overflowed>().add_observer(
+            |overflow: On<TimelineOverflowed>, mut overflowed: ResMut<Overflowed>| {
+                overflowed.0 += overflow.count;
+            },
+        );
+        app.update();
+
+        run(
+            &mut app,
+            |mut q: Single<(&VolumeNode, &mut AudioEvents), With<TestMarker>>,
+             time: Res<Time<Audio>>| {
+                let (node, events) = &mut *q;
+                assert_eq!(events.limit().capacity, 1);
+
+                for i in 1..=3 {
+                    let at = time.delay(DurationSeconds(i as f64));
+                    events.schedule(at, *node, |node| {
+                        node.volume = Volume::Linear(i as f32 * 0.1);
+                    });
+                }
+
+                assert_eq!(events.overflow_count(), 2);
+            },
+        );
+
+        app.update();
+
+        assert_eq!(app.world().resource::<Overflowed>().0, 2);
+    }
+
+    #[test]
+    fn test_timeline_conflicts() {
+        let mut app = prepare_app(|| {});
+
+        run(&mut app, |time: Res<Time<Audio>>| {
+            let node = VolumeNode::default();
+            let start = time.now();
+            let mid = start + DurationSeconds(1.0);
+            let end = start + DurationSeconds(2.0);
+            let after = start + DurationSeconds(3.0);
+
+            // With equal priorities, the later event takes over.
+            let mut events = AudioEvents::new(&time);
+            node.fade_at(Volume::Linear(0.0), start, end, &mut events);
+            events.schedule(mid, &node, |node| node.volume = Volume::Linear(0.5));
+
+            let value = events.get_value_at(after, &node);
+            assert_eq!(value.volume, Volume::Linear(0.5));
+            assert!(matches!(
+                events.debug_validate(),
+                Err(TimelineError::OverlappingRanges { .. })
+            ));
+
+            // A higher priority event isn't interrupted.
+            let mut events = AudioEvents::new(&time);
+            events.with_priority(TimelinePriority::HIGH, |events| {
+                node.fade_at(Volume::Linear(0.0), start, end, events);
+            });
+            events.schedule(mid, &node, |node| node.volume = Volume::Linear(0.5));
+
+            let value = events.get_value_at(after, &node);
+            assert!(value.volume.linear() < 1e-6);
+        });
+    }
+
+    #[test]
+    fn test_describe_timeline() {
+        let mut app = prepare_app(|| {});
+
+        run(&mut app, |time: Res<Time<Audio>>| {
+            let node = VolumeNode::default();
+            let settings = PlaybackSettings::default();
+            let start = time.now();
+            let end = start + DurationSeconds(1.0);
+
+            let mut events = AudioEvents::new(&time);
+            node.fade_at(
+                Volume::Linear(0.5),
+                start + DurationSeconds(0.5),
+                end,
+                &mut events,
+            );
+            settings.pause_at(start, &mut events);
+
+            let description = events.describe();
+            assert_eq!(description.events.len(), 2);
+
+            let pause = &description.events[0];
+            assert!(pause.target.contains("PlaybackSettings"));
+            assert_eq!((pause.start, pause.end), (start, start));
+
+            let fade = &description.events[1];
+            assert!(fade.target.contains("VolumeNode"));
+            assert_eq!(fade.end, end);
+            assert!(fade.params.len() > 1);
+            assert!(fade.params.iter().all(|p| p.time <= end));
+            assert_eq!(description.upcoming().count(), 2);
+        });
+    }
+
+    #[test]
+    fn test_repeating_timeline() {
+        let mut app = prepare_app(|| {});
+
+        run(&mut app, |time: Res<Time<Audio>>| {
+            let node = VolumeNode::default();
+            let start = time.now();
+
+            let mut events = AudioEvents::new(&time);
+            events.repeating(DurationSeconds(1.0), |events| {
+                events.schedule(start, &node, |node| node.volume = Volume::Linear(0.2));
+                events.schedule(start + DurationSeconds(0.5), &node, |node| {
+                    node.volume = Volume::Linear(0.8)
+                });
+            });
+
+            let value = events.get_value_at(start + DurationSeconds(3.2), &node);
+            assert_eq!(value.volume, Volume::Linear(0.2));
+            let value = events.get_value_at(start + DurationSeconds(3.6), &node);
+            assert_eq!(value.volume, Volume::Linear(0.8));
+
+            // Both events should render in order across each repetition's seam.
+            let mut rendered = Vec::new();
+            for timeline in &mut events.timeline {
+                timeline
+                    .render(start, start + DurationSeconds(2.6), |_, time| {
+                        rendered.push(time.0 - start.0)
+                    })
+                    .unwrap();
+            }
+            rendered.sort_by(f64::total_cmp);
+
+            let expected = [0.0, 0.5, 1.0, 1.5, 2.0, 2.5];
+            assert_eq!(rendered.len(), expected.len());
+            for (rendered, expected) in rendered.iter().zip(expected) {
+                assert!((rendered - expected).abs() < 1e-9);
+            }
+
+            // Stopping removes future repetitions.
+            events.stop_repeating(start + DurationSeconds(1.2));
+            let value = events.get_value_at(start + DurationSeconds(3.2), &node);
+            assert_eq!(value.volume, Volume::Linear(0.2));
+            assert!(
+                events
+                    .timeline
+                    .iter()
+                    .all(|t| t.completely_elapsed(start + DurationSeconds(3.0)))
+            );
+        });
+    }
+
+    #[test]
+    fn test_linear_lerp() {
+        assert_eq!(2.0f64.audio_lerp(4.0, 0.5), 3.0);
+        assert_eq!(
+            Vec3::ZERO.audio_lerp(Vec3::new(2.0, 4.0, -2.0), 0.5),
+            Vec3::new(1.0, 2.0, -1.0)
+        );
+        assert_eq!(
+            Volume::Linear(0.0).audio_lerp(Volume::Linear(1.0), 0.25),
+            Volume::Linear(0.25)
+        );
+
+        let quarter = Quat::IDENTITY.audio_lerp(Quat::from_rotation_y(core::f32::consts::PI), 0.5);
+        assert!(quarter.abs_diff_eq(Quat::from_rotation_y(core::f32::consts::FRAC_PI_2), 1e-6));
+    }
+
+    #[test]
+    fn test_f64_fade() {
+        #[derive(Diff, Patch, Clone, Debug, PartialEq)]
+        struct Speed {
+            speed: f64,
+        }
+
+        let mut app = prepare_app(|| {});
+
+        run(&mut app, |time: Res<Time<Audio>>| {
+            let value = Speed { speed: 1.0 };
+            let start = time.now();
+            let end = start + DurationSeconds(1.0);
+
+            let mut events = AudioEvents::new(&time);
+            value.fade_param_at(2.0, start, end, &mut events, |v| &mut v.speed);
+
+            let mid = events.get_value_at(start + DurationSeconds(0.5), &value);
+            assert!((mid.speed - 1.5).abs() < 0.01);
+            assert_eq!(events.get_value_at(end, &value).speed, 2.0);
+        });
+    }
+
+    #[derive(Diff, Patch, Clone, Debug, PartialEq)]
+    struct TestFilter {
+        cutoff: f32,
+        q: f32,
+    }
+
+    #[test]
+    fn test_param_fade() {
+        let mut app = prepare_app(|| {});
+
+        run(&mut app, |time: Res<Time<Audio>>| {
+            let value = TestFilter {
+                cutoff: 1000.0,
+                q: 0.7,
+            };
+            let start = time.context().instant();
+
+            let mut events = AudioEvents::new(&time);
+            value.fade_param_to(500.0, DurationSeconds(1.0), &mut events, |f| &mut f.cutoff);
+
+            // Only the selected field is animated.
+            let mid = events.get_value_at(start + DurationSeconds(0.5), &value);
+            assert!((mid.cutoff - 750.0).abs() < 10.0);
+            assert_eq!(mid.q, 0.7);
+
+            let end = events.get_value_at(start + DurationSeconds(1.0), &value);
+            assert_eq!(
+                end,
+                TestFilter {
+                    cutoff: 500.0,
+                    q: 0.7
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_param_fade_sequence() {
+        let mut app = prepare_app(|| {});
+
+        run(&mut app, |time: Res<Time<Audio>>| {
+            let value = TestFilter {
+                cutoff: 1000.0,
+                q: 0.7,
+            };
+            let start = time.now();
+            let mid = start + DurationSeconds(1.0);
+            let end = mid + DurationSeconds(1.0);
+
+            let mut events = AudioEvents::new(&time);
+            value.fade_param_at(2000.0, start, mid, &mut events, |f| &mut f.cutoff);
+            // The second fade picks up where the first left off.
+            value.fade_param_at(4.0, mid, end, &mut events, |f| &mut f.q);
+
+            let before = events.get_value_at(start + DurationSeconds(0.5), &value);
+            assert!((before.cutoff - 1500.0).abs() < 10.0);
+            assert_eq!(before.q, 0.7);
+
+            let after = events.get_value_at(mid + DurationSeconds(0.5), &value);
+            assert_eq!(after.cutoff, 2000.0);
+            assert!((after.q - 2.35).abs() < 0.05);
+
+            assert_eq!(
+                events.get_value_at(end, &value),
+                TestFilter {
+                    cutoff: 2000.0,
+                    q: 4.0
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn test_late_event_policy() {
+        let now = InstantSeconds(10.0);
+        let mut config = SchedulingConfig {
+            min_lead: DurationSeconds(0.02),
+            ..Default::default()
+        };
+
+        // Events after the deadline are sent as scheduled.
+        assert_eq!(
+            config.send_time(now, InstantSeconds(10.5)),
+            SendTime::OnTime(InstantSeconds(10.5))
+        );
+
+        // Recently elapsed events are quietly snapped to the deadline.
+        assert_eq!(
+            config.send_time(now, InstantSeconds(9.95)),
+            SendTime::OnTime(InstantSeconds(10.02))
+        );
+
+        // Events beyond the tolerance are late.
+        assert_eq!(
+            config.send_time(now, InstantSeconds(9.0)),
+            SendTime::Late(Some(InstantSeconds(10.02)))
+        );
+
+        config.late_events = LateEventPolicy::Drop;
+        assert_eq!(
+            config.send_time(now, InstantSeconds(9.95)),
+            SendTime::OnTime(InstantSeconds(10.02))
+        );
+        assert_eq!(
+            config.send_time(now, InstantSeconds(9.0)),
+            SendTime::Late(None)
+        );
+    }
+
+    #[test]
+    fn test_late_event_log() {
+        let mut log = LateEventLog::default();
+        let late = |i: usize| LateEvent {
+            entity: Entity::PLACEHOLDER,
+            scheduled: InstantSeconds(i as f64),
+            sent: None,
+        };
+
+        for i in 0..LateEventLog::CAPACITY + 10 {
+            log.push(late(i));
+        }
+
+        // Only the most recent events are retained.
+        assert_eq!(log.iter().count(), LateEventLog::CAPACITY);
+        assert_eq!(log.iter().next(), Some(&late(10)));
+        assert_eq!(log.total(), LateEventLog::CAPACITY as u64 + 10);
+
+        log.clear();
+        assert_eq!(log.iter().count(), 0);
+        assert_eq!(log.total(), LateEventLog::CAPACITY as u64 + 10);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_lookahead() {
+        let mut app = prepare_app(|| {});
+
+        app.insert_resource(AudioScheduleLookahead(DurationSeconds(0.25)));
+        app.update();
+
+        let config = app.world().resource::<SchedulingConfig>();
+        assert_eq!(config.max_queue_ahead, DurationSeconds(0.25));
+    }
 }
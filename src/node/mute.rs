@@ -0,0 +1,265 @@
+//! Muting for volume-bearing nodes.
+
+use super::{Baseline, events::AudioEvents, follower::FollowerOf};
+use crate::pool::sample_effects::EffectOf;
+use bevy_ecs::prelude::*;
+use firewheel::{
+    Volume,
+    diff::Diff,
+    nodes::{volume::VolumeNode, volume_pan::VolumePanNode},
+};
+
+/// Mute a volume-bearing audio node.
+///
+/// While this component is present, the node's audio-side
+/// volume is held at [`Volume::SILENT`] without touching
+/// its `volume` field, so the configured level is restored
+/// once the component is removed.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn toggle_mute(
+///     main_bus: Single<(Entity, Has<AudioMute>), With<MainBus>>,
+///     mut commands: Commands,
+/// ) {
+///     let (entity, muted) = *main_bus;
+///
+///     if muted {
+///         commands.entity(entity).remove::<AudioMute>();
+///     } else {
+///         commands.entity(entity).insert(AudioMute);
+///     }
+/// }
+/// ```
+///
+/// The transition relies on the node's parameter smoothing,
+/// so muting and unmuting are declicked. Changes to `volume`
+/// while muted are tracked but not sent until the node is unmuted.
+/// Events scheduled through [`AudioEvents`] are not masked.
+///
+/// This applies to [`VolumeNode`] and [`VolumePanNode`], including effects
+/// in [`SampleEffects`][crate::prelude::SampleEffects]. Muting an effect
+/// mutes the node on whichever sampler the sample is assigned to.
+#[derive(Component, Clone, Debug)]
+pub struct AudioMute;
+
+impl AudioMute {
+    /// Mirror each effect's mute onto the nodes following it.
+    pub(super) fn sync_followers(
+        followers: Query<(Entity, Ref<FollowerOf>, Has<AudioMute>)>,
+        sources: Query<Ref<AudioMute>, With<EffectOf>>,
+        mut removed: RemovedComponents<AudioMute>,
+        effects: Query<(), With<EffectOf>>,
+        mut commands: Commands,
+    ) {
+        let mut unmuted: Vec<_> = removed.read().filter(|e| effects.contains(*e)).collect();
+        unmuted.sort_unstable();
+
+        for (follower, source, muted) in &followers {
+            let source_mute = sources.get(source.0).ok();
+            let source_changed = source_mute.as_ref().is_some_and(|m| m.is_changed())
+                || unmuted.binary_search(&source.0).is_ok();
+
+            if !source.is_changed() && !source_changed {
+                continue;
+            }
+
+            match (source_mute.is_some(), muted) {
+                (true, false) => {
+                    commands.entity(follower).insert(AudioMute);
+                }
+                (false, true) => {
+                    commands.entity(follower).remove::<AudioMute>();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Nodes with a mutable `volume` parameter.
+pub(crate) trait VolumeParams: Diff + Clone + Component {
+    fn volume_mut(&mut self) -> &mut Volume;
+
+    fn volume(&self) -> Volume;
+}
+
+impl VolumeParams for VolumeNode {
+    fn volume_mut(&mut self) -> &mut Volume {
+        &mut self.volume
+    }
+
+    fn volume(&self) -> Volume {
+        self.volume
+    }
+}
+
+impl VolumeParams for VolumePanNode {
+    fn volume_mut(&mut self) -> &mut Volume {
+        &mut self.volume
+    }
+
+    fn volume(&self) -> Volume {
+        self.volume
+    }
+}
+
+/// Send a patch moving the node's volume from `from` to `to`.
+pub(crate) fn push_volume<T: VolumeParams>(
+    params: &T,
+    from: Volume,
+    to: Volume,
+    events: &mut AudioEvents,
+) {
+    let mut baseline = params.clone();
+    *baseline.volume_mut() = from;
+
+    let mut target = params.clone();
+    *target.volume_mut() = to;
+
+    target.diff(&baseline, Default::default(), events);
+}
+
+pub(crate) fn update_muted<T: VolumeParams>(
+    mut muted: Query<(&T, &mut Baseline<T>, &mut AudioEvents, Ref<AudioMute>)>,
+    mut unmuted: RemovedComponents<AudioMute>,
+    mut nodes: Query<(&T, &mut AudioEvents), Without<AudioMute>>,
+) {
+    for (params, mut baseline, mut events, mute) in muted.iter_mut() {
+        if mute.is_changed() {
+            push_volume(params, params.volume(), Volume::SILENT, &mut events);
+        }
+
+        // Keeping the baseline in sync prevents the diffing
+        // systems from sending the volume while muted.
+        if baseline.0.volume() != params.volume() {
+            *baseline.0.volume_mut() = params.volume();
+        }
+    }
+
+    for entity in unmuted.read() {
+        if let Ok((params, mut events)) = nodes.get_mut(entity) {
+            push_volume(params, Volume::SILENT, params.volume(), &mut events);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        prelude::*,
+        test::{prepare_app, run},
+    };
+
+    #[test]
+    fn test_mute_preserves_volume() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((
+                VolumeNode {
+                    volume: Volume::Linear(0.5),
+                    ..Default::default()
+                },
+                AudioMute,
+            ));
+        });
+
+        run(
+            &mut app,
+            |mut node: Single<(&mut VolumeNode, &Baseline<VolumeNode>)>| {
+                assert_eq!(node.0.volume, Volume::Linear(0.5));
+                assert_eq!(node.1.0.volume, Volume::Linear(0.5));
+
+                node.0.volume = Volume::Linear(0.25);
+            },
+        );
+
+        app.update();
+
+        run(
+            &mut app,
+            |node: Single<(Entity, &VolumeNode, &Baseline<VolumeNode>)>, mut commands: Commands| {
+                let (entity, params, baseline) = *node;
+
+                assert_eq!(params.volume, Volume::Linear(0.25));
+                assert_eq!(baseline.0.volume, Volume::Linear(0.25));
+
+                commands.entity(entity).remove::<AudioMute>();
+            },
+        );
+
+        app.update();
+
+        run(&mut app, |node: Single<&VolumeNode, Without<AudioMute>>| {
+            assert_eq!(node.volume, Volume::Linear(0.25));
+        });
+    }
+
+    #[test]
+    fn test_follower_mute() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            let effect = commands
+                .spawn(sample_effects![(
+                    VolumeNode {
+                        volume: Volume::Linear(0.5),
+                        ..Default::default()
+                    },
+                    AudioMute
+                )])
+                .id();
+
+            commands.queue(move |world: &mut World| {
+                let effect = world.get::<SampleEffects>(effect).unwrap()[0];
+                world.spawn((
+                    VolumeNode {
+                        volume: Volume::Linear(0.5),
+                        ..Default::default()
+                    },
+                    FollowerOf(effect),
+                ));
+            });
+        });
+
+        app.update();
+
+        run(
+            &mut app,
+            |follower: Single<Has<AudioMute>, With<FollowerOf>>| {
+                assert!(*follower);
+            },
+        );
+
+        // The muted follower sends a silent volume to its node.
+        run(&mut app, update_muted::<VolumeNode>);
+        run(
+            &mut app,
+            |follower: Single<(&VolumeNode, &AudioEvents), With<FollowerOf>>| {
+                let (params, events) = *follower;
+                let mut sent = params.clone();
+                for event in &events.queue {
+                    crate::node::apply_patch(&mut sent, event).unwrap();
+                }
+
+                assert_eq!(params.volume, Volume::Linear(0.5));
+                assert_eq!(sent.volume, Volume::SILENT);
+            },
+        );
+
+        run(
+            &mut app,
+            |effect: Single<Entity, With<EffectOf>>, mut commands: Commands| {
+                commands.entity(*effect).remove::<AudioMute>();
+            },
+        );
+
+        app.update();
+
+        run(
+            &mut app,
+            |follower: Single<Has<AudioMute>, With<FollowerOf>>| {
+                assert!(!*follower);
+            },
+        );
+    }
+}
@@ -0,0 +1,403 @@
+//! Parameter snapshots for A/B comparison.
+
+use super::{
+    FirewheelNode,
+    events::{AudioEvents, VolumeFade},
+    follower::FollowerOf,
+};
+use crate::{
+    edge::{BusQuery, EdgeTarget, NodeMap},
+    pool::PoolSamplerOf,
+    time::{Audio, AudioTime},
+};
+use bevy_ecs::{component::Mutable, prelude::*, system::SystemState};
+use bevy_log::prelude::*;
+use bevy_platform::collections::HashMap;
+use bevy_time::Time;
+use core::any::Any;
+use firewheel::{
+    Volume,
+    clock::{DurationSeconds, InstantSeconds},
+    nodes::volume::VolumeNode,
+};
+
+type CaptureFn = fn(&World, Entity) -> Option<Box<dyn Any + Send + Sync>>;
+type ApplyFn = fn(&mut World, Entity, &(dyn Any + Send + Sync));
+
+/// Type-erased accessors for every node registered with diffing.
+#[derive(Resource, Default)]
+pub(crate) struct SnapshotRegistry(Vec<(CaptureFn, ApplyFn)>);
+
+impl SnapshotRegistry {
+    pub(crate) fn register<T: Component<Mutability = Mutable> + Clone>(&mut self) {
+        self.0.push((
+            |world, entity| {
+                world
+                    .get::<T>(entity)
+                    .map(|value| Box::new(value.clone()) as Box<dyn Any + Send + Sync>)
+            },
+            |world, entity, value| {
+                if let (Some(mut node), Some(value)) =
+                    (world.get_mut::<T>(entity), value.downcast_ref::<T>())
+                {
+                    *node = value.clone();
+                }
+            },
+        ));
+    }
+}
+
+/// A captured set of node parameters.
+///
+/// See [`ParamSnapshots`].
+pub struct ParamSnapshot {
+    bus: Entity,
+    values: Vec<(Entity, usize, Box<dyn Any + Send + Sync>)>,
+}
+
+impl core::fmt::Debug for ParamSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParamSnapshot")
+            .field("bus", &self.bus)
+            .field("values", &self.values.len())
+            .finish()
+    }
+}
+
+impl ParamSnapshot {
+    /// The bus this snapshot was taken from.
+    pub fn bus(&self) -> Entity {
+        self.bus
+    }
+
+    /// The number of node components captured.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no node components were captured.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Named parameter snapshots for tuning sessions.
+///
+/// A snapshot captures the parameters of a bus and every node
+/// feeding into it. Recalling a snapshot writes those values back,
+/// and the usual diffing sends them to the audio graph. Storing a few
+/// snapshots makes it easy to A/B different mixes at runtime.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// #[derive(NodeLabel, PartialEq, Eq, Debug, Hash, Clone)]
+/// struct SfxBus;
+///
+/// fn compare(keys: Res<ButtonInput<KeyCode>>, mut commands: Commands) {
+///     if keys.just_pressed(KeyCode::KeyS) {
+///         commands.snapshot_params("a", SfxBus);
+///     }
+///
+///     if keys.just_pressed(KeyCode::KeyA) {
+///         commands.queue(RecallParams::new("a").with_fade(DurationSeconds(0.1)));
+///     }
+/// }
+/// ```
+///
+/// Sampler nodes and nodes following [`SampleEffects`] are skipped,
+/// since their parameters are driven by the samples they play.
+/// Only nodes registered with [`RegisterNode::register_node`] are captured.
+///
+/// [`SampleEffects`]: crate::prelude::SampleEffects
+/// [`RegisterNode::register_node`]: crate::prelude::RegisterNode::register_node
+#[derive(Resource, Debug, Default)]
+pub struct ParamSnapshots(HashMap<String, ParamSnapshot>);
+
+impl ParamSnapshots {
+    /// Get a snapshot by name.
+    pub fn get(&self, name: &str) -> Option<&ParamSnapshot> {
+        self.0.get(name)
+    }
+
+    /// Remove a snapshot, returning `true` if it existed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.0.remove(name).is_some()
+    }
+
+    /// Iterate over the names of all stored snapshots.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+}
+
+/// Resolve a bus target to its entity.
+pub(crate) fn resolve_bus(world: &mut World, target: &EdgeTarget) -> Option<Entity> {
+    match target {
+        EdgeTarget::Entity(entity) => Some(*entity),
+        EdgeTarget::Label(label) => world.resource::<NodeMap>().get(label).copied(),
+        EdgeTarget::Node(id) => world
+            .query::<(Entity, &FirewheelNode)>()
+            .iter(world)
+            .find(|(_, node)| node.0 == *id)
+            .map(|(entity, _)| entity),
+    }
+}
+
+/// Capture a bus's parameters into [`ParamSnapshots`].
+///
+/// An existing snapshot with the same name is replaced.
+#[derive(Debug)]
+pub struct SnapshotParams {
+    name: String,
+    bus: EdgeTarget,
+}
+
+impl SnapshotParams {
+    /// Snapshot `bus` and everything upstream of it as `name`.
+    pub fn new(name: impl Into<String>, bus: impl Into<EdgeTarget>) -> Self {
+        Self {
+            name: name.into(),
+            bus: bus.into(),
+        }
+    }
+}
+
+impl Command for SnapshotParams {
+    fn apply(self, world: &mut World) {
+        let Some(bus) = resolve_bus(world, &self.bus) else {
+            warn!("Failed to resolve bus for snapshot \"{}\".", self.name);
+            return;
+        };
+
+        let mut state = SystemState::<BusQuery>::new(world);
+        let mut entities: Vec<_> = state.get(world).upstream_of(bus).collect();
+        entities.push(bus);
+
+        let registry = world.get_resource_or_init::<SnapshotRegistry>().0.clone();
+        let mut values = Vec::new();
+        for entity in entities {
+            let driven = world
+                .get_entity(entity)
+                .is_ok_and(|e| e.contains::<FollowerOf>() || e.contains::<PoolSamplerOf>());
+            if driven {
+                continue;
+            }
+
+            for (index, (capture, _)) in registry.iter().enumerate() {
+                if let Some(value) = capture(world, entity) {
+                    values.push((entity, index, value));
+                }
+            }
+        }
+
+        world
+            .get_resource_or_init::<ParamSnapshots>()
+            .0
+            .insert(self.name, ParamSnapshot { bus, values });
+    }
+}
+
+/// Recall a snapshot stored in [`ParamSnapshots`].
+///
+/// By default, the values are applied immediately. With a fade,
+/// the bus's [`VolumeNode`] dips to silence over the first half of
+/// the fade, the snapshot is applied, and the bus fades back in.
+#[derive(Debug)]
+pub struct RecallParams {
+    name: String,
+    fade: DurationSeconds,
+}
+
+impl RecallParams {
+    /// Recall the snapshot `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            fade: DurationSeconds(0.0),
+        }
+    }
+
+    /// Fade the bus out and back in around the switch.
+    pub fn with_fade(self, fade: DurationSeconds) -> Self {
+        Self { fade, ..self }
+    }
+}
+
+impl Command for RecallParams {
+    fn apply(self, world: &mut World) {
+        let Some(bus) = world
+            .get_resource::<ParamSnapshots>()
+            .and_then(|s| s.get(&self.name))
+            .map(ParamSnapshot::bus)
+        else {
+            warn!("No parameter snapshot named \"{}\".", self.name);
+            return;
+        };
+
+        let has_volume = world.get::<VolumeNode>(bus).is_some();
+        if self.fade.0 <= 0.0 || !has_volume {
+            apply_snapshot(world, &self.name);
+            return;
+        }
+
+        let now = world.resource::<Time<Audio>>().now();
+        let midpoint = now + DurationSeconds(self.fade.0 * 0.5);
+        fade_bus(world, bus, Volume::SILENT, now, midpoint);
+
+        world
+            .get_resource_or_init::<PendingRecalls>()
+            .0
+            .push(PendingRecall {
+                name: self.name,
+                bus,
+                apply_at: midpoint,
+                end: now + self.fade,
+            });
+    }
+}
+
+/// Fade `bus` to `target` over `start..end`.
+fn fade_bus(
+    world: &mut World,
+    bus: Entity,
+    target: Volume,
+    start: InstantSeconds,
+    end: InstantSeconds,
+) {
+    let Ok(mut entity) = world.get_entity_mut(bus) else {
+        return;
+    };
+    let Some(node) = entity.get::<VolumeNode>().cloned() else {
+        return;
+    };
+    let Some(mut events) = entity.get_mut::<AudioEvents>() else {
+        return;
+    };
+
+    let current = events.get_value_at(start, &node).volume;
+    node.fade_at(target, start, end, &mut events);
+
+    // The timeline moves the volume from here, so it
+    // shouldn't jump to a freshly recalled value.
+    if let Some(mut node) = entity.get_mut::<VolumeNode>() {
+        node.volume = current;
+    }
+}
+
+fn apply_snapshot(world: &mut World, name: &str) {
+    let Some(registry) = world
+        .get_resource::<SnapshotRegistry>()
+        .map(|r| r.0.clone())
+    else {
+        return;
+    };
+
+    world.resource_scope(|world, snapshots: Mut<ParamSnapshots>| {
+        let Some(snapshot) = snapshots.get(name) else {
+            return;
+        };
+
+        for (entity, index, value) in &snapshot.values {
+            let (_, apply) = registry[*index];
+            apply(world, *entity, value.as_ref());
+        }
+    });
+}
+
+struct PendingRecall {
+    name: String,
+    bus: Entity,
+    apply_at: InstantSeconds,
+    end: InstantSeconds,
+}
+
+/// Faded recalls waiting for their bus to fall silent.
+#[derive(Resource, Default)]
+pub(crate) struct PendingRecalls(Vec<PendingRecall>);
+
+pub(crate) fn apply_pending_recalls(world: &mut World) {
+    let now = world.resource::<Time<Audio>>().now();
+    let Some(mut pending) = world.get_resource_mut::<PendingRecalls>() else {
+        return;
+    };
+    if pending.0.is_empty() {
+        return;
+    }
+
+    let (ready, waiting) = core::mem::take(&mut pending.0)
+        .into_iter()
+        .partition::<Vec<_>, _>(|recall| recall.apply_at <= now);
+    pending.0 = waiting;
+
+    for recall in ready {
+        apply_snapshot(world, &recall.name);
+
+        let Some(target) = world.get::<VolumeNode>(recall.bus).map(|node| node.volume) else {
+            continue;
+        };
+        let end = if recall.end > now { recall.end } else { now };
+        fade_bus(world, recall.bus, target, now, end);
+    }
+}
+
+/// Provides methods on [`Commands`] for parameter snapshots.
+pub trait SnapshotCommands {
+    /// Snapshot `bus` and everything upstream of it as `name`.
+    ///
+    /// See [`ParamSnapshots`].
+    fn snapshot_params(&mut self, name: impl Into<String>, bus: impl Into<EdgeTarget>);
+
+    /// Immediately recall the snapshot `name`.
+    ///
+    /// For a faded switch, see [`RecallParams::with_fade`].
+    fn recall_params(&mut self, name: impl Into<String>);
+}
+
+impl SnapshotCommands for Commands<'_, '_> {
+    fn snapshot_params(&mut self, name: impl Into<String>, bus: impl Into<EdgeTarget>) {
+        self.queue(SnapshotParams::new(name, bus));
+    }
+
+    fn recall_params(&mut self, name: impl Into<String>) {
+        self.queue(RecallParams::new(name));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        prelude::*,
+        test::{prepare_app, run},
+    };
+
+    #[test]
+    fn test_snapshot_recall() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands
+                .spawn(FastLowpassNode::<2>::from_cutoff_hz(500.0))
+                .connect(MainBus);
+        });
+
+        run(&mut app, |mut commands: Commands| {
+            commands.snapshot_params("a", MainBus);
+        });
+
+        run(
+            &mut app,
+            |mut low_pass: Single<&mut FastLowpassNode<2>>, snapshots: Res<ParamSnapshots>| {
+                assert!(snapshots.get("a").is_some_and(|s| s.len() >= 2));
+                low_pass.cutoff_hz = 2000.0;
+            },
+        );
+
+        run(&mut app, |mut commands: Commands| {
+            commands.recall_params("a");
+        });
+
+        run(&mut app, |low_pass: Single<&FastLowpassNode<2>>| {
+            assert_eq!(low_pass.cutoff_hz, 500.0);
+        });
+    }
+}
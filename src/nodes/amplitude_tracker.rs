@@ -0,0 +1,255 @@
+//! Smoothed amplitude tracking for audio-reactive gameplay.
+
+use bevy_ecs::prelude::*;
+use bevy_seedling_dsp::envelope::{EnvelopeCoeffs, EnvelopeFollower};
+use core::sync::atomic::{AtomicU32, Ordering};
+use firewheel::{
+    channel_config::{ChannelConfig, NonZeroChannelCount},
+    collector::ArcGc,
+    diff::{Diff, Patch},
+    event::ProcEvents,
+    node::{
+        AudioNode, AudioNodeInfo, AudioNodeProcessor, ConstructProcessorContext, NodeError,
+        ProcBuffers, ProcExtra, ProcInfo, ProcStreamCtx, ProcessStatus,
+    },
+};
+use std::num::NonZeroU32;
+
+use crate::{
+    node::{
+        AudioState,
+        follower::{FollowerOf, Followers},
+    },
+    pool::sample_effects::EffectOf,
+};
+
+/// A pass-through effect that publishes the smoothed loudness
+/// of its input to the ECS.
+///
+/// When used as a sample effect, the loudness is written to the
+/// [`SamplePlayer`][crate::prelude::SamplePlayer] entity as a
+/// [`SampleAmplitude`] component, making it easy to drive mouth flaps,
+/// speaker cones, or other audio-reactive visuals.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn speak(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("dialogue.wav")),
+///         sample_effects![AmplitudeTrackerNode::default()],
+///     ));
+/// }
+///
+/// fn flap_mouth(mut speakers: Query<(&SampleAmplitude, &mut Transform)>) {
+///     for (amplitude, mut transform) in &mut speakers {
+///         transform.scale.y = 1.0 + amplitude.0 * 0.5;
+///     }
+/// }
+/// ```
+#[derive(Diff, Patch, Debug, Clone, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct AmplitudeTrackerNode {
+    /// How long it takes to react to increases in amplitude, in seconds.
+    ///
+    /// By default, this is 0.01s.
+    pub attack: f32,
+    /// How long it takes to react to decreases in amplitude, in seconds.
+    ///
+    /// By default, this is 0.1s.
+    pub release: f32,
+    /// The span of decibels below full scale mapped onto `0.0..=1.0`.
+    ///
+    /// With the default of 48 dB, -48 dBFS and below reads as `0.0`,
+    /// while 0 dBFS reads as `1.0`.
+    pub range_db: f32,
+}
+
+impl Default for AmplitudeTrackerNode {
+    fn default() -> Self {
+        Self {
+            attack: 0.01,
+            release: 0.1,
+            range_db: 48.0,
+        }
+    }
+}
+
+impl AmplitudeTrackerNode {
+    /// Map a linear amplitude onto `0.0..=1.0` according to
+    /// [`AmplitudeTrackerNode::range_db`].
+    pub fn normalize(&self, amplitude: f32) -> f32 {
+        if self.range_db <= 0.0 || !amplitude.is_finite() {
+            return 0.0;
+        }
+
+        let db = 20.0 * amplitude.max(f32::EPSILON).log10();
+        ((db + self.range_db) / self.range_db).clamp(0.0, 1.0)
+    }
+}
+
+/// Configuration for an [`AmplitudeTrackerNode`].
+#[derive(Debug, Clone, Component, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct AmplitudeTrackerConfig {
+    /// The number of input and output channels.
+    ///
+    /// The tracker follows the loudest channel.
+    /// By default, this is stereo.
+    pub channels: NonZeroChannelCount,
+}
+
+impl Default for AmplitudeTrackerConfig {
+    fn default() -> Self {
+        Self {
+            channels: NonZeroChannelCount::STEREO,
+        }
+    }
+}
+
+/// The shared atomic used by [`AmplitudeTrackerNode`] to communicate
+/// its current linear amplitude.
+#[derive(Debug, Clone)]
+pub struct AmplitudeTrackerState(ArcGc<AtomicU32>);
+
+impl AmplitudeTrackerState {
+    /// The current smoothed amplitude.
+    pub fn amplitude(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+}
+
+/// The smoothed loudness of a sample in the range `0.0..=1.0`.
+///
+/// This is inserted on [`SamplePlayer`][crate::prelude::SamplePlayer]
+/// entities with an [`AmplitudeTrackerNode`] effect. While the sample
+/// is waiting for a sampler, this reads `0.0`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct SampleAmplitude(pub f32);
+
+impl AudioNode for AmplitudeTrackerNode {
+    type Configuration = AmplitudeTrackerConfig;
+
+    fn info(&self, config: &Self::Configuration) -> Result<AudioNodeInfo, NodeError> {
+        Ok(AudioNodeInfo::new()
+            .debug_name("amplitude tracker")
+            .channel_config(ChannelConfig {
+                num_inputs: config.channels.get(),
+                num_outputs: config.channels.get(),
+            })
+            .custom_state(AmplitudeTrackerState(ArcGc::new(AtomicU32::new(
+                0f32.to_bits(),
+            )))))
+    }
+
+    fn construct_processor(
+        &self,
+        _: &Self::Configuration,
+        cx: ConstructProcessorContext,
+    ) -> Result<impl AudioNodeProcessor, NodeError> {
+        let sample_rate = cx.stream_info.sample_rate;
+
+        Ok(AmplitudeTrackerProcessor {
+            attack: self.attack,
+            release: self.release,
+            coeffs: EnvelopeCoeffs::new(self.attack, self.release, sample_rate),
+            follower: EnvelopeFollower::default(),
+            sample_rate,
+            state: cx.custom_state().cloned().unwrap(),
+        })
+    }
+}
+
+struct AmplitudeTrackerProcessor {
+    attack: f32,
+    release: f32,
+    coeffs: EnvelopeCoeffs,
+    follower: EnvelopeFollower,
+    sample_rate: NonZeroU32,
+    state: AmplitudeTrackerState,
+}
+
+impl AudioNodeProcessor for AmplitudeTrackerProcessor {
+    fn events(&mut self, _info: &ProcInfo, events: &mut ProcEvents, _extra: &mut ProcExtra) {
+        for patch in events.drain_patches::<AmplitudeTrackerNode>() {
+            match patch {
+                AmplitudeTrackerNodePatch::Attack(attack) => self.attack = attack,
+                AmplitudeTrackerNodePatch::Release(release) => self.release = release,
+                // The range is applied in the ECS.
+                AmplitudeTrackerNodePatch::RangeDb(_) => {}
+            }
+        }
+
+        self.coeffs = EnvelopeCoeffs::new(self.attack, self.release, self.sample_rate);
+    }
+
+    fn process(
+        &mut self,
+        proc_info: &ProcInfo,
+        buffers: ProcBuffers,
+        _: &mut ProcExtra,
+    ) -> ProcessStatus {
+        let silent = proc_info
+            .in_silence_mask
+            .all_channels_silent(buffers.inputs.len());
+
+        let envelope = if silent {
+            self.follower
+                .process_silence(proc_info.frames, &self.coeffs)
+        } else {
+            self.follower
+                .process_block(buffers.inputs, proc_info.frames, &self.coeffs)
+        };
+
+        self.state.0.store(envelope.to_bits(), Ordering::Relaxed);
+
+        ProcessStatus::Bypass
+    }
+
+    fn new_stream(&mut self, stream_info: &firewheel::StreamInfo, _: &mut ProcStreamCtx) {
+        self.sample_rate = stream_info.sample_rate;
+        self.coeffs = EnvelopeCoeffs::new(self.attack, self.release, self.sample_rate);
+    }
+}
+
+/// Write each tracker's amplitude to its sample player.
+pub(crate) fn publish_sample_amplitude(
+    trackers: Query<(&AmplitudeTrackerNode, &EffectOf, Option<&Followers>)>,
+    states: Query<&AudioState<AmplitudeTrackerState>, With<FollowerOf>>,
+    mut amplitudes: Query<&mut SampleAmplitude>,
+    mut commands: Commands,
+) {
+    for (tracker, effect_of, followers) in &trackers {
+        let amplitude = followers
+            .and_then(|f| states.iter_many(f.iter()).next())
+            .map(|state| tracker.normalize(state.0.amplitude()))
+            .unwrap_or_default();
+
+        match amplitudes.get_mut(effect_of.0) {
+            Ok(mut current) => {
+                current.set_if_neq(SampleAmplitude(amplitude));
+            }
+            Err(_) => {
+                if let Ok(mut player) = commands.get_entity(effect_of.0) {
+                    player.insert(SampleAmplitude(amplitude));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let tracker = AmplitudeTrackerNode::default();
+
+        assert_eq!(tracker.normalize(1.0), 1.0);
+        assert_eq!(tracker.normalize(0.0), 0.0);
+        assert!((tracker.normalize(10f32.powf(-24.0 / 20.0)) - 0.5).abs() < 1e-4);
+        assert_eq!(tracker.normalize(2.0), 1.0);
+    }
+}
@@ -0,0 +1,246 @@
+//! Bit depth and sample rate reduction.
+
+use bevy_ecs::component::Component;
+use firewheel::{
+    channel_config::{ChannelConfig, NonZeroChannelCount},
+    diff::{Diff, Patch},
+    event::ProcEvents,
+    node::{
+        AudioNode, AudioNodeInfo, AudioNodeProcessor, ConstructProcessorContext, NodeError,
+        ProcBuffers, ProcExtra, ProcInfo, ProcessStatus,
+    },
+};
+
+/// A bit-crusher and sample rate reducer for retro-style effects.
+///
+/// Each parameter can be freely automated, for example
+/// with [`ParamFade`][crate::prelude::ParamFade].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn crunchy(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("my_sample.wav")),
+///         sample_effects![BitCrusherNode {
+///             bit_depth: 6.0,
+///             downsample: 4.0,
+///             mix: 0.8,
+///         }],
+///     ));
+/// }
+/// ```
+#[derive(Diff, Patch, Debug, Clone, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct BitCrusherNode {
+    /// The bit depth of the output, clamped to `1.0..=24.0`.
+    ///
+    /// Fractional values are permitted, which
+    /// allows for smooth automation.
+    ///
+    /// By default, this is `8.0`.
+    pub bit_depth: f32,
+    /// The factor by which the sample rate is reduced.
+    ///
+    /// `1.0` leaves the sample rate untouched, while `4.0` holds
+    /// each sample for four frames. Values below `1.0` are clamped.
+    ///
+    /// By default, this is `1.0`.
+    pub downsample: f32,
+    /// The dry/wet mix, where `0.0` is fully dry and `1.0` is fully wet.
+    ///
+    /// By default, this is `1.0`.
+    pub mix: f32,
+}
+
+impl Default for BitCrusherNode {
+    fn default() -> Self {
+        Self {
+            bit_depth: 8.0,
+            downsample: 1.0,
+            mix: 1.0,
+        }
+    }
+}
+
+/// Configuration for a [`BitCrusherNode`].
+#[derive(Debug, Clone, Component, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct BitCrusherConfig {
+    /// The number of input and output channels.
+    ///
+    /// By default, this is stereo.
+    pub channels: NonZeroChannelCount,
+}
+
+impl Default for BitCrusherConfig {
+    fn default() -> Self {
+        Self {
+            channels: NonZeroChannelCount::STEREO,
+        }
+    }
+}
+
+impl AudioNode for BitCrusherNode {
+    type Configuration = BitCrusherConfig;
+
+    fn info(&self, config: &Self::Configuration) -> Result<AudioNodeInfo, NodeError> {
+        Ok(AudioNodeInfo::new()
+            .debug_name("bit crusher")
+            .channel_config(ChannelConfig {
+                num_inputs: config.channels.get(),
+                num_outputs: config.channels.get(),
+            }))
+    }
+
+    fn construct_processor(
+        &self,
+        config: &Self::Configuration,
+        _: ConstructProcessorContext,
+    ) -> Result<impl AudioNodeProcessor, NodeError> {
+        Ok(BitCrusherProcessor {
+            params: self.clone(),
+            crusher: BitCrusher::new(config.channels.get().get() as usize),
+        })
+    }
+}
+
+/// Quantize `sample` to the given bit depth.
+fn quantize(sample: f32, bit_depth: f32) -> f32 {
+    let steps = 2f32.powf(bit_depth.clamp(1.0, 24.0) - 1.0);
+    (sample * steps).round() / steps
+}
+
+/// The sample-and-hold and quantization DSP, separated from the processor for testing.
+struct BitCrusher {
+    /// The currently held sample for each channel.
+    held: Box<[f32]>,
+    /// The hold phase, where a new sample is taken at `1.0`.
+    phase: f32,
+}
+
+impl BitCrusher {
+    fn new(channels: usize) -> Self {
+        Self {
+            held: vec![0.0; channels].into(),
+            phase: 1.0,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.held.fill(0.0);
+        self.phase = 1.0;
+    }
+
+    fn process(
+        &mut self,
+        params: &BitCrusherNode,
+        inputs: &[&[f32]],
+        outputs: &mut [&mut [f32]],
+        frames: usize,
+    ) {
+        let step = 1.0 / params.downsample.max(1.0);
+        let bit_depth = params.bit_depth;
+        let wet = params.mix.clamp(0.0, 1.0);
+        let dry = 1.0 - wet;
+
+        for frame in 0..frames {
+            let sample_and_hold = self.phase >= 1.0;
+            if sample_and_hold {
+                self.phase -= 1.0;
+            }
+            self.phase += step;
+
+            for ((input, output), held) in inputs
+                .iter()
+                .zip(outputs.iter_mut())
+                .zip(self.held.iter_mut())
+            {
+                if sample_and_hold {
+                    *held = quantize(input[frame], bit_depth);
+                }
+
+                output[frame] = input[frame] * dry + *held * wet;
+            }
+        }
+    }
+}
+
+struct BitCrusherProcessor {
+    params: BitCrusherNode,
+    crusher: BitCrusher,
+}
+
+impl AudioNodeProcessor for BitCrusherProcessor {
+    fn events(&mut self, _info: &ProcInfo, events: &mut ProcEvents, _extra: &mut ProcExtra) {
+        for patch in events.drain_patches::<BitCrusherNode>() {
+            self.params.apply(patch);
+        }
+    }
+
+    fn process(
+        &mut self,
+        proc_info: &ProcInfo,
+        ProcBuffers { inputs, outputs }: ProcBuffers,
+        _: &mut ProcExtra,
+    ) -> ProcessStatus {
+        if proc_info.in_silence_mask.all_channels_silent(inputs.len()) {
+            self.crusher.reset();
+            return ProcessStatus::ClearAllOutputs;
+        }
+
+        self.crusher
+            .process(&self.params, inputs, outputs, proc_info.frames);
+
+        ProcessStatus::OutputsModified
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn crush(params: &BitCrusherNode, input: &[f32]) -> Vec<f32> {
+        let mut crusher = BitCrusher::new(1);
+        let mut output = vec![0.0; input.len()];
+        crusher.process(params, &[input], &mut [output.as_mut_slice()], input.len());
+        output
+    }
+
+    #[test]
+    fn test_quantize() {
+        // One bit leaves only the extremes and zero.
+        assert_eq!(quantize(0.3, 1.0), 0.0);
+        assert_eq!(quantize(0.7, 1.0), 1.0);
+        assert_eq!(quantize(-0.7, 1.0), -1.0);
+
+        // Three bits give steps of a quarter.
+        assert_eq!(quantize(0.3, 3.0), 0.25);
+    }
+
+    #[test]
+    fn test_sample_and_hold() {
+        let params = BitCrusherNode {
+            bit_depth: 24.0,
+            downsample: 2.0,
+            mix: 1.0,
+        };
+
+        let output = crush(&params, &[0.1, 0.2, 0.3, 0.4]);
+        for (output, expected) in output.iter().zip([0.1, 0.1, 0.3, 0.3]) {
+            assert!((output - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_dry_mix() {
+        let params = BitCrusherNode {
+            bit_depth: 1.0,
+            downsample: 4.0,
+            mix: 0.0,
+        };
+
+        let input = [0.1, -0.2, 0.3, -0.4];
+        assert_eq!(crush(&params, &input), input);
+    }
+}
@@ -0,0 +1,461 @@
+//! Rendering a bus's output into a sample.
+//!
+//! On weak hardware, a dense ambient bed can cost far more than
+//! it's worth. [`BounceBus`] records a bus's output into an
+//! [`AudioSample`], optionally replacing the bus and everything
+//! feeding it with a single looping sampler, much like freezing
+//! a track in a DAW.
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use bevy_seedling::prelude::*;
+//! #[derive(NodeLabel, PartialEq, Eq, Debug, Hash, Clone)]
+//! struct AmbienceBus;
+//!
+//! fn freeze_ambience(mut commands: Commands) {
+//!     commands.queue(BounceBus::new(AmbienceBus, DurationSeconds(8.0)).replace());
+//! }
+//!
+//! fn on_bounce(bounce: On<BounceComplete>) {
+//!     info!("bounced {:?} into {:?}", bounce.entity, bounce.sample);
+//! }
+//! ```
+//!
+//! Since each node's processor lives on the audio thread, the
+//! subgraph can't be rendered ahead of time. Instead, the bus's
+//! output is recorded as it plays, so a bounce completes after
+//! its duration has elapsed.
+
+use crate::{
+    context::SampleRate,
+    edge::{BusQuery, Connect, EdgeTarget},
+    node::{AudioState, FirewheelNode, FirewheelNodeInfo, events::AudioEvents, snapshot},
+    sample::AudioSample,
+    time::Audio,
+};
+use bevy_asset::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_log::prelude::*;
+use bevy_time::Time;
+use core::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicU32, AtomicUsize, Ordering},
+};
+use firewheel::{
+    channel_config::{ChannelConfig, ChannelCount, NonZeroChannelCount},
+    clock::DurationSeconds,
+    collector::ArcGc,
+    event::ProcEvents,
+    node::{
+        AudioNode, AudioNodeInfo, AudioNodeProcessor, ConstructProcessorContext, NodeError,
+        ProcBuffers, ProcExtra, ProcInfo, ProcessStatus,
+    },
+    nodes::sampler::{RepeatMode, SamplerConfig, SamplerNode},
+};
+
+/// A node that records a fixed number of frames from its input.
+///
+/// This is the capture node used by [`BounceBus`]. Once
+/// [`BounceConfig::frames`] have been recorded, further
+/// input is ignored.
+#[derive(Debug, Default, Clone, Component)]
+pub struct BounceNode;
+
+/// Configuration for [`BounceNode`].
+#[derive(Debug, Clone, Component, PartialEq)]
+pub struct BounceConfig {
+    /// The number of input channels.
+    ///
+    /// By default, this is stereo.
+    pub channels: NonZeroChannelCount,
+    /// The number of frames to record.
+    ///
+    /// The buffer is allocated up front, so this
+    /// should be kept to a reasonable length.
+    pub frames: usize,
+}
+
+impl Default for BounceConfig {
+    fn default() -> Self {
+        Self {
+            channels: NonZeroChannelCount::STEREO,
+            frames: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BounceBuffer {
+    /// Interleaved samples, stored as bits.
+    samples: Box<[AtomicU32]>,
+    channels: usize,
+    /// The number of frames written so far.
+    written: AtomicUsize,
+}
+
+/// The shared buffer [`BounceNode`] records into.
+#[derive(Debug, Clone)]
+pub struct BounceState(ArcGc<BounceBuffer>);
+
+impl BounceState {
+    /// The number of frames recorded so far.
+    pub fn frames_recorded(&self) -> usize {
+        self.0.written.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` once the buffer has been filled.
+    pub fn is_complete(&self) -> bool {
+        self.frames_recorded() * self.0.channels >= self.0.samples.len()
+    }
+
+    /// Copy out the recorded frames as interleaved samples.
+    pub fn to_interleaved(&self) -> Vec<f32> {
+        let len = self.frames_recorded() * self.0.channels;
+
+        self.0.samples[..len]
+            .iter()
+            .map(|s| f32::from_bits(s.load(Ordering::Relaxed)))
+            .collect()
+    }
+}
+
+impl AudioNode for BounceNode {
+    type Configuration = BounceConfig;
+
+    fn info(&self, config: &Self::Configuration) -> Result<AudioNodeInfo, NodeError> {
+        let channels = config.channels.get().get() as usize;
+        let samples = (0..config.frames * channels)
+            .map(|_| AtomicU32::new(0))
+            .collect();
+
+        Ok(AudioNodeInfo::new()
+            .debug_name("bounce")
+            .channel_config(ChannelConfig {
+                num_inputs: config.channels.get(),
+                num_outputs: ChannelCount::ZERO,
+            })
+            .custom_state(BounceState(ArcGc::new(BounceBuffer {
+                samples,
+                channels,
+                written: AtomicUsize::new(0),
+            }))))
+    }
+
+    fn construct_processor(
+        &self,
+        _: &Self::Configuration,
+        cx: ConstructProcessorContext,
+    ) -> Result<impl AudioNodeProcessor, NodeError> {
+        Ok(BounceProcessor {
+            state: cx.custom_state().cloned().unwrap(),
+        })
+    }
+}
+
+struct BounceProcessor {
+    state: BounceState,
+}
+
+impl AudioNodeProcessor for BounceProcessor {
+    fn events(&mut self, _info: &ProcInfo, _events: &mut ProcEvents, _extra: &mut ProcExtra) {}
+
+    fn process(
+        &mut self,
+        proc_info: &ProcInfo,
+        buffers: ProcBuffers,
+        _: &mut ProcExtra,
+    ) -> ProcessStatus {
+        let buffer = &self.state.0;
+        let channels = buffer.channels;
+        let written = buffer.written.load(Ordering::Relaxed);
+        let remaining = buffer.samples.len() / channels - written;
+        let frames = proc_info.frames.min(remaining);
+
+        if frames == 0 {
+            return ProcessStatus::Bypass;
+        }
+
+        for (channel, input) in buffers.inputs.iter().enumerate().take(channels) {
+            for (frame, sample) in input[..frames].iter().enumerate() {
+                buffer.samples[(written + frame) * channels + channel]
+                    .store(sample.to_bits(), Ordering::Relaxed);
+            }
+        }
+
+        buffer.written.store(written + frames, Ordering::Release);
+
+        ProcessStatus::Bypass
+    }
+}
+
+/// Record a bus's output into an [`AudioSample`].
+///
+/// A [`BounceNode`] is connected to the bus's output, and once
+/// `duration` has been recorded, [`BounceComplete`] is triggered
+/// on the bus with the new sample.
+///
+/// With [`BounceBus::replace`], the bus and every node feeding
+/// into it are then despawned, and a looping [`SamplerNode`]
+/// playing the bounce takes the bus's place in the graph.
+/// Components on the bus, like its [`NodeLabel`], aren't carried
+/// over, so replacing is best suited to leaf submixes.
+///
+/// See the [module docs][self] for an example.
+///
+/// [`NodeLabel`]: crate::prelude::NodeLabel
+#[derive(Debug)]
+pub struct BounceBus {
+    bus: EdgeTarget,
+    duration: DurationSeconds,
+    replace: bool,
+}
+
+impl BounceBus {
+    /// Record `duration` of `bus`'s output.
+    pub fn new(bus: impl Into<EdgeTarget>, duration: DurationSeconds) -> Self {
+        Self {
+            bus: bus.into(),
+            duration,
+            replace: false,
+        }
+    }
+
+    /// Replace the bus and its inputs with a sampler
+    /// playing the bounce once it completes.
+    pub fn replace(self) -> Self {
+        Self {
+            replace: true,
+            ..self
+        }
+    }
+}
+
+/// Links a [`BounceNode`] to the bus it's recording.
+#[derive(Debug, Component)]
+pub(crate) struct PendingBounce {
+    bus: Entity,
+    replace: bool,
+}
+
+impl Command for BounceBus {
+    fn apply(self, world: &mut World) {
+        let Some(bus) = snapshot::resolve_bus(world, &self.bus) else {
+            warn!("Failed to resolve bus for bounce.");
+            return;
+        };
+
+        let Some(info) = world.get::<FirewheelNodeInfo>(bus).copied() else {
+            warn!("Bus {bus:?} must be in the audio graph before it can be bounced.");
+            return;
+        };
+
+        let Some(channels) = NonZeroChannelCount::new(info.channel_config.num_outputs.get()) else {
+            warn!("Bus {bus:?} has no outputs to bounce.");
+            return;
+        };
+
+        let Some(sample_rate) = world.get_resource::<SampleRate>().map(SampleRate::get) else {
+            warn!("Bus {bus:?} can't be bounced while no audio stream is running.");
+            return;
+        };
+
+        let frames = (self.duration.0 * sample_rate.get() as f64).round() as usize;
+        let ports: Vec<_> = (0..channels.get().get()).map(|i| (i, i)).collect();
+
+        let capture = world
+            .spawn((
+                BounceNode,
+                BounceConfig { channels, frames },
+                PendingBounce {
+                    bus,
+                    replace: self.replace,
+                },
+            ))
+            .id();
+
+        world.commands().entity(bus).connect_with(capture, &ports);
+        world.flush();
+    }
+}
+
+/// Triggered on a bus when a [`BounceBus`] recording completes.
+#[derive(Debug, EntityEvent)]
+pub struct BounceComplete {
+    /// The bounced bus.
+    ///
+    /// If the bus was replaced, this entity has been despawned.
+    pub entity: Entity,
+    /// The recorded sample.
+    pub sample: Handle<AudioSample>,
+    /// The sampler that replaced the bus, if any.
+    pub replacement: Option<Entity>,
+}
+
+/// Collect completed bounces, replacing their buses if requested.
+pub(crate) fn finish_bounces(
+    captures: Query<(
+        Entity,
+        &PendingBounce,
+        &BounceConfig,
+        &AudioState<BounceState>,
+        &FirewheelNode,
+    )>,
+    buses: BusQuery,
+    mut assets: ResMut<Assets<AudioSample>>,
+    sample_rate: Res<SampleRate>,
+    time: Res<Time<Audio>>,
+    mut commands: Commands,
+) {
+    for (capture, pending, config, state, capture_node) in &captures {
+        if !state.0.is_complete() {
+            continue;
+        }
+
+        let channels = NonZeroUsize::new(config.channels.get().get() as usize).unwrap();
+        let sample = AudioSample::from_pcm(state.0.to_interleaved(), channels, sample_rate.get());
+        let resource = sample.get();
+        let sample = assets.add(sample);
+
+        commands.entity(capture).despawn();
+
+        let replacement = pending.replace.then(|| {
+            let mut node = SamplerNode::default();
+            node.repeat_mode = RepeatMode::RepeatEndlessly;
+            *node.play = true;
+
+            let mut events = AudioEvents::new(&time);
+            events.push_event(SamplerNode::set_dyn_sample_event(resource));
+
+            let replacement = commands
+                .spawn((
+                    node,
+                    SamplerConfig {
+                        channels: config.channels,
+                        ..Default::default()
+                    },
+                    events,
+                ))
+                .id();
+
+            for (target, ports) in buses.outputs_of(pending.bus) {
+                if target != capture_node.0 {
+                    commands
+                        .entity(replacement)
+                        .connect_with(EdgeTarget::Node(target), &ports);
+                }
+            }
+
+            replacement
+        });
+
+        commands.trigger(BounceComplete {
+            entity: pending.bus,
+            sample,
+            replacement,
+        });
+
+        if replacement.is_some() {
+            for node in buses.upstream_of(pending.bus) {
+                commands.entity(node).try_despawn();
+            }
+            commands.entity(pending.bus).try_despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        prelude::*,
+        test::{prepare_app, run},
+    };
+    use firewheel::sample_resource::SampleResourceInfo;
+    use std::time::Instant;
+
+    #[derive(Component)]
+    struct Source;
+
+    #[derive(Resource, Default)]
+    struct Bounced(Option<BounceComplete>);
+
+    fn bounce(replace: bool) -> (App, BounceComplete) {
+        let mut app = prepare_app(|mut commands: Commands| {
+            let bus = commands
+                .spawn((VolumeNode::default(), TestBus))
+                .connect(MainBus)
+                .id();
+            commands.spawn((VolumeNode::default(), Source)).connect(bus);
+        });
+
+        app.init_resource::<Bounced>().add_observer(
+            |bounce: On<BounceComplete>, mut bounced: ResMut<Bounced>| {
+                bounced.0 = Some(BounceComplete {
+                    entity: bounce.entity,
+                    sample: bounce.sample.clone(),
+                    replacement: bounce.replacement,
+                });
+            },
+        );
+
+        run(&mut app, move |mut commands: Commands| {
+            let bounce = BounceBus::new(TestBus, DurationSeconds(0.01));
+            commands.queue(if replace { bounce.replace() } else { bounce });
+        });
+
+        let start = Instant::now();
+        loop {
+            if let Some(bounced) = app.world_mut().resource_mut::<Bounced>().0.take() {
+                return (app, bounced);
+            }
+
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+    }
+
+    #[derive(NodeLabel, PartialEq, Eq, Debug, Hash, Clone)]
+    struct TestBus;
+
+    #[test]
+    fn test_bounce() {
+        let (mut app, bounced) = bounce(false);
+
+        assert!(bounced.replacement.is_none());
+        run(
+            &mut app,
+            move |assets: Res<Assets<AudioSample>>,
+                  captures: Query<(), With<BounceNode>>,
+                  sources: Query<(), With<Source>>| {
+                let sample = assets.get(&bounced.sample).unwrap();
+
+                assert_eq!(sample.get().num_channels().get(), 2);
+                assert_eq!(sample.get().len_frames(), 480);
+                assert!(captures.is_empty());
+                assert_eq!(sources.iter().len(), 1);
+            },
+        );
+    }
+
+    #[test]
+    fn test_bounce_replace() {
+        let (mut app, bounced) = bounce(true);
+        let replacement = bounced.replacement.unwrap();
+
+        app.update();
+
+        run(
+            &mut app,
+            move |sources: Query<(), With<Source>>,
+                  entities: Query<Entity>,
+                  samplers: Query<(), (With<SamplerNode>, With<FirewheelNode>)>| {
+                assert!(sources.is_empty());
+                assert!(!entities.contains(bounced.entity));
+                assert!(samplers.contains(replacement));
+            },
+        );
+    }
+}
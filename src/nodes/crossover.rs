@@ -0,0 +1,317 @@
+//! Three-band Linkwitz-Riley crossover.
+
+use crate::edge::OutputPort;
+use bevy_ecs::component::Component;
+use bevy_seedling_dsp::{Biquad, BiquadCoeffs, LinkwitzRiley};
+use firewheel::{
+    channel_config::{ChannelConfig, NonZeroChannelCount},
+    diff::{Diff, Patch},
+    event::ProcEvents,
+    node::{
+        AudioNode, AudioNodeInfo, AudioNodeProcessor, ConstructProcessorContext, NodeError,
+        ProcBuffers, ProcExtra, ProcInfo, ProcStreamCtx, ProcessStatus,
+    },
+};
+use std::num::NonZeroU32;
+
+/// One of the three bands produced by a [`CrossoverNode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum CrossoverBand {
+    /// Frequencies below [`CrossoverNode::low_hz`].
+    Low,
+    /// Frequencies between [`CrossoverNode::low_hz`] and [`CrossoverNode::high_hz`].
+    Mid,
+    /// Frequencies above [`CrossoverNode::high_hz`].
+    High,
+}
+
+/// A three-band crossover.
+///
+/// The input is split into low, mid, and high bands using
+/// fourth-order Linkwitz-Riley filters, so the bands sum
+/// back together with a flat frequency response. This makes it
+/// possible to build multiband chains, processing each band with
+/// its own effects before mixing them back together.
+///
+/// ## Output ports
+///
+/// Each band is an [`OutputPort`] with as many channels as the input.
+/// The bands are laid out in order: low, mid, then high. For a
+/// stereo crossover, the outputs are:
+///
+/// | Outputs | Band |
+/// |---------|------|
+/// | 0, 1    | Low  |
+/// | 2, 3    | Mid  |
+/// | 4, 5    | High |
+///
+/// [`CrossoverConfig::port`] provides the port for a band, which
+/// can be passed to [`Connect::connect_port`][crate::prelude::Connect::connect_port].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn multiband(mut commands: Commands) {
+///     let config = CrossoverConfig::default();
+///
+///     // Each band gets its own processing before being summed at the bus.
+///     let low = commands.spawn(LimiterNode::default()).id();
+///     let mid = commands.spawn(VolumeNode::default()).id();
+///     let high = commands.spawn(BitCrusherNode::default()).id();
+///
+///     commands
+///         .spawn((CrossoverNode::default(), config.clone()))
+///         .connect_port(low, config.port(CrossoverBand::Low))
+///         .connect_port(mid, config.port(CrossoverBand::Mid))
+///         .connect_port(high, config.port(CrossoverBand::High));
+/// }
+/// ```
+#[derive(Diff, Patch, Debug, Clone, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct CrossoverNode {
+    /// The crossover frequency between the low and mid bands, in hertz.
+    ///
+    /// By default, this is 200 Hz.
+    pub low_hz: f32,
+    /// The crossover frequency between the mid and high bands, in hertz.
+    ///
+    /// By default, this is 2000 Hz.
+    pub high_hz: f32,
+}
+
+impl CrossoverNode {
+    /// Create a new [`CrossoverNode`].
+    pub fn new(low_hz: f32, high_hz: f32) -> Self {
+        Self { low_hz, high_hz }
+    }
+}
+
+impl Default for CrossoverNode {
+    fn default() -> Self {
+        Self::new(200.0, 2000.0)
+    }
+}
+
+/// Configuration for a [`CrossoverNode`].
+#[derive(Debug, Clone, Component, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct CrossoverConfig {
+    /// The number of input channels, and the number
+    /// of output channels per band.
+    ///
+    /// By default, this is stereo.
+    pub channels: NonZeroChannelCount,
+}
+
+impl CrossoverConfig {
+    /// Get the [`OutputPort`] for `band`.
+    pub fn port(&self, band: CrossoverBand) -> OutputPort {
+        let channels = self.channels.get().get();
+        let index = match band {
+            CrossoverBand::Low => 0,
+            CrossoverBand::Mid => 1,
+            CrossoverBand::High => 2,
+        };
+
+        OutputPort::new(index * channels, channels)
+    }
+}
+
+impl Default for CrossoverConfig {
+    fn default() -> Self {
+        Self {
+            channels: NonZeroChannelCount::STEREO,
+        }
+    }
+}
+
+impl AudioNode for CrossoverNode {
+    type Configuration = CrossoverConfig;
+
+    fn info(&self, config: &Self::Configuration) -> Result<AudioNodeInfo, NodeError> {
+        let channels = config.channels.get().get();
+
+        Ok(AudioNodeInfo::new()
+            .debug_name("crossover")
+            .channel_config(ChannelConfig::new(channels, channels * 3)))
+    }
+
+    fn construct_processor(
+        &self,
+        config: &Self::Configuration,
+        cx: ConstructProcessorContext,
+    ) -> Result<impl AudioNodeProcessor, NodeError> {
+        let mut processor = CrossoverProcessor {
+            params: self.clone(),
+            channels: (0..config.channels.get().get())
+                .map(|_| ChannelFilters::default())
+                .collect(),
+            coeffs: Default::default(),
+            sample_rate: cx.stream_info.sample_rate,
+        };
+        processor.update_coefficients();
+
+        Ok(processor)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct CrossoverCoeffs {
+    low_lowpass: BiquadCoeffs,
+    low_highpass: BiquadCoeffs,
+    high_lowpass: BiquadCoeffs,
+    high_highpass: BiquadCoeffs,
+    high_allpass: BiquadCoeffs,
+}
+
+#[derive(Debug, Default, Clone)]
+struct ChannelFilters {
+    low_lowpass: LinkwitzRiley,
+    low_highpass: LinkwitzRiley,
+    high_lowpass: LinkwitzRiley,
+    high_highpass: LinkwitzRiley,
+    /// Compensates the low band for the phase shift of the upper split.
+    high_allpass: Biquad,
+}
+
+impl ChannelFilters {
+    #[inline]
+    fn process(&mut self, input: f32, c: &CrossoverCoeffs) -> [f32; 3] {
+        let low = self.low_lowpass.process(input, &c.low_lowpass);
+        let low = self.high_allpass.process(low, &c.high_allpass);
+
+        let upper = self.low_highpass.process(input, &c.low_highpass);
+        let mid = self.high_lowpass.process(upper, &c.high_lowpass);
+        let high = self.high_highpass.process(upper, &c.high_highpass);
+
+        [low, mid, high]
+    }
+}
+
+struct CrossoverProcessor {
+    params: CrossoverNode,
+    channels: Box<[ChannelFilters]>,
+    coeffs: CrossoverCoeffs,
+    sample_rate: NonZeroU32,
+}
+
+impl CrossoverProcessor {
+    fn update_coefficients(&mut self) {
+        let sample_rate = self.sample_rate;
+        let low = self.params.low_hz;
+        let high = self.params.high_hz.max(low);
+
+        self.coeffs = CrossoverCoeffs {
+            low_lowpass: BiquadCoeffs::lowpass(low, sample_rate),
+            low_highpass: BiquadCoeffs::highpass(low, sample_rate),
+            high_lowpass: BiquadCoeffs::lowpass(high, sample_rate),
+            high_highpass: BiquadCoeffs::highpass(high, sample_rate),
+            high_allpass: BiquadCoeffs::allpass(high, sample_rate),
+        };
+    }
+}
+
+impl AudioNodeProcessor for CrossoverProcessor {
+    fn events(&mut self, _info: &ProcInfo, events: &mut ProcEvents, _extra: &mut ProcExtra) {
+        let mut changed = false;
+        for patch in events.drain_patches::<CrossoverNode>() {
+            self.params.apply(patch);
+            changed = true;
+        }
+
+        if changed {
+            self.update_coefficients();
+        }
+    }
+
+    fn process(
+        &mut self,
+        proc_info: &ProcInfo,
+        ProcBuffers { inputs, outputs }: ProcBuffers,
+        _: &mut ProcExtra,
+    ) -> ProcessStatus {
+        if proc_info.in_silence_mask.all_channels_silent(inputs.len()) {
+            self.channels.fill(ChannelFilters::default());
+            return ProcessStatus::ClearAllOutputs;
+        }
+
+        let channels = inputs.len();
+        for (channel, (input, filters)) in inputs.iter().zip(self.channels.iter_mut()).enumerate() {
+            for frame in 0..proc_info.frames {
+                let bands = filters.process(input[frame], &self.coeffs);
+
+                for (band, value) in bands.into_iter().enumerate() {
+                    outputs[band * channels + channel][frame] = value;
+                }
+            }
+        }
+
+        ProcessStatus::OutputsModified
+    }
+
+    fn new_stream(&mut self, stream_info: &firewheel::StreamInfo, _: &mut ProcStreamCtx) {
+        self.sample_rate = stream_info.sample_rate;
+        self.channels.fill(ChannelFilters::default());
+        self.update_coefficients();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_RATE: NonZeroU32 = NonZeroU32::new(48000).unwrap();
+
+    fn coeffs() -> CrossoverCoeffs {
+        let mut processor = CrossoverProcessor {
+            params: CrossoverNode::default(),
+            channels: Box::new([]),
+            coeffs: Default::default(),
+            sample_rate: SAMPLE_RATE,
+        };
+        processor.update_coefficients();
+        processor.coeffs
+    }
+
+    #[test]
+    fn test_ports() {
+        let config = CrossoverConfig::default();
+
+        assert_eq!(config.port(CrossoverBand::Low), OutputPort::new(0, 2));
+        assert_eq!(config.port(CrossoverBand::Mid), OutputPort::new(2, 2));
+        assert_eq!(config.port(CrossoverBand::High), OutputPort::new(4, 2));
+    }
+
+    #[test]
+    fn test_dc_in_low_band() {
+        let coeffs = coeffs();
+        let mut filters = ChannelFilters::default();
+
+        let mut bands = [0.0; 3];
+        for _ in 0..48000 {
+            bands = filters.process(1.0, &coeffs);
+        }
+
+        assert!((bands[0] - 1.0).abs() < 1e-3);
+        assert!(bands[1].abs() < 1e-3);
+        assert!(bands[2].abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_flat_sum() {
+        let coeffs = coeffs();
+        let mut filters = ChannelFilters::default();
+
+        // The bands of an impulse should sum to an allpass response,
+        // which preserves the impulse's energy.
+        let mut energy = 0.0;
+        for i in 0..48000 {
+            let input = if i == 0 { 1.0 } else { 0.0 };
+            let sum: f32 = filters.process(input, &coeffs).iter().sum();
+            energy += sum * sum;
+        }
+
+        assert!((energy - 1.0).abs() < 1e-2);
+    }
+}
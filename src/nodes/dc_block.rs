@@ -0,0 +1,160 @@
+//! A DC-blocking high-pass filter.
+
+use bevy_ecs::component::Component;
+use bevy_seedling_dsp::{DcBlocker, one_pole::pole};
+use firewheel::{
+    channel_config::{ChannelConfig, NonZeroChannelCount},
+    diff::{Diff, Patch},
+    event::ProcEvents,
+    node::{
+        AudioNode, AudioNodeInfo, AudioNodeProcessor, ConstructProcessorContext, NodeError,
+        ProcBuffers, ProcExtra, ProcInfo, ProcStreamCtx, ProcessStatus,
+    },
+};
+use std::num::NonZeroU32;
+
+/// A one-pole high-pass filter that removes DC offsets.
+///
+/// Procedural or custom nodes can accidentally introduce a constant
+/// offset into their output, which wastes headroom and can cause clicks
+/// when playback starts or stops. This node is cheap insurance against that.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn protect_synth(mut commands: Commands) {
+///     commands
+///         .spawn(DcBlockNode::default())
+///         .connect(MainBus);
+/// }
+/// ```
+///
+/// To insert one after the [`MainBus`][crate::prelude::MainBus]
+/// automatically, see [`MainBusDcBlock`][crate::prelude::MainBusDcBlock].
+#[derive(Diff, Patch, Debug, Clone, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct DcBlockNode {
+    /// The filter's cutoff frequency, in hertz.
+    ///
+    /// By default, this is 20 Hz.
+    pub cutoff_hz: f32,
+}
+
+impl Default for DcBlockNode {
+    fn default() -> Self {
+        Self { cutoff_hz: 20.0 }
+    }
+}
+
+/// Configuration for a [`DcBlockNode`].
+#[derive(Debug, Clone, Component, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct DcBlockConfig {
+    /// The number of input and output channels.
+    ///
+    /// By default, this is stereo.
+    pub channels: NonZeroChannelCount,
+}
+
+impl Default for DcBlockConfig {
+    fn default() -> Self {
+        Self {
+            channels: NonZeroChannelCount::STEREO,
+        }
+    }
+}
+
+impl AudioNode for DcBlockNode {
+    type Configuration = DcBlockConfig;
+
+    fn info(&self, config: &Self::Configuration) -> Result<AudioNodeInfo, NodeError> {
+        Ok(AudioNodeInfo::new()
+            .debug_name("dc block")
+            .channel_config(ChannelConfig {
+                num_inputs: config.channels.get(),
+                num_outputs: config.channels.get(),
+            }))
+    }
+
+    fn construct_processor(
+        &self,
+        config: &Self::Configuration,
+        cx: ConstructProcessorContext,
+    ) -> Result<impl AudioNodeProcessor, NodeError> {
+        let channels = config.channels.get().get() as usize;
+
+        Ok(DcBlockProcessor {
+            cutoff_hz: self.cutoff_hz,
+            pole: pole(self.cutoff_hz, cx.stream_info.sample_rate),
+            states: vec![DcBlocker::default(); channels].into(),
+            sample_rate: cx.stream_info.sample_rate,
+        })
+    }
+}
+
+struct DcBlockProcessor {
+    cutoff_hz: f32,
+    pole: f32,
+    states: Box<[DcBlocker]>,
+    sample_rate: NonZeroU32,
+}
+
+impl AudioNodeProcessor for DcBlockProcessor {
+    fn events(&mut self, _info: &ProcInfo, events: &mut ProcEvents, _extra: &mut ProcExtra) {
+        for patch in events.drain_patches::<DcBlockNode>() {
+            let DcBlockNodePatch::CutoffHz(cutoff_hz) = patch;
+            self.cutoff_hz = cutoff_hz;
+            self.pole = pole(cutoff_hz, self.sample_rate);
+        }
+    }
+
+    fn process(
+        &mut self,
+        proc_info: &ProcInfo,
+        ProcBuffers { inputs, outputs }: ProcBuffers,
+        _: &mut ProcExtra,
+    ) -> ProcessStatus {
+        if proc_info.in_silence_mask.all_channels_silent(inputs.len())
+            && self.states.iter().all(DcBlocker::is_settled)
+        {
+            self.states.fill(DcBlocker::default());
+            return ProcessStatus::ClearAllOutputs;
+        }
+
+        for ((input, output), state) in inputs
+            .iter()
+            .zip(outputs.iter_mut())
+            .zip(self.states.iter_mut())
+        {
+            for frame in 0..proc_info.frames {
+                output[frame] = state.process(input[frame], self.pole);
+            }
+        }
+
+        ProcessStatus::OutputsModified
+    }
+
+    fn new_stream(&mut self, stream_info: &firewheel::StreamInfo, _: &mut ProcStreamCtx) {
+        self.sample_rate = stream_info.sample_rate;
+        self.pole = pole(self.cutoff_hz, stream_info.sample_rate);
+        self.states.fill(DcBlocker::default());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_removes_offset() {
+        let pole = pole(20.0, NonZeroU32::new(48000).unwrap());
+        let mut blocker = DcBlocker::default();
+
+        let mut output = 0.0;
+        for _ in 0..48000 {
+            output = blocker.process(0.5, pole);
+        }
+
+        assert!(output.abs() < 1e-3);
+    }
+}
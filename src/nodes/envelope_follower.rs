@@ -0,0 +1,288 @@
+//! Amplitude envelope follower for sidechain-style modulation.
+
+use bevy_app::prelude::*;
+use bevy_ecs::{component::Mutable, prelude::*};
+use bevy_seedling_dsp::envelope::{EnvelopeCoeffs, EnvelopeFollower};
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicU32, Ordering},
+};
+use firewheel::{
+    channel_config::{ChannelConfig, ChannelCount, NonZeroChannelCount},
+    collector::ArcGc,
+    diff::{Diff, Patch},
+    event::ProcEvents,
+    node::{
+        AudioNode, AudioNodeInfo, AudioNodeProcessor, ConstructProcessorContext, NodeError,
+        ProcBuffers, ProcExtra, ProcInfo, ProcStreamCtx, ProcessStatus,
+    },
+};
+use std::{num::NonZeroU32, sync::Arc};
+
+use crate::{SeedlingSystems, node::AudioState};
+
+/// A node that measures the amplitude envelope of its input.
+///
+/// The envelope is published through [`EnvelopeFollowerState`],
+/// which is available on the node entity as an
+/// [`AudioState<EnvelopeFollowerState>`][AudioState].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn spawn_follower(mut commands: Commands) {
+///     let follower = commands.spawn(EnvelopeFollowerNode::default()).id();
+///
+///     // Route a bus into the follower in addition to its usual output.
+///     commands
+///         .spawn(VolumeNode::default())
+///         .connect(follower)
+///         .connect(MainBus);
+/// }
+///
+/// fn read_envelope(follower: Single<&AudioState<EnvelopeFollowerState>>) {
+///     info!("envelope: {}", follower.0.amplitude());
+/// }
+/// ```
+#[derive(Diff, Patch, Debug, Clone, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct EnvelopeFollowerNode {
+    /// How long it takes to react to increases in amplitude, in seconds.
+    ///
+    /// By default, this is 0.005s.
+    pub attack: f32,
+    /// How long it takes to react to decreases in amplitude, in seconds.
+    ///
+    /// By default, this is 0.15s.
+    pub release: f32,
+}
+
+impl EnvelopeFollowerNode {
+    /// Create a new [`EnvelopeFollowerNode`].
+    pub fn new(attack: f32, release: f32) -> Self {
+        Self { attack, release }
+    }
+}
+
+impl Default for EnvelopeFollowerNode {
+    fn default() -> Self {
+        Self::new(0.005, 0.15)
+    }
+}
+
+/// Configuration for an [`EnvelopeFollowerNode`].
+#[derive(Debug, Clone, Component, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct EnvelopeFollowerConfig {
+    /// How many channels to analyze.
+    ///
+    /// The envelope tracks the loudest channel.
+    /// By default, this is stereo.
+    pub channels: NonZeroChannelCount,
+}
+
+impl Default for EnvelopeFollowerConfig {
+    fn default() -> Self {
+        Self {
+            channels: NonZeroChannelCount::STEREO,
+        }
+    }
+}
+
+/// The shared atomic used by [`EnvelopeFollowerNode`] to communicate
+/// its current envelope.
+///
+/// Because audio is processed in chunks, this will typically
+/// update at a rate of 40-80 hertz.
+#[derive(Debug, Clone)]
+pub struct EnvelopeFollowerState(ArcGc<AtomicU32>);
+
+impl EnvelopeFollowerState {
+    /// The current envelope as a linear amplitude.
+    pub fn amplitude(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    /// The current envelope in decibels.
+    pub fn decibels(&self) -> f32 {
+        20.0 * self.amplitude().max(f32::EPSILON).log10()
+    }
+}
+
+impl AudioNode for EnvelopeFollowerNode {
+    type Configuration = EnvelopeFollowerConfig;
+
+    fn info(&self, config: &Self::Configuration) -> Result<AudioNodeInfo, NodeError> {
+        Ok(AudioNodeInfo::new()
+            .debug_name("envelope follower")
+            .channel_config(ChannelConfig {
+                num_inputs: config.channels.get(),
+                num_outputs: ChannelCount::ZERO,
+            })
+            .custom_state(EnvelopeFollowerState(ArcGc::new(AtomicU32::new(
+                0f32.to_bits(),
+            )))))
+    }
+
+    fn construct_processor(
+        &self,
+        _: &Self::Configuration,
+        cx: ConstructProcessorContext,
+    ) -> Result<impl AudioNodeProcessor, NodeError> {
+        let sample_rate = cx.stream_info.sample_rate;
+
+        Ok(EnvelopeFollowerProcessor {
+            attack: self.attack,
+            release: self.release,
+            coeffs: EnvelopeCoeffs::new(self.attack, self.release, sample_rate),
+            follower: EnvelopeFollower::default(),
+            sample_rate,
+            state: cx.custom_state().cloned().unwrap(),
+        })
+    }
+}
+
+struct EnvelopeFollowerProcessor {
+    attack: f32,
+    release: f32,
+    coeffs: EnvelopeCoeffs,
+    follower: EnvelopeFollower,
+    sample_rate: NonZeroU32,
+    state: EnvelopeFollowerState,
+}
+
+impl AudioNodeProcessor for EnvelopeFollowerProcessor {
+    fn events(&mut self, _info: &ProcInfo, events: &mut ProcEvents, _extra: &mut ProcExtra) {
+        for patch in events.drain_patches::<EnvelopeFollowerNode>() {
+            match patch {
+                EnvelopeFollowerNodePatch::Attack(attack) => self.attack = attack,
+                EnvelopeFollowerNodePatch::Release(release) => self.release = release,
+            }
+        }
+
+        self.coeffs = EnvelopeCoeffs::new(self.attack, self.release, self.sample_rate);
+    }
+
+    fn process(
+        &mut self,
+        proc_info: &ProcInfo,
+        buffers: ProcBuffers,
+        _: &mut ProcExtra,
+    ) -> ProcessStatus {
+        let silent = proc_info
+            .in_silence_mask
+            .all_channels_silent(buffers.inputs.len());
+
+        let envelope = if silent {
+            self.follower
+                .process_silence(proc_info.frames, &self.coeffs)
+        } else {
+            self.follower
+                .process_block(buffers.inputs, proc_info.frames, &self.coeffs)
+        };
+
+        self.state.0.store(envelope.to_bits(), Ordering::Relaxed);
+
+        ProcessStatus::Bypass
+    }
+
+    fn new_stream(&mut self, stream_info: &firewheel::StreamInfo, _: &mut ProcStreamCtx) {
+        self.sample_rate = stream_info.sample_rate;
+        self.coeffs = EnvelopeCoeffs::new(self.attack, self.release, self.sample_rate);
+    }
+}
+
+/// Drives a parameter of a node from an [`EnvelopeFollowerNode`].
+///
+/// Each frame, the `mapping` closure is called with the follower's current
+/// linear amplitude. The node's parameters are then diffed as usual.
+///
+/// This requires an [`EnvelopeDriverPlugin`] for the target node type.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// # fn plugin(app: &mut App) {
+/// app.add_plugins(EnvelopeDriverPlugin::<FastLowpassNode>::default());
+/// # }
+///
+/// fn pump_filter(mut commands: Commands, kick_follower: Single<Entity, With<EnvelopeFollowerNode>>) {
+///     commands.spawn((
+///         FastLowpassNode::<2>::default(),
+///         EnvelopeDriven::new(*kick_follower, |filter: &mut FastLowpassNode, amp| {
+///             filter.cutoff_hz = 20_000.0 - amp.min(1.0) * 19_000.0;
+///         }),
+///     ));
+/// }
+/// ```
+#[derive(Component)]
+pub struct EnvelopeDriven<T: Send + Sync + 'static> {
+    /// The entity containing the [`EnvelopeFollowerNode`].
+    pub source: Entity,
+    mapping: Arc<dyn Fn(&mut T, f32) + Send + Sync>,
+}
+
+impl<T: Send + Sync + 'static> EnvelopeDriven<T> {
+    /// Create a new [`EnvelopeDriven`] component.
+    pub fn new<F>(source: Entity, mapping: F) -> Self
+    where
+        F: Fn(&mut T, f32) + Send + Sync + 'static,
+    {
+        Self {
+            source,
+            mapping: Arc::new(mapping),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> Clone for EnvelopeDriven<T> {
+    fn clone(&self) -> Self {
+        Self {
+            source: self.source,
+            mapping: self.mapping.clone(),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> core::fmt::Debug for EnvelopeDriven<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EnvelopeDriven")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Enables [`EnvelopeDriven`] for the node `T`.
+#[derive(Debug)]
+pub struct EnvelopeDriverPlugin<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for EnvelopeDriverPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Plugin for EnvelopeDriverPlugin<T>
+where
+    T: Component<Mutability = Mutable>,
+{
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Last,
+            drive_from_envelope::<T>.before(SeedlingSystems::Acquire),
+        );
+    }
+}
+
+fn drive_from_envelope<T: Component<Mutability = Mutable>>(
+    mut targets: Query<(&mut T, &EnvelopeDriven<T>)>,
+    followers: Query<&AudioState<EnvelopeFollowerState>>,
+) {
+    for (mut target, driven) in &mut targets {
+        let Ok(state) = followers.get(driven.source) else {
+            continue;
+        };
+
+        (driven.mapping)(&mut target, state.0.amplitude());
+    }
+}
@@ -0,0 +1,289 @@
+//! Noise gate with hold and adjustable range.
+
+use bevy_ecs::component::Component;
+use firewheel::{
+    Volume,
+    channel_config::{ChannelConfig, NonZeroChannelCount},
+    diff::{Diff, Patch},
+    dsp::filter::smoothing_filter::{SmoothingFilter, SmoothingFilterCoeff},
+    event::ProcEvents,
+    node::{
+        AudioNode, AudioNodeInfo, AudioNodeProcessor, ConstructProcessorContext, NodeError,
+        ProcBuffers, ProcExtra, ProcInfo, ProcStreamCtx, ProcessStatus,
+    },
+};
+use std::num::NonZeroU32;
+
+/// A noise gate.
+///
+/// When the input's peak level falls below [`GateNode::threshold`]
+/// for longer than [`GateNode::hold`], the signal is attenuated to
+/// [`GateNode::range`]. Setting a moderate range, rather than full
+/// silence, produces a gentler, expander-like effect.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn gate_loop(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("my_sample.wav")).looping(),
+///         sample_effects![GateNode {
+///             threshold: Volume::Decibels(-30.0),
+///             range: Volume::Decibels(-20.0),
+///             ..Default::default()
+///         }],
+///     ));
+/// }
+/// ```
+#[derive(Diff, Patch, Debug, Clone, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct GateNode {
+    /// The level above which the gate opens.
+    ///
+    /// By default, this is -40 dB.
+    pub threshold: Volume,
+    /// How long the gate takes to open, in seconds.
+    ///
+    /// By default, this is 0.001s.
+    pub attack: f32,
+    /// How long the gate remains open after the input
+    /// falls below the threshold, in seconds.
+    ///
+    /// By default, this is 0.05s.
+    pub hold: f32,
+    /// How long the gate takes to close, in seconds.
+    ///
+    /// By default, this is 0.1s.
+    pub release: f32,
+    /// The gain applied while the gate is closed.
+    ///
+    /// By default, this is [`Volume::SILENT`].
+    pub range: Volume,
+}
+
+impl Default for GateNode {
+    fn default() -> Self {
+        Self {
+            threshold: Volume::Decibels(-40.0),
+            attack: 0.001,
+            hold: 0.05,
+            release: 0.1,
+            range: Volume::SILENT,
+        }
+    }
+}
+
+/// Configuration for a [`GateNode`].
+#[derive(Debug, Clone, Component, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct GateConfig {
+    /// The number of input and output channels.
+    ///
+    /// The gate is linked across channels, opening
+    /// when any one of them exceeds the threshold.
+    /// By default, this is stereo.
+    pub channels: NonZeroChannelCount,
+}
+
+impl Default for GateConfig {
+    fn default() -> Self {
+        Self {
+            channels: NonZeroChannelCount::STEREO,
+        }
+    }
+}
+
+impl AudioNode for GateNode {
+    type Configuration = GateConfig;
+
+    fn info(&self, config: &Self::Configuration) -> Result<AudioNodeInfo, NodeError> {
+        Ok(AudioNodeInfo::new()
+            .debug_name("gate")
+            .channel_config(ChannelConfig {
+                num_inputs: config.channels.get(),
+                num_outputs: config.channels.get(),
+            }))
+    }
+
+    fn construct_processor(
+        &self,
+        _: &Self::Configuration,
+        cx: ConstructProcessorContext,
+    ) -> Result<impl AudioNodeProcessor, NodeError> {
+        Ok(GateProcessor {
+            params: self.clone(),
+            gate: Gate::new(self, cx.stream_info.sample_rate),
+        })
+    }
+}
+
+/// The gating DSP, separated from the processor for testing.
+struct Gate {
+    coeff_open: SmoothingFilterCoeff,
+    coeff_close: SmoothingFilterCoeff,
+    gain: SmoothingFilter,
+    /// The number of frames remaining in the hold stage.
+    hold_remaining: u32,
+    sample_rate: NonZeroU32,
+}
+
+impl Gate {
+    fn new(params: &GateNode, sample_rate: NonZeroU32) -> Self {
+        Self {
+            coeff_open: SmoothingFilterCoeff::new(sample_rate, params.attack),
+            coeff_close: SmoothingFilterCoeff::new(sample_rate, params.release),
+            gain: SmoothingFilter::new(params.range.amp()),
+            hold_remaining: 0,
+            sample_rate,
+        }
+    }
+
+    fn update_coefficients(&mut self, params: &GateNode) {
+        self.coeff_open = SmoothingFilterCoeff::new(self.sample_rate, params.attack);
+        self.coeff_close = SmoothingFilterCoeff::new(self.sample_rate, params.release);
+    }
+
+    fn set_sample_rate(&mut self, params: &GateNode, sample_rate: NonZeroU32) {
+        self.sample_rate = sample_rate;
+        self.update_coefficients(params);
+    }
+
+    /// Close the gate immediately.
+    fn reset(&mut self, params: &GateNode) {
+        self.hold_remaining = 0;
+        self.gain.z1 = params.range.amp();
+    }
+
+    fn process(
+        &mut self,
+        params: &GateNode,
+        inputs: &[&[f32]],
+        outputs: &mut [&mut [f32]],
+        frames: usize,
+    ) {
+        let closed_gain = params.range.amp();
+        let threshold = params.threshold.amp();
+        let hold_frames = (params.hold.max(0.0) * self.sample_rate.get() as f32) as u32;
+
+        for frame in 0..frames {
+            let peak = inputs
+                .iter()
+                .map(|input| input[frame])
+                .filter(|x| x.is_finite())
+                .fold(0f32, |amp, x| amp.max(x.abs()));
+
+            if peak >= threshold {
+                self.hold_remaining = hold_frames;
+            } else {
+                self.hold_remaining = self.hold_remaining.saturating_sub(1);
+            }
+
+            let (target, coeff) = if peak >= threshold || self.hold_remaining > 0 {
+                (1.0, &self.coeff_open)
+            } else {
+                (closed_gain, &self.coeff_close)
+            };
+
+            let gain = self.gain.process_sample_a(target * coeff.a0, coeff.b1);
+
+            for (input, output) in inputs.iter().zip(outputs.iter_mut()) {
+                output[frame] = input[frame] * gain;
+            }
+        }
+    }
+}
+
+struct GateProcessor {
+    params: GateNode,
+    gate: Gate,
+}
+
+impl AudioNodeProcessor for GateProcessor {
+    fn events(&mut self, _info: &ProcInfo, events: &mut ProcEvents, _extra: &mut ProcExtra) {
+        let mut update_coefficients = false;
+        for patch in events.drain_patches::<GateNode>() {
+            update_coefficients |=
+                matches!(patch, GateNodePatch::Attack(_) | GateNodePatch::Release(_));
+            self.params.apply(patch);
+        }
+
+        if update_coefficients {
+            self.gate.update_coefficients(&self.params);
+        }
+    }
+
+    fn process(
+        &mut self,
+        proc_info: &ProcInfo,
+        ProcBuffers { inputs, outputs }: ProcBuffers,
+        _: &mut ProcExtra,
+    ) -> ProcessStatus {
+        if proc_info.in_silence_mask.all_channels_silent(inputs.len()) {
+            self.gate.reset(&self.params);
+            return ProcessStatus::ClearAllOutputs;
+        }
+
+        self.gate
+            .process(&self.params, inputs, outputs, proc_info.frames);
+
+        ProcessStatus::OutputsModified
+    }
+
+    fn new_stream(&mut self, stream_info: &firewheel::StreamInfo, _: &mut ProcStreamCtx) {
+        self.gate
+            .set_sample_rate(&self.params, stream_info.sample_rate);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_RATE: NonZeroU32 = NonZeroU32::new(48000).unwrap();
+
+    fn run_gate(gate: &mut Gate, params: &GateNode, level: f32, frames: usize) -> f32 {
+        let input = vec![level; frames];
+        let mut output = vec![0.0; frames];
+        gate.process(params, &[&input], &mut [output.as_mut_slice()], frames);
+
+        output[frames - 1]
+    }
+
+    #[test]
+    fn test_gate_opens() {
+        let params = GateNode::default();
+        let mut gate = Gate::new(&params, SAMPLE_RATE);
+
+        // Well above the threshold, the gate should pass the signal.
+        let output = run_gate(&mut gate, &params, 0.5, 4800);
+        assert!((output - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_gate_stays_closed() {
+        let params = GateNode::default();
+        let mut gate = Gate::new(&params, SAMPLE_RATE);
+
+        // -60 dB is below the default threshold.
+        let output = run_gate(&mut gate, &params, 0.001, 4800);
+        assert!(output.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_gate_holds_then_closes() {
+        let params = GateNode {
+            range: Volume::Decibels(-20.0),
+            ..Default::default()
+        };
+        let mut gate = Gate::new(&params, SAMPLE_RATE);
+        run_gate(&mut gate, &params, 0.5, 4800);
+
+        // Within the 50 ms hold, the gate stays open.
+        let held = run_gate(&mut gate, &params, 0.001, 1200);
+        assert!((held - 0.001).abs() < 1e-6);
+
+        // After the hold and release, it settles at `range`.
+        let closed = run_gate(&mut gate, &params, 0.001, 48000);
+        assert!((closed - 0.001 * params.range.amp()).abs() < 1e-6);
+    }
+}
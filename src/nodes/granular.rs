@@ -0,0 +1,432 @@
+//! Granular sample playback.
+
+use super::rng::{Xorshift, unique_seed};
+use crate::{
+    node::{FirewheelNode, events::AudioEvents},
+    sample::AudioSample,
+};
+use bevy_asset::prelude::*;
+use bevy_ecs::prelude::*;
+use firewheel::{
+    Volume,
+    channel_config::{ChannelConfig, ChannelCount, NonZeroChannelCount},
+    collector::ArcGc,
+    diff::{Diff, Patch},
+    event::{NodeEventType, ProcEvents},
+    node::{
+        AudioNode, AudioNodeInfo, AudioNodeProcessor, ConstructProcessorContext, NodeError,
+        ProcBuffers, ProcExtra, ProcInfo, ProcStreamCtx, ProcessStatus,
+    },
+    sample_resource::SampleResource,
+};
+use smallvec::SmallVec;
+
+/// A granular sample player.
+///
+/// Short, windowed grains are read from a sample at a steady
+/// rate, with each grain's position and pitch randomly sprayed
+/// around their centers. This is great for scrubbing through
+/// textures, wind, and sci-fi effects.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn wind(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         GranularNode {
+///             density: 40.0,
+///             grain_size: 0.12,
+///             position_spray: 0.1,
+///             pitch_spray: 2.0,
+///             ..Default::default()
+///         },
+///         GranularSample(server.load("wind.ogg")),
+///     ));
+/// }
+/// ```
+///
+/// Like the [`SamplerNode`][crate::prelude::SamplerNode], the sample is
+/// supplied with an event. [`GranularSample`] sends it once the asset
+/// loads, or you can send it yourself with [`GranularNode::set_sample_event`].
+///
+/// Overlapping grains are summed, so the output grows
+/// louder as `density * grain_size` increases.
+#[derive(Diff, Patch, Debug, Clone, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct GranularNode {
+    /// The output volume.
+    ///
+    /// By default, this is [`Volume::UNITY_GAIN`].
+    pub volume: Volume,
+    /// The center of the grains' read positions, normalized
+    /// to the sample's length in the range `0.0..=1.0`.
+    ///
+    /// Automating this scrubs through the sample.
+    ///
+    /// By default, this is `0.0`.
+    pub position: f32,
+    /// The maximum random offset applied to each grain's position,
+    /// normalized to the sample's length.
+    ///
+    /// By default, this is `0.05`.
+    pub position_spray: f32,
+    /// The number of grains started per second.
+    ///
+    /// By default, this is `20.0`.
+    pub density: f32,
+    /// The length of each grain in seconds.
+    ///
+    /// By default, this is `0.1`.
+    pub grain_size: f32,
+    /// The pitch of each grain in semitones.
+    ///
+    /// By default, this is `0.0`.
+    pub pitch: f32,
+    /// The maximum random pitch offset applied to each grain, in semitones.
+    ///
+    /// By default, this is `0.0`.
+    pub pitch_spray: f32,
+}
+
+impl Default for GranularNode {
+    fn default() -> Self {
+        Self {
+            volume: Volume::UNITY_GAIN,
+            position: 0.0,
+            position_spray: 0.05,
+            density: 20.0,
+            grain_size: 0.1,
+            pitch: 0.0,
+            pitch_spray: 0.0,
+        }
+    }
+}
+
+/// Configuration for a [`GranularNode`].
+#[derive(Debug, Clone, Component, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct GranularConfig {
+    /// The number of output channels.
+    ///
+    /// Mono samples are copied to every channel, while samples
+    /// with fewer channels than this repeat their last channel.
+    ///
+    /// By default, this is stereo.
+    pub channels: NonZeroChannelCount,
+    /// The maximum number of simultaneous grains.
+    ///
+    /// Grains started beyond this limit are skipped.
+    ///
+    /// By default, this is `64`.
+    pub max_grains: usize,
+    /// The PRNG seed for position and pitch spray.
+    ///
+    /// When `None`, each node receives a distinct seed.
+    /// By default, this is `None`.
+    pub seed: Option<u64>,
+}
+
+impl Default for GranularConfig {
+    fn default() -> Self {
+        Self {
+            channels: NonZeroChannelCount::STEREO,
+            max_grains: 64,
+            seed: None,
+        }
+    }
+}
+
+/// The sample played by a [`GranularNode`].
+///
+/// The sample is sent to the node's processor once it loads,
+/// and again whenever the handle changes.
+#[derive(Component, Debug, Clone)]
+pub struct GranularSample(pub Handle<AudioSample>);
+
+/// The asset most recently sent to a [`GranularNode`].
+#[derive(Component)]
+struct SentGranularSample(AssetId<AudioSample>);
+
+pub(crate) fn send_granular_samples(
+    mut nodes: Query<
+        (
+            Entity,
+            &GranularSample,
+            &mut AudioEvents,
+            Option<&SentGranularSample>,
+        ),
+        (With<GranularNode>, With<FirewheelNode>),
+    >,
+    assets: Res<Assets<AudioSample>>,
+    mut commands: Commands,
+) {
+    for (entity, sample, mut events, sent) in &mut nodes {
+        let id = sample.0.id();
+        if sent.is_some_and(|sent| sent.0 == id) {
+            continue;
+        }
+
+        let Some(asset) = assets.get(id) else {
+            continue;
+        };
+
+        events.push_event(GranularNode::set_sample_event(asset));
+        commands.entity(entity).insert(SentGranularSample(id));
+    }
+}
+
+/// The event carrying a [`GranularNode`]'s sample.
+struct SetGranularSample(Option<ArcGc<dyn SampleResource + Send + Sync>>);
+
+impl GranularNode {
+    /// Construct an event that sets the node's sample.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn set_sample(
+    ///     mut node: Single<&mut AudioEvents, With<GranularNode>>,
+    ///     samples: Res<Assets<AudioSample>>,
+    ///     server: Res<AssetServer>,
+    /// ) {
+    ///     if let Some(sample) = samples.get(&server.load("wind.ogg")) {
+    ///         node.push_event(GranularNode::set_sample_event(sample));
+    ///     }
+    /// }
+    /// ```
+    pub fn set_sample_event(sample: &AudioSample) -> NodeEventType {
+        NodeEventType::custom(SetGranularSample(Some(sample.get())))
+    }
+}
+
+/// The fastest rate at which grains read their sample.
+const MAX_RATE: f64 = 8.0;
+
+impl AudioNode for GranularNode {
+    type Configuration = GranularConfig;
+
+    fn info(&self, config: &Self::Configuration) -> Result<AudioNodeInfo, NodeError> {
+        Ok(AudioNodeInfo::new()
+            .debug_name("granular")
+            .channel_config(ChannelConfig {
+                num_inputs: ChannelCount::ZERO,
+                num_outputs: config.channels.get(),
+            }))
+    }
+
+    fn construct_processor(
+        &self,
+        config: &Self::Configuration,
+        cx: ConstructProcessorContext,
+    ) -> Result<impl AudioNodeProcessor, NodeError> {
+        let scratch_len = scratch_len(cx.stream_info.max_block_frames.get());
+
+        Ok(GranularProcessor {
+            params: self.clone(),
+            sample: None,
+            rng: Xorshift::new(config.seed.unwrap_or_else(unique_seed)),
+            grains: vec![Grain::default(); config.max_grains].into(),
+            scratch: (0..config.channels.get().get())
+                .map(|_| vec![0.0; scratch_len])
+                .collect(),
+            sample_rate: cx.stream_info.sample_rate.get() as f64,
+            until_next: 0.0,
+        })
+    }
+}
+
+/// The number of source frames a grain may read in a single block.
+fn scratch_len(max_block_frames: u32) -> usize {
+    (max_block_frames as f64 * MAX_RATE).ceil() as usize + 2
+}
+
+/// A Hann window evaluated at `age` within a grain of `length` frames.
+fn hann(age: u32, length: u32) -> f32 {
+    let phase = age as f32 / length.max(1) as f32;
+    0.5 - 0.5 * (core::f32::consts::TAU * phase).cos()
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Grain {
+    active: bool,
+    /// The read position in source frames.
+    position: f64,
+    /// The source frames advanced per output frame.
+    rate: f64,
+    age: u32,
+    length: u32,
+    /// The frame within the current block where this grain begins.
+    offset: u32,
+}
+
+struct GranularProcessor {
+    params: GranularNode,
+    sample: Option<ArcGc<dyn SampleResource + Send + Sync>>,
+    rng: Xorshift,
+    grains: Box<[Grain]>,
+    /// Per-channel buffers for reading source frames.
+    scratch: Box<[Vec<f32>]>,
+    sample_rate: f64,
+    /// The frames remaining until the next grain starts.
+    until_next: f64,
+}
+
+impl GranularProcessor {
+    fn spawn_grain(&mut self, offset: u32, len_frames: u64, source_rate: f64) {
+        let Some(grain) = self.grains.iter_mut().find(|g| !g.active) else {
+            return;
+        };
+
+        let params = &self.params;
+        let position = (params.position + params.position_spray.abs() * self.rng.bipolar())
+            .clamp(0.0, 1.0) as f64;
+        let semitones = params.pitch + params.pitch_spray.abs() * self.rng.bipolar();
+        let rate = (2f64.powf(semitones as f64 / 12.0) * source_rate / self.sample_rate)
+            .clamp(1.0 / MAX_RATE, MAX_RATE);
+
+        *grain = Grain {
+            active: true,
+            position: position * len_frames.saturating_sub(1) as f64,
+            rate,
+            age: 0,
+            length: (params.grain_size.max(0.0) as f64 * self.sample_rate).max(1.0) as u32,
+            offset,
+        };
+    }
+}
+
+impl AudioNodeProcessor for GranularProcessor {
+    fn events(&mut self, _info: &ProcInfo, events: &mut ProcEvents, _extra: &mut ProcExtra) {
+        for patch in events.drain_patches::<GranularNode>() {
+            self.params.apply(patch);
+        }
+
+        for event in events.drain() {
+            if let NodeEventType::Custom(mut event) = event
+                && let Some(sample) = event.downcast_mut::<SetGranularSample>()
+            {
+                // The previous sample is dropped in the event,
+                // keeping deallocation off the audio thread.
+                core::mem::swap(&mut self.sample, &mut sample.0);
+                self.grains.iter_mut().for_each(|g| g.active = false);
+            }
+        }
+    }
+
+    fn process(
+        &mut self,
+        proc_info: &ProcInfo,
+        ProcBuffers { outputs, .. }: ProcBuffers,
+        _: &mut ProcExtra,
+    ) -> ProcessStatus {
+        let frames = proc_info.frames;
+
+        let Some(sample) = self.sample.clone() else {
+            return ProcessStatus::ClearAllOutputs;
+        };
+
+        let len_frames = sample.len_frames();
+        if len_frames < 2 {
+            return ProcessStatus::ClearAllOutputs;
+        }
+
+        let source_rate = sample
+            .sample_rate()
+            .map_or(self.sample_rate, |r| r.get() as f64);
+
+        let density = self.params.density.max(0.0) as f64;
+        if density > 0.0 {
+            let interval = self.sample_rate / density;
+            while self.until_next < frames as f64 {
+                self.spawn_grain(self.until_next.max(0.0) as u32, len_frames, source_rate);
+                self.until_next += interval;
+            }
+            self.until_next -= frames as f64;
+        } else {
+            self.until_next = 0.0;
+        }
+
+        if !self.grains.iter().any(|g| g.active) {
+            return ProcessStatus::ClearAllOutputs;
+        }
+
+        for output in outputs.iter_mut() {
+            output[..frames].fill(0.0);
+        }
+
+        let gain = self.params.volume.linear();
+        let channels = sample.num_channels().get().min(self.scratch.len());
+
+        for grain in self.grains.iter_mut().filter(|g| g.active) {
+            let start = core::mem::take(&mut grain.offset) as usize;
+            let count = (frames.saturating_sub(start)).min((grain.length - grain.age) as usize);
+
+            let base = grain.position.floor();
+            let needed =
+                ((grain.rate * count as f64).ceil() as usize + 2).min(self.scratch[0].len());
+            let available = (len_frames.saturating_sub(base as u64) as usize).min(needed);
+
+            {
+                let mut buffers: SmallVec<[&mut [f32]; 8]> = self.scratch[..channels]
+                    .iter_mut()
+                    .map(|b| &mut b[..needed])
+                    .collect();
+
+                sample.fill_buffers(&mut buffers, 0..available, base as u64);
+
+                for buffer in buffers.iter_mut() {
+                    buffer[available..].fill(0.0);
+                }
+            }
+
+            let mut local = grain.position - base;
+            for i in 0..count {
+                let index = local as usize;
+                if index + 1 >= available {
+                    break;
+                }
+
+                let fraction = (local - index as f64) as f32;
+                let window = hann(grain.age + i as u32, grain.length) * gain;
+
+                for (channel, output) in outputs.iter_mut().enumerate() {
+                    let source = &self.scratch[channel.min(channels - 1)];
+                    let value = source[index] + (source[index + 1] - source[index]) * fraction;
+                    output[start + i] += value * window;
+                }
+
+                local += grain.rate;
+            }
+
+            grain.position += grain.rate * count as f64;
+            grain.age += count as u32;
+
+            if grain.age >= grain.length || grain.position >= (len_frames - 1) as f64 {
+                grain.active = false;
+            }
+        }
+
+        ProcessStatus::OutputsModified
+    }
+
+    fn new_stream(&mut self, stream_info: &firewheel::StreamInfo, _: &mut ProcStreamCtx) {
+        self.sample_rate = stream_info.sample_rate.get() as f64;
+
+        let scratch_len = scratch_len(stream_info.max_block_frames.get());
+        for buffer in &mut self.scratch {
+            buffer.resize(scratch_len, 0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hann_window() {
+        assert_eq!(hann(0, 100), 0.0);
+        assert!((hann(50, 100) - 1.0).abs() < 1e-6);
+        assert!(hann(99, 100) < 0.01);
+    }
+}
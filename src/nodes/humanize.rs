@@ -0,0 +1,299 @@
+//! Per-trigger volume and pitch variation.
+
+use super::rng::{Xorshift, unique_seed};
+use bevy_ecs::component::Component;
+use bevy_seedling_dsp::DelayLine;
+use firewheel::{
+    channel_config::{ChannelConfig, NonZeroChannelCount},
+    clock::DurationSeconds,
+    diff::{Diff, Patch},
+    event::ProcEvents,
+    node::{
+        AudioNode, AudioNodeInfo, AudioNodeProcessor, ConstructProcessorContext, NodeError,
+        ProcBuffers, ProcExtra, ProcInfo, ProcStreamCtx, ProcessStatus,
+    },
+};
+
+/// Applies small random variations in volume and pitch
+/// each time a sound is triggered.
+///
+/// Unlike [`RandomPitch`][crate::prelude::RandomPitch], the variations
+/// are rolled on the audio thread with a seeded PRNG, so massively
+/// repeated sounds like bullet impacts need no ECS round-trips.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn impact(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("impact.wav")),
+///         sample_effects![HumanizeNode {
+///             volume_db: 2.0,
+///             pitch_semitones: 0.75,
+///         }],
+///     ));
+/// }
+/// ```
+///
+/// A trigger is any input following silence, such as a pooled
+/// sampler starting a new sample. Pitch variation reads the input through
+/// a short delay line, so it adds [`HumanizeConfig::pitch_window`] of
+/// latency and only holds its variation for sounds shorter than the
+/// window divided by the pitch ratio's deviation. Longer sounds
+/// gradually return to their original pitch without clicking.
+#[derive(Diff, Patch, Debug, Clone, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct HumanizeNode {
+    /// The maximum volume variation in decibels, in either direction.
+    ///
+    /// By default, this is `1.5`.
+    pub volume_db: f32,
+    /// The maximum pitch variation in semitones, in either direction.
+    ///
+    /// By default, this is `0.5`.
+    pub pitch_semitones: f32,
+}
+
+impl Default for HumanizeNode {
+    fn default() -> Self {
+        Self {
+            volume_db: 1.5,
+            pitch_semitones: 0.5,
+        }
+    }
+}
+
+/// Configuration for a [`HumanizeNode`].
+#[derive(Debug, Clone, Component, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct HumanizeConfig {
+    /// The number of input and output channels.
+    ///
+    /// By default, this is stereo.
+    pub channels: NonZeroChannelCount,
+    /// The PRNG seed.
+    ///
+    /// When `None`, each node receives a distinct seed.
+    /// By default, this is `None`.
+    pub seed: Option<u64>,
+    /// The lookahead used for pitch variation.
+    ///
+    /// A zero window disables pitch variation, along with its latency.
+    ///
+    /// By default, this is 20 milliseconds.
+    pub pitch_window: DurationSeconds,
+}
+
+impl Default for HumanizeConfig {
+    fn default() -> Self {
+        Self {
+            channels: NonZeroChannelCount::STEREO,
+            seed: None,
+            pitch_window: DurationSeconds(0.02),
+        }
+    }
+}
+
+impl AudioNode for HumanizeNode {
+    type Configuration = HumanizeConfig;
+
+    fn info(&self, config: &Self::Configuration) -> Result<AudioNodeInfo, NodeError> {
+        Ok(AudioNodeInfo::new()
+            .debug_name("humanize")
+            .channel_config(ChannelConfig {
+                num_inputs: config.channels.get(),
+                num_outputs: config.channels.get(),
+            }))
+    }
+
+    fn construct_processor(
+        &self,
+        config: &Self::Configuration,
+        cx: ConstructProcessorContext,
+    ) -> Result<impl AudioNodeProcessor, NodeError> {
+        let seed = config.seed.unwrap_or_else(unique_seed);
+        let window = window_frames(config.pitch_window, cx.stream_info.sample_rate.get());
+
+        Ok(HumanizeProcessor {
+            params: self.clone(),
+            rng: Xorshift::new(seed),
+            lines: (0..config.channels.get().get())
+                .map(|_| DelayLine::new(window * 2 + 1))
+                .collect(),
+            pitch_window: config.pitch_window,
+            window,
+            variation: Variation::default(),
+            delay: window as f32,
+            tail: 0,
+            silent: true,
+        })
+    }
+}
+
+/// The pitch variation window in frames.
+fn window_frames(window: DurationSeconds, sample_rate: u32) -> usize {
+    (window.0.max(0.0) * sample_rate as f64).round() as usize
+}
+
+/// The variation rolled for a single trigger.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Variation {
+    gain: f32,
+    /// The playback rate, where `1.0` is the original pitch.
+    rate: f32,
+}
+
+impl Default for Variation {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            rate: 1.0,
+        }
+    }
+}
+
+impl Variation {
+    fn roll(params: &HumanizeNode, rng: &mut Xorshift) -> Self {
+        let db = params.volume_db.abs() * rng.bipolar();
+        let semitones = params.pitch_semitones.abs() * rng.bipolar();
+
+        Self {
+            gain: 10f32.powf(db / 20.0),
+            rate: 2f32.powf(semitones / 12.0),
+        }
+    }
+}
+
+struct HumanizeProcessor {
+    params: HumanizeNode,
+    rng: Xorshift,
+    lines: Box<[DelayLine]>,
+    pitch_window: DurationSeconds,
+    /// The pitch window in frames.
+    window: usize,
+    variation: Variation,
+    /// The read head's distance behind the write head, in frames.
+    delay: f32,
+    /// The delayed frames left to flush after the input falls silent.
+    tail: usize,
+    /// Whether the previous block was silent.
+    silent: bool,
+}
+
+impl AudioNodeProcessor for HumanizeProcessor {
+    fn events(&mut self, _info: &ProcInfo, events: &mut ProcEvents, _extra: &mut ProcExtra) {
+        for patch in events.drain_patches::<HumanizeNode>() {
+            self.params.apply(patch);
+        }
+    }
+
+    fn process(
+        &mut self,
+        proc_info: &ProcInfo,
+        ProcBuffers { inputs, outputs }: ProcBuffers,
+        _: &mut ProcExtra,
+    ) -> ProcessStatus {
+        let silent = proc_info.in_silence_mask.all_channels_silent(inputs.len());
+        let triggered = self.silent && !silent;
+        self.silent = silent;
+
+        // Keep flushing the delay lines until they're silent too.
+        if silent && self.tail == 0 {
+            return ProcessStatus::ClearAllOutputs;
+        }
+
+        if triggered {
+            self.variation = Variation::roll(&self.params, &mut self.rng);
+
+            if self.window == 0 {
+                self.variation.rate = 1.0;
+            } else if self.tail == 0 {
+                self.delay = self.window as f32;
+            }
+        }
+
+        let max_delay = (self.window * 2) as f32;
+        let rate = if silent { 1.0 } else { self.variation.rate };
+        let gain = self.variation.gain;
+
+        for frame in 0..proc_info.frames {
+            if self.window == 0 {
+                for (input, output) in inputs.iter().zip(outputs.iter_mut()) {
+                    output[frame] = input[frame] * gain;
+                }
+
+                continue;
+            }
+
+            // Reading slower than we write raises the delay and lowers the pitch.
+            self.delay = (self.delay + 1.0 - rate).clamp(0.0, max_delay);
+
+            for ((input, output), line) in inputs
+                .iter()
+                .zip(outputs.iter_mut())
+                .zip(self.lines.iter_mut())
+            {
+                line.write(input[frame]);
+                output[frame] = line.read_at(self.delay) * gain;
+            }
+        }
+
+        self.tail = if silent {
+            self.tail.saturating_sub(proc_info.frames)
+        } else {
+            self.delay.ceil() as usize
+        };
+
+        ProcessStatus::OutputsModified
+    }
+
+    fn new_stream(&mut self, stream_info: &firewheel::StreamInfo, _: &mut ProcStreamCtx) {
+        if stream_info.sample_rate != stream_info.prev_sample_rate {
+            self.window = window_frames(self.pitch_window, stream_info.sample_rate.get());
+            self.delay = self.window as f32;
+            self.tail = 0;
+
+            for line in &mut self.lines {
+                line.resize(self.window * 2 + 1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_variation_range() {
+        let params = HumanizeNode {
+            volume_db: 3.0,
+            pitch_semitones: 1.0,
+        };
+        let mut rng = Xorshift::new(7);
+
+        let (min_gain, max_gain) = (10f32.powf(-3.0 / 20.0), 10f32.powf(3.0 / 20.0));
+        let (min_rate, max_rate) = (2f32.powf(-1.0 / 12.0), 2f32.powf(1.0 / 12.0));
+
+        for _ in 0..1000 {
+            let variation = Variation::roll(&params, &mut rng);
+
+            assert!((min_gain..=max_gain).contains(&variation.gain));
+            assert!((min_rate..=max_rate).contains(&variation.rate));
+        }
+    }
+
+    #[test]
+    fn test_seeded_variation() {
+        let params = HumanizeNode::default();
+        let roll = |seed| {
+            let mut rng = Xorshift::new(seed);
+            (0..4)
+                .map(|_| Variation::roll(&params, &mut rng))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(roll(42), roll(42));
+        assert_ne!(roll(42), roll(43));
+    }
+}
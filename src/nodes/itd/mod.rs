@@ -2,7 +2,7 @@
 
 use bevy_ecs::component::Component;
 use bevy_math::Vec3;
-use delay_line::DelayLine;
+use bevy_seedling_dsp::DelayLine;
 use firewheel::{
     channel_config::{ChannelConfig, NonZeroChannelCount},
     diff::{Diff, Patch},
@@ -13,8 +13,6 @@ use firewheel::{
     },
 };
 
-mod delay_line;
-
 /// The speed of sound in air, 20 degrees C, at sea level, in meters per second.
 const SPEED_OF_SOUND: f32 = 343.0;
 
@@ -29,6 +27,23 @@ const SPEED_OF_SOUND: f32 = 343.0;
 /// Note that stereo sounds are converted to mono before applying
 /// the spatialization, so some sounds may appear to be "compacted"
 /// by the transformation.
+///
+/// Like [`SpatialBasicNode`][firewheel::nodes::spatial_basic::SpatialBasicNode],
+/// the [`direction`][ItdNode::direction] is updated automatically from the
+/// nearest [spatial listener][crate::prelude::SpatialListener3D] when the node
+/// has a transform or is a sample effect on an entity with a transform.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn spawn_spatial(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("my_sample.wav")),
+///         Transform::from_xyz(5.0, 0.0, 0.0),
+///         sample_effects![SpatialBasicNode::default(), ItdNode::default()],
+///     ));
+/// }
+/// ```
 #[derive(Debug, Default, Clone, Component, Diff, Patch)]
 #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct ItdNode {
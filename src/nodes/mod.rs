@@ -1,12 +1,31 @@
 //! All of `bevy_seedling`'s audio nodes.
 
-use crate::{SeedlingSystems, prelude::RegisterNode};
+use crate::{SeedlingSystems, context::SampleRate, prelude::RegisterNode};
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 
+#[cfg(feature = "envelopes")]
+pub mod amplitude_tracker;
+pub mod bitcrusher;
+pub mod bounce;
+#[cfg(feature = "filters")]
+pub mod crossover;
+#[cfg(feature = "filters")]
+pub mod dc_block;
+#[cfg(feature = "envelopes")]
+pub mod envelope_follower;
+pub mod gate;
+pub mod granular;
+pub mod humanize;
+#[cfg(feature = "itd")]
 pub mod itd;
+#[cfg(feature = "limiter")]
 pub mod limiter;
+pub mod pan;
+mod rng;
 pub mod send;
+pub mod stereo_width;
+pub mod synth;
 
 #[cfg(feature = "loudness")]
 pub mod loudness;
@@ -23,8 +42,12 @@ pub mod core {
 }
 
 /// Effects and analysis nodes from Firewheel.
-#[cfg(feature = "effects")]
+#[cfg(any(feature = "effects", feature = "freeverb"))]
 pub mod effects {
+    #[cfg(feature = "freeverb")]
+    pub use firewheel::nodes::freeverb::FreeverbNode;
+
+    #[cfg(feature = "effects")]
     pub use firewheel::nodes::{
         convolution::{ConvolutionNode, ConvolutionNodeConfig},
         delay_compensation::{DelayCompNodeConfig, DelayCompensationNode},
@@ -32,7 +55,6 @@ pub mod effects {
             bandpass::FastBandpassNode, highpass::FastHighpassNode, lowpass::FastLowpassNode,
         },
         fast_rms::{FastRmsNode, FastRmsState},
-        freeverb::FreeverbNode,
         mix::{MixNode, MixNodeConfig},
         noise_generator::{
             pink::{PinkNoiseGenConfig, PinkNoiseGenNode},
@@ -52,13 +74,58 @@ impl Plugin for SeedlingNodesPlugin {
 
         // seedling nodes
         app.register_node::<send::SendNode>()
-            .register_node::<limiter::LimiterNode>()
-            .register_node::<itd::ItdNode>()
+            .register_node::<stereo_width::StereoWidthNode>()
+            .register_node::<pan::PanNode>()
+            .register_node::<bitcrusher::BitCrusherNode>()
+            .register_node::<gate::GateNode>()
+            .register_node::<humanize::HumanizeNode>()
+            .register_node::<granular::GranularNode>()
+            .register_node::<synth::SynthNode>()
+            .register_simple_node::<bounce::BounceNode>()
+            .register_node_state::<bounce::BounceNode, bounce::BounceState>()
             .add_systems(
                 Last,
-                (send::connect_sends, send::update_remote_sends).before(SeedlingSystems::Acquire),
+                (
+                    (send::connect_sends, send::update_remote_sends)
+                        .before(SeedlingSystems::Acquire),
+                    bounce::finish_bounces
+                        .run_if(resource_exists::<SampleRate>)
+                        .before(SeedlingSystems::Acquire),
+                    granular::send_granular_samples
+                        .after(SeedlingSystems::Acquire)
+                        .before(SeedlingSystems::Queue),
+                ),
             );
 
+        #[cfg(feature = "limiter")]
+        app.register_node::<limiter::LimiterNode>();
+
+        #[cfg(feature = "itd")]
+        app.register_node::<itd::ItdNode>();
+
+        #[cfg(feature = "filters")]
+        app.register_node::<crossover::CrossoverNode>()
+            .register_node::<dc_block::DcBlockNode>();
+
+        #[cfg(feature = "envelopes")]
+        app.register_node::<envelope_follower::EnvelopeFollowerNode>()
+            .register_node_state::<
+                envelope_follower::EnvelopeFollowerNode,
+                envelope_follower::EnvelopeFollowerState,
+            >()
+            .register_node::<amplitude_tracker::AmplitudeTrackerNode>()
+            .register_node_state::<
+                amplitude_tracker::AmplitudeTrackerNode,
+                amplitude_tracker::AmplitudeTrackerState,
+            >()
+            .add_systems(
+                Last,
+                amplitude_tracker::publish_sample_amplitude.before(SeedlingSystems::Acquire),
+            );
+
+        #[cfg(feature = "freeverb")]
+        app.register_node::<effects::FreeverbNode>();
+
         #[cfg(feature = "loudness")]
         app.register_node::<loudness::LoudnessNode>()
             .register_node_state::<loudness::LoudnessNode, loudness::LoudnessState>();
@@ -79,7 +146,6 @@ impl Plugin for SeedlingNodesPlugin {
             app.register_simple_node::<DelayCompensationNode>()
                 .register_node_state::<FastRmsNode, FastRmsState>()
                 .register_node_state::<PeakMeterNode, PeakMeterState>()
-                .register_node::<FreeverbNode>()
                 .register_node::<SvfNode>()
                 .register_node::<FastBandpassNode>()
                 .register_node::<FastHighpassNode>()
@@ -0,0 +1,243 @@
+//! Mono panning with selectable pan laws.
+
+use bevy_ecs::component::Component;
+use firewheel::{
+    channel_config::{ChannelConfig, ChannelCount, NonZeroChannelCount},
+    diff::{Diff, Patch},
+    dsp::filter::smoothing_filter::{SmoothingFilter, SmoothingFilterCoeff},
+    event::ProcEvents,
+    node::{
+        AudioNode, AudioNodeInfo, AudioNodeProcessor, ConstructProcessorContext, NodeError,
+        ProcBuffers, ProcExtra, ProcInfo, ProcStreamCtx, ProcessStatus,
+    },
+};
+
+/// The time over which pan changes are smoothed, in seconds.
+const SMOOTH_SECS: f32 = 0.015;
+
+/// Determines how a signal's gain is distributed between two channels.
+///
+/// Each law is named for the attenuation applied to both channels
+/// when the signal is panned to the center.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum PanLaw {
+    /// Sine/cosine panning, attenuating the center by 3 dB.
+    ///
+    /// This keeps the perceived loudness constant as a
+    /// signal moves between speakers.
+    #[default]
+    ConstantPower,
+    /// A compromise between constant power and linear panning,
+    /// attenuating the center by 4.5 dB.
+    Compromise,
+    /// Linear panning, attenuating the center by 6 dB.
+    ///
+    /// This sums to unity when both channels are mixed to mono.
+    Linear,
+}
+
+impl PanLaw {
+    /// Calculate the gains for two adjacent channels, where
+    /// `t` is the position between them in the range `0..=1`.
+    pub fn gains(&self, t: f32) -> (f32, f32) {
+        let t = t.clamp(0.0, 1.0);
+        let angle = t * core::f32::consts::FRAC_PI_2;
+
+        match self {
+            Self::ConstantPower => (angle.cos(), angle.sin()),
+            Self::Compromise => (((1.0 - t) * angle.cos()).sqrt(), (t * angle.sin()).sqrt()),
+            Self::Linear => (1.0 - t, t),
+        }
+    }
+}
+
+/// A mono panner with a configurable [`PanLaw`].
+///
+/// The mono input is panned across any number of output channels,
+/// which are treated as a line of evenly spaced speakers from left
+/// to right. With stereo output, this is conventional left-right panning.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn pan(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("my_sample.wav")),
+///         sample_effects![(
+///             PanNode::new(-0.5),
+///             PanConfig {
+///                 law: PanLaw::Linear,
+///                 ..Default::default()
+///             }
+///         )],
+///     ));
+/// }
+/// ```
+///
+/// For stereo sources, consider Firewheel's [`VolumePanNode`][crate::prelude::VolumePanNode].
+#[derive(Diff, Patch, Debug, Clone, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct PanNode {
+    /// The pan position, from `-1.0` (fully left) to `1.0` (fully right).
+    ///
+    /// By default, this is `0.0`.
+    pub pan: f32,
+}
+
+impl PanNode {
+    /// Create a new [`PanNode`].
+    pub fn new(pan: f32) -> Self {
+        Self { pan }
+    }
+}
+
+impl Default for PanNode {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+/// Configuration for a [`PanNode`].
+#[derive(Debug, Clone, Component, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct PanConfig {
+    /// The pan law.
+    ///
+    /// By default, this is [`PanLaw::ConstantPower`].
+    pub law: PanLaw,
+    /// The number of output channels.
+    ///
+    /// By default, this is stereo.
+    pub output_channels: NonZeroChannelCount,
+}
+
+impl Default for PanConfig {
+    fn default() -> Self {
+        Self {
+            law: PanLaw::ConstantPower,
+            output_channels: NonZeroChannelCount::STEREO,
+        }
+    }
+}
+
+impl AudioNode for PanNode {
+    type Configuration = PanConfig;
+
+    fn info(&self, config: &Self::Configuration) -> Result<AudioNodeInfo, NodeError> {
+        Ok(AudioNodeInfo::new()
+            .debug_name("pan")
+            .channel_config(ChannelConfig {
+                num_inputs: ChannelCount::MONO,
+                num_outputs: config.output_channels.get(),
+            }))
+    }
+
+    fn construct_processor(
+        &self,
+        config: &Self::Configuration,
+        cx: ConstructProcessorContext,
+    ) -> Result<impl AudioNodeProcessor, NodeError> {
+        let coeff = SmoothingFilterCoeff::new(cx.stream_info.sample_rate, SMOOTH_SECS);
+        let pan = self.pan.clamp(-1.0, 1.0);
+
+        Ok(PanProcessor {
+            law: config.law,
+            target: pan,
+            filter: SmoothingFilter::new(pan),
+            coeff,
+        })
+    }
+}
+
+/// Calculate the pair of channels a signal falls between, along with
+/// the position between them.
+fn channel_pair(pan: f32, channels: usize) -> (usize, usize, f32) {
+    if channels < 2 {
+        return (0, 0, 0.0);
+    }
+
+    let position = (pan.clamp(-1.0, 1.0) + 1.0) * 0.5 * (channels - 1) as f32;
+    let lower = (position.floor() as usize).min(channels - 2);
+
+    (lower, lower + 1, position - lower as f32)
+}
+
+struct PanProcessor {
+    law: PanLaw,
+    target: f32,
+    filter: SmoothingFilter,
+    coeff: SmoothingFilterCoeff,
+}
+
+impl AudioNodeProcessor for PanProcessor {
+    fn events(&mut self, _info: &ProcInfo, events: &mut ProcEvents, _extra: &mut ProcExtra) {
+        for patch in events.drain_patches::<PanNode>() {
+            let PanNodePatch::Pan(pan) = patch;
+            self.target = pan.clamp(-1.0, 1.0);
+        }
+    }
+
+    fn process(
+        &mut self,
+        proc_info: &ProcInfo,
+        ProcBuffers { inputs, outputs }: ProcBuffers,
+        _: &mut ProcExtra,
+    ) -> ProcessStatus {
+        if proc_info.in_silence_mask.all_channels_silent(1) {
+            self.filter.z1 = self.target;
+            return ProcessStatus::ClearAllOutputs;
+        }
+
+        if outputs.len() == 1 {
+            outputs[0][..proc_info.frames].copy_from_slice(&inputs[0][..proc_info.frames]);
+            return ProcessStatus::OutputsModified;
+        }
+
+        for output in outputs.iter_mut() {
+            output[..proc_info.frames].fill(0.0);
+        }
+
+        let target_times_a = self.target * self.coeff.a0;
+        for frame in 0..proc_info.frames {
+            let pan = self.filter.process_sample_a(target_times_a, self.coeff.b1);
+            let (a, b, t) = channel_pair(pan, outputs.len());
+            let (gain_a, gain_b) = self.law.gains(t);
+
+            let input = inputs[0][frame];
+            outputs[a][frame] = input * gain_a;
+            outputs[b][frame] = input * gain_b;
+        }
+
+        ProcessStatus::OutputsModified
+    }
+
+    fn new_stream(&mut self, stream_info: &firewheel::StreamInfo, _: &mut ProcStreamCtx) {
+        self.coeff = SmoothingFilterCoeff::new(stream_info.sample_rate, SMOOTH_SECS);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn center_decibels(law: PanLaw) -> f32 {
+        20.0 * law.gains(0.5).0.log10()
+    }
+
+    #[test]
+    fn test_center_attenuation() {
+        assert!((center_decibels(PanLaw::ConstantPower) + 3.01).abs() < 0.01);
+        assert!((center_decibels(PanLaw::Compromise) + 4.52).abs() < 0.01);
+        assert!((center_decibels(PanLaw::Linear) + 6.02).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_channel_pair() {
+        assert_eq!(channel_pair(-1.0, 2), (0, 1, 0.0));
+        assert_eq!(channel_pair(1.0, 2), (0, 1, 1.0));
+        assert_eq!(channel_pair(0.0, 3), (1, 2, 0.0));
+        assert_eq!(channel_pair(0.5, 5), (3, 4, 0.0));
+        assert_eq!(channel_pair(0.0, 1), (0, 0, 0.0));
+    }
+}
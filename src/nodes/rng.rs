@@ -0,0 +1,34 @@
+//! A tiny PRNG for audio-thread randomization.
+
+use bevy_platform::sync::atomic::{AtomicU64, Ordering};
+
+/// Distinguishes unseeded nodes from one another.
+static NEXT_SEED: AtomicU64 = AtomicU64::new(0x9e37_79b9_7f4a_7c15);
+
+/// Produce a seed distinct from any previously returned.
+pub(crate) fn unique_seed() -> u64 {
+    NEXT_SEED.fetch_add(0x9e37_79b9_7f4a_7c15, Ordering::Relaxed)
+}
+
+/// A tiny, allocation-free PRNG suitable for the audio thread.
+#[derive(Debug, Clone)]
+pub(crate) struct Xorshift(u64);
+
+impl Xorshift {
+    pub(crate) fn new(seed: u64) -> Self {
+        // xorshift gets stuck on zero
+        Self(seed.max(1))
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A value in `-1.0..1.0`.
+    pub(crate) fn bipolar(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+    }
+}
@@ -0,0 +1,242 @@
+//! Mid/side stereo width control.
+
+use bevy_ecs::component::Component;
+use bevy_seedling_dsp::one_pole::{OnePoleLowpass, lowpass_coeff};
+use firewheel::{
+    channel_config::{ChannelConfig, ChannelCount},
+    diff::{Diff, Patch},
+    dsp::filter::smoothing_filter::{SmoothingFilter, SmoothingFilterCoeff},
+    event::ProcEvents,
+    node::{
+        AudioNode, AudioNodeInfo, AudioNodeProcessor, ConstructProcessorContext, EmptyConfig,
+        NodeError, ProcBuffers, ProcExtra, ProcInfo, ProcStreamCtx, ProcessStatus,
+    },
+};
+use std::num::NonZeroU32;
+
+/// The time over which width changes are smoothed, in seconds.
+const SMOOTH_SECS: f32 = 0.015;
+
+/// A stereo width node using mid/side processing.
+///
+/// The stereo input is split into its mid (`L + R`) and side (`L - R`)
+/// components. The side component is scaled by [`StereoWidthNode::width`]
+/// before recombining, narrowing or widening the stereo image.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn master_bus(mut commands: Commands) {
+///     // Slightly widen the mix, but keep the low end centered.
+///     commands.spawn((
+///         MainBus,
+///         StereoWidthNode {
+///             width: 1.25,
+///             mono_below_hz: 120.0,
+///         },
+///     ));
+/// }
+/// ```
+#[derive(Diff, Patch, Debug, Clone, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct StereoWidthNode {
+    /// The stereo width.
+    ///
+    /// `0.0` collapses the signal to mono, `1.0` leaves it untouched,
+    /// and values above `1.0` exaggerate the difference between channels.
+    ///
+    /// By default, this is `1.0`.
+    pub width: f32,
+    /// Frequencies below this cutoff are collapsed to mono, in hertz.
+    ///
+    /// Keeping the low end centered is common when mastering,
+    /// since wide bass tends to sound muddy and translates poorly
+    /// to mono playback systems. A value of `0.0` disables this.
+    ///
+    /// By default, this is `0.0`.
+    pub mono_below_hz: f32,
+}
+
+impl StereoWidthNode {
+    /// Create a new [`StereoWidthNode`] with the given width.
+    pub fn new(width: f32) -> Self {
+        Self {
+            width,
+            mono_below_hz: 0.0,
+        }
+    }
+}
+
+impl Default for StereoWidthNode {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl AudioNode for StereoWidthNode {
+    type Configuration = EmptyConfig;
+
+    fn info(&self, _: &Self::Configuration) -> Result<AudioNodeInfo, NodeError> {
+        Ok(AudioNodeInfo::new()
+            .debug_name("stereo width")
+            .channel_config(ChannelConfig {
+                num_inputs: ChannelCount::STEREO,
+                num_outputs: ChannelCount::STEREO,
+            }))
+    }
+
+    fn construct_processor(
+        &self,
+        _: &Self::Configuration,
+        cx: ConstructProcessorContext,
+    ) -> Result<impl AudioNodeProcessor, NodeError> {
+        Ok(StereoWidthProcessor {
+            params: self.clone(),
+            width: StereoWidth::new(self.width, self.mono_below_hz, cx.stream_info.sample_rate),
+            sample_rate: cx.stream_info.sample_rate,
+        })
+    }
+}
+
+/// The mid/side DSP, separated from the processor for testing.
+struct StereoWidth {
+    width: SmoothingFilter,
+    target_times_a: f32,
+    coeff: SmoothingFilterCoeff,
+    /// One-pole lowpass coefficient for the side channel.
+    crossover: f32,
+    side_low: OnePoleLowpass,
+}
+
+impl StereoWidth {
+    fn new(width: f32, mono_below_hz: f32, sample_rate: NonZeroU32) -> Self {
+        let coeff = SmoothingFilterCoeff::new(sample_rate, SMOOTH_SECS);
+
+        Self {
+            width: SmoothingFilter::new(width),
+            target_times_a: width * coeff.a0,
+            coeff,
+            crossover: lowpass_coeff(mono_below_hz, sample_rate),
+            side_low: OnePoleLowpass::default(),
+        }
+    }
+
+    fn set_width(&mut self, width: f32) {
+        self.target_times_a = width * self.coeff.a0;
+    }
+
+    fn set_sample_rate(&mut self, mono_below_hz: f32, sample_rate: NonZeroU32) {
+        let target = self.target_times_a / self.coeff.a0;
+        self.coeff = SmoothingFilterCoeff::new(sample_rate, SMOOTH_SECS);
+        self.target_times_a = target * self.coeff.a0;
+        self.crossover = lowpass_coeff(mono_below_hz, sample_rate);
+        self.side_low.reset();
+    }
+
+    #[inline]
+    fn process(&mut self, left: f32, right: f32) -> (f32, f32) {
+        let width = self
+            .width
+            .process_sample_a(self.target_times_a, self.coeff.b1);
+
+        let mid = (left + right) * 0.5;
+        let mut side = (left - right) * 0.5;
+
+        if self.crossover > 0.0 {
+            side -= self.side_low.process(side, self.crossover);
+        }
+
+        let side = side * width;
+
+        (mid + side, mid - side)
+    }
+}
+
+struct StereoWidthProcessor {
+    params: StereoWidthNode,
+    width: StereoWidth,
+    sample_rate: NonZeroU32,
+}
+
+impl AudioNodeProcessor for StereoWidthProcessor {
+    fn events(&mut self, _info: &ProcInfo, events: &mut ProcEvents, _extra: &mut ProcExtra) {
+        for patch in events.drain_patches::<StereoWidthNode>() {
+            self.params.apply(patch);
+        }
+
+        self.width.set_width(self.params.width);
+        self.width.crossover = lowpass_coeff(self.params.mono_below_hz, self.sample_rate);
+    }
+
+    fn process(
+        &mut self,
+        proc_info: &ProcInfo,
+        ProcBuffers { inputs, outputs }: ProcBuffers,
+        _: &mut ProcExtra,
+    ) -> ProcessStatus {
+        if proc_info.in_silence_mask.all_channels_silent(2) {
+            self.width.side_low.reset();
+            return ProcessStatus::ClearAllOutputs;
+        }
+
+        let in_left = &inputs[0][..proc_info.frames];
+        let in_right = &inputs[1][..proc_info.frames];
+
+        let (out_left, rest) = outputs.split_first_mut().unwrap();
+        let out_left = &mut out_left[..proc_info.frames];
+        let out_right = &mut rest[0][..proc_info.frames];
+
+        for frame in 0..proc_info.frames {
+            let (left, right) = self.width.process(in_left[frame], in_right[frame]);
+            out_left[frame] = left;
+            out_right[frame] = right;
+        }
+
+        ProcessStatus::OutputsModified
+    }
+
+    fn new_stream(&mut self, stream_info: &firewheel::StreamInfo, _: &mut ProcStreamCtx) {
+        self.sample_rate = stream_info.sample_rate;
+        self.width
+            .set_sample_rate(self.params.mono_below_hz, stream_info.sample_rate);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_RATE: NonZeroU32 = NonZeroU32::new(48000).unwrap();
+
+    #[test]
+    fn test_unity_width() {
+        let mut width = StereoWidth::new(1.0, 0.0, SAMPLE_RATE);
+
+        let (left, right) = width.process(0.75, -0.25);
+        assert!((left - 0.75).abs() < 1e-6);
+        assert!((right + 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_zero_width() {
+        let mut width = StereoWidth::new(0.0, 0.0, SAMPLE_RATE);
+
+        let (left, right) = width.process(1.0, 0.0);
+        assert_eq!(left, right);
+        assert!((left - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_mono_below() {
+        let mut width = StereoWidth::new(1.0, 200.0, SAMPLE_RATE);
+
+        // A constant (DC) side signal should eventually collapse to mono.
+        let mut output = (0.0, 0.0);
+        for _ in 0..48000 {
+            output = width.process(1.0, -1.0);
+        }
+
+        assert!(output.0.abs() < 1e-3);
+        assert!(output.1.abs() < 1e-3);
+    }
+}
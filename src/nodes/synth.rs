@@ -0,0 +1,418 @@
+//! A small polyphonic synthesizer.
+
+use bevy_ecs::component::Component;
+use firewheel::{
+    Volume,
+    channel_config::{ChannelConfig, ChannelCount, NonZeroChannelCount},
+    diff::{Diff, Patch},
+    event::{NodeEventType, ProcEvents},
+    node::{
+        AudioNode, AudioNodeInfo, AudioNodeProcessor, ConstructProcessorContext, NodeError,
+        ProcBuffers, ProcExtra, ProcInfo, ProcStreamCtx, ProcessStatus,
+    },
+};
+
+/// A polyphonic synthesizer with basic waveforms and FM.
+///
+/// Notes are played by queueing [`SynthNote`] events, which makes
+/// it easy to generate jingles and dynamic stingers without samples.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn spawn_synth(mut commands: Commands) {
+///     commands.spawn(SynthNode {
+///         waveform: Waveform::Triangle,
+///         fm_index: 1.5,
+///         ..Default::default()
+///     });
+/// }
+///
+/// fn play_chord(mut synth: Single<&mut AudioEvents, With<SynthNode>>) {
+///     for note in [60, 64, 67] {
+///         synth.push_custom(SynthNote::On { note, velocity: 0.8 });
+///     }
+/// }
+///
+/// fn release(mut synth: Single<&mut AudioEvents, With<SynthNode>>) {
+///     synth.push_custom(SynthNote::AllOff);
+/// }
+/// ```
+///
+/// Each voice passes its [`waveform`][SynthNode::waveform] through
+/// a sine modulator. When [`fm_index`][SynthNode::fm_index] is zero,
+/// the modulator is disabled.
+#[derive(Diff, Patch, Debug, Clone, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct SynthNode {
+    /// The output volume.
+    ///
+    /// By default, this is [`Volume::UNITY_GAIN`].
+    pub volume: Volume,
+    /// The carrier's waveform.
+    ///
+    /// By default, this is [`Waveform::Sine`].
+    pub waveform: Waveform,
+    /// The modulator's frequency as a ratio of each note's frequency.
+    ///
+    /// By default, this is `2.0`.
+    pub fm_ratio: f32,
+    /// The depth of frequency modulation.
+    ///
+    /// By default, this is `0.0`.
+    pub fm_index: f32,
+    /// The time in seconds for a note to reach full volume.
+    ///
+    /// By default, this is `0.005`.
+    pub attack: f32,
+    /// The time in seconds for a released note to fall silent.
+    ///
+    /// By default, this is `0.2`.
+    pub release: f32,
+}
+
+impl Default for SynthNode {
+    fn default() -> Self {
+        Self {
+            volume: Volume::UNITY_GAIN,
+            waveform: Waveform::Sine,
+            fm_ratio: 2.0,
+            fm_index: 0.0,
+            attack: 0.005,
+            release: 0.2,
+        }
+    }
+}
+
+/// The basic waveforms of a [`SynthNode`].
+///
+/// Sawtooth and square waves are band-limited
+/// with PolyBLEP to reduce aliasing.
+#[derive(Diff, Patch, Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum Waveform {
+    /// A pure tone.
+    #[default]
+    Sine,
+    /// A soft, hollow tone.
+    Triangle,
+    /// A bright, buzzy tone.
+    Sawtooth,
+    /// A reedy tone.
+    Square,
+}
+
+/// A note event for a [`SynthNode`].
+///
+/// These are queued with [`AudioEvents::push_custom`].
+///
+/// [`AudioEvents::push_custom`]: crate::prelude::AudioEvents::push_custom
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SynthNote {
+    /// Start playing a MIDI note number.
+    ///
+    /// If every voice is busy, the oldest is stolen.
+    On {
+        /// The MIDI note number, where `69` is A4 at 440 Hz.
+        note: u8,
+        /// The note's volume, from `0.0` to `1.0`.
+        velocity: f32,
+    },
+    /// Release a MIDI note number.
+    Off {
+        /// The MIDI note number.
+        note: u8,
+    },
+    /// Release every playing note.
+    AllOff,
+}
+
+/// Configuration for a [`SynthNode`].
+#[derive(Debug, Clone, Component, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct SynthConfig {
+    /// The number of output channels.
+    ///
+    /// The synth is mono, so every channel receives the same signal.
+    /// By default, this is stereo.
+    pub channels: NonZeroChannelCount,
+    /// The maximum number of simultaneous notes.
+    ///
+    /// By default, this is `8`.
+    pub voices: usize,
+}
+
+impl Default for SynthConfig {
+    fn default() -> Self {
+        Self {
+            channels: NonZeroChannelCount::STEREO,
+            voices: 8,
+        }
+    }
+}
+
+impl AudioNode for SynthNode {
+    type Configuration = SynthConfig;
+
+    fn info(&self, config: &Self::Configuration) -> Result<AudioNodeInfo, NodeError> {
+        Ok(AudioNodeInfo::new()
+            .debug_name("synth")
+            .channel_config(ChannelConfig {
+                num_inputs: ChannelCount::ZERO,
+                num_outputs: config.channels.get(),
+            }))
+    }
+
+    fn construct_processor(
+        &self,
+        config: &Self::Configuration,
+        cx: ConstructProcessorContext,
+    ) -> Result<impl AudioNodeProcessor, NodeError> {
+        Ok(SynthProcessor {
+            params: self.clone(),
+            voices: vec![Voice::default(); config.voices.max(1)].into(),
+            sample_rate: cx.stream_info.sample_rate.get() as f32,
+            started: 0,
+        })
+    }
+}
+
+/// Convert a MIDI note number to its frequency in Hz.
+fn note_frequency(note: u8) -> f32 {
+    440.0 * 2f32.powf((note as f32 - 69.0) / 12.0)
+}
+
+/// The PolyBLEP residual for a discontinuity at phase `0.0`.
+fn poly_blep(phase: f32, dt: f32) -> f32 {
+    if phase < dt {
+        let t = phase / dt;
+        t + t - t * t - 1.0
+    } else if phase > 1.0 - dt {
+        let t = (phase - 1.0) / dt;
+        t * t + t + t + 1.0
+    } else {
+        0.0
+    }
+}
+
+impl Waveform {
+    /// Evaluate the waveform at `phase` in the range `0.0..1.0`,
+    /// where `dt` is the phase increment per frame.
+    fn sample(&self, phase: f32, dt: f32) -> f32 {
+        match self {
+            Self::Sine => (phase * core::f32::consts::TAU).sin(),
+            Self::Triangle => 1.0 - 4.0 * (phase - 0.5).abs(),
+            Self::Sawtooth => 2.0 * phase - 1.0 - poly_blep(phase, dt),
+            Self::Square => {
+                let square = if phase < 0.5 { 1.0 } else { -1.0 };
+                square + poly_blep(phase, dt) - poly_blep((phase + 0.5).fract(), dt)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Voice {
+    note: u8,
+    velocity: f32,
+    /// The order in which this voice started, for stealing.
+    started: u64,
+    phase: f32,
+    mod_phase: f32,
+    level: f32,
+    releasing: bool,
+    active: bool,
+}
+
+impl Voice {
+    fn start(&mut self, note: u8, velocity: f32, started: u64) {
+        // A stolen voice keeps its level to avoid clicking.
+        let level = if self.active { self.level } else { 0.0 };
+
+        *self = Self {
+            note,
+            velocity: velocity.clamp(0.0, 1.0),
+            started,
+            level,
+            active: true,
+            ..Default::default()
+        };
+    }
+
+    /// Render the next frame, advancing the voice's phase and envelope.
+    fn next(&mut self, params: &SynthNode, sample_rate: f32) -> f32 {
+        if self.releasing {
+            self.level -= 1.0 / (params.release.max(0.0) * sample_rate).max(1.0);
+            if self.level <= 0.0 {
+                self.active = false;
+                return 0.0;
+            }
+        } else if self.level < 1.0 {
+            self.level =
+                (self.level + 1.0 / (params.attack.max(0.0) * sample_rate).max(1.0)).min(1.0);
+        }
+
+        let frequency = note_frequency(self.note);
+        let dt = (frequency / sample_rate).min(0.5);
+
+        let mut phase = self.phase;
+        if params.fm_index != 0.0 {
+            let modulator = (self.mod_phase * core::f32::consts::TAU).sin();
+            phase = (phase + params.fm_index * modulator / core::f32::consts::TAU).rem_euclid(1.0);
+
+            self.mod_phase = (self.mod_phase + dt * params.fm_ratio).rem_euclid(1.0);
+        }
+
+        let output = params.waveform.sample(phase, dt) * self.level * self.velocity;
+        self.phase = (self.phase + dt).fract();
+
+        output
+    }
+}
+
+struct SynthProcessor {
+    params: SynthNode,
+    voices: Box<[Voice]>,
+    sample_rate: f32,
+    /// The number of notes started, for voice stealing.
+    started: u64,
+}
+
+impl SynthProcessor {
+    fn handle_note(&mut self, note: SynthNote) {
+        match note {
+            SynthNote::On { note, velocity } => {
+                self.started += 1;
+
+                let voice = match self.voices.iter().position(|v| !v.active) {
+                    Some(free) => free,
+                    None => self
+                        .voices
+                        .iter()
+                        .enumerate()
+                        .min_by_key(|(_, v)| v.started)
+                        .map(|(i, _)| i)
+                        .unwrap_or_default(),
+                };
+
+                self.voices[voice].start(note, velocity, self.started);
+            }
+            SynthNote::Off { note } => {
+                for voice in self.voices.iter_mut().filter(|v| v.note == note) {
+                    voice.releasing = true;
+                }
+            }
+            SynthNote::AllOff => {
+                for voice in self.voices.iter_mut() {
+                    voice.releasing = true;
+                }
+            }
+        }
+    }
+}
+
+impl AudioNodeProcessor for SynthProcessor {
+    fn events(&mut self, _info: &ProcInfo, events: &mut ProcEvents, _extra: &mut ProcExtra) {
+        for patch in events.drain_patches::<SynthNode>() {
+            self.params.apply(patch);
+        }
+
+        for event in events.drain() {
+            if let NodeEventType::Custom(mut event) = event
+                && let Some(note) = event.downcast_mut::<SynthNote>()
+            {
+                self.handle_note(*note);
+            }
+        }
+    }
+
+    fn process(
+        &mut self,
+        proc_info: &ProcInfo,
+        ProcBuffers { outputs, .. }: ProcBuffers,
+        _: &mut ProcExtra,
+    ) -> ProcessStatus {
+        if !self.voices.iter().any(|v| v.active) {
+            return ProcessStatus::ClearAllOutputs;
+        }
+
+        let gain = self.params.volume.linear();
+        let (first, rest) = outputs.split_first_mut().unwrap();
+
+        for frame in first[..proc_info.frames].iter_mut() {
+            let mut sum = 0.0;
+            for voice in self.voices.iter_mut().filter(|v| v.active) {
+                sum += voice.next(&self.params, self.sample_rate);
+            }
+
+            *frame = sum * gain;
+        }
+
+        for output in rest {
+            output[..proc_info.frames].copy_from_slice(&first[..proc_info.frames]);
+        }
+
+        ProcessStatus::OutputsModified
+    }
+
+    fn new_stream(&mut self, stream_info: &firewheel::StreamInfo, _: &mut ProcStreamCtx) {
+        self.sample_rate = stream_info.sample_rate.get() as f32;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_note_frequency() {
+        assert_eq!(note_frequency(69), 440.0);
+        assert!((note_frequency(81) - 880.0).abs() < 1e-3);
+        assert!((note_frequency(60) - 261.63).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_voice_envelope() {
+        let params = SynthNode {
+            attack: 0.01,
+            release: 0.01,
+            ..Default::default()
+        };
+        let sample_rate = 1000.0;
+
+        let mut voice = Voice::default();
+        voice.start(69, 1.0, 1);
+
+        for _ in 0..10 {
+            voice.next(&params, sample_rate);
+        }
+        assert_eq!(voice.level, 1.0);
+
+        voice.releasing = true;
+        for _ in 0..11 {
+            voice.next(&params, sample_rate);
+        }
+        assert!(!voice.active);
+    }
+
+    #[test]
+    fn test_voice_stealing() {
+        let mut synth = SynthProcessor {
+            params: SynthNode::default(),
+            voices: vec![Voice::default(); 2].into(),
+            sample_rate: 48000.0,
+            started: 0,
+        };
+
+        for note in [60, 62, 64] {
+            synth.handle_note(SynthNote::On {
+                note,
+                velocity: 1.0,
+            });
+        }
+
+        let mut notes: Vec<_> = synth.voices.iter().map(|v| v.note).collect();
+        notes.sort();
+        assert_eq!(notes, [62, 64]);
+    }
+}
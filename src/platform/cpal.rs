@@ -3,11 +3,15 @@
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 use bevy_log::{error, warn};
-use firewheel::cpal::{self};
+use firewheel::{
+    FirewheelContext,
+    cpal::{self},
+};
+use std::num::NonZeroU32;
 
 use crate::{
     SeedlingSystems,
-    context::{AudioContext, SampleRate, StreamRestartEvent},
+    context::{AudioContext, DspLoad, LocalStore, SampleRate},
     platform::*,
     prelude::SeedlingStartupSystems,
     resource_changed_without_insert,
@@ -41,6 +45,14 @@ impl Plugin for CpalPlatformPlugin {
             return;
         }
 
+        #[cfg(not(target_arch = "wasm32"))]
+        if app
+            .world()
+            .contains_resource::<AudioStreamConfig<crate::platform::null::NullConfig>>()
+        {
+            return;
+        }
+
         app.init_resource::<AudioStreamConfig<CpalConfig>>()
             .add_systems(
                 PostStartup,
@@ -48,44 +60,159 @@ impl Plugin for CpalPlatformPlugin {
             )
             .add_systems(
                 PostUpdate,
-                (crate::context::pre_restart_stream, restart_stream)
+                (
+                    super::begin_stream_switch,
+                    (crate::context::pre_restart_stream, start_stream)
+                        .chain()
+                        .run_if(super::stream_switch_ready),
+                )
                     .chain()
-                    .run_if(resource_changed_without_insert::<AudioStreamConfig<CpalConfig>>),
+                    .run_if(resource_changed_without_insert::<AudioStreamConfig<CpalConfig>>)
+                    .run_if(super::stream_active),
+            )
+            .add_systems(
+                Last,
+                (poll_stream, start_stream.run_if(super::stream_retry_due))
+                    .in_set(SeedlingSystems::PollStream),
             )
-            .add_systems(Last, poll_stream.in_set(SeedlingSystems::PollStream))
             .add_observer(observe_restart);
     }
 }
 
+impl AudioStreamConfig<CpalConfig> {
+    /// Select a specific `cpal` host for the output stream.
+    ///
+    /// Some hosts offer significantly lower latency than the system
+    /// default, like ASIO on Windows or JACK on Linux. These require
+    /// the `asio` and `jack` features, respectively.
+    ///
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// # use bevy_seedling::platform::cpal::{CpalConfig, available_hosts};
+    /// # #[cfg(all(target_os = "linux", feature = "jack"))]
+    /// # fn plugin(app: &mut App) {
+    /// use firewheel::cpal::cpal::HostId;
+    ///
+    /// if available_hosts().contains(&HostId::Jack) {
+    ///     app.insert_resource(AudioStreamConfig(CpalConfig::default()).with_host(HostId::Jack));
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// If the host is unavailable, stream initialization will fail.
+    /// Use [`available_hosts`] to check first.
+    pub fn with_host(mut self, host: cpal::cpal::HostId) -> Self {
+        self.0.output.host = Some(host);
+        self
+    }
+
+    /// Request a specific block size for the output stream, in frames.
+    ///
+    /// Smaller blocks reduce latency at the cost of higher CPU overhead
+    /// and a greater risk of underruns. For rhythm games, 128 or 256
+    /// frames is a reasonable starting point. The host may not honor the
+    /// request exactly.
+    ///
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// # use bevy_seedling::platform::cpal::CpalConfig;
+    /// # fn plugin(app: &mut App) {
+    /// app.insert_resource(AudioStreamConfig(CpalConfig::default()).with_block_frames(128));
+    /// # }
+    /// ```
+    pub fn with_block_frames(mut self, frames: u32) -> Self {
+        self.0.output.desired_block_frames = Some(frames);
+        self
+    }
+}
+
+/// Returns the `cpal` hosts available on this platform.
+///
+/// The set of hosts depends on both the platform and the enabled
+/// features. For example, [`HostId::Asio`][cpal::cpal::HostId] is only
+/// present on Windows with the `asio` feature.
+pub fn available_hosts() -> Vec<cpal::cpal::HostId> {
+    cpal::cpal::available_hosts()
+}
+
 fn start_stream(
     mut context: ResMut<AudioContext>,
     stream_config: Res<AudioStreamConfig<CpalConfig>>,
-    commands: Commands,
-) -> Result {
-    // TODO: it's not possible for the user to recover if this fails
-    let sample_rate = context.with_store(|context, store| {
-        let stream = cpal::CpalStream::new(context, stream_config.0.clone())?;
-        let sample_rate = stream.info().sample_rate;
+    sample_rate: Option<Res<SampleRate>>,
+    mut commands: Commands,
+) {
+    // drop it like it's hot
+    let config = stream_config.0.clone();
+    match context.try_with_store(move |context, store| open_stream(context, store, config)) {
+        Ok(Ok(current_rate)) => {
+            super::stream_available(current_rate, sample_rate.as_deref(), &mut commands);
+        }
+        Ok(Err(error)) => super::stream_unavailable(error, &mut commands),
+        // A dead audio thread is reported by the context plugin.
+        Err(_) => {}
+    }
+}
 
-        let previous = store.insert(stream);
-        debug_assert!(previous.is_none());
+/// Start a new stream, replacing any existing one.
+///
+/// If the configured host has no output devices, this falls
+/// back to the [`NullBackendPlugin`][crate::platform::null::NullBackendPlugin]'s stream.
+fn open_stream(
+    context: &mut FirewheelContext,
+    store: &mut LocalStore,
+    config: CpalConfig,
+) -> Result<NonZeroU32> {
+    let _ = store.remove::<cpal::CpalStream>();
 
-        Ok::<_, StartStreamError>(sample_rate)
-    })?;
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        use crate::platform::null::{NullConfig, NullStream};
 
-    super::initialize_stream(SampleRate::new(sample_rate), commands);
+        let _ = store.remove::<NullStream>();
 
-    Ok(())
+        if !has_output_device(&config) {
+            warn!("no audio output devices found, falling back to the null backend");
+
+            let null_config = NullConfig::default();
+            NullStream::restart(context, store, &null_config)?;
+            return Ok(null_config.sample_rate);
+        }
+    }
+
+    let stream = cpal::CpalStream::new(context, config)?;
+    let sample_rate = stream.info().sample_rate;
+    store.insert_stream(stream);
+
+    Ok(sample_rate)
 }
 
-fn poll_stream(mut context: ResMut<AudioContext>, mut commands: Commands) -> Result {
-    let errors = context.with_store(|_, store| {
+#[cfg(not(target_arch = "wasm32"))]
+fn has_output_device(config: &CpalConfig) -> bool {
+    use cpal::cpal::traits::HostTrait;
+
+    let host = match config.output.host {
+        Some(id) => cpal::cpal::host_from_id(id).ok(),
+        None => Some(cpal::cpal::default_host()),
+    };
+
+    host.is_some_and(|host| host.default_output_device().is_some())
+}
+
+fn poll_stream(
+    mut context: ResMut<AudioContext>,
+    mut load: ResMut<DspLoad>,
+    mut commands: Commands,
+) -> Result {
+    // A dead audio thread is reported by the context plugin.
+    let errors = context.try_with_store(|_, store| {
         store
             .get_mut::<cpal::CpalStream>()
             .map(|stream| stream.poll_status().collect::<Vec<_>>())
     });
 
-    for error in errors.into_iter().flatten() {
+    for error in errors.into_iter().flatten().flatten() {
         match error {
             IoStreamError::Input(error) => match error.kind() {
                 // nothing to do here
@@ -109,6 +236,7 @@ fn poll_stream(mut context: ResMut<AudioContext>, mut commands: Commands) -> Res
                 // nothing to do here
                 ErrorKind::DeviceChanged => {}
                 ErrorKind::Xrun => {
+                    load.record_underrun();
                     warn!("audio output stream encountered underrun or overrun");
                 }
                 ErrorKind::StreamInvalidated
@@ -136,31 +264,3 @@ fn poll_stream(mut context: ResMut<AudioContext>, mut commands: Commands) -> Res
 fn observe_restart(_: On<RestartAudioStream>, mut config: ResMut<AudioStreamConfig<CpalConfig>>) {
     config.set_changed();
 }
-
-fn restart_stream(
-    stream_config: Res<AudioStreamConfig<CpalConfig>>,
-    mut graph: ResMut<AudioContext>,
-    sample_rate: Res<SampleRate>,
-    mut commands: Commands,
-) -> Result {
-    // drop it like it's hot
-    let current_rate = graph.with_store(|context, store| {
-        let _ = store.remove::<cpal::CpalStream>();
-
-        let stream = cpal::CpalStream::new(context, stream_config.0.clone())?;
-        let sample_rate = stream.info().sample_rate;
-        store.insert(stream);
-
-        Ok::<_, StartStreamError>(sample_rate)
-    })?;
-
-    let previous_rate = sample_rate.get();
-    sample_rate.set(current_rate);
-
-    commands.trigger(StreamRestartEvent {
-        previous_rate,
-        current_rate,
-    });
-
-    Ok(())
-}
@@ -1,8 +1,23 @@
 //! Components that abstract over different backends.
 
+use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
+use bevy_log::warn;
+use bevy_platform::time::Instant;
+use core::{fmt::Display, num::NonZeroU32, time::Duration};
+use firewheel::{Volume, nodes::volume::VolumeNode};
 
-use crate::context::{SampleRate, StreamStartEvent};
+use crate::{
+    SeedlingSystems,
+    context::{AudioContext, SampleRate, StreamRestartEvent, StreamStartEvent},
+    node::{
+        Baseline,
+        events::{AudioEvents, AudioLerp},
+        generate_param_events,
+        label::MainBus,
+        mute::{AudioMute, push_volume, update_muted},
+    },
+};
 
 #[cfg(feature = "cpal")]
 pub mod cpal;
@@ -13,8 +28,31 @@ pub mod rtaudio;
 #[cfg(feature = "web_audio")]
 pub mod web_audio;
 
-#[cfg(any(feature = "profiling", test))]
-pub mod mock;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod null;
+
+pub(crate) struct PlatformPlugin;
+
+impl Plugin for PlatformPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StreamRetryInterval>()
+            .init_resource::<StreamSwitchFade>()
+            .init_resource::<StreamSwitch>()
+            .add_systems(
+                Last,
+                (
+                    restart_on_resume
+                        .run_if(resource_exists::<AudioContext>)
+                        .before(SeedlingSystems::PollStream),
+                    update_stream_switch
+                        .after(update_muted::<VolumeNode>)
+                        .before(generate_param_events::<VolumeNode>),
+                ),
+            )
+            .add_observer(observe_restart)
+            .add_observer(fade_in_stream);
+    }
+}
 
 /// A [`Resource`] containing the audio context's stream configuration.
 ///
@@ -54,3 +92,445 @@ pub fn initialize_stream(sample_rate: SampleRate, mut commands: Commands) {
         sample_rate: raw_sample_rate,
     });
 }
+
+/// Triggered globally when the audio stream fails to start.
+///
+/// Rather than panicking, `bevy_seedling` enters a deactivated state
+/// where the audio graph can still be freely modified. Connections,
+/// nodes, and samples are applied once a stream is available.
+///
+/// The stream is retried according to [`StreamRetryInterval`]
+/// or when [`RestartAudioStream`] is triggered. This event is
+/// triggered for each failed attempt.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::platform::AudioUnavailable;
+/// fn notify_player(unavailable: On<AudioUnavailable>) {
+///     warn!("audio is unavailable: {}", unavailable.error);
+/// }
+/// ```
+#[derive(Event, Debug, Clone)]
+pub struct AudioUnavailable {
+    /// A description of the error that prevented the stream from starting.
+    pub error: String,
+}
+
+/// A [`Resource`] determining how often a failed audio stream is retried.
+///
+/// When `None`, the stream is only retried when
+/// [`RestartAudioStream`] is triggered.
+///
+/// Defaults to five seconds.
+#[derive(Resource, Debug, Clone)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct StreamRetryInterval(pub Option<Duration>);
+
+impl Default for StreamRetryInterval {
+    fn default() -> Self {
+        Self(Some(Duration::from_secs(5)))
+    }
+}
+
+/// A [`Resource`] that fades the output out and back in
+/// when the audio stream restarts.
+///
+/// Switching devices through [`AudioStreamConfig`] tears down the
+/// stream, which can produce a loud pop. With a duration set, the
+/// [`MainBus`] fades out before the stream is torn down and fades
+/// back in once the new stream starts.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::platform::StreamSwitchFade;
+/// # use core::time::Duration;
+/// fn plugin(app: &mut App) {
+///     app.insert_resource(StreamSwitchFade(Some(Duration::from_millis(50))));
+/// }
+/// ```
+///
+/// Restarts the backend didn't ask for, like a lost device, can't fade
+/// out, but the new stream still fades in. Only a [`MainBus`] with
+/// a [`VolumeNode`] is faded.
+///
+/// The fade is applied to the audio side only, so the node's
+/// `volume` field is never touched. Changes made during the fade,
+/// such as from [`AudioSettings`] or [`AudioMute`], are kept.
+///
+/// [`AudioSettings`]: crate::settings::AudioSettings
+///
+/// Defaults to `None`, restarting the stream immediately.
+#[derive(Resource, Debug, Clone, Default)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct StreamSwitchFade(pub Option<Duration>);
+
+/// The progress of a faded stream switch.
+///
+/// The fades are driven by the wall clock since the
+/// audio clock restarts along with the stream.
+#[derive(Resource, Debug, Default)]
+pub(crate) enum StreamSwitch {
+    #[default]
+    Idle,
+    FadingOut {
+        start: Instant,
+    },
+    Restarting,
+    FadingIn {
+        start: Instant,
+    },
+}
+
+/// The volume most recently sent to a [`MainBus`] during a stream switch.
+///
+/// The fade is applied on the audio side only, like [`AudioMute`],
+/// so the node's `volume` field is never touched.
+#[derive(Component, Debug)]
+struct SwitchLevel(Volume);
+
+type MainBusQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static VolumeNode,
+        &'static mut Baseline<VolumeNode>,
+        &'static mut AudioEvents,
+        Option<&'static SwitchLevel>,
+        Has<AudioMute>,
+    ),
+    With<MainBus>,
+>;
+
+/// Send each main bus's faded `volume` to its audio side.
+///
+/// Keeping the baseline in sync prevents the diffing systems from
+/// sending the full volume mid-fade, while any changes to `volume`
+/// are still picked up by the fade.
+fn send_switch_level(
+    main_bus: &mut MainBusQuery,
+    level: impl Fn(Volume) -> Volume,
+    commands: &mut Commands,
+) {
+    for (entity, node, mut baseline, mut events, sent, muted) in main_bus {
+        let target = if muted {
+            Volume::SILENT
+        } else {
+            level(node.volume)
+        };
+        let sent = match sent {
+            Some(sent) => sent.0,
+            None if muted => Volume::SILENT,
+            None => node.volume,
+        };
+
+        if sent != target {
+            push_volume(node, sent, target, &mut events);
+        }
+
+        if baseline.0.volume != node.volume {
+            baseline.0.volume = node.volume;
+        }
+
+        commands.entity(entity).insert(SwitchLevel(target));
+    }
+}
+
+/// Begin fading out ahead of a stream restart.
+///
+/// This should run just before a backend restarts its stream
+/// in response to a changed [`AudioStreamConfig`], with the restart
+/// itself gated by [`stream_switch_ready`].
+pub(crate) fn begin_stream_switch(fade: Res<StreamSwitchFade>, mut switch: ResMut<StreamSwitch>) {
+    if fade.0.is_none() || !matches!(*switch, StreamSwitch::Idle) {
+        return;
+    }
+
+    *switch = StreamSwitch::FadingOut {
+        start: Instant::now(),
+    };
+}
+
+/// A run condition that returns `false` while the output fades out.
+pub(crate) fn stream_switch_ready(switch: Res<StreamSwitch>) -> bool {
+    !matches!(*switch, StreamSwitch::FadingOut { .. })
+}
+
+fn update_stream_switch(
+    fade: Res<StreamSwitchFade>,
+    mut switch: ResMut<StreamSwitch>,
+    mut main_bus: MainBusQuery,
+    mut commands: Commands,
+) {
+    let duration = fade.0.unwrap_or_default();
+    let progress =
+        |start: &Instant| (start.elapsed().as_secs_f32() / duration.as_secs_f32()).min(1.0);
+
+    match &*switch {
+        StreamSwitch::Idle => {}
+        StreamSwitch::FadingOut { start } => {
+            let progress = progress(start);
+            send_switch_level(
+                &mut main_bus,
+                |volume| volume.audio_lerp(Volume::SILENT, progress),
+                &mut commands,
+            );
+
+            if progress >= 1.0 {
+                // Restarting sets the stream's config as changed,
+                // and the observer moves us into `Restarting`.
+                commands.trigger(RestartAudioStream);
+            }
+        }
+        StreamSwitch::Restarting => {
+            send_switch_level(&mut main_bus, |_| Volume::SILENT, &mut commands);
+        }
+        StreamSwitch::FadingIn { start } => {
+            let progress = progress(start);
+            send_switch_level(
+                &mut main_bus,
+                |volume| Volume::SILENT.audio_lerp(volume, progress),
+                &mut commands,
+            );
+
+            if progress >= 1.0 {
+                *switch = StreamSwitch::Idle;
+                for (entity, ..) in &main_bus {
+                    commands.entity(entity).remove::<SwitchLevel>();
+                }
+            }
+        }
+    }
+}
+
+fn fade_in_stream(
+    _: On<StreamRestartEvent>,
+    fade: Res<StreamSwitchFade>,
+    mut switch: ResMut<StreamSwitch>,
+    mut main_bus: MainBusQuery,
+    mut commands: Commands,
+) {
+    if !matches!(*switch, StreamSwitch::Restarting) {
+        return;
+    }
+
+    if fade.0.is_some_and(|d| !d.is_zero()) {
+        *switch = StreamSwitch::FadingIn {
+            start: Instant::now(),
+        };
+    } else {
+        *switch = StreamSwitch::Idle;
+        send_switch_level(&mut main_bus, |volume| volume, &mut commands);
+        for (entity, ..) in &main_bus {
+            commands.entity(entity).remove::<SwitchLevel>();
+        }
+    }
+}
+
+/// Inserted while the audio stream is unavailable.
+#[derive(Resource, Debug)]
+pub(crate) struct PendingStreamRetry {
+    last_attempt: Instant,
+    immediate: bool,
+}
+
+/// Bookkeeping for a failed stream start.
+///
+/// This enters the deactivated state, scheduling a retry
+/// and triggering [`AudioUnavailable`].
+pub(crate) fn stream_unavailable(error: impl Display, commands: &mut Commands) {
+    let error = error.to_string();
+    warn!("audio stream unavailable: {error}");
+
+    commands.insert_resource(PendingStreamRetry {
+        last_attempt: Instant::now(),
+        immediate: false,
+    });
+    commands.trigger(AudioUnavailable { error });
+}
+
+/// Bookkeeping for a successful stream start or restart.
+///
+/// If the stream has never started, this calls [`initialize_stream`].
+/// Otherwise, [`StreamRestartEvent`] is triggered.
+pub(crate) fn stream_available(
+    current_rate: NonZeroU32,
+    sample_rate: Option<&SampleRate>,
+    commands: &mut Commands,
+) {
+    commands.remove_resource::<PendingStreamRetry>();
+
+    match sample_rate {
+        Some(sample_rate) => {
+            let previous_rate = sample_rate.get();
+            sample_rate.set(current_rate);
+
+            commands.trigger(StreamRestartEvent {
+                previous_rate,
+                current_rate,
+            });
+        }
+        None => initialize_stream(SampleRate::new(current_rate), commands.reborrow()),
+    }
+}
+
+/// A run condition that returns `true` when a failed stream should be retried.
+pub(crate) fn stream_retry_due(
+    retry: Option<Res<PendingStreamRetry>>,
+    interval: Res<StreamRetryInterval>,
+    context: Res<AudioContext>,
+) -> bool {
+    if context.is_suspended() {
+        return false;
+    }
+
+    retry.is_some_and(|retry| {
+        retry.immediate
+            || interval
+                .0
+                .is_some_and(|interval| retry.last_attempt.elapsed() >= interval)
+    })
+}
+
+/// A run condition that returns `true` when the audio stream isn't suspended.
+///
+/// See [`AudioContext::suspend`] for more details.
+pub fn stream_active(context: Res<AudioContext>) -> bool {
+    !context.is_suspended()
+}
+
+fn restart_on_resume(
+    context: Res<AudioContext>,
+    mut was_suspended: Local<bool>,
+    mut commands: Commands,
+) {
+    let suspended = context.is_suspended();
+    if *was_suspended && !suspended {
+        commands.trigger(RestartAudioStream);
+    }
+
+    *was_suspended = suspended;
+}
+
+fn observe_restart(
+    _: On<RestartAudioStream>,
+    retry: Option<ResMut<PendingStreamRetry>>,
+    fade: Res<StreamSwitchFade>,
+    mut switch: ResMut<StreamSwitch>,
+    mut main_bus: MainBusQuery,
+    mut commands: Commands,
+) {
+    if let Some(mut retry) = retry {
+        retry.immediate = true;
+    }
+
+    if fade.0.is_none() {
+        return;
+    }
+
+    // There's no time to fade out an unexpected restart,
+    // so we silence the output until the new stream fades in.
+    *switch = StreamSwitch::Restarting;
+    send_switch_level(&mut main_bus, |_| Volume::SILENT, &mut commands);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::{prepare_app, run};
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[derive(Resource, Default)]
+    struct Unavailable(usize);
+
+    #[test]
+    fn test_stream_retry() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.init_resource::<Unavailable>();
+        });
+
+        app.insert_resource(StreamRetryInterval(None)).add_observer(
+            |_: On<AudioUnavailable>, mut count: ResMut<Unavailable>| {
+                count.0 += 1;
+            },
+        );
+
+        run(&mut app, |mut commands: Commands| {
+            stream_unavailable("no devices", &mut commands);
+        });
+
+        assert_eq!(app.world().resource::<Unavailable>().0, 1);
+        assert!(!app.world_mut().run_system_once(stream_retry_due).unwrap());
+
+        app.world_mut().trigger(RestartAudioStream);
+        assert!(app.world_mut().run_system_once(stream_retry_due).unwrap());
+    }
+
+    #[derive(Resource, Default)]
+    struct Restarts(usize);
+
+    #[test]
+    fn test_stream_switch_fade() {
+        use crate::platform::null::NullConfig;
+
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.init_resource::<Restarts>();
+            commands.spawn((
+                MainBus,
+                VolumeNode {
+                    volume: Volume::Linear(0.8),
+                    ..Default::default()
+                },
+            ));
+        });
+
+        app.insert_resource(StreamSwitchFade(Some(Duration::from_millis(20))))
+            .add_observer(
+                |_: On<StreamRestartEvent>, mut restarts: ResMut<Restarts>| {
+                    restarts.0 += 1;
+                },
+            );
+
+        app.world_mut()
+            .resource_mut::<AudioStreamConfig<NullConfig>>()
+            .0
+            .block_frames = NonZeroU32::new(256).unwrap();
+        app.update();
+
+        // The restart should wait for the fade out.
+        assert_eq!(app.world().resource::<Restarts>().0, 0);
+        assert!(matches!(
+            app.world().resource::<StreamSwitch>(),
+            StreamSwitch::FadingOut { .. }
+        ));
+
+        // The fade never touches the node's volume, so
+        // changes made mid-fade are kept.
+        run(
+            &mut app,
+            |mut main_bus: Single<&mut VolumeNode, With<MainBus>>| {
+                assert_eq!(main_bus.volume, Volume::Linear(0.8));
+                main_bus.volume = Volume::Linear(0.6);
+            },
+        );
+
+        let start = Instant::now();
+        while !matches!(app.world().resource::<StreamSwitch>(), StreamSwitch::Idle) {
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            std::thread::sleep(Duration::from_millis(5));
+            app.update();
+        }
+
+        assert_eq!(app.world().resource::<Restarts>().0, 1);
+        run(&mut app, |main_bus: Single<&VolumeNode, With<MainBus>>| {
+            assert_eq!(main_bus.volume, Volume::Linear(0.6));
+        });
+
+        run(&mut app, |switching: Query<(), With<SwitchLevel>>| {
+            assert!(switching.is_empty());
+        });
+    }
+}
@@ -0,0 +1,241 @@
+//! A null backend for headless environments.
+
+use audioadapter_buffers::direct::InterleavedSlice;
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use firewheel::{ActivateInfo, FirewheelContext, node::StreamStatus};
+use std::{
+    num::NonZeroU32,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::JoinHandle,
+};
+
+use crate::{
+    context::{AudioContext, LocalStore, SampleRate, StreamRestartEvent},
+    platform::{AudioStreamConfig, RestartAudioStream},
+    prelude::SeedlingStartupSystems,
+    resource_changed_without_insert,
+};
+
+/// A backend that processes audio without any devices.
+///
+/// The audio graph is driven in real time by a background thread,
+/// and all output is discarded. This allows headless servers and CI
+/// machines to run the full game logic, including sample playback
+/// and events, without audio hardware.
+///
+/// To select the null backend, add it before [`SeedlingPlugins`].
+/// Other platform plugins will yield to it.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// # use bevy_seedling::platform::null::NullBackendPlugin;
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, NullBackendPlugin, SeedlingPlugins))
+///         .run();
+/// }
+/// ```
+///
+/// Alternatively, insert an [`AudioStreamConfig<NullConfig>`] before
+/// adding [`SeedlingPlugins`] to customize the stream.
+///
+/// With the `cpal` backend, the null backend is also selected
+/// automatically when no output devices are available.
+///
+/// [`SeedlingPlugins`]: crate::SeedlingPlugins
+#[derive(Debug, Default)]
+pub struct NullBackendPlugin;
+
+impl Plugin for NullBackendPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AudioStreamConfig<NullConfig>>()
+            .add_systems(
+                PostStartup,
+                start_stream.in_set(SeedlingStartupSystems::StreamInitialization),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    super::begin_stream_switch,
+                    (crate::context::pre_restart_stream, restart_stream)
+                        .chain()
+                        .run_if(super::stream_switch_ready),
+                )
+                    .chain()
+                    .run_if(resource_changed_without_insert::<AudioStreamConfig<NullConfig>>)
+                    .run_if(super::stream_active),
+            )
+            .add_observer(observe_restart);
+    }
+}
+
+/// The configuration for the [`NullBackendPlugin`]'s stream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NullConfig {
+    /// The stream's sample rate.
+    ///
+    /// Defaults to 48 kHz.
+    pub sample_rate: NonZeroU32,
+    /// The number of frames processed per block.
+    ///
+    /// Defaults to `128`.
+    pub block_frames: NonZeroU32,
+    /// The number of input channels.
+    ///
+    /// Inputs are always silent. Defaults to `2`.
+    pub num_input_channels: u32,
+    /// The number of output channels.
+    ///
+    /// Defaults to `2`.
+    pub num_output_channels: u32,
+}
+
+impl Default for NullConfig {
+    fn default() -> Self {
+        Self {
+            sample_rate: NonZeroU32::new(48000).unwrap(),
+            block_frames: NonZeroU32::new(128).unwrap(),
+            num_input_channels: 2,
+            num_output_channels: 2,
+        }
+    }
+}
+
+/// A running null stream.
+///
+/// The processing thread is stopped and joined when dropped.
+pub(crate) struct NullStream {
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for NullStream {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl NullStream {
+    pub(crate) fn new(context: &mut FirewheelContext, config: &NullConfig) -> Result<Self> {
+        let block_frames = config.block_frames.get() as usize;
+        let in_channels = config.num_input_channels as usize;
+        let out_channels = config.num_output_channels as usize;
+        let sample_rate = config.sample_rate;
+
+        let mut processor = context.activate(ActivateInfo {
+            sample_rate,
+            max_block_frames: config.block_frames,
+            num_stream_in_channels: config.num_input_channels,
+            num_stream_out_channels: config.num_output_channels,
+            input_to_output_latency_seconds: 0.0,
+        })?;
+
+        let running = Arc::new(AtomicBool::new(true));
+        let thread = std::thread::spawn({
+            let running = running.clone();
+            move || {
+                let block_duration = block_frames as f64 / sample_rate.get() as f64;
+                let input = vec![0f32; block_frames * in_channels];
+                let mut output = vec![0f32; block_frames * out_channels];
+                let stream_start = std::time::Instant::now();
+                let mut blocks: u64 = 0;
+
+                while running.load(Ordering::Relaxed) {
+                    let now = std::time::Instant::now();
+
+                    let input = InterleavedSlice::new(&input, in_channels, block_frames).unwrap();
+                    let mut output =
+                        InterleavedSlice::new_mut(&mut output, out_channels, block_frames).unwrap();
+
+                    processor.process(
+                        &input,
+                        &mut output,
+                        firewheel::backend::BackendProcessInfo {
+                            frames: block_frames,
+                            process_timestamp: Some(now),
+                            duration_since_stream_start: now - stream_start,
+                            input_stream_status: StreamStatus::empty(),
+                            output_stream_status: StreamStatus::empty(),
+                            dropped_frames: 0,
+                            process_to_playback_delay: None,
+                        },
+                    );
+
+                    // Sleeping until an absolute deadline keeps the stream
+                    // in step with real time, regardless of processing cost.
+                    blocks += 1;
+                    let deadline = stream_start
+                        + std::time::Duration::from_secs_f64(blocks as f64 * block_duration);
+                    std::thread::sleep(
+                        deadline.saturating_duration_since(std::time::Instant::now()),
+                    );
+                }
+            }
+        });
+
+        Ok(Self {
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    /// Replace any running null stream in the store with a new one.
+    pub(crate) fn restart(
+        context: &mut FirewheelContext,
+        store: &mut LocalStore,
+        config: &NullConfig,
+    ) -> Result {
+        drop(store.remove::<NullStream>());
+        store.insert_stream(NullStream::new(context, config)?);
+
+        Ok(())
+    }
+}
+
+fn start_stream(
+    mut context: ResMut<AudioContext>,
+    stream_config: Res<AudioStreamConfig<NullConfig>>,
+    commands: Commands,
+) -> Result {
+    let config = stream_config.0.clone();
+    context
+        .try_with_store(move |context, store| NullStream::restart(context, store, &config))??;
+
+    super::initialize_stream(SampleRate::new(stream_config.0.sample_rate), commands);
+
+    Ok(())
+}
+
+fn observe_restart(_: On<RestartAudioStream>, mut config: ResMut<AudioStreamConfig<NullConfig>>) {
+    config.set_changed();
+}
+
+fn restart_stream(
+    stream_config: Res<AudioStreamConfig<NullConfig>>,
+    mut context: ResMut<AudioContext>,
+    sample_rate: Res<SampleRate>,
+    mut commands: Commands,
+) -> Result {
+    let config = stream_config.0.clone();
+    context
+        .try_with_store(move |context, store| NullStream::restart(context, store, &config))??;
+
+    let previous_rate = sample_rate.get();
+    let current_rate = stream_config.0.sample_rate;
+    sample_rate.set(current_rate);
+
+    commands.trigger(StreamRestartEvent {
+        previous_rate,
+        current_rate,
+    });
+
+    Ok(())
+}
@@ -26,13 +26,20 @@ mod inner {
 
     use crate::{
         SeedlingSystems,
-        context::{AudioContext, SampleRate, StreamRestartEvent},
+        context::{AudioContext, SampleRate},
         platform::{self, *},
         prelude::SeedlingStartupSystems,
         resource_changed_without_insert,
     };
 
     pub(super) fn build_plugin(app: &mut App) {
+        if app
+            .world()
+            .contains_resource::<AudioStreamConfig<platform::null::NullConfig>>()
+        {
+            return;
+        }
+
         app.init_resource::<AudioStreamConfig<RtAudioConfig>>()
             .add_systems(
                 PostStartup,
@@ -40,36 +47,53 @@ mod inner {
             )
             .add_systems(
                 PostUpdate,
-                (crate::context::pre_restart_stream, restart_stream)
+                (
+                    platform::begin_stream_switch,
+                    (crate::context::pre_restart_stream, start_stream)
+                        .chain()
+                        .run_if(platform::stream_switch_ready),
+                )
                     .chain()
-                    .run_if(resource_changed_without_insert::<AudioStreamConfig<RtAudioConfig>>),
+                    .run_if(resource_changed_without_insert::<AudioStreamConfig<RtAudioConfig>>)
+                    .run_if(platform::stream_active),
+            )
+            .add_systems(
+                Last,
+                (poll_stream, start_stream.run_if(platform::stream_retry_due))
+                    .in_set(SeedlingSystems::PollStream),
             )
-            .add_systems(Last, poll_stream.in_set(SeedlingSystems::PollStream))
             .add_observer(observe_restart);
     }
 
     fn start_stream(
         mut context: ResMut<AudioContext>,
         stream_config: Res<AudioStreamConfig<RtAudioConfig>>,
-        commands: Commands,
-    ) -> Result {
-        let sample_rate = context.with_store(|context, store| {
-            let stream = RtAudioStream::new(context, stream_config.0.clone())?;
-            let sample_rate = stream_sample_rate(&stream);
+        sample_rate: Option<Res<SampleRate>>,
+        mut commands: Commands,
+    ) {
+        let config = stream_config.0.clone();
+        let result = context.try_with_store(move |context, store| {
+            let _ = store.remove::<RtAudioStream>();
 
-            let previous = store.insert(stream);
-            debug_assert!(previous.is_none());
+            let stream = RtAudioStream::new(context, config)?;
+            let sample_rate = stream_sample_rate(&stream);
+            store.insert_stream(stream);
 
             Ok::<_, StartStreamError>(sample_rate)
-        })?;
-
-        platform::initialize_stream(SampleRate::new(sample_rate), commands);
+        });
 
-        Ok(())
+        match result {
+            Ok(Ok(current_rate)) => {
+                platform::stream_available(current_rate, sample_rate.as_deref(), &mut commands);
+            }
+            Ok(Err(error)) => platform::stream_unavailable(error, &mut commands),
+            // A dead audio thread is reported by the context plugin.
+            Err(_) => {}
+        }
     }
 
     fn poll_stream(mut context: ResMut<AudioContext>, mut commands: Commands) -> Result {
-        let status = context.with_store(|_, store| {
+        let status = context.try_with_store(|_, store| {
             store.get_mut::<RtAudioStream>().map(|stream| {
                 let errors = stream.poll_status();
                 let is_running = stream.is_running();
@@ -78,7 +102,8 @@ mod inner {
             })
         });
 
-        if let Some((errors, is_running)) = status {
+        // A dead audio thread is reported by the context plugin.
+        if let Ok(Some((errors, is_running))) = status {
             for error in errors {
                 match error.type_ {
                     RtAudioErrorType::Warning => {
@@ -106,32 +131,6 @@ mod inner {
         config.set_changed();
     }
 
-    fn restart_stream(
-        stream_config: Res<AudioStreamConfig<RtAudioConfig>>,
-        mut context: ResMut<AudioContext>,
-        sample_rate: Res<SampleRate>,
-        mut commands: Commands,
-    ) -> Result {
-        let previous_rate = sample_rate.get();
-        let current_rate = context.with_store(|context, store| {
-            let _ = store.remove::<RtAudioStream>();
-
-            let stream = RtAudioStream::new(context, stream_config.0.clone())?;
-            let sample_rate = stream_sample_rate(&stream);
-            store.insert(stream);
-
-            Ok::<_, StartStreamError>(sample_rate)
-        })?;
-
-        sample_rate.set(current_rate);
-        commands.trigger(StreamRestartEvent {
-            previous_rate,
-            current_rate,
-        });
-
-        Ok(())
-    }
-
     fn stream_sample_rate(stream: &RtAudioStream) -> NonZeroU32 {
         NonZeroU32::new(stream.stream_info().sample_rate)
             .expect("RtAudio streams should always report a non-zero sample rate")
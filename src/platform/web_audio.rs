@@ -44,9 +44,15 @@ mod inner {
             )
             .add_systems(
                 PostUpdate,
-                (crate::context::pre_restart_stream, restart_stream)
+                (
+                    begin_stream_switch,
+                    (crate::context::pre_restart_stream, restart_stream)
+                        .chain()
+                        .run_if(stream_switch_ready),
+                )
                     .chain()
-                    .run_if(resource_changed_without_insert::<AudioStreamConfig<WebAudioConfig>>),
+                    .run_if(resource_changed_without_insert::<AudioStreamConfig<WebAudioConfig>>)
+                    .run_if(stream_active),
             )
             .add_systems(Last, poll_stream.in_set(SeedlingSystems::PollStream))
             .add_observer(observe_restart);
@@ -59,15 +65,15 @@ mod inner {
     ) -> Result {
         // TODO: it's not possible for the user to recover if this fails
         let sample_rate =
-            context.with_store(|context, store| -> Result<_, WebAudioStartError> {
+            context.try_with_store(|context, store| -> Result<_, WebAudioStartError> {
                 let stream = WebAudioBackend::new(context, stream_config.0.clone())?;
                 let sample_rate = stream.sample_rate();
 
-                let previous = store.insert(stream);
+                let previous = store.insert_stream(stream);
                 debug_assert!(previous.is_none());
 
                 Ok(sample_rate)
-            })?;
+            })??;
 
         crate::platform::initialize_stream(SampleRate::new(sample_rate), commands);
 
@@ -75,12 +81,12 @@ mod inner {
     }
 
     fn poll_stream(mut context: ResMut<AudioContext>) -> Result {
-        context.with_store(|_, store| {
+        context.try_with_store(|_, store| {
             store
                 .get_mut::<WebAudioBackend>()
                 .map(|context| context.poll())
                 .unwrap_or(Ok(()))
-        })?;
+        })??;
 
         Ok(())
     }
@@ -99,15 +105,16 @@ mod inner {
         mut commands: Commands,
     ) -> Result {
         // drop it like it's hot
-        let current_rate = graph.with_store(|context, store| -> Result<_, WebAudioStartError> {
-            let _ = store.remove::<WebAudioBackend>();
+        let current_rate =
+            graph.try_with_store(|context, store| -> Result<_, WebAudioStartError> {
+                let _ = store.remove::<WebAudioBackend>();
 
-            let stream = WebAudioBackend::new(context, stream_config.0.clone())?;
-            let sample_rate = stream.sample_rate();
-            store.insert(stream);
+                let stream = WebAudioBackend::new(context, stream_config.0.clone())?;
+                let sample_rate = stream.sample_rate();
+                store.insert_stream(stream);
 
-            Ok(sample_rate)
-        })?;
+                Ok(sample_rate)
+            })??;
 
         let previous_rate = sample_rate.get();
         sample_rate.set(current_rate);
@@ -0,0 +1,186 @@
+//! Spawning nodes followed by a series of effects.
+
+use super::sample_effects::EffectOf;
+use crate::edge::{EdgeTarget, PendingConnections, PendingEdge};
+use bevy_ecs::{entity::EntityCloner, prelude::*};
+
+/// The ends of a chain spawned with [`SpawnChain`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainEnds {
+    /// The node at the start of the chain.
+    pub head: Entity,
+    /// The last effect in the chain.
+    ///
+    /// If the chain has no effects, this is the head.
+    pub tail: Entity,
+}
+
+/// Spawn a node followed by clones of a series of effects.
+///
+/// This is how each [`SamplerNode`] in a [`SamplerPool`] is built. The
+/// effects are cloned from template entities, like those in a
+/// [`SampleEffects`], and connected in series after the head node.
+/// The clones are parented to the head, in order, so they're despawned
+/// along with it.
+///
+/// This makes it easy to build custom voice systems with
+/// per-voice effects.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// #[derive(Component)]
+/// struct VoiceTemplate;
+///
+/// fn spawn_template(mut commands: Commands) {
+///     commands.spawn((
+///         VoiceTemplate,
+///         sample_effects![FastLowpassNode::<2>::default(), VolumeNode::default()],
+///     ));
+/// }
+///
+/// fn spawn_voice(template: Single<&SampleEffects, With<VoiceTemplate>>, mut commands: Commands) {
+///     let ends = SpawnChain::new(template.iter())
+///         .with_target(MainBus)
+///         .spawn(VolumeNode::default(), &mut commands);
+///
+///     // `ends.head` feeds the low-pass filter, and
+///     // `ends.tail` is the volume node routed to the main bus.
+/// }
+/// ```
+///
+/// Without a target, the tail is routed like any other unconnected node.
+///
+/// [`SamplerNode`]: firewheel::nodes::sampler::SamplerNode
+/// [`SamplerPool`]: super::SamplerPool
+/// [`SampleEffects`]: super::sample_effects::SampleEffects
+#[derive(Debug, Clone)]
+pub struct SpawnChain {
+    effects: Vec<Entity>,
+    target: Option<EdgeTarget>,
+}
+
+impl SpawnChain {
+    /// Create a chain from effect template entities.
+    ///
+    /// The templates are left untouched, so they may be reused.
+    pub fn new(effects: impl IntoIterator<Item = Entity>) -> Self {
+        Self {
+            effects: effects.into_iter().collect(),
+            target: None,
+        }
+    }
+
+    /// Connect the tail of the chain to `target`.
+    pub fn with_target(self, target: impl Into<EdgeTarget>) -> Self {
+        Self {
+            target: Some(target.into()),
+            ..self
+        }
+    }
+
+    /// Spawn `head` followed by the chain's effects.
+    ///
+    /// The entities are reserved immediately, while the
+    /// effects are cloned when the commands are applied.
+    pub fn spawn(self, head: impl Bundle, commands: &mut Commands) -> ChainEnds {
+        let head = commands.spawn(head).id();
+        let chain: Vec<_> = self
+            .effects
+            .iter()
+            .map(|_| commands.spawn_empty().id())
+            .collect();
+        let tail = chain.last().copied().unwrap_or(head);
+
+        let Self { effects, target } = self;
+        commands.queue(move |world: &mut World| -> Result {
+            let mut cloner = EntityCloner::build_opt_out(world);
+            cloner.deny::<EffectOf>();
+            let mut cloner = cloner.finish();
+
+            for (template, effect) in effects.into_iter().zip(&chain) {
+                cloner.clone_entity(world, template, *effect);
+            }
+
+            world.get_entity_mut(head)?.add_children(&chain);
+
+            // Until we come up with a good way to implement the
+            // connect trait for `WorldEntityMut`, we're stuck with
+            // a bit of boilerplate.
+            let mut previous = head;
+            for effect in chain {
+                connect(world, previous, effect.into())?;
+                previous = effect;
+            }
+
+            if let Some(target) = target {
+                connect(world, tail, target)?;
+            }
+
+            Ok(())
+        });
+
+        ChainEnds { head, tail }
+    }
+}
+
+fn connect(world: &mut World, source: Entity, target: EdgeTarget) -> Result {
+    world
+        .get_entity_mut(source)?
+        .entry::<PendingConnections>()
+        .or_default()
+        .into_mut()
+        .push(PendingEdge::new(target, None));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        node::FirewheelNode,
+        prelude::*,
+        sample_effects,
+        test::{prepare_app, run},
+    };
+    use firewheel::nodes::fast_filters::lowpass::FastLowpassNode;
+
+    #[test]
+    fn test_spawn_chain() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn(sample_effects![
+                FastLowpassNode::<2>::default(),
+                VolumeNode::default()
+            ]);
+        });
+
+        let ends = run(
+            &mut app,
+            |template: Single<&SampleEffects>, mut commands: Commands| {
+                SpawnChain::new(template.iter())
+                    .with_target(MainBus)
+                    .spawn(VolumeNode::default(), &mut commands)
+            },
+        );
+
+        app.update();
+
+        run(
+            &mut app,
+            move |children: Query<&Children>,
+                  low_pass: Query<(), (With<FastLowpassNode<2>>, Without<EffectOf>)>,
+                  volume: Query<(), (With<VolumeNode>, With<FirewheelNode>, Without<EffectOf>)>,
+                  templates: Query<(), With<EffectOf>>| {
+                let children = children.get(ends.head).unwrap();
+
+                assert_eq!(children.len(), 2);
+                assert!(low_pass.contains(children[0]));
+                assert_eq!(children[1], ends.tail);
+                assert!(volume.contains(ends.head));
+                assert!(volume.contains(ends.tail));
+                assert_eq!(templates.iter().len(), 2);
+            },
+        );
+    }
+}
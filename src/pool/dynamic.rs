@@ -47,25 +47,49 @@
 //!
 //! Note that when no effects are applied, your samples will be queued in the
 //! [`DefaultPool`][crate::prelude::DefaultPool], not a dynamic pool.
+//!
+//! ## Reusing static pools
+//!
+//! If a static pool already has the right shape, there's no need to
+//! spawn a dynamic one. Marking a [`SamplerPool`] with [`AcceptDynamic`]
+//! allows samples whose effects exactly match the pool's to be routed there
+//! instead. [`DynamicPoolStats`] reports how often each path is taken,
+//! which can help identify shapes worth promoting to static pools.
+//!
+//! ## Limits
+//!
+//! By default, dynamic pools are never despawned. [`DynamicPoolLimits`]
+//! bounds the number of shapes and sampler chains, evicting idle pools
+//! as necessary.
 
-use super::{DefaultPoolSize, PoolSize, SamplerPool, sample_effects::EffectOf};
+use super::{
+    DefaultPoolSize, PoolMarker, PoolSamplers, PoolShape, PoolSize, SamplerOf, SamplerPool,
+    label::DefaultPool, sample_effects::EffectOf,
+};
 use crate::{
     edge::Connect,
     node::EffectId,
-    pool::{label::PoolLabelContainer, sample_effects::SampleEffects},
-    sample::{QueuedSample, SamplePlayer},
+    pool::{
+        label::PoolLabelContainer,
+        sample_effects::{SampleEffects, SharedEffects},
+    },
+    sample::{Queued, SamplePlayer},
 };
 use bevy_app::prelude::*;
 use bevy_ecs::{component::ComponentId, entity::EntityCloner, prelude::*};
 use bevy_log::prelude::*;
 use bevy_platform::collections::HashMap;
 use bevy_seedling_macros::{NodeLabel, PoolLabel};
+use bevy_time::{Real, Time};
+use core::time::Duration;
 
 pub(super) struct DynamicPlugin;
 
 impl Plugin for DynamicPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<Registries>();
+        app.init_resource::<Registries>()
+            .init_resource::<DynamicPoolLimits>()
+            .init_resource::<DynamicPoolStats>();
     }
 }
 
@@ -74,34 +98,197 @@ impl Plugin for DynamicPlugin {
 #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct DynamicBus;
 
+/// Allow dynamic samples to play in this [`SamplerPool`].
+///
+/// Samples with [`SampleEffects`] but no pool label are normally
+/// queued in a dynamic pool. If their effects exactly match the shape
+/// of a pool with this marker, they're routed to that pool instead.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// #[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct SpatialSfx;
+///
+/// fn spawn_pool(mut commands: Commands) {
+///     commands.spawn((
+///         SamplerPool(SpatialSfx),
+///         AcceptDynamic,
+///         sample_effects![SpatialBasicNode::default()],
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct AcceptDynamic;
+
+/// Limits on the resources dynamic pools may consume.
+///
+/// Each unique effect shape spawns a new dynamic pool, so projects with
+/// many permutations can accumulate a large number of sampler chains.
+/// Once a limit is reached, the least-recently-used idle pool is evicted
+/// to make room. If every pool is busy, the sample falls back to the
+/// [`DefaultPool`], discarding any effects it doesn't have.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// # use std::time::Duration;
+/// fn limit_dynamic_pools(mut commands: Commands) {
+///     commands.insert_resource(DynamicPoolLimits {
+///         max_shapes: 8,
+///         idle_timeout: Some(Duration::from_secs(30)),
+///         ..Default::default()
+///     });
+/// }
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct DynamicPoolLimits {
+    /// The maximum number of dynamic pools, one per unique effect shape.
+    ///
+    /// By default, this is `usize::MAX`.
+    pub max_shapes: usize,
+    /// The maximum number of sampler chains across all dynamic pools.
+    ///
+    /// New pools are capped to the remaining budget.
+    ///
+    /// By default, this is `usize::MAX`.
+    pub max_chains: usize,
+    /// How long a dynamic pool may sit idle before it's despawned.
+    ///
+    /// When `None`, idle pools are only evicted to make room
+    /// for new shapes. By default, this is `None`.
+    pub idle_timeout: Option<Duration>,
+}
+
+impl Default for DynamicPoolLimits {
+    fn default() -> Self {
+        Self {
+            max_shapes: usize::MAX,
+            max_chains: usize::MAX,
+            idle_timeout: None,
+        }
+    }
+}
+
+/// Counts of how dynamic samples have been routed.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct DynamicPoolStats {
+    pools_spawned: u64,
+    pools_evicted: u64,
+    dynamic_assignments: u64,
+    static_assignments: u64,
+    fallbacks: u64,
+}
+
+impl DynamicPoolStats {
+    /// The number of dynamic pools spawned.
+    pub fn pools_spawned(&self) -> u64 {
+        self.pools_spawned
+    }
+
+    /// The number of dynamic pools despawned by [`DynamicPoolLimits`].
+    pub fn pools_evicted(&self) -> u64 {
+        self.pools_evicted
+    }
+
+    /// The number of samples queued in dynamic pools.
+    pub fn dynamic_assignments(&self) -> u64 {
+        self.dynamic_assignments
+    }
+
+    /// The number of samples routed to a static pool with [`AcceptDynamic`].
+    pub fn static_assignments(&self) -> u64 {
+        self.static_assignments
+    }
+
+    /// The number of samples sent to the [`DefaultPool`]
+    /// because [`DynamicPoolLimits`] were reached.
+    pub fn fallbacks(&self) -> u64 {
+        self.fallbacks
+    }
+}
+
 /// A label reserved for dynamic pools.
 #[derive(PoolLabel, Clone, Copy, PartialEq, Eq, Hash, Debug)]
 struct DynamicPoolLabel(usize);
 
 struct RegistryEntry {
     label: DynamicPoolLabel,
+    pool: Entity,
+    chains: usize,
+    last_used: Duration,
 }
 
 #[derive(Resource, Default)]
-pub(super) struct Registries(HashMap<Vec<ComponentId>, RegistryEntry>);
+pub(super) struct Registries {
+    entries: HashMap<Vec<ComponentId>, RegistryEntry>,
+    next_label: usize,
+}
 
+impl Registries {
+    fn chains(&self) -> usize {
+        self.entries.values().map(|e| e.chains).sum()
+    }
+}
+
+/// Returns `true` if none of the pool's samplers are assigned.
+fn is_idle(
+    pool: Entity,
+    pools: &Query<&PoolSamplers>,
+    samplers: &Query<(), With<SamplerOf>>,
+) -> bool {
+    pools
+        .get(pool)
+        .map(|p| samplers.iter_many(p.iter()).next().is_none())
+        .unwrap_or(true)
+}
+
+#[expect(clippy::too_many_arguments)]
 pub(super) fn update_dynamic_pools(
     queued_samples: Query<
-        (Entity, &SampleEffects),
         (
-            With<QueuedSample>,
-            With<SamplePlayer>,
-            Without<PoolLabelContainer>,
+            Entity,
+            &SamplePlayer,
+            Option<&SampleEffects>,
+            Option<&SharedEffects>,
         ),
+        (Queued, Without<PoolLabelContainer>),
     >,
+    groups: Query<&SampleEffects>,
     // TODO: make sure to migrate this to `If<Single<_>>` for 0.17
     dynamic_bus: Single<Entity, With<DynamicBus>>,
     mut effects: Query<&EffectId>,
+    static_pools: Query<(&PoolLabelContainer, &PoolShape), (With<AcceptDynamic>, With<PoolMarker>)>,
+    pools: Query<&PoolSamplers>,
+    samplers: Query<(), With<SamplerOf>>,
     mut registries: ResMut<Registries>,
+    mut stats: ResMut<DynamicPoolStats>,
+    limits: Res<DynamicPoolLimits>,
+    time: Res<Time<Real>>,
     mut commands: Commands,
     dynamic_range: Res<DefaultPoolSize>,
 ) -> Result {
-    for (sample, sample_effects) in queued_samples.iter() {
+    let now = time.elapsed();
+
+    if let Some(timeout) = limits.idle_timeout {
+        registries.entries.retain(|_, entry| {
+            let expired = now.saturating_sub(entry.last_used) >= timeout
+                && is_idle(entry.pool, &pools, &samplers);
+
+            if expired {
+                commands.entity(entry.pool).despawn();
+                stats.pools_evicted += 1;
+            }
+
+            !expired
+        });
+    }
+
+    for (sample, player, sample_effects, shared) in queued_samples.iter() {
+        let Some(sample_effects) = SharedEffects::resolve(sample_effects, shared, &groups) else {
+            continue;
+        };
+
         let component_ids =
             match super::fetch_effect_ids(sample_effects, &mut effects.as_query_lens()) {
                 Ok(ids) => ids,
@@ -112,38 +299,99 @@ pub(super) fn update_dynamic_pools(
                 }
             };
 
-        match registries.0.get_mut(&component_ids) {
-            Some(entry) => {
-                commands.entity(sample).insert(entry.label);
-            }
-            None => {
-                let label = DynamicPoolLabel(registries.0.len());
-
-                let bus = commands
-                    .spawn((SamplerPool(label), PoolSize(dynamic_range.0.clone())))
-                    .connect(*dynamic_bus)
-                    .head();
-
-                let effects: Vec<_> = sample_effects.iter().collect();
-                commands.queue(move |world: &mut World| {
-                    let mut cloner = EntityCloner::build_opt_out(world);
-                    cloner.deny::<EffectOf>();
-                    let mut cloner = cloner.finish();
-
-                    let mut cloned = Vec::new();
-                    for effect in effects {
-                        let effect = cloner.spawn_clone(world, effect);
-                        cloned.push(effect);
-                    }
+        if let Some((label, _)) = static_pools
+            .iter()
+            .find(|(_, shape)| shape.0 == component_ids)
+        {
+            // The container alone is enough for queueing.
+            commands.entity(sample).insert(label.clone());
+            stats.static_assignments += 1;
+            continue;
+        }
 
-                    world.entity_mut(bus).add_related::<EffectOf>(&cloned);
-                });
+        if let Some(entry) = registries.entries.get_mut(&component_ids) {
+            entry.last_used = now;
+            commands.entity(sample).insert(entry.label);
+            stats.dynamic_assignments += 1;
+            continue;
+        }
 
-                registries.0.insert(component_ids, RegistryEntry { label });
+        // Make room by evicting the least-recently-used idle pool.
+        let mut budget = limits.max_chains.saturating_sub(registries.chains());
+        if registries.entries.len() >= limits.max_shapes || budget == 0 {
+            let lru = registries
+                .entries
+                .iter()
+                // Pools chosen this frame may not have assigned their samples yet.
+                .filter(|(_, entry)| {
+                    entry.last_used < now && is_idle(entry.pool, &pools, &samplers)
+                })
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(shape, _)| shape.clone());
 
-                commands.entity(sample).insert(label);
+            match lru {
+                Some(shape) => {
+                    let entry = registries.entries.remove(&shape).unwrap();
+                    commands.entity(entry.pool).despawn();
+                    stats.pools_evicted += 1;
+                    budget += entry.chains;
+                }
+                None => {
+                    match player.sample.path() {
+                        Some(path) => warn!(
+                            "Dynamic pool limits reached; queued sample \"{}\" will fall back to the default pool.",
+                            path
+                        ),
+                        None => warn!(
+                            "Dynamic pool limits reached; queued sample will fall back to the default pool."
+                        ),
+                    }
+                    commands.entity(sample).insert(DefaultPool);
+                    stats.fallbacks += 1;
+                    continue;
+                }
             }
         }
+
+        let label = DynamicPoolLabel(registries.next_label);
+        registries.next_label += 1;
+
+        let end = (*dynamic_range.0.end()).min(budget);
+        let size = (*dynamic_range.0.start()).min(end)..=end;
+
+        let bus = commands
+            .spawn((SamplerPool(label), PoolSize(size)))
+            .connect(*dynamic_bus)
+            .head();
+
+        let effects: Vec<_> = sample_effects.iter().collect();
+        commands.queue(move |world: &mut World| {
+            let mut cloner = EntityCloner::build_opt_out(world);
+            cloner.deny::<EffectOf>();
+            let mut cloner = cloner.finish();
+
+            let mut cloned = Vec::new();
+            for effect in effects {
+                let effect = cloner.spawn_clone(world, effect);
+                cloned.push(effect);
+            }
+
+            world.entity_mut(bus).add_related::<EffectOf>(&cloned);
+        });
+
+        registries.entries.insert(
+            component_ids,
+            RegistryEntry {
+                label,
+                pool: bus,
+                chains: end,
+                last_used: now,
+            },
+        );
+
+        commands.entity(sample).insert(label);
+        stats.pools_spawned += 1;
+        stats.dynamic_assignments += 1;
     }
 
     Ok(())
@@ -4,10 +4,7 @@
 //! Any node that doesn't provide an explicit pool when spawned
 //! and has no effects will be automatically played in the [`DefaultPool`].
 
-use bevy_ecs::{
-    component::ComponentId, intern::Interned, lifecycle::HookContext, prelude::*,
-    world::DeferredWorld,
-};
+use bevy_ecs::{intern::Interned, lifecycle::HookContext, prelude::*, world::DeferredWorld};
 
 pub use bevy_seedling_macros::PoolLabel;
 
@@ -88,33 +85,62 @@ pub struct DefaultPool;
 pub type InternedPoolLabel = Interned<dyn PoolLabel>;
 
 /// A type-erased pool label container.
+///
+/// Each pool label component keeps a container in sync on its entity,
+/// which lets pool systems match labels without knowing their types.
+/// Labels can be freely added, removed, and swapped on live entities:
+///
+/// - Inserting a label inserts its container, replacing any other label.
+/// - Removing the label removes its container.
+/// - Removing the container removes its label.
+///
+/// A label only determines where a sample is next queued. Changing
+/// the label of a sample that's already playing won't move it;
+/// for that, see [`MoveToPool`][crate::prelude::MoveToPool].
 #[derive(Component, Debug, Clone)]
-#[component(on_remove = Self::on_remove)]
+#[component(on_replace = Self::on_replace)]
 pub struct PoolLabelContainer {
     pub(crate) label: InternedPoolLabel,
-    pub(crate) label_id: ComponentId,
+    remove_label: fn(&mut EntityWorldMut, InternedPoolLabel),
 }
 
 impl PoolLabelContainer {
     /// Create a new interned pool label.
-    pub fn new<T: PoolLabel>(label: &T, id: ComponentId) -> Self {
+    pub fn new<T: PoolLabel + Component>(label: &T) -> Self {
         Self {
             label: label.intern(),
-            label_id: id,
+            remove_label: |entity, label| {
+                if entity.get::<T>().is_some_and(|l| l.intern() == label) {
+                    entity.remove::<T>();
+                }
+            },
         }
     }
 
-    fn on_remove(mut world: DeferredWorld, context: HookContext) {
-        let id = world
-            .get::<PoolLabelContainer>(context.entity)
-            .unwrap()
-            .label_id;
+    /// The interned label.
+    pub fn label(&self) -> InternedPoolLabel {
+        self.label
+    }
+
+    fn on_replace(mut world: DeferredWorld, context: HookContext) {
+        let Some(old) = world.get::<PoolLabelContainer>(context.entity).cloned() else {
+            return;
+        };
 
         world.commands().queue(move |world: &mut World| {
             let Ok(mut entity) = world.get_entity_mut(context.entity) else {
                 return;
             };
-            entity.remove_by_id(id);
+
+            // Re-inserting the same label shouldn't remove it.
+            if entity
+                .get::<PoolLabelContainer>()
+                .is_some_and(|container| container.label == old.label)
+            {
+                return;
+            }
+
+            (old.remove_label)(&mut entity, old.label);
         });
     }
 }
@@ -122,23 +148,29 @@ impl PoolLabelContainer {
 /// Insert a type-erased label container.
 #[doc(hidden)]
 pub fn insert_pool_label<L: PoolLabel + Component>(mut world: DeferredWorld, context: HookContext) {
-    let value = world.get::<L>(context.entity).unwrap();
-    let container = PoolLabelContainer::new(value, context.component_id);
+    let Some(value) = world.get::<L>(context.entity) else {
+        return;
+    };
+    let container = PoolLabelContainer::new(value);
     world.commands().entity(context.entity).insert(container);
 }
 
 /// Remove this label's associated type-erased label container.
 #[doc(hidden)]
 pub fn remove_pool_label<L: PoolLabel + Component>(mut world: DeferredWorld, context: HookContext) {
+    let Some(label) = world.get::<L>(context.entity).map(|l| l.intern()) else {
+        return;
+    };
+
     world.commands().queue(move |world: &mut World| {
         let Ok(mut entity) = world.get_entity_mut(context.entity) else {
             return;
         };
-        let Some(container) = entity.get::<PoolLabelContainer>() else {
-            return;
-        };
 
-        if container.label_id == context.component_id {
+        if entity
+            .get::<PoolLabelContainer>()
+            .is_some_and(|container| container.label == label)
+        {
             entity.remove::<PoolLabelContainer>();
         }
     });
@@ -152,6 +184,15 @@ mod test {
     #[derive(PoolLabel, Debug, PartialEq, Eq, Hash, Clone)]
     struct TestLabel;
 
+    #[derive(PoolLabel, Debug, PartialEq, Eq, Hash, Clone)]
+    struct OtherLabel;
+
+    #[derive(PoolLabel, Debug, PartialEq, Eq, Hash, Clone)]
+    enum ValueLabel {
+        A,
+        B,
+    }
+
     // These are simple test that just confirm the order of
     // hooks _and_ their queued effects works how this module
     // expects.
@@ -213,4 +254,65 @@ mod test {
         world.flush();
         assert!(world.entity(entity).contains::<PoolLabelContainer>());
     }
+
+    #[test]
+    fn test_swap_label_types() {
+        let mut app = prepare_app(|| ());
+        let world = app.world_mut();
+
+        let entity = world.spawn(TestLabel).id();
+        world.entity_mut(entity).insert(OtherLabel);
+        world.flush();
+
+        let entity = world.entity(entity);
+        assert!(!entity.contains::<TestLabel>());
+        assert!(entity.contains::<OtherLabel>());
+        assert_eq!(
+            entity.get::<PoolLabelContainer>().unwrap().label(),
+            OtherLabel.intern()
+        );
+    }
+
+    #[test]
+    fn test_swap_label_values() {
+        let mut app = prepare_app(|| ());
+        let world = app.world_mut();
+
+        let entity = world.spawn(ValueLabel::A).id();
+        world.entity_mut(entity).insert(ValueLabel::B);
+        world.flush();
+
+        let entity = world.entity(entity);
+        assert_eq!(entity.get::<ValueLabel>(), Some(&ValueLabel::B));
+        assert_eq!(
+            entity.get::<PoolLabelContainer>().unwrap().label(),
+            ValueLabel::B.intern()
+        );
+    }
+
+    #[test]
+    fn test_reinsert_label() {
+        let mut app = prepare_app(|| ());
+        let world = app.world_mut();
+
+        let entity = world.spawn(TestLabel).id();
+        world.entity_mut(entity).insert(TestLabel);
+        world.flush();
+
+        let entity = world.entity(entity);
+        assert!(entity.contains::<TestLabel>());
+        assert!(entity.contains::<PoolLabelContainer>());
+    }
+
+    #[test]
+    fn test_despawn_labeled() {
+        let mut app = prepare_app(|| ());
+        let world = app.world_mut();
+
+        let entity = world.spawn(TestLabel).id();
+        world.despawn(entity);
+        world.flush();
+
+        assert!(world.get_entity(entity).is_err());
+    }
 }
@@ -2,36 +2,44 @@
 
 use crate::{
     SeedlingSystems,
-    context::{PreStreamRestartEvent, SampleRate, StreamRestartEvent},
-    edge::{PendingConnections, PendingEdge},
+    context::{
+        PreStreamRestartEvent, SampleRate, StreamRestartEvent, graph::SeedlingStartupSystems,
+    },
+    edge::DefaultRoute,
     error::SeedlingError,
     node::{AudioState, DiffTimestamp, EffectId, FirewheelNode, RegisterNode},
     pool::label::PoolLabelContainer,
-    prelude::{AudioEvents, PoolLabel},
-    sample::{OnComplete, PlaybackSettings, QueuedSample, SamplePlayer},
+    prelude::{AudioEvents, ParamFade, PoolLabel, Volume},
+    sample::{
+        AudioSample, OnComplete, PlaybackDirection, PlaybackSettings, PlayerUpdate, QueuedSample,
+        SamplePlayer, SampleQueue, SampleQueueItemComplete, SmoothSeek,
+    },
     time::{Audio, AudioTime},
 };
 use bevy_app::prelude::*;
 use bevy_asset::prelude::*;
 use bevy_ecs::{
-    component::ComponentId, entity::EntityCloner, lifecycle::HookContext, prelude::*,
-    system::QueryLens, world::DeferredWorld,
+    component::ComponentId, lifecycle::HookContext, prelude::*, system::QueryLens,
+    world::DeferredWorld,
 };
 use core::ops::{Deref, RangeInclusive};
 use firewheel::{
-    clock::{DurationSamples, DurationSeconds},
+    clock::{DurationSamples, DurationSeconds, InstantSeconds},
+    collector::ArcGc,
     nodes::{
-        sampler::{PlayFrom, SamplerConfig, SamplerNode, SamplerState},
+        sampler::{PlayFrom, RepeatMode, SamplerConfig, SamplerNode, SamplerState},
         volume::VolumeNode,
     },
+    sample_resource::{SampleResource, SampleResourceInfo},
 };
-use queue::SkipTimer;
-use sample_effects::{EffectOf, SampleEffects};
+use sample_effects::{EffectOf, SampleEffects, SharedEffects};
 
+pub mod chain;
 pub mod dynamic;
 pub mod label;
 mod queue;
 pub mod sample_effects;
+pub mod scaling;
 
 pub(crate) struct SamplePoolPlugin;
 
@@ -39,35 +47,55 @@ impl Plugin for SamplePoolPlugin {
     fn build(&self, app: &mut App) {
         app.register_node::<SamplerNode>()
             .register_node_state::<SamplerNode, SamplerState>()
+            .init_resource::<queue::QueueTimers>()
+            .init_resource::<PoolSizePolicy>()
+            .add_systems(
+                PreStartup,
+                apply_pool_size_policy.before(SeedlingStartupSystems::GraphSetup),
+            )
             .add_systems(
                 Last,
                 (
                     (
                         queue::assign_default,
                         dynamic::update_dynamic_pools,
+                        scaling::scale_voices.run_if(resource_exists::<scaling::VoiceScaling>),
+                        scaling::restore_voices.run_if(resource_removed::<scaling::VoiceScaling>),
                         populate_pool,
                         queue::grow_pools,
                     )
                         .chain()
                         .before(SeedlingSystems::Acquire),
-                    poll_finished
+                    (advance_sample_queues, poll_finished)
+                        .chain()
                         .before(SeedlingSystems::Pool)
                         .after(SeedlingSystems::Connect),
-                    watch_sample_players
+                    (
+                        apply_smooth_seeks,
+                        apply_stops,
+                        finish_releases,
+                        watch_direction,
+                        watch_sample_players,
+                    )
+                        .chain()
                         .before(SeedlingSystems::Queue)
                         .after(SeedlingSystems::Pool),
                     (queue::assign_work, queue::update_followers)
                         .chain()
                         .in_set(SeedlingSystems::Pool),
-                    (queue::tick_skipped, queue::mark_skipped)
-                        .chain()
-                        .after(SeedlingSystems::Pool),
+                    sync_sampler_configs.before(SeedlingSystems::Acquire),
+                    queue::tick_queue.after(SeedlingSystems::Pool),
                 ),
             )
             .add_observer(remove_finished)
             .add_observer(generate_snapshots)
             .add_observer(apply_snapshots)
             .add_observer(Sampler::observe_discard)
+            .add_observer(SharedEffects::observe_release)
+            .add_observer(crate::sample::StartDelay::observe_assignment)
+            .add_observer(restart_samples)
+            .add_observer(set_repeat_mode)
+            .add_observer(set_static_volume)
             .add_plugins(dynamic::DynamicPlugin);
     }
 }
@@ -111,6 +139,9 @@ impl Plugin for SamplePoolPlugin {
 /// # }
 /// ```
 ///
+/// To process the pool's combined output before its volume node,
+/// provide a [`PoolBus`].
+///
 /// Finally, you can insert arbitrary effects.
 ///
 /// ```
@@ -258,25 +289,96 @@ impl Plugin for SamplePoolPlugin {
 /// ```
 #[derive(Debug, Component)]
 #[component(immutable, on_insert = Self::on_insert_hook)]
-#[require(PoolMarker, SamplerConfig)]
+#[require(PoolMarker, StealFade)]
 #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct SamplerPool<T: PoolLabel + Component + Clone>(pub T);
 
 impl<T: PoolLabel + Component + Clone> SamplerPool<T> {
     fn on_insert_hook(mut world: DeferredWorld, context: HookContext) {
-        world.commands().queue(move |world: &mut World| {
-            let id = match world.component_id::<T>() {
-                Some(id) => id,
-                None => world.register_component::<T>(),
-            };
+        let Some(value) = world.get::<SamplerPool<T>>(context.entity) else {
+            return;
+        };
 
-            let Some(value) = world.get::<SamplerPool<T>>(context.entity) else {
-                return;
-            };
+        let container = PoolLabelContainer::new(&value.0);
+        world.commands().entity(context.entity).insert(container);
+    }
+}
 
-            let container = PoolLabelContainer::new(&value.0, id);
-            world.entity_mut(context.entity).insert(container);
-        });
+/// Construct all of a pool's samplers up front.
+///
+/// Pools normally start with [`PoolSize`]'s lower bound, growing as demand
+/// increases. Since each new sampler chain must be constructed and connected
+/// in the audio graph, the first sounds in a busy pool may hitch. With this
+/// marker, the pool is spawned at its upper bound instead, so the cost can
+/// be paid during a loading screen.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// # #[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash)]
+/// # struct FootstepPool;
+/// fn spawn_pool(mut commands: Commands) {
+///     commands.spawn((
+///         SamplerPool(FootstepPool),
+///         PoolSize(4..=16),
+///         PreWarm,
+///         sample_effects![SpatialBasicNode::default()],
+///     ));
+/// }
+/// ```
+///
+/// To pre-warm the pools spawned by the [`AudioGraphTemplate`], see
+/// [`PreWarmPools`].
+///
+/// [`AudioGraphTemplate`]: crate::prelude::AudioGraphTemplate
+/// [`PreWarmPools`]: crate::prelude::PreWarmPools
+#[derive(Debug, Default, Clone, Copy, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct PreWarm;
+
+/// How long a pool takes to crossfade when a playing voice is stolen.
+///
+/// When a pool is full, new samples may interrupt the least important
+/// playing sample. Rather than cutting the old sample off, the sampler
+/// fades it out over the first half of this duration, then fades in the
+/// new sample over the second half.
+///
+/// This is required by [`SamplerPool`] and defaults to 10 milliseconds.
+/// A duration of zero switches samples immediately.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// # #[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash)]
+/// # struct GunshotPool;
+/// fn spawn_pool(mut commands: Commands) {
+///     commands.spawn((
+///         SamplerPool(GunshotPool),
+///         PoolSize(4..=4),
+///         StealFade(DurationSeconds(0.02)),
+///         // Sampler declicking is configured per pool as well.
+///         SamplerConfig::default(),
+///     ));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Component)]
+pub struct StealFade(pub DurationSeconds);
+
+impl Default for StealFade {
+    fn default() -> Self {
+        Self(DurationSeconds(0.01))
+    }
+}
+
+/// Apply changes to a pool's [`SamplerConfig`] to its samplers.
+fn sync_sampler_configs(
+    pools: Query<(&SamplerConfig, &PoolSamplers), (With<PoolMarker>, Changed<SamplerConfig>)>,
+    mut commands: Commands,
+) {
+    for (config, samplers) in &pools {
+        for sampler in samplers.iter() {
+            commands.entity(sampler).insert(config.clone());
+        }
     }
 }
 
@@ -284,13 +386,80 @@ impl<T: PoolLabel + Component + Clone> SamplerPool<T> {
 #[derive(Component, Default)]
 struct PoolMarker;
 
+/// A relationship pointing from a [`SamplerNode`] entity
+/// to the [`SamplerPool`] it belongs to.
 #[derive(Debug, Component)]
 #[relationship(relationship_target = PoolSamplers)]
-struct PoolSamplerOf(pub Entity);
+pub struct PoolSamplerOf(pub Entity);
+
+/// A relationship pointing from a pool's bus node to its [`SamplerPool`].
+///
+/// See [`PoolBus`] for more details.
+#[derive(Debug, Component)]
+#[relationship(relationship_target = PoolBus)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct PoolBusOf(pub Entity);
+
+/// The bus node that a [`SamplerPool`]'s samplers feed into.
+///
+/// By default, every sampler in a pool is routed directly to the
+/// pool entity's node, which is a [`VolumeNode`] unless otherwise
+/// specified. When a [`PoolBus`] is provided, samplers are instead
+/// routed to the bus, making it easy to apply processing to the
+/// whole pool, like compression or limiting.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// # fn spawn_pool(mut commands: Commands) {
+/// #[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct DialoguePool;
+///
+/// commands.spawn((
+///     SamplerPool(DialoguePool),
+///     PoolBus::spawn_one(LimiterNode::default()),
+/// ));
+/// # }
+/// ```
+///
+/// Unless the bus is connected elsewhere, it's automatically
+/// routed to the pool entity, producing a graph like:
+///
+/// ```text
+/// ┌───────┐┌───────┐┌───────┐┌───────┐
+/// │Sampler││Sampler││Sampler││Sampler│
+/// └┬──────┘└┬──────┘└┬──────┘└┬──────┘
+/// ┌▽────────▽────────▽────────▽┐
+/// │Limiter                     │
+/// └┬───────────────────────────┘
+/// ┌▽───────────────────────────┐
+/// │Volume                      │
+/// └┬───────────────────────────┘
+/// ┌▽──────┐
+/// │MainBus│
+/// └───────┘
+/// ```
+///
+/// The bus is despawned along with its pool. Note that the bus is only
+/// read when the pool is populated, so it can't be swapped out afterwards.
+#[derive(Debug, Component)]
+#[relationship_target(relationship = PoolBusOf, linked_spawn)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct PoolBus(Entity);
 
+impl PoolBus {
+    /// Returns the bus entity.
+    pub fn bus(&self) -> Entity {
+        self.0
+    }
+}
+
+/// The [`SamplerNode`] entities that belong to a [`SamplerPool`].
+///
+/// Samplers are despawned along with their pool.
 #[derive(Debug, Component)]
 #[relationship_target(relationship = PoolSamplerOf, linked_spawn)]
-struct PoolSamplers(Vec<Entity>);
+pub struct PoolSamplers(Vec<Entity>);
 
 /// A sampler assignment relationships.
 ///
@@ -299,17 +468,89 @@ struct PoolSamplers(Vec<Entity>);
 #[derive(Debug, Component)]
 #[relationship(relationship_target = Sampler)]
 #[component(on_remove = Self::on_remove_hook)]
+#[require(SamplerGeneration)]
 #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct SamplerOf(pub Entity);
 
 impl SamplerOf {
     fn on_remove_hook(mut world: DeferredWorld, context: HookContext) {
+        // Releasing samplers play out before stopping.
+        if world.get::<SamplerRelease>(context.entity).is_some() {
+            return;
+        }
+
         if let Some(mut sampler) = world.get_mut::<SamplerNode>(context.entity) {
             sampler.stop();
         }
     }
 }
 
+/// A sampler playing out a release after its sample player despawned.
+///
+/// The sampler isn't stopped when its assignment is removed. Instead,
+/// it's stopped at the given instant, and until then it's only reused
+/// if its pool runs out of inactive samplers.
+#[derive(Debug, Component)]
+pub(crate) struct SamplerRelease(pub(crate) InstantSeconds);
+
+/// Stop samplers whose release has elapsed.
+fn finish_releases(
+    mut samplers: Query<(Entity, &SamplerRelease, &mut SamplerNode), Without<SamplerOf>>,
+    time: Res<bevy_time::Time<Audio>>,
+    mut commands: Commands,
+) {
+    for (entity, release, mut node) in &mut samplers {
+        if time.now() >= release.0 {
+            node.stop();
+            commands.entity(entity).remove::<SamplerRelease>();
+        }
+    }
+}
+
+/// Tracks which assignment a sampler's playback state belongs to.
+///
+/// A sampler's shared state only reports the last playback ID it
+/// finished, so a sample that finishes just as the sampler is
+/// reassigned can look like the newcomer finishing. Each assignment
+/// starts a new generation with a fresh playback ID, and completion
+/// is only reported for playback that belongs to the current one.
+#[derive(Debug, Default, Component)]
+pub(crate) struct SamplerGeneration {
+    generation: u64,
+    sample: Option<Entity>,
+    /// The first playback ID issued in this generation.
+    playback_id: u64,
+}
+
+impl SamplerGeneration {
+    /// Returns `true` if `sample` is the one this generation was started for.
+    fn is_current(&self, sample: Entity) -> bool {
+        self.sample == Some(sample)
+    }
+
+    /// Begin a new generation for `sample`.
+    ///
+    /// The sample's play notification is reissued so its ID is
+    /// newer than anything the sampler has already finished.
+    fn advance(&mut self, sample: Entity, settings: &mut PlaybackSettings, node: &mut SamplerNode) {
+        let playing = *settings.play;
+        *settings.play = playing;
+        node.play = settings.play;
+
+        self.generation += 1;
+        self.sample = Some(sample);
+        self.playback_id = node.playback_id();
+    }
+
+    /// Returns `true` if the current generation's playback has finished.
+    pub(crate) fn finished(&self, node: &SamplerNode, state: &SamplerState) -> bool {
+        self.sample.is_some()
+            && node.playback_id() >= self.playback_id
+            && *node.play
+            && state.playback_finished(node.playback_id())
+    }
+}
+
 /// A relationship that provides information about a sample player's
 /// assigned [`SamplerNode`].
 ///
@@ -416,10 +657,26 @@ impl Sampler {
     // Whenever this link is broken, all the effects should also remove their control.
     fn observe_discard(
         trigger: On<Discard, Self>,
-        target: Query<&SampleEffects>,
+        target: Query<(&Sampler, Option<&SampleEffects>, Has<SharedEffects>)>,
+        children: Query<&Children>,
         mut commands: Commands,
     ) {
-        let Ok(effects) = target.get(trigger.entity) else {
+        let Ok((sampler, effects, shared)) = target.get(trigger.entity) else {
+            return;
+        };
+
+        // Shared effects may still be followed by other samplers,
+        // so only this sampler's chain should stop following.
+        if shared {
+            for follower in children.get(sampler.sampler).into_iter().flatten() {
+                if let Ok(mut entity) = commands.get_entity(follower) {
+                    entity.try_remove::<crate::node::follower::FollowerOf>();
+                }
+            }
+            return;
+        }
+
+        let Some(effects) = effects else {
             return;
         };
 
@@ -522,7 +779,13 @@ fn fetch_effect_ids(
 /// A kind of specialization of [`FollowerOf`][crate::node::follower::FollowerOf] for
 /// sampler nodes.
 fn watch_sample_players(
-    mut q: Query<(Entity, &mut SamplerNode, &mut AudioEvents, &SamplerOf)>,
+    mut q: Query<(
+        Entity,
+        &mut SamplerNode,
+        &mut AudioEvents,
+        &SamplerOf,
+        &mut SamplerGeneration,
+    )>,
     mut samples: Query<
         (
             &mut PlaybackSettings,
@@ -536,11 +799,15 @@ fn watch_sample_players(
 ) -> Result {
     let render_range = time.render_range();
 
-    for (sampler_entity, mut sampler_node, mut events, sample) in q.iter_mut() {
+    for (sampler_entity, mut sampler_node, mut events, sample, mut generation) in q.iter_mut() {
         let Ok((mut settings, mut source_events, timestamp)) = samples.get_mut(sample.0) else {
             continue;
         };
 
+        if !generation.is_current(sample.0) {
+            generation.advance(sample.0, &mut settings, &mut sampler_node);
+        }
+
         // The order here is very important!
         // If we applied the scheduled events before this, the
         // sampler itself would call `value_at` afterwards, meaning we'd
@@ -564,57 +831,197 @@ fn watch_sample_players(
     Ok(())
 }
 
+/// The [`PlaybackDirection`] of the sample currently loaded into a sampler.
+#[derive(Debug, Component)]
+struct AssignedDirection(PlaybackDirection);
+
+impl AssignedDirection {
+    /// Get the sample resource for `direction`.
+    fn sample(
+        asset: &AudioSample,
+        direction: PlaybackDirection,
+    ) -> ArcGc<dyn SampleResource + Send + Sync> {
+        match direction {
+            PlaybackDirection::Forward => asset.get(),
+            PlaybackDirection::Reverse => asset.get_reversed(),
+        }
+    }
+
+    /// Mirror `playhead` for a sample that's `len` frames long.
+    ///
+    /// Looping samplers may report playheads past the end of
+    /// the sample, so the playhead is wrapped into the sample first.
+    fn mirror(len: u64, playhead: u64) -> u64 {
+        if len == 0 {
+            return 0;
+        }
+
+        len - playhead % len
+    }
+}
+
+/// Swap in the reversed (or un-reversed) sample when a
+/// player's direction changes, mirroring the playhead.
+fn watch_direction(
+    mut players: Query<
+        (
+            &mut PlaybackSettings,
+            &SamplePlayer,
+            &Sampler,
+            Option<&SampleQueue>,
+        ),
+        (Changed<PlaybackSettings>, Without<SamplerOf>),
+    >,
+    mut samplers: Query<(&mut AssignedDirection, &mut AudioEvents), With<SamplerOf>>,
+    assets: Res<Assets<AudioSample>>,
+) {
+    for (mut settings, player, sampler, queue) in &mut players {
+        let Ok((mut assigned, mut events)) = samplers.get_mut(sampler.sampler()) else {
+            continue;
+        };
+
+        if assigned.0 == settings.direction {
+            continue;
+        }
+
+        let sample = queue
+            .and_then(SampleQueue::current)
+            .unwrap_or(&player.sample);
+        let Some(asset) = assets.get(sample) else {
+            continue;
+        };
+
+        let len = asset.get().len_frames();
+        let playhead = sampler
+            .try_playhead_frames()
+            .map(|p| p.0.max(0) as u64)
+            .unwrap_or_default();
+
+        assigned.0 = settings.direction;
+        events.push(SamplerNode::set_dyn_sample_event(
+            AssignedDirection::sample(asset, settings.direction),
+        ));
+
+        // Replacing the sample resets the sampler, so we
+        // restart playback from the mirrored position.
+        settings.play_from = PlayFrom::Frames(AssignedDirection::mirror(len, playhead));
+        if *settings.play {
+            // Notify the sampler even though the value is unchanged.
+            *settings.play = true;
+        }
+    }
+}
+
+/// Schedule declicked seeks on the sampler assigned to each player.
+fn apply_smooth_seeks(
+    mut players: Query<
+        (
+            Entity,
+            &SmoothSeek,
+            &mut PlaybackSettings,
+            &mut AudioEvents,
+            Option<&Sampler>,
+        ),
+        Without<SamplerOf>,
+    >,
+    mut samplers: Query<(&SamplerNode, &mut AudioEvents), With<SamplerOf>>,
+    time: Res<bevy_time::Time<Audio>>,
+    mut commands: Commands,
+) {
+    for (entity, seek, mut settings, mut events, sampler) in &mut players {
+        commands.entity(entity).remove::<SmoothSeek>();
+
+        let Some((sampler_node, mut sampler_events)) =
+            sampler.and_then(|s| samplers.get_mut(s.sampler()).ok())
+        else {
+            settings.play_from = seek.position;
+            continue;
+        };
+
+        let half = DurationSeconds(seek.fade.0 * 0.5);
+        let start = time.now();
+        let midpoint = start + half;
+        let end = midpoint + half;
+
+        let volume = sampler_node.volume;
+        sampler_node.fade_param_at(Volume::SILENT, start, midpoint, &mut sampler_events, |s| {
+            &mut s.volume
+        });
+        sampler_node.fade_param_at(volume, midpoint, end, &mut sampler_events, |s| {
+            &mut s.volume
+        });
+
+        settings.play_at(Some(seek.position), midpoint, &mut events);
+    }
+}
+
+/// A sample player that's fading out before completion.
+#[derive(Debug, Component)]
+pub(crate) struct Stopping {
+    pub(crate) fade: DurationSeconds,
+    pub(crate) end: Option<InstantSeconds>,
+}
+
+/// Fade out stopping samples, completing them once silent.
+fn apply_stops(
+    mut players: Query<(Entity, &mut Stopping, Option<&Sampler>), Without<SamplerOf>>,
+    mut samplers: Query<(&SamplerNode, &mut AudioEvents), With<SamplerOf>>,
+    time: Res<bevy_time::Time<Audio>>,
+    mut commands: Commands,
+) {
+    let now = time.now();
+
+    for (entity, mut stopping, sampler) in &mut players {
+        let end = match stopping.end {
+            Some(end) => end,
+            None => {
+                let Some((sampler_node, mut events)) =
+                    sampler.and_then(|s| samplers.get_mut(s.sampler()).ok())
+                else {
+                    // Queued samples have nothing to fade.
+                    commands.trigger(PlaybackCompletion {
+                        entity,
+                        reason: CompletionReason::PlaybackStopped,
+                    });
+                    continue;
+                };
+
+                let end = now + stopping.fade;
+                sampler_node
+                    .fade_param_at(Volume::SILENT, now, end, &mut events, |s| &mut s.volume);
+                stopping.end = Some(end);
+
+                end
+            }
+        };
+
+        if now >= end {
+            commands.trigger(PlaybackCompletion {
+                entity,
+                reason: CompletionReason::PlaybackStopped,
+            });
+        }
+    }
+}
+
 fn spawn_chain(
+    pool: Entity,
     bus: Entity,
     config: Option<SamplerConfig>,
     effects: &[Entity],
     commands: &mut Commands,
 ) -> Entity {
-    let sampler = commands
-        .spawn((
-            SamplerNode::default(),
-            config.unwrap_or_default(),
-            PoolSamplerOf(bus),
-        ))
-        .id();
-
-    let effects = effects.to_vec();
-    commands.queue(move |world: &mut World| -> Result {
-        let mut cloner = EntityCloner::build_opt_out(world);
-        cloner.deny::<EffectOf>();
-        let mut cloner = cloner.finish();
-
-        let mut chain = Vec::new();
-        chain.reserve_exact(effects.len() + 1);
-        for effect in effects {
-            chain.push(cloner.spawn_clone(world, effect));
-        }
-        chain.push(bus);
-
-        // Until we come up with a good way to implement the
-        // connect trait for `WorldEntityMut`, we're stuck with
-        // a bit of boilerplate.
-        world
-            .get_entity_mut(sampler)?
-            .add_children(&chain)
-            .entry::<PendingConnections>()
-            .or_default()
-            .into_mut()
-            .push(PendingEdge::new(chain[0], None));
-
-        for pair in chain.windows(2) {
-            world
-                .get_entity_mut(pair[0])?
-                .entry::<PendingConnections>()
-                .or_default()
-                .into_mut()
-                .push(PendingEdge::new(pair[1], None));
-        }
-
-        Ok(())
-    });
-
-    sampler
+    chain::SpawnChain::new(effects.iter().copied())
+        .with_target(bus)
+        .spawn(
+            (
+                SamplerNode::default(),
+                config.unwrap_or_default(),
+                PoolSamplerOf(pool),
+            ),
+            commands,
+        )
+        .head
 }
 
 /// The size of a [`SamplerPool`].
@@ -655,66 +1062,331 @@ impl Default for DefaultPoolSize {
     }
 }
 
-fn populate_pool(
-    q: Query<
-        (
-            Entity,
-            &SamplerConfig,
-            Option<&PoolSize>,
-            Option<&SampleEffects>,
-            Option<&EffectId>,
-        ),
-        (
-            With<PoolLabelContainer>,
-            With<PoolMarker>,
-            Without<PoolSamplers>,
-        ),
-    >,
-    mut effects: Query<&EffectId>,
-    default_pool_size: Res<DefaultPoolSize>,
-    mut commands: Commands,
-) -> Result {
-    for (pool, config, size, pool_effects, effect_id) in &q {
-        if effect_id.is_none() {
-            commands.entity(pool).insert(VolumeNode::default());
-        }
-
-        let component_ids = fetch_effect_ids(
-            pool_effects.map(|e| e.deref()).unwrap_or(&[]),
-            &mut effects.as_query_lens(),
-        )?;
-
-        let size = size
-            .map(|p| p.0.clone())
-            .unwrap_or(default_pool_size.0.clone());
-
-        commands
-            .entity(pool)
-            .insert((PoolShape(component_ids), PoolSize(size.clone())));
+/// The default [`SamplerConfig`] applied to [`SamplerPool`]s.
+///
+/// Pools spawned without an explicit [`SamplerConfig`], including
+/// dynamic pools, receive a copy of this configuration when they're
+/// populated. This makes it easy to set the channel count, playback
+/// quality, and declicking for a whole project at once.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// use bevy_seedling::firewheel::channel_config::NonZeroChannelCount;
+///
+/// fn plugin(app: &mut App) {
+///     app.insert_resource(DefaultSamplerConfig(SamplerConfig {
+///         channels: NonZeroChannelCount::new(1).unwrap(),
+///         ..Default::default()
+///     }));
+/// }
+/// ```
+///
+/// Changing this resource doesn't affect pools that have already
+/// been populated. A pool's own [`SamplerConfig`], on the other hand,
+/// is applied to all of its samplers, including when it changes
+/// after the pool is spawned.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct DefaultSamplerConfig(pub SamplerConfig);
+
+/// A category of target platform, used by [`PoolSizePolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum TargetPlatform {
+    /// Desktop operating systems.
+    Desktop,
+    /// Android and iOS.
+    Mobile,
+    /// The web, via WebAssembly.
+    Web,
+}
 
-        let size = (*size.start()).max(1);
-        for _ in 0..size {
-            spawn_chain(
-                pool,
-                Some(*config),
-                pool_effects.map(|e| e.deref()).unwrap_or(&[]),
-                &mut commands,
-            );
+impl TargetPlatform {
+    /// The platform this crate was compiled for.
+    pub const fn current() -> Self {
+        if cfg!(target_arch = "wasm32") {
+            Self::Web
+        } else if cfg!(any(target_os = "android", target_os = "ios")) {
+            Self::Mobile
+        } else {
+            Self::Desktop
         }
     }
-
-    Ok(())
 }
 
-/// An event triggered on [`SamplePlayer`] entities when
-/// their playback completes.
+/// Scales the [`DefaultPoolSize`] according to the [`TargetPlatform`].
 ///
-/// Note that this may be triggered even when the sample isn't
-/// played, such as when it can't find space in a sampler pool
-/// within its [`SampleQueueLifetime`][crate::sample::SampleQueueLifetime]
-/// component.
-#[derive(Debug, EntityEvent)]
-#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+/// Since the [`DefaultPoolSize`] also applies to the pools in
+/// [`AudioGraphTemplate::Game`][crate::prelude::AudioGraphTemplate::Game],
+/// this lets weaker platforms claim fewer resources without per-platform
+/// configuration. Pools with an explicit [`PoolSize`] are unaffected.
+///
+/// The policy is applied once in [`PreStartup`], so like the
+/// [`AudioGraphTemplate`][crate::prelude::AudioGraphTemplate], it
+/// should be inserted before the app runs. The result is recorded
+/// in [`ResolvedPoolSize`].
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// App::new()
+///     .add_plugins((DefaultPlugins, SeedlingPlugins))
+///     .insert_resource(PoolSizePolicy {
+///         web: 0.25,
+///         ..Default::default()
+///     })
+///     .run();
+/// ```
+///
+/// By default, mobile and web pools are half the size of desktop pools.
+#[derive(Debug, Clone, PartialEq, Resource)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct PoolSizePolicy {
+    /// The scale applied on [`TargetPlatform::Desktop`].
+    pub desktop: f32,
+    /// The scale applied on [`TargetPlatform::Mobile`].
+    pub mobile: f32,
+    /// The scale applied on [`TargetPlatform::Web`].
+    pub web: f32,
+}
+
+impl Default for PoolSizePolicy {
+    fn default() -> Self {
+        Self {
+            desktop: 1.0,
+            mobile: 0.5,
+            web: 0.5,
+        }
+    }
+}
+
+impl PoolSizePolicy {
+    /// Get the scale for `platform`.
+    pub fn scale(&self, platform: TargetPlatform) -> f32 {
+        match platform {
+            TargetPlatform::Desktop => self.desktop,
+            TargetPlatform::Mobile => self.mobile,
+            TargetPlatform::Web => self.web,
+        }
+    }
+
+    /// Scale `size` for `platform`.
+    ///
+    /// Non-zero bounds never scale below one, so `0..=0`
+    /// (which disables dynamic pools) is preserved.
+    pub fn apply(
+        &self,
+        size: RangeInclusive<usize>,
+        platform: TargetPlatform,
+    ) -> RangeInclusive<usize> {
+        let scale = self.scale(platform);
+        let apply = |bound: usize| {
+            if bound == 0 {
+                0
+            } else {
+                ((bound as f32 * scale).round() as usize).max(1)
+            }
+        };
+
+        apply(*size.start())..=apply(*size.end())
+    }
+}
+
+/// The [`DefaultPoolSize`] selected by the [`PoolSizePolicy`] at startup.
+#[derive(Debug, Clone, Resource)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct ResolvedPoolSize {
+    /// The detected platform.
+    pub platform: TargetPlatform,
+    /// The scale applied to the [`DefaultPoolSize`].
+    pub scale: f32,
+    /// The resulting default size.
+    pub size: RangeInclusive<usize>,
+}
+
+fn apply_pool_size_policy(
+    policy: Res<PoolSizePolicy>,
+    mut default_size: ResMut<DefaultPoolSize>,
+    mut commands: Commands,
+) {
+    let platform = TargetPlatform::current();
+    let size = policy.apply(default_size.0.clone(), platform);
+
+    default_size.0 = size.clone();
+    commands.insert_resource(ResolvedPoolSize {
+        platform,
+        scale: policy.scale(platform),
+        size,
+    });
+}
+
+/// The strategy a [`SamplerPool`] uses to choose samplers for queued samples.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// # fn spawn_pool(mut commands: Commands) {
+/// #[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct FootstepPool;
+///
+/// commands.spawn((
+///     SamplerPool(FootstepPool),
+///     PoolSize(8..=8),
+///     AssignmentStrategy::RoundRobin,
+/// ));
+/// # }
+/// ```
+///
+/// Regardless of strategy, idle samplers are always preferred, and
+/// a sample will never steal a sampler from a higher-priority sample.
+/// The strategy decides between samplers that are otherwise equally suitable.
+/// If no strategy is provided, [`AssignmentStrategy::BestScore`] is used.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Component)]
+#[component(immutable)]
+#[require(AssignmentHistory)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum AssignmentStrategy {
+    /// Pick the first suitable sampler in the pool.
+    ///
+    /// When the pool is full, samplers are ranked by priority and
+    /// playback progress.
+    #[default]
+    BestScore,
+    /// Cycle through the pool's samplers in order, starting after
+    /// the most recently assigned sampler.
+    ///
+    /// This spreads work evenly across a pool, which can be helpful
+    /// when each sampler's effects hold state, like reverb tails.
+    RoundRobin,
+    /// Pick the sampler that was assigned least recently.
+    LeastRecentlyUsed,
+}
+
+/// Tracks the state required by [`AssignmentStrategy`].
+#[derive(Debug, Default, Component)]
+pub(crate) struct AssignmentHistory {
+    /// The index of the most recently assigned sampler.
+    last: Option<usize>,
+    /// The total number of assignments made.
+    count: u64,
+}
+
+/// The value of [`AssignmentHistory::count`] when a sampler was last assigned.
+#[derive(Debug, Clone, Copy, Component)]
+pub(crate) struct LastAssigned(u64);
+
+impl AssignmentStrategy {
+    /// Rank the sampler at `index` in a pool of `len` samplers,
+    /// where lower ranks are assigned first.
+    pub(crate) fn rank(
+        &self,
+        index: usize,
+        len: usize,
+        history: &AssignmentHistory,
+        last_assigned: Option<&LastAssigned>,
+    ) -> u64 {
+        match self {
+            Self::BestScore => index as u64,
+            Self::RoundRobin => {
+                let start = history.last.map(|l| l + 1).unwrap_or_default();
+                ((index + len - start % len.max(1)) % len.max(1)) as u64
+            }
+            Self::LeastRecentlyUsed => last_assigned.map(|l| l.0).unwrap_or_default(),
+        }
+    }
+}
+
+impl AssignmentHistory {
+    /// Record an assignment to the sampler at `index`.
+    pub(crate) fn record(&mut self, index: usize) -> LastAssigned {
+        self.last = Some(index);
+        self.count += 1;
+        LastAssigned(self.count)
+    }
+}
+
+fn populate_pool(
+    q: Query<
+        (
+            Entity,
+            Option<&SamplerConfig>,
+            Option<&PoolSize>,
+            Option<&SampleEffects>,
+            Option<&EffectId>,
+            Option<&PoolBus>,
+            Has<PreWarm>,
+        ),
+        (
+            With<PoolLabelContainer>,
+            With<PoolMarker>,
+            Without<PoolSamplers>,
+        ),
+    >,
+    mut effects: Query<&EffectId>,
+    default_pool_size: Res<DefaultPoolSize>,
+    default_config: Res<DefaultSamplerConfig>,
+    mut commands: Commands,
+) -> Result {
+    for (pool, config, size, pool_effects, effect_id, pool_bus, pre_warm) in &q {
+        if effect_id.is_none() {
+            commands.entity(pool).insert(VolumeNode::default());
+        }
+
+        let config = match config {
+            Some(config) => *config,
+            None => {
+                commands.entity(pool).insert(default_config.0);
+                default_config.0
+            }
+        };
+
+        let bus = match pool_bus {
+            Some(pool_bus) => {
+                commands
+                    .entity(pool_bus.bus())
+                    .insert_if_new(DefaultRoute::new(pool));
+                pool_bus.bus()
+            }
+            None => pool,
+        };
+
+        let component_ids = fetch_effect_ids(
+            pool_effects.map(|e| e.deref()).unwrap_or(&[]),
+            &mut effects.as_query_lens(),
+        )?;
+
+        let size = size
+            .map(|p| p.0.clone())
+            .unwrap_or(default_pool_size.0.clone());
+
+        commands
+            .entity(pool)
+            .insert((PoolShape(component_ids), PoolSize(size.clone())));
+
+        let size = if pre_warm { *size.end() } else { *size.start() }.max(1);
+        for _ in 0..size {
+            spawn_chain(
+                pool,
+                bus,
+                Some(config),
+                pool_effects.map(|e| e.deref()).unwrap_or(&[]),
+                &mut commands,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// An event triggered on [`SamplePlayer`] entities when
+/// their playback completes.
+///
+/// Note that this may be triggered even when the sample isn't
+/// played, such as when it can't find space in a sampler pool
+/// within its [`SampleQueueLifetime`][crate::sample::SampleQueueLifetime]
+/// component.
+#[derive(Debug, EntityEvent)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct PlaybackCompletion {
     /// The [`SamplePlayer`] entity.
     pub entity: Entity,
@@ -739,6 +1411,8 @@ pub enum CompletionReason {
     /// This means the sample never actually played before this
     /// event triggered.
     QueueLifetimeElapsed,
+    /// The sample was stopped with [`StopAll`] or [`StopWhere`].
+    PlaybackStopped,
 }
 
 /// Clean up sample resources according to their playback settings.
@@ -756,9 +1430,9 @@ fn remove_finished(
         return Ok(());
     };
 
-    match settings.on_complete {
+    match &settings.on_complete {
         OnComplete::Preserve => {
-            entity.remove::<(Sampler, QueuedSample, SkipTimer)>();
+            entity.remove::<(Sampler, QueuedSample, Stopping)>();
         }
         OnComplete::Remove => {
             entity
@@ -768,28 +1442,142 @@ fn remove_finished(
                     PoolLabelContainer,
                     Sampler,
                     QueuedSample,
-                    SkipTimer,
+                    Stopping,
                     AudioEvents,
                 )>();
         }
         OnComplete::Despawn => {
             entity.despawn();
         }
+        OnComplete::Trigger(callback) => {
+            callback.run(sample_entity, &mut commands);
+            commands.entity(sample_entity).despawn();
+        }
     }
 
     Ok(())
 }
 
+/// Play the next item in each [`SampleQueue`] once the current one finishes.
+fn advance_sample_queues(
+    samplers: Query<(
+        Entity,
+        &SamplerNode,
+        &SamplerOf,
+        &SamplerGeneration,
+        &AudioState<SamplerState>,
+        &AssignedDirection,
+    )>,
+    mut sampler_events: Query<&mut AudioEvents, With<SamplerOf>>,
+    mut players: Query<
+        (
+            &mut SampleQueue,
+            &SamplePlayer,
+            &mut PlaybackSettings,
+            &mut AudioEvents,
+        ),
+        Without<SamplerOf>,
+    >,
+    assets: Res<Assets<AudioSample>>,
+    time: Res<bevy_time::Time<Audio>>,
+    mut commands: Commands,
+) {
+    for (sampler, node, active, generation, state, direction) in &samplers {
+        let Ok((mut queue, player, mut settings, mut events)) = players.get_mut(active.0) else {
+            continue;
+        };
+
+        // Wait until the sampler picks up the next item.
+        if let Some(id) = queue.starting {
+            if node.playback_id() == id {
+                continue;
+            }
+
+            queue.starting = None;
+        }
+
+        if !generation.is_current(active.0) || !generation.finished(node, &state.0) {
+            continue;
+        }
+
+        let Some(next) = queue.items.front() else {
+            continue;
+        };
+
+        // Wait for the next sample to load.
+        let Some(asset) = assets.get(&next.sample) else {
+            continue;
+        };
+
+        let Ok(mut sampler_events) = sampler_events.get_mut(sampler) else {
+            continue;
+        };
+
+        let next = queue.items.pop_front().unwrap();
+        // `SamplePlayer` is immutable, so the queue tracks the playing item.
+        let completed = queue
+            .current
+            .replace(next.sample)
+            .unwrap_or_else(|| player.sample.clone());
+
+        commands.trigger(SampleQueueItemComplete {
+            entity: active.0,
+            index: queue.index,
+            sample: completed,
+        });
+
+        queue.index += 1;
+        queue.starting = Some(node.playback_id());
+
+        sampler_events.push(SamplerNode::set_dyn_sample_event(
+            AssignedDirection::sample(asset, direction.0),
+        ));
+
+        if next.gap.0 > 0.0 {
+            settings.play_at(
+                Some(PlayFrom::Frames(0)),
+                time.now() + next.gap,
+                &mut events,
+            );
+        } else {
+            settings.play_from = PlayFrom::Frames(0);
+            // Notify the sampler even though the value is unchanged.
+            *settings.play = true;
+        }
+    }
+}
+
 /// Automatically remove or despawn sample players when their
 /// sample has finished playing.
+///
+/// Completion is only attributed to the sample that started the
+/// sampler's current [`SamplerGeneration`].
 fn poll_finished(
-    nodes: Query<(&SamplerNode, &SamplerOf, &AudioState<SamplerState>)>,
+    nodes: Query<(
+        &SamplerNode,
+        &SamplerOf,
+        &SamplerGeneration,
+        &AudioState<SamplerState>,
+    )>,
+    queues: Query<(&SampleQueue, &SamplePlayer)>,
     mut commands: Commands,
 ) {
-    for (node, active, state) in nodes.iter() {
-        let finished = *node.play && state.0.playback_finished(node.playback_id());
+    for (node, active, generation, state) in nodes.iter() {
+        let finished = generation.is_current(active.0) && generation.finished(node, &state.0);
 
         if finished {
+            if let Ok((queue, player)) = queues.get(active.0) {
+                if queue.is_pending() {
+                    continue;
+                }
+
+                commands.trigger(SampleQueueItemComplete {
+                    entity: active.0,
+                    index: queue.index,
+                    sample: queue.current().unwrap_or(&player.sample).clone(),
+                });
+            }
+
             commands.trigger(PlaybackCompletion {
                 entity: active.0,
                 reason: CompletionReason::PlaybackComplete,
@@ -853,6 +1641,131 @@ impl<T: PoolLabel + Component + Clone> Command for PoolDespawn<T> {
     }
 }
 
+/// The default fade applied by [`StopAll`] and [`StopWhere`].
+const DEFAULT_STOP_FADE: DurationSeconds = DurationSeconds(0.01);
+
+/// A command that stops all sample players.
+///
+/// Each sample is faded out to prevent clicks before completing
+/// according to its [`OnComplete`] setting. Samples waiting
+/// in a pool's queue complete immediately. Samples that have
+/// already finished, like those kept with [`OnComplete::Preserve`],
+/// are left alone.
+///
+/// This can be used directly or via the [`PoolCommands`] trait.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn game_over(mut commands: Commands) {
+///     commands.queue(StopAll::default().with_fade(DurationSeconds(0.5)));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StopAll {
+    fade: DurationSeconds,
+}
+
+impl Default for StopAll {
+    fn default() -> Self {
+        Self {
+            fade: DEFAULT_STOP_FADE,
+        }
+    }
+}
+
+impl StopAll {
+    /// Set the fade-out duration.
+    ///
+    /// By default, this is 10 milliseconds.
+    pub fn with_fade(self, fade: DurationSeconds) -> Self {
+        Self { fade }
+    }
+}
+
+impl Command for StopAll {
+    type Out = ();
+    fn apply(self, world: &mut World) {
+        StopWhere::new(|_| true).with_fade(self.fade).apply(world);
+    }
+}
+
+/// A command that stops all sample players matching a filter.
+///
+/// Each sample is faded out to prevent clicks before completing
+/// according to its [`OnComplete`] setting. Samples waiting
+/// in a pool's queue complete immediately. Samples that have
+/// already finished, like those kept with [`OnComplete::Preserve`],
+/// are left alone.
+///
+/// This can be used directly or via the [`PoolCommands`] trait.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// #[derive(Component)]
+/// struct BossMusic;
+///
+/// fn boss_defeated(mut commands: Commands) {
+///     commands.queue(StopWhere::new(|e| e.contains::<BossMusic>()));
+/// }
+/// ```
+pub struct StopWhere<F> {
+    filter: F,
+    fade: DurationSeconds,
+}
+
+impl<F> core::fmt::Debug for StopWhere<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StopWhere")
+            .field("fade", &self.fade)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: Fn(EntityRef) -> bool + Send + 'static> StopWhere<F> {
+    /// Construct a new [`StopWhere`] with the provided filter.
+    ///
+    /// The filter receives each [`SamplePlayer`] entity.
+    pub fn new(filter: F) -> Self {
+        Self {
+            filter,
+            fade: DEFAULT_STOP_FADE,
+        }
+    }
+
+    /// Set the fade-out duration.
+    ///
+    /// By default, this is 10 milliseconds.
+    pub fn with_fade(self, fade: DurationSeconds) -> Self {
+        Self { fade, ..self }
+    }
+}
+
+impl<F: Fn(EntityRef) -> bool + Send + 'static> Command for StopWhere<F> {
+    type Out = ();
+    fn apply(self, world: &mut World) {
+        let mut players = world.query_filtered::<EntityRef, (
+            With<SamplePlayer>,
+            Or<(With<Sampler>, With<QueuedSample>)>,
+            Without<Stopping>,
+        )>();
+
+        let matches: Vec<_> = players
+            .iter(world)
+            .filter(|e| (self.filter)(*e))
+            .map(|e| e.id())
+            .collect();
+
+        for entity in matches {
+            world.entity_mut(entity).insert(Stopping {
+                fade: self.fade,
+                end: None,
+            });
+        }
+    }
+}
+
 /// Provides methods on [`Commands`] to manage sample pools.
 pub trait PoolCommands {
     /// Despawn a sample pool, cleaning up its resources
@@ -861,31 +1774,341 @@ pub trait PoolCommands {
     /// Despawning the terminal volume node recursively
     /// will produce the same effect.
     fn despawn_pool<T: PoolLabel + Component + Clone>(&mut self, label: T);
+
+    /// Stop all sample players with a short fade.
+    ///
+    /// See [`StopAll`].
+    fn stop_all(&mut self);
+
+    /// Stop all sample players in the pool labeled `T`
+    /// with a short fade.
+    ///
+    /// See [`StopWhere`].
+    fn stop_pool<T: PoolLabel + Component + Clone>(&mut self, label: T);
+
+    /// Stop all sample players matching `filter` with a short fade.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// #[derive(Component)]
+    /// struct BossMusic;
+    ///
+    /// fn boss_defeated(mut commands: Commands) {
+    ///     commands.stop_samples(|e| e.contains::<BossMusic>());
+    /// }
+    /// ```
+    ///
+    /// See [`StopWhere`].
+    fn stop_samples<F: Fn(EntityRef) -> bool + Send + 'static>(&mut self, filter: F);
 }
 
 impl PoolCommands for Commands<'_, '_> {
     fn despawn_pool<T: PoolLabel + Component + Clone>(&mut self, label: T) {
         self.queue(PoolDespawn::new(label));
     }
-}
 
-#[cfg(test)]
-mod test {
-    use std::time::Instant;
+    fn stop_all(&mut self) {
+        self.queue(StopAll::default());
+    }
 
-    use super::*;
-    use crate::{
-        prelude::*,
-        sample_effects,
-        test::{prepare_app, run},
-    };
-    use bevy_seedling_macros::PoolLabel;
-    use firewheel::nodes::fast_filters::lowpass::FastLowpassNode;
+    fn stop_pool<T: PoolLabel + Component + Clone>(&mut self, label: T) {
+        let interned = label.intern();
+        self.queue(StopWhere::new(move |e| {
+            e.get::<PoolLabelContainer>()
+                .is_some_and(|container| container.label == interned)
+        }));
+    }
 
-    #[derive(PoolLabel, Clone, Debug, PartialEq, Eq, Hash)]
-    struct TestPool;
+    fn stop_samples<F: Fn(EntityRef) -> bool + Send + 'static>(&mut self, filter: F) {
+        self.queue(StopWhere::new(filter));
+    }
+}
 
-    #[test]
+/// An event that restarts a [`SamplePlayer`] from the beginning.
+///
+/// If the sample holds a sampler, it's replayed in place with
+/// [`PlaybackSettings::restart`]. Otherwise, as with samples that
+/// completed with [`OnComplete::Preserve`], it's queued again
+/// without reloading its asset.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn retrigger(mut commands: Commands, engine: Single<Entity, With<SamplePlayer>>) {
+///     commands.trigger(Restart { entity: *engine });
+/// }
+/// ```
+#[derive(Debug, EntityEvent)]
+pub struct Restart {
+    /// The [`SamplePlayer`] entity.
+    pub entity: Entity,
+}
+
+fn restart_samples(
+    trigger: On<Restart>,
+    mut samples: Query<(&mut PlaybackSettings, Has<Sampler>), With<SamplePlayer>>,
+    mut commands: Commands,
+) {
+    let Ok((mut settings, has_sampler)) = samples.get_mut(trigger.entity) else {
+        return;
+    };
+
+    settings.restart();
+
+    if !has_sampler {
+        commands.entity(trigger.entity).insert(QueuedSample);
+    }
+}
+
+/// An event that changes a [`SamplePlayer`]'s [`RepeatMode`] without restarting it.
+///
+/// If the sample holds a sampler, the new mode is sent to the sampler directly,
+/// so a looping sample can be told to finish its current iteration, for example.
+/// Otherwise, the sample simply picks up the new mode once it's assigned.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn stop_looping(mut commands: Commands, engine: Single<Entity, With<SamplePlayer>>) {
+///     commands.trigger(SetRepeatMode {
+///         entity: *engine,
+///         repeat_mode: RepeatMode::PlayOnce,
+///     });
+/// }
+/// ```
+///
+/// Where the sampler can't apply the change in place, such as a sample
+/// that has already finished, it takes effect the next time the sample is played.
+#[derive(Debug, EntityEvent)]
+pub struct SetRepeatMode {
+    /// The [`SamplePlayer`] entity.
+    pub entity: Entity,
+    /// The new repeat mode.
+    pub repeat_mode: RepeatMode,
+}
+
+/// An event that changes a [`SamplePlayer`]'s volume without restarting it.
+///
+/// If the sample holds a sampler, the new volume is sent to the sampler directly
+/// and smoothed like any other parameter change. Otherwise, the sample simply
+/// picks up the new volume once it's assigned.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn quieter(mut commands: Commands, engine: Single<Entity, With<SamplePlayer>>) {
+///     commands.trigger(SetStaticVolume {
+///         entity: *engine,
+///         volume: Volume::Decibels(-6.0),
+///     });
+/// }
+/// ```
+///
+/// For frequent or scheduled volume changes, prefer a
+/// [`VolumeNode`] in the sample's [`SampleEffects`].
+#[derive(Debug, EntityEvent)]
+pub struct SetStaticVolume {
+    /// The [`SamplePlayer`] entity.
+    pub entity: Entity,
+    /// The new volume.
+    pub volume: Volume,
+}
+
+/// Replace a sample's [`SamplePlayer`] without restarting or queueing it again.
+///
+/// [`SamplePlayer`] is immutable, and reinserting it would otherwise
+/// stop the current playback and insert its required [`QueuedSample`].
+fn replace_player(entity: Entity, player: SamplePlayer, commands: &mut Commands) {
+    commands.queue(move |world: &mut World| {
+        let Ok(mut entity) = world.get_entity_mut(entity) else {
+            return;
+        };
+
+        let queued = entity.contains::<QueuedSample>();
+        entity.insert((player, PlayerUpdate));
+        entity.remove::<PlayerUpdate>();
+        if !queued {
+            entity.remove::<QueuedSample>();
+        }
+    });
+}
+
+fn set_repeat_mode(
+    trigger: On<SetRepeatMode>,
+    samples: Query<(&SamplePlayer, Option<&Sampler>)>,
+    mut nodes: Query<&mut SamplerNode, With<PoolSamplerOf>>,
+    mut commands: Commands,
+) {
+    let Ok((player, sampler)) = samples.get(trigger.entity) else {
+        return;
+    };
+
+    if let Some(mut node) = sampler.and_then(|s| nodes.get_mut(s.sampler()).ok()) {
+        node.repeat_mode = trigger.repeat_mode;
+    }
+
+    let player = SamplePlayer {
+        repeat_mode: trigger.repeat_mode,
+        ..player.clone()
+    };
+    replace_player(trigger.entity, player, &mut commands);
+}
+
+fn set_static_volume(
+    trigger: On<SetStaticVolume>,
+    samples: Query<(&SamplePlayer, Option<&Sampler>)>,
+    mut nodes: Query<&mut SamplerNode, With<PoolSamplerOf>>,
+    mut commands: Commands,
+) {
+    let Ok((player, sampler)) = samples.get(trigger.entity) else {
+        return;
+    };
+
+    if let Some(mut node) = sampler.and_then(|s| nodes.get_mut(s.sampler()).ok()) {
+        node.volume = trigger.volume;
+    }
+
+    let player = SamplePlayer {
+        volume: trigger.volume,
+        ..player.clone()
+    };
+    replace_player(trigger.entity, player, &mut commands);
+}
+
+/// An entity command that migrates a [`SamplePlayer`] to a different [`SamplerPool`].
+///
+/// If the sample is currently playing, its playhead is preserved, and it
+/// resumes in the destination pool as soon as a sampler is available.
+/// Effects the destination pool doesn't have are despawned, and any effects
+/// the sample is missing are cloned from the destination pool.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// #[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct InventoryPool;
+///
+/// fn pick_up_radio(mut commands: Commands, radio: Single<Entity, With<SamplePlayer>>) {
+///     commands.entity(*radio).queue(MoveToPool::new(InventoryPool));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct MoveToPool<T>(T);
+
+impl<T: PoolLabel + Component + Clone> MoveToPool<T> {
+    /// Construct a new [`MoveToPool`] with the destination label.
+    pub fn new(label: T) -> Self {
+        Self(label)
+    }
+}
+
+impl<T: PoolLabel + Component + Clone> EntityCommand for MoveToPool<T> {
+    type Out = ();
+
+    fn apply(self, mut entity: EntityWorldMut) {
+        if !entity.contains::<SamplePlayer>() {
+            return;
+        }
+
+        let interned = self.0.intern();
+        if entity
+            .get::<PoolLabelContainer>()
+            .is_some_and(|container| container.label == interned)
+        {
+            return;
+        }
+
+        let playhead = entity
+            .get::<Sampler>()
+            .and_then(|sampler| sampler.try_playhead_seconds());
+        let effects: Vec<_> = entity
+            .get::<SampleEffects>()
+            .map(|effects| effects.iter().collect())
+            .unwrap_or_default();
+
+        entity.world_scope(|world| {
+            let mut pools =
+                world.query_filtered::<(&PoolLabelContainer, &PoolShape), With<PoolMarker>>();
+            let Some(shape) = pools
+                .iter(world)
+                .find(|(label, _)| label.label == interned)
+                .map(|(_, shape)| shape.0.clone())
+            else {
+                return;
+            };
+
+            // Effects that don't fit the destination pool are discarded,
+            // while missing effects are filled in once the sample is assigned.
+            for effect in effects {
+                let keep = world
+                    .get::<EffectId>(effect)
+                    .is_some_and(|id| shape.contains(&id.0));
+
+                if !keep {
+                    world.despawn(effect);
+                }
+            }
+        });
+
+        // Removing the container also removes the previous label.
+        entity.remove::<PoolLabelContainer>();
+        entity.world_scope(|world| world.flush());
+        entity.insert(self.0);
+
+        if entity.contains::<Sampler>() {
+            if let (Some(playhead), Some(mut settings)) =
+                (playhead, entity.get_mut::<PlaybackSettings>())
+            {
+                settings.play_from = PlayFrom::Seconds(playhead.0);
+            }
+
+            entity.remove::<Sampler>().insert(QueuedSample);
+        }
+    }
+}
+
+/// Provides methods on [`EntityCommands`] to manage a sample's pool.
+pub trait PoolEntityCommands {
+    /// Move this sample to the pool labeled `T`, preserving its playhead.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// # #[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    /// # struct InventoryPool;
+    /// fn pick_up_radio(mut commands: Commands, radio: Single<Entity, With<SamplePlayer>>) {
+    ///     commands.entity(*radio).move_to_pool(InventoryPool);
+    /// }
+    /// ```
+    ///
+    /// See [`MoveToPool`].
+    fn move_to_pool<T: PoolLabel + Component + Clone>(&mut self, label: T) -> &mut Self;
+}
+
+impl PoolEntityCommands for EntityCommands<'_> {
+    fn move_to_pool<T: PoolLabel + Component + Clone>(&mut self, label: T) -> &mut Self {
+        self.queue(MoveToPool::new(label))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::{
+        prelude::*,
+        sample_effects,
+        test::{prepare_app, run},
+    };
+    use bevy_seedling_macros::PoolLabel;
+    use firewheel::nodes::fast_filters::lowpass::FastLowpassNode;
+
+    #[derive(PoolLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct TestPool;
+
+    #[test]
     fn test_spawn() {
         let mut app = prepare_app(|mut commands: Commands| {
             commands.spawn((
@@ -902,95 +2125,808 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_pre_warm() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((SamplerPool(TestPool), PoolSize(2..=6), PreWarm));
+        });
+
+        run(&mut app, |pool: Single<&PoolSamplers>| {
+            assert_eq!(pool.len(), 6);
+        });
+    }
+
+    #[test]
+    fn test_effects_builder() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((
+                SamplerPool(TestPool),
+                EffectsBuilder::new()
+                    .push(VolumeNode::default())
+                    .push_if(false, FastLowpassNode::<2>::default())
+                    .push_some(Some(FastLowpassNode::<2>::default()))
+                    .build(),
+            ));
+        });
+
+        run(
+            &mut app,
+            |q: Single<&SampleEffects, With<SamplerPool<TestPool>>>,
+             lowpass: Query<(), With<FastLowpassNode<2>>>| {
+                assert_eq!(q.len(), 2);
+                assert!(lowpass.get(q[1]).is_ok());
+            },
+        );
+    }
+
     #[test]
     fn test_despawn() {
         let mut app = prepare_app(|mut commands: Commands| {
             commands.spawn((
-                SamplerPool(TestPool),
-                PoolSize(4..=32),
-                sample_effects![FastLowpassNode::<2>::default()],
+                SamplerPool(TestPool),
+                PoolSize(4..=32),
+                sample_effects![FastLowpassNode::<2>::default()],
+            ));
+        });
+
+        run(&mut app, |pool_nodes: Query<&FirewheelNode>| {
+            // 2 * 4 (sampler and low pass nodes) + (pool volume) + 1 (global volume) + 1 (input)
+            assert_eq!(pool_nodes.iter().count(), 11);
+        });
+
+        run(&mut app, |mut commands: Commands| {
+            commands.despawn_pool(TestPool);
+        });
+
+        app.update();
+
+        run(&mut app, |pool_nodes: Query<&FirewheelNode>| {
+            // 1 (global volume) + 1 (input)
+            assert_eq!(pool_nodes.iter().count(), 2);
+        });
+    }
+
+    #[test]
+    fn test_playback_starts() {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn((
+                SamplerPool(TestPool),
+                sample_effects![FastLowpassNode::<2>::default()],
+            ));
+            commands.spawn((
+                TestPool,
+                SamplePlayer::new(server.load("caw.ogg")).looping(),
+                EmptyComponent,
+            ));
+        });
+
+        loop {
+            let players = run(
+                &mut app,
+                |q: Query<Entity, (With<SamplePlayer>, With<Sampler>)>| q.iter().len(),
+            );
+
+            if players == 1 {
+                break;
+            }
+
+            app.update();
+        }
+    }
+
+    #[test]
+    fn test_samples_of() {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn((
+                SamplerPool(TestPool),
+                sample_effects![FastLowpassNode::<2>::default()],
+            ));
+            commands.spawn((
+                TestPool,
+                SamplePlayer::new(server.load("caw.ogg")).looping(),
+            ));
+            commands.spawn((VolumeNode::default(), EmptyComponent));
+        });
+
+        loop {
+            let players = run(
+                &mut app,
+                |q: Query<Entity, (With<SamplePlayer>, With<Sampler>)>| q.iter().len(),
+            );
+
+            if players == 1 {
+                break;
+            }
+
+            app.update();
+        }
+
+        run(
+            &mut app,
+            |buses: BusQuery,
+             pool: Single<Entity, With<SamplerPool<TestPool>>>,
+             player: Single<Entity, With<SamplePlayer>>,
+             unrelated: Single<Entity, (With<VolumeNode>, With<EmptyComponent>)>| {
+                let samples: Vec<_> = buses.samples_of(*pool).collect();
+                assert_eq!(samples, [*player]);
+
+                assert_eq!(buses.samples_of(*unrelated).count(), 0);
+            },
+        );
+    }
+
+    #[test]
+    fn test_pool_bus() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((
+                SamplerPool(TestPool),
+                PoolSize(2..=2),
+                PoolBus::spawn_one(VolumeNode::default()),
+            ));
+        });
+
+        app.update();
+
+        run(
+            &mut app,
+            |pool: Single<(&FirewheelNode, &PoolBus, &PoolSamplers)>,
+             nodes: Query<&FirewheelNode>,
+             mut context: ResMut<crate::context::AudioContext>| {
+                let (pool_node, bus, samplers) = pool.into_inner();
+                let bus_node = nodes.get(bus.bus()).unwrap().0;
+
+                context.with(|context| {
+                    let edges: Vec<_> = context.edges().map(|e| (e.src_node, e.dst_node)).collect();
+
+                    assert!(edges.contains(&(bus_node, pool_node.0)));
+                    for sampler in nodes.iter_many(samplers.iter()) {
+                        assert!(edges.contains(&(sampler.0, bus_node)));
+                        assert!(!edges.contains(&(sampler.0, pool_node.0)));
+                    }
+                });
+            },
+        );
+    }
+
+    #[test]
+    fn test_move_to_pool() {
+        #[derive(PoolLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct OtherPool;
+
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn((SamplerPool(TestPool), PoolSize(1..=1)));
+            commands.spawn((SamplerPool(OtherPool), PoolSize(1..=1)));
+            commands.spawn((
+                TestPool,
+                SamplePlayer::new(server.load("caw.ogg")).looping(),
+            ));
+        });
+
+        let in_pool = |app: &mut App, pool: Entity| {
+            run(
+                app,
+                move |players: Query<&Sampler, With<SamplePlayer>>,
+                      samplers: Query<&PoolSamplerOf>| {
+                    players
+                        .iter()
+                        .any(|s| samplers.get(s.sampler()).is_ok_and(|p| p.0 == pool))
+                },
+            )
+        };
+
+        let test_pool = run(
+            &mut app,
+            |q: Single<Entity, With<SamplerPool<TestPool>>>| *q,
+        );
+        let other_pool = run(
+            &mut app,
+            |q: Single<Entity, With<SamplerPool<OtherPool>>>| *q,
+        );
+
+        let start = Instant::now();
+        while !in_pool(&mut app, test_pool) {
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+
+        run(
+            &mut app,
+            |player: Single<Entity, With<SamplePlayer>>, mut commands: Commands| {
+                commands.entity(*player).move_to_pool(OtherPool);
+            },
+        );
+
+        let start = Instant::now();
+        while !in_pool(&mut app, other_pool) {
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+
+        run(
+            &mut app,
+            |player: Single<(Has<TestPool>, Has<OtherPool>), With<SamplePlayer>>| {
+                assert_eq!(*player, (false, true));
+            },
+        );
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((
+                SamplerPool(TestPool),
+                PoolSize(2..=2),
+                AssignmentStrategy::RoundRobin,
+            ));
+        });
+
+        let mut assigned = Vec::new();
+        for _ in 0..3 {
+            let player = run(
+                &mut app,
+                |mut commands: Commands, server: Res<AssetServer>| {
+                    commands
+                        .spawn((
+                            TestPool,
+                            SamplePlayer::new(server.load("caw.ogg")).looping(),
+                        ))
+                        .id()
+                },
+            );
+
+            let start = Instant::now();
+            let sampler = loop {
+                let sampler = run(&mut app, move |q: Query<&Sampler>| {
+                    q.get(player).ok().map(|s| s.sampler())
+                });
+
+                if let Some(sampler) = sampler {
+                    break sampler;
+                }
+
+                if start.elapsed().as_secs() > 5 {
+                    panic!("test exceeded timeout");
+                }
+
+                app.update();
+            };
+
+            assigned.push(sampler);
+            app.world_mut().despawn(player);
+            app.update();
+        }
+
+        assert_ne!(assigned[0], assigned[1]);
+        assert_eq!(assigned[0], assigned[2]);
+    }
+
+    #[test]
+    fn test_stop_where() {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn(SamplerPool(TestPool));
+            commands.spawn((
+                TestPool,
+                SamplePlayer::new(server.load("caw.ogg")).looping(),
+                EmptyComponent,
+            ));
+            commands.spawn((
+                TestPool,
+                SamplePlayer::new(server.load("caw.ogg")).looping(),
+            ));
+        });
+
+        loop {
+            let players = run(
+                &mut app,
+                |q: Query<Entity, (With<SamplePlayer>, With<Sampler>)>| q.iter().len(),
+            );
+
+            if players == 2 {
+                break;
+            }
+
+            app.update();
+        }
+
+        run(&mut app, |mut commands: Commands| {
+            commands.stop_samples(|e| e.contains::<EmptyComponent>());
+        });
+
+        let start = Instant::now();
+        loop {
+            let (stopped, remaining) = run(
+                &mut app,
+                |stopped: Query<(), With<EmptyComponent>>, all: Query<(), With<SamplePlayer>>| {
+                    (stopped.iter().len(), all.iter().len())
+                },
+            );
+
+            if stopped == 0 {
+                assert_eq!(remaining, 1);
+                break;
+            }
+
+            assert!(start.elapsed().as_secs() < 5, "sample was never stopped");
+
+            app.update();
+        }
+    }
+
+    #[test]
+    fn test_stop_preserved() {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn(SamplerPool(TestPool));
+            commands.spawn((
+                TestPool,
+                SamplePlayer::new(server.load("sine_440hz_1ms.wav")),
+                PlaybackSettings {
+                    on_complete: OnComplete::Preserve,
+                    ..Default::default()
+                },
+            ));
+        });
+
+        let start = Instant::now();
+        while run(
+            &mut app,
+            |q: Query<(), (With<SamplePlayer>, Or<(With<Sampler>, With<QueuedSample>)>)>| {
+                !q.is_empty()
+            },
+        ) {
+            assert!(start.elapsed().as_secs() < 5, "sample never finished");
+            app.update();
+        }
+
+        app.world_mut()
+            .add_observer(|_: On<PlaybackCompletion>| panic!("finished sample completed again"));
+
+        run(&mut app, |mut commands: Commands| {
+            commands.stop_all();
+        });
+
+        app.update();
+        app.update();
+
+        run(
+            &mut app,
+            |player: Single<Has<Stopping>, With<SamplePlayer>>| {
+                assert!(!*player);
+            },
+        );
+    }
+
+    #[derive(Component)]
+    struct EmptyComponent;
+
+    #[test]
+    fn test_remove_in_dynamic() {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            // make sure we can spawn dynamic pools
+            commands.spawn((VolumeNode::default(), dynamic::DynamicBus));
+            commands
+                .spawn((VolumeNode::default(), MainBus))
+                .connect(crate::edge::AudioGraphOutput);
+
+            // We'll play a short sample
+            commands.spawn((
+                SamplePlayer::new(server.load("sine_440hz_1ms.wav")),
+                EmptyComponent,
+                PlaybackSettings::default().remove(),
+                sample_effects![FastLowpassNode::<2>::default()],
+            ));
+        });
+
+        let start = Instant::now();
+
+        // Then wait until the sample player is removed.
+        loop {
+            let players = run(
+                &mut app,
+                |q: Query<Entity, (With<SamplePlayer>, With<EmptyComponent>)>| q.iter().len(),
+            );
+
+            if players == 0 {
+                break;
+            }
+
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+
+        // Once removed, we'll verify that _all_ audio-related components are removed.
+        let world = app.world_mut();
+        let mut q = world.query_filtered::<EntityRef, With<EmptyComponent>>();
+        let entity = q.single(world).unwrap();
+
+        let archetype = entity.archetype();
+
+        assert_eq!(archetype.components().len(), 1);
+        assert!(entity.contains::<EmptyComponent>());
+    }
+
+    #[test]
+    fn test_accept_dynamic() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((VolumeNode::default(), dynamic::DynamicBus));
+            commands
+                .spawn((VolumeNode::default(), MainBus))
+                .connect(crate::edge::AudioGraphOutput);
+
+            commands.spawn((
+                SamplerPool(TestPool),
+                AcceptDynamic,
+                sample_effects![FastLowpassNode::<2>::default()],
+            ));
+        });
+
+        run(
+            &mut app,
+            |mut commands: Commands, server: Res<AssetServer>| {
+                commands.spawn((
+                    SamplePlayer::new(server.load("sine_440hz_1ms.wav")),
+                    PlaybackSettings::default().preserve(),
+                    sample_effects![FastLowpassNode::<2>::default()],
+                ));
+            },
+        );
+
+        app.update();
+
+        run(
+            &mut app,
+            |sample: Single<&PoolLabelContainer, With<SamplePlayer>>,
+             stats: Res<DynamicPoolStats>| {
+                assert_eq!(sample.label, TestPool.intern());
+                assert_eq!(stats.static_assignments(), 1);
+                assert_eq!(stats.pools_spawned(), 0);
+            },
+        );
+    }
+
+    #[test]
+    fn test_dynamic_eviction() {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.insert_resource(DynamicPoolLimits {
+                max_shapes: 1,
+                ..Default::default()
+            });
+
+            commands.spawn((VolumeNode::default(), dynamic::DynamicBus));
+            commands
+                .spawn((VolumeNode::default(), MainBus))
+                .connect(crate::edge::AudioGraphOutput);
+
+            commands.spawn((
+                SamplePlayer::new(server.load("sine_440hz_1ms.wav")),
+                sample_effects![FastLowpassNode::<2>::default()],
+            ));
+        });
+
+        let start = Instant::now();
+        loop {
+            let players = run(&mut app, |q: Query<(), With<SamplePlayer>>| q.iter().len());
+            if players == 0 {
+                break;
+            }
+
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+
+        run(
+            &mut app,
+            |mut commands: Commands, server: Res<AssetServer>| {
+                commands.spawn((
+                    SamplePlayer::new(server.load("sine_440hz_1ms.wav")),
+                    sample_effects![VolumePanNode::default()],
+                ));
+            },
+        );
+
+        app.update();
+
+        run(&mut app, |stats: Res<DynamicPoolStats>| {
+            assert_eq!(stats.pools_spawned(), 2);
+            assert_eq!(stats.pools_evicted(), 1);
+            assert_eq!(stats.fallbacks(), 0);
+        });
+    }
+
+    #[derive(Event, Clone)]
+    struct Finished;
+
+    #[derive(Resource, Default)]
+    struct FinishedCount(usize);
+
+    #[test]
+    fn test_shared_effects() {
+        #[derive(Component)]
+        struct Group;
+
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn((
+                SamplerPool(TestPool),
+                sample_effects![FastLowpassNode::<2>::default()],
+            ));
+
+            let group = commands
+                .spawn((Group, sample_effects![FastLowpassNode::<2>::default()]))
+                .id();
+
+            for _ in 0..2 {
+                commands.spawn((
+                    TestPool,
+                    SamplePlayer::new(server.load("caw.ogg")).looping(),
+                    SharedEffects(group),
+                ));
+            }
+        });
+
+        let start = Instant::now();
+        loop {
+            let players = run(
+                &mut app,
+                |q: Query<Entity, (With<SamplePlayer>, With<Sampler>)>| q.iter().len(),
+            );
+
+            if players == 2 {
+                break;
+            }
+
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+
+        app.update();
+
+        run(
+            &mut app,
+            |group: Single<(&SampleEffects, &SharedEffectsUsers), With<Group>>,
+             players: Query<(), (With<SamplePlayer>, Without<SampleEffects>)>,
+             followers: Query<&crate::node::follower::FollowerOf>| {
+                let (effects, users) = group.into_inner();
+                assert_eq!(effects.len(), 1);
+                assert_eq!(users.len(), 2);
+                assert_eq!(players.iter().len(), 2);
+                assert_eq!(followers.iter().filter(|f| f.0 == effects[0]).count(), 2);
+            },
+        );
+
+        run(
+            &mut app,
+            |players: Query<Entity, With<SamplePlayer>>, mut commands: Commands| {
+                for player in &players {
+                    commands.entity(player).despawn();
+                }
+            },
+        );
+
+        app.update();
+
+        run(
+            &mut app,
+            |group: Query<(), With<Group>>, effects: Query<(), With<EffectOf>>| {
+                assert!(group.is_empty());
+                assert_eq!(effects.iter().len(), 1);
+            },
+        );
+    }
+
+    #[test]
+    fn test_completion_trigger() {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn(SamplerPool(TestPool));
+            commands.spawn((
+                TestPool,
+                SamplePlayer::new(server.load("sine_440hz_1ms.wav")),
+                PlaybackSettings::default()
+                    .with_on_complete(OnComplete::Trigger(CompletionTrigger::event(Finished))),
+            ));
+        });
+
+        app.init_resource::<FinishedCount>().add_observer(
+            |_: On<Finished>, mut count: ResMut<FinishedCount>| {
+                count.0 += 1;
+            },
+        );
+
+        let start = Instant::now();
+        loop {
+            if app.world().resource::<FinishedCount>().0 > 0 {
+                break;
+            }
+
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+
+        assert_eq!(app.world().resource::<FinishedCount>().0, 1);
+        let players = run(&mut app, |q: Query<(), With<SamplePlayer>>| q.iter().len());
+        assert_eq!(players, 0);
+    }
+
+    #[test]
+    fn test_sample_queue() {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn(SamplerPool(TestPool));
+            commands.spawn((
+                TestPool,
+                SamplePlayer::new(server.load("sine_440hz_1ms.wav")),
+                SampleQueue::default().then(server.load("sine_440hz_1ms.wav")),
             ));
         });
 
-        run(&mut app, |pool_nodes: Query<&FirewheelNode>| {
-            // 2 * 4 (sampler and low pass nodes) + (pool volume) + 1 (global volume) + 1 (input)
-            assert_eq!(pool_nodes.iter().count(), 11);
-        });
+        app.init_resource::<FinishedCount>().add_observer(
+            |_: On<SampleQueueItemComplete>, mut count: ResMut<FinishedCount>| {
+                count.0 += 1;
+            },
+        );
 
-        run(&mut app, |mut commands: Commands| {
-            commands.despawn_pool(TestPool);
-        });
+        let start = Instant::now();
+        loop {
+            let players = run(&mut app, |q: Query<(), With<SamplePlayer>>| q.iter().len());
+            if players == 0 {
+                break;
+            }
 
-        app.update();
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
 
-        run(&mut app, |pool_nodes: Query<&FirewheelNode>| {
-            // 1 (global volume) + 1 (input)
-            assert_eq!(pool_nodes.iter().count(), 2);
-        });
+            app.update();
+        }
+
+        assert_eq!(app.world().resource::<FinishedCount>().0, 2);
     }
 
+    #[derive(Resource)]
+    struct StaleSettings(PlaybackSettings);
+
     #[test]
-    fn test_playback_starts() {
+    fn test_stale_completion() {
         let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
-            commands.spawn((
-                SamplerPool(TestPool),
-                sample_effects![FastLowpassNode::<2>::default()],
-            ));
+            // Created before the first sample plays, so its play
+            // notification is older than anything the sampler finishes.
+            commands.insert_resource(StaleSettings(PlaybackSettings::default()));
+
+            commands.spawn((SamplerPool(TestPool), PoolSize(1..=1)));
             commands.spawn((
                 TestPool,
-                SamplePlayer::new(server.load("caw.ogg")).looping(),
-                EmptyComponent,
+                SamplePlayer::new(server.load("sine_440hz_1ms.wav")),
             ));
         });
 
-        loop {
-            let players = run(
-                &mut app,
-                |q: Query<Entity, (With<SamplePlayer>, With<Sampler>)>| q.iter().len(),
-            );
+        app.init_resource::<FinishedCount>().add_observer(
+            |_: On<PlaybackCompletion>, mut count: ResMut<FinishedCount>| {
+                count.0 += 1;
+            },
+        );
 
-            if players == 1 {
-                break;
+        let start = Instant::now();
+        while app.world().resource::<FinishedCount>().0 == 0 {
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
             }
 
             app.update();
         }
-    }
 
-    #[derive(Component)]
-    struct EmptyComponent;
+        let player = run(
+            &mut app,
+            |mut commands: Commands, server: Res<AssetServer>, stale: Res<StaleSettings>| {
+                commands
+                    .spawn((
+                        TestPool,
+                        SamplePlayer::new(server.load("caw.ogg")).looping(),
+                        stale.0.clone(),
+                    ))
+                    .id()
+            },
+        );
+
+        let start = Instant::now();
+        while run(&mut app, move |q: Query<(), With<Sampler>>| {
+            !q.contains(player)
+        }) {
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        // The looping sample must not inherit the first sample's completion.
+        assert_eq!(app.world().resource::<FinishedCount>().0, 1);
+        run(
+            &mut app,
+            move |players: Query<&Sampler>, generations: Query<&SamplerGeneration>| {
+                let sampler = players.get(player).unwrap().sampler();
+                let generation = generations.get(sampler).unwrap();
+
+                assert_eq!(generation.generation, 2);
+                assert!(generation.is_current(player));
+            },
+        );
+    }
 
     #[test]
-    fn test_remove_in_dynamic() {
+    fn test_restart() {
         let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
-            // make sure we can spawn dynamic pools
-            commands.spawn((VolumeNode::default(), dynamic::DynamicBus));
-            commands
-                .spawn((VolumeNode::default(), MainBus))
-                .connect(crate::edge::AudioGraphOutput);
-
-            // We'll play a short sample
+            commands.spawn(SamplerPool(TestPool));
             commands.spawn((
+                TestPool,
                 SamplePlayer::new(server.load("sine_440hz_1ms.wav")),
-                EmptyComponent,
-                PlaybackSettings::default().remove(),
-                sample_effects![FastLowpassNode::<2>::default()],
+                PlaybackSettings::default().preserve(),
             ));
         });
 
+        app.init_resource::<FinishedCount>().add_observer(
+            |_: On<PlaybackCompletion>, mut count: ResMut<FinishedCount>| {
+                count.0 += 1;
+            },
+        );
+
         let start = Instant::now();
+        for expected in 1..=2 {
+            while app.world().resource::<FinishedCount>().0 < expected {
+                if start.elapsed().as_secs() > 5 {
+                    panic!("test exceeded timeout");
+                }
 
-        // Then wait until the sample player is removed.
-        loop {
-            let players = run(
-                &mut app,
-                |q: Query<Entity, (With<SamplePlayer>, With<EmptyComponent>)>| q.iter().len(),
-            );
+                app.update();
+            }
 
-            if players == 0 {
-                break;
+            if expected == 1 {
+                run(
+                    &mut app,
+                    |player: Single<Entity, With<SamplePlayer>>, mut commands: Commands| {
+                        commands.trigger(Restart { entity: *player });
+                    },
+                );
             }
+        }
+
+        run(&mut app, |q: Query<(), With<SamplePlayer>>| {
+            assert_eq!(q.iter().len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_live_player_changes() {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn(SamplerPool(TestPool));
+            commands.spawn((
+                TestPool,
+                SamplePlayer::new(server.load("caw.ogg")).looping(),
+            ));
+        });
 
+        let start = Instant::now();
+        while run(&mut app, |q: Query<&Sampler>| {
+            !q.iter().any(Sampler::is_playing)
+        }) {
             if start.elapsed().as_secs() > 5 {
                 panic!("test exceeded timeout");
             }
@@ -998,15 +2934,43 @@ mod test {
             app.update();
         }
 
-        // Once removed, we'll verify that _all_ audio-related components are removed.
-        let world = app.world_mut();
-        let mut q = world.query_filtered::<EntityRef, With<EmptyComponent>>();
-        let entity = q.single(world).unwrap();
+        let sampler = run(
+            &mut app,
+            |player: Single<(Entity, &Sampler)>, mut commands: Commands| {
+                let (player, sampler) = *player;
+                commands.trigger(SetStaticVolume {
+                    entity: player,
+                    volume: Volume::Linear(0.5),
+                });
+                commands.trigger(SetRepeatMode {
+                    entity: player,
+                    repeat_mode: RepeatMode::PlayOnce,
+                });
+
+                sampler.sampler()
+            },
+        );
 
-        let archetype = entity.archetype();
+        // Give any stray removal a chance to land.
+        app.update();
 
-        assert_eq!(archetype.components().len(), 1);
-        assert!(entity.contains::<EmptyComponent>());
+        run(
+            &mut app,
+            |player: Single<(&SamplePlayer, &Sampler)>, nodes: Query<&SamplerNode>| {
+                // Still holding the same `Sampler` means the sample wasn't
+                // stopped or queued again.
+                let (player, held) = *player;
+                assert_eq!(held.sampler(), sampler);
+                assert!(held.is_playing());
+
+                let node = nodes.get(sampler).unwrap();
+
+                assert_eq!(player.volume, Volume::Linear(0.5));
+                assert_eq!(player.repeat_mode, RepeatMode::PlayOnce);
+                assert_eq!(node.volume, Volume::Linear(0.5));
+                assert_eq!(node.repeat_mode, RepeatMode::PlayOnce);
+            },
+        );
     }
 
     #[test]
@@ -1102,4 +3066,249 @@ mod test {
         let mut q = world.query_filtered::<Entity, With<SamplePlayer>>();
         assert_eq!(q.iter(world).len(), 4);
     }
+
+    #[test]
+    fn test_steal_fade() {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn((
+                SamplerPool(TestPool),
+                PoolSize(1..=1),
+                StealFade(DurationSeconds(10.0)),
+            ));
+
+            commands.spawn((
+                TestPool,
+                SamplePlayer::new(server.load("caw.ogg")).looping(),
+            ));
+        });
+
+        let start = Instant::now();
+        while run(&mut app, |players: Query<(), With<Sampler>>| {
+            players.count()
+        }) < 1
+        {
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+
+        run(
+            &mut app,
+            |mut commands: Commands, server: Res<AssetServer>| {
+                commands.spawn((
+                    TestPool,
+                    SamplePlayer::new(server.load("caw.ogg")).looping(),
+                    SamplePriority(1),
+                ));
+            },
+        );
+        app.update();
+
+        // The stolen voice should be silent halfway through the fade.
+        run(
+            &mut app,
+            |sampler: Single<(&SamplerNode, &AudioEvents), With<PoolSamplerOf>>,
+             time: Res<Time<Audio>>| {
+                let (node, events) = *sampler;
+                let midpoint = time.now() + DurationSeconds(5.0);
+
+                let value = events.get_value_at(midpoint, node);
+                assert!(value.volume.linear() < 0.01);
+
+                let value = events.get_value_at(midpoint + DurationSeconds(5.0), node);
+                assert_eq!(value.volume, Volume::UNITY_GAIN);
+            },
+        );
+    }
+
+    #[test]
+    fn test_voice_scaling() {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn((SamplerPool(TestPool), PoolSize(4..=4)));
+
+            for priority in 0..4 {
+                commands.spawn((
+                    TestPool,
+                    SamplePlayer::new(server.load("caw.ogg")).looping(),
+                    SamplePriority(priority),
+                ));
+            }
+        });
+
+        let start = Instant::now();
+        while run(&mut app, |players: Query<(), With<Sampler>>| {
+            players.count()
+        }) < 4
+        {
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+
+        app.insert_resource(VoiceScaling {
+            step: 0.5,
+            interval: core::time::Duration::ZERO,
+            ..Default::default()
+        });
+        app.world_mut()
+            .resource_mut::<crate::context::DspLoad>()
+            .record_underrun();
+        app.update();
+
+        let virtualized = |app: &mut App| {
+            run(
+                app,
+                |pool: Single<(&VoiceBudget, &PoolSize)>,
+                 players: Query<&SamplePriority, With<Virtualized>>| {
+                    let (budget, size) = *pool;
+                    let mut priorities: Vec<_> = players.iter().map(|p| p.0).collect();
+                    priorities.sort();
+
+                    (budget.limit(), size.0.clone(), priorities)
+                },
+            )
+        };
+
+        assert_eq!(virtualized(&mut app), (2, 2..=2, vec![0, 1]));
+
+        // With no load reported, the budget should recover.
+        app.update();
+
+        assert_eq!(virtualized(&mut app), (3, 3..=3, vec![0]));
+
+        // Removing the scaling restores everything.
+        app.world_mut().remove_resource::<VoiceScaling>();
+        app.update();
+
+        run(
+            &mut app,
+            |pool: Single<(&PoolSize, Has<VoiceBudget>)>,
+             virtualized: Query<(), With<Virtualized>>,
+             players: Query<&PlaybackSettings>| {
+                let (size, budgeted) = *pool;
+
+                assert_eq!(size.0, 4..=4);
+                assert!(!budgeted);
+                assert!(virtualized.is_empty());
+                assert!(players.iter().all(|settings| *settings.play));
+            },
+        );
+    }
+
+    #[test]
+    fn test_pool_size_policy() {
+        let policy = PoolSizePolicy::default();
+
+        assert_eq!(policy.apply(4..=32, TargetPlatform::Desktop), 4..=32);
+        assert_eq!(policy.apply(4..=32, TargetPlatform::Mobile), 2..=16);
+        assert_eq!(policy.apply(1..=3, TargetPlatform::Web), 1..=2);
+        assert_eq!(policy.apply(0..=0, TargetPlatform::Web), 0..=0);
+
+        let mut app = prepare_app(|| ());
+
+        run(
+            &mut app,
+            |resolved: Res<ResolvedPoolSize>, default_size: Res<DefaultPoolSize>| {
+                assert_eq!(resolved.platform, TargetPlatform::current());
+                assert_eq!(resolved.size, default_size.0);
+            },
+        );
+    }
+
+    #[test]
+    fn test_default_sampler_config() {
+        let mono = firewheel::channel_config::NonZeroChannelCount::new(1).unwrap();
+
+        let mut app = prepare_app(|| ());
+        app.insert_resource(DefaultSamplerConfig(SamplerConfig {
+            channels: mono,
+            ..Default::default()
+        }));
+
+        run(&mut app, |mut commands: Commands| {
+            commands.spawn((SamplerPool(TestPool), PoolSize(2..=2)));
+        });
+
+        app.update();
+
+        run(
+            &mut app,
+            move |pool: Single<&SamplerConfig, With<PoolMarker>>,
+                  samplers: Query<&SamplerConfig, With<PoolSamplerOf>>| {
+                assert_eq!(pool.channels, mono);
+                assert_eq!(samplers.iter().len(), 2);
+                assert!(samplers.iter().all(|config| config.channels == mono));
+            },
+        );
+    }
+
+    #[test]
+    fn test_smooth_seek() {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn(SamplerPool(TestPool));
+            commands.spawn((
+                TestPool,
+                SamplePlayer::new(server.load("caw.ogg")).looping(),
+            ));
+        });
+
+        let start = Instant::now();
+        while run(&mut app, |q: Query<(), With<Sampler>>| q.iter().len()) == 0 {
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+
+        let position = PlayFrom::Seconds(0.5);
+        run(
+            &mut app,
+            move |player: Single<Entity, With<SamplePlayer>>, mut commands: Commands| {
+                commands
+                    .entity(*player)
+                    .insert(SmoothSeek::new(position).with_fade(DurationSeconds(1.0)));
+            },
+        );
+        app.update();
+
+        run(
+            &mut app,
+            move |player: Single<
+                (&PlaybackSettings, &AudioEvents, &Sampler),
+                Without<SmoothSeek>,
+            >,
+                  samplers: Query<(&SamplerNode, &AudioEvents)>,
+                  time: Res<bevy_time::Time<Audio>>| {
+                let (settings, events, sampler) = player.into_inner();
+                let (node, sampler_events) = samplers.get(sampler.sampler()).unwrap();
+                let now = time.now();
+
+                // The sampler dips in volume around the seek...
+                let dipped = sampler_events.get_value_at(now + DurationSeconds(0.5), node);
+                assert!(dipped.volume.linear() < node.volume.linear());
+
+                // ...and recovers once it's complete.
+                let recovered = sampler_events.get_value_at(now + DurationSeconds(2.0), node);
+                assert_eq!(recovered.volume, node.volume);
+
+                let seeked = events.get_value_at(now + DurationSeconds(2.0), settings);
+                assert_eq!(seeked.play_from, position);
+                assert!(*seeked.play);
+            },
+        );
+    }
+
+    #[test]
+    fn test_mirror_playhead() {
+        assert_eq!(AssignedDirection::mirror(100, 0), 100);
+        assert_eq!(AssignedDirection::mirror(100, 25), 75);
+        // Looping playheads wrap into the sample.
+        assert_eq!(AssignedDirection::mirror(100, 225), 75);
+        assert_eq!(AssignedDirection::mirror(0, 25), 0);
+    }
 }
@@ -1,23 +1,38 @@
 use super::{
-    PlaybackCompletion, PoolSamplerOf, PoolSamplers, PoolShape, PoolSize, SamplerOf,
-    sample_effects::{EffectOf, SampleEffects},
+    AssignedDirection, AssignmentHistory, AssignmentStrategy, LastAssigned, PlaybackCompletion,
+    PoolBus, PoolSamplerOf, PoolSamplers, PoolShape, PoolSize, SamplerGeneration, SamplerOf,
+    SamplerRelease, StealFade,
+    sample_effects::{EffectOf, SampleEffects, SharedEffects},
 };
 use crate::{
-    node::{AudioState, EffectId, IgnoreDiffTimer, follower::FollowerOf},
+    node::{AudioState, DiffTimestamp, EffectId, IgnoreDiffTimer, follower::FollowerOf},
     pool::label::PoolLabelContainer,
-    prelude::{AudioEvents, DefaultPool},
-    sample::{AudioSample, QueuedSample, SamplePlayer, SamplePriority, SampleQueueLifetime},
+    prelude::{AudioEvents, DefaultPool, ParamFade, Volume},
+    profiling::audio_span,
+    sample::{
+        AudioSample, PlaybackSettings, Queued, SamplePlayer, SamplePriority, SampleQueueLifetime,
+    },
+    time::{Audio, AudioTime},
 };
 use bevy_asset::prelude::*;
-use bevy_ecs::{entity::EntityCloner, prelude::*, relationship::Relationship};
+use bevy_ecs::{
+    entity::{EntityCloner, EntityHashMap, EntityHashSet},
+    prelude::*,
+    relationship::Relationship,
+};
 use bevy_log::prelude::*;
 use bevy_platform::collections::HashMap;
-use bevy_time::{Stopwatch, Time};
+use bevy_time::Time;
+use core::time::Duration;
 use firewheel::{
+    clock::DurationSeconds,
     diff::EventQueue,
     nodes::sampler::{PlaybackState, RepeatMode, SamplerConfig, SamplerNode, SamplerState},
 };
-use std::ops::Deref;
+use std::{
+    ops::Deref,
+    sync::{Mutex, PoisonError},
+};
 
 #[derive(PartialEq, Debug, Eq, PartialOrd, Ord, Copy, Clone)]
 struct SamplerScore {
@@ -40,7 +55,7 @@ impl Default for SamplerScore {
 
 /// Eagerly grow pools to handle over-allocation when possible.
 pub(super) fn grow_pools(
-    queued_samples: Query<(&SamplePlayer, &PoolLabelContainer), With<QueuedSample>>,
+    queued_samples: Query<(&SamplePlayer, &PoolLabelContainer), Queued>,
     pools: Query<(
         Entity,
         &PoolLabelContainer,
@@ -48,6 +63,7 @@ pub(super) fn grow_pools(
         &PoolSize,
         Option<&SampleEffects>,
         &SamplerConfig,
+        Option<&PoolBus>,
     )>,
     nodes: Query<Option<&SamplerOf>, With<PoolSamplerOf>>,
     assets: Res<Assets<AudioSample>>,
@@ -65,7 +81,7 @@ pub(super) fn grow_pools(
         return Ok(());
     }
 
-    for (pool_entity, label, samplers, size, pool_effects, pool_config) in pools {
+    for (pool_entity, label, samplers, size, pool_effects, pool_config, pool_bus) in pools {
         let Some(queued_samples) = queued_samples.get(&label.label).copied() else {
             continue;
         };
@@ -87,28 +103,21 @@ pub(super) fn grow_pools(
             let new_size = (samplers.len() + growth_size).min(*size.0.end());
 
             #[cfg(debug_assertions)]
-            commands.queue({
-                let id = label.label_id;
-                let num_samplers = samplers.len();
-                move |world: &mut World| {
-                    let component = world.components().get_descriptor(id);
-
-                    if let Some(component) = component {
-                        let s = if new_size != 1 { "s" } else { "" };
-                        debug!(
-                            "growing {} from {} to {} sampler{s} ({} over-allocated)",
-                            component.name(),
-                            num_samplers,
-                            new_size,
-                            difference,
-                        );
-                    }
-                }
-            });
+            {
+                let s = if new_size != 1 { "s" } else { "" };
+                debug!(
+                    "growing {:?} from {} to {} sampler{s} ({} over-allocated)",
+                    label.label,
+                    samplers.len(),
+                    new_size,
+                    difference,
+                );
+            }
 
             for _ in samplers.len()..new_size {
                 super::spawn_chain(
                     pool_entity,
+                    pool_bus.map(|b| b.bus()).unwrap_or(pool_entity),
                     Some(*pool_config),
                     pool_effects.map(|e| e.deref()).unwrap_or(&[]),
                     &mut commands,
@@ -122,9 +131,11 @@ pub(super) fn grow_pools(
 
 /// Reconcile a sample's effects with the pool's effects, cloning pool defaults for any missing entries.
 ///
+/// `effects_owner` is the sample itself, or its [`SharedEffects`] group.
+///
 /// Returns `true` if the caller should skip this sample.
 fn normalize_effects(
-    sample_entity: Entity,
+    effects_owner: Entity,
     sample_effects: Option<&SampleEffects>,
     pool_effects: Option<&SampleEffects>,
     player: &SamplePlayer,
@@ -190,7 +201,7 @@ fn normalize_effects(
                 }
 
                 commands
-                    .entity(sample_entity)
+                    .entity(effects_owner)
                     .remove_related::<EffectOf>(sample_effects)
                     .add_related::<EffectOf>(&new_effects);
 
@@ -220,7 +231,7 @@ fn normalize_effects(
                 }
 
                 world
-                    .entity_mut(sample_entity)
+                    .entity_mut(effects_owner)
                     .add_related::<EffectOf>(&sample_effects);
             });
         }
@@ -229,25 +240,68 @@ fn normalize_effects(
     false
 }
 
+/// A queued sample's assignment to a sampler, planned in parallel.
+struct PlannedAssignment<'a> {
+    sample_entity: Entity,
+    player: &'a SamplePlayer,
+    asset: &'a AudioSample,
+    sample_effects: Option<&'a SampleEffects>,
+    sampler_entity: Entity,
+    /// The sampler's index within its pool.
+    sampler_index: usize,
+    /// The sample currently occupying the sampler, if any.
+    interrupted: Option<Entity>,
+}
+
+/// The planned assignments for a single pool.
+struct PoolPlan<'a> {
+    pool: Entity,
+    assignments: Vec<PlannedAssignment<'a>>,
+    #[cfg(debug_assertions)]
+    summary: (
+        usize,
+        usize,
+        usize,
+        PoolSize,
+        crate::pool::label::InternedPoolLabel,
+    ),
+}
+
+type QueuedData<'a> = (
+    Entity,
+    &'a SamplePlayer,
+    &'a AudioSample,
+    Option<&'a SampleEffects>,
+    &'a SamplePriority,
+);
+
 /// Scan through the set of pending sample players
 /// and assign work to the most appropriate sampler node.
+///
+/// Each pool's assignments are planned in parallel, since pools
+/// never share samplers. The plans are then applied serially.
 pub(super) fn assign_work(
-    mut queued_samples: Query<
+    queued_samples: Query<
         (
             Entity,
             &SamplePlayer,
             &PoolLabelContainer,
             Option<&SampleEffects>,
+            Option<&SharedEffects>,
             &SamplePriority,
         ),
-        With<QueuedSample>,
+        Queued,
     >,
-    pools: Query<(
+    groups: Query<&SampleEffects>,
+    mut pools: Query<(
+        Entity,
         &PoolLabelContainer,
         &PoolSamplers,
         &PoolSize,
         &PoolShape,
         Option<&SampleEffects>,
+        Option<(&AssignmentStrategy, &mut AssignmentHistory)>,
+        &StealFade,
     )>,
     mut nodes: Query<
         (
@@ -256,18 +310,25 @@ pub(super) fn assign_work(
             &mut AudioEvents,
             &AudioState<SamplerState>,
             Option<&SamplerOf>,
+            Option<&LastAssigned>,
+            Has<SamplerRelease>,
         ),
         With<PoolSamplerOf>,
     >,
     active_samples: Query<(&SamplePlayer, &SamplePriority)>,
+    settings: Query<&PlaybackSettings>,
+    shared: Query<&SharedEffects>,
+    generations: Query<&SamplerGeneration>,
     mut effects: Query<&EffectId, With<EffectOf>>,
     assets: Res<Assets<AudioSample>>,
+    time: Res<Time<Audio>>,
     mut commands: Commands,
 ) -> Result {
-    let mut queued_samples: HashMap<_, Vec<_>> = queued_samples
-        .iter_mut()
-        .filter_map(|(entity, player, label, effects, priority)| {
+    let queued_samples: HashMap<_, Vec<QueuedData>> = queued_samples
+        .iter()
+        .filter_map(|(entity, player, label, effects, shared, priority)| {
             let asset = assets.get(&player.sample)?;
+            let effects = SharedEffects::resolve(effects, shared, &groups);
 
             Some((label.label, (entity, player, asset, effects, priority)))
         })
@@ -280,180 +341,336 @@ pub(super) fn assign_work(
         return Ok(());
     }
 
-    for (label, samplers, size, pool_shape, pool_effects) in pools {
-        // To suppress warnings when debug assertions are disabled, as `size` is only used in the debug-only `commands.queue` call below.
-        #[cfg(not(debug_assertions))]
-        let _size = size;
+    audio_span!("seedling_queue", pools = queued_samples.len());
+    let plans = Mutex::new(Vec::new());
+    pools
+        .par_iter()
+        .for_each(|(pool, label, samplers, size, _, _, strategy, _)| {
+            let Some(queued_samples) = queued_samples.get(&label.label) else {
+                return;
+            };
+
+            let plan = plan_pool(
+                pool,
+                label,
+                samplers,
+                size,
+                strategy,
+                queued_samples.clone(),
+                &nodes,
+                &active_samples,
+            );
+
+            plans
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(plan);
+        });
 
-        let Some(mut queued_samples) = queued_samples.remove(&label.label) else {
-            continue;
-        };
+    let mut plans = plans.into_inner().unwrap_or_else(PoisonError::into_inner);
+    // keep application order stable regardless of scheduling
+    plans.sort_unstable_by_key(|plan| plan.pool);
 
-        // if there is enough sampler availability in the pool,
-        // don't bother sorting samples by priority
+    // shared groups only need to be normalized once
+    let mut normalized_groups = EntityHashSet::default();
 
-        let inactive_samplers: Vec<_> = samplers
-            .iter()
-            .filter(|s| nodes.get(*s).is_ok_and(|n| n.4.is_none()))
-            .collect();
+    for plan in plans {
+        let (_, _, _, _, pool_shape, pool_effects, mut strategy, steal_fade) =
+            pools.get_mut(plan.pool)?;
 
         #[cfg(debug_assertions)]
-        commands.queue({
-            let inactive = inactive_samplers.len();
-            let queued_len = queued_samples.len();
-            let total_samplers = samplers.len();
-            let size = size.clone();
-            let id = label.label_id;
-            move |world: &mut World| {
-                let component = world.components().get_descriptor(id);
-
-                if let Some(component) = component {
-                    let s = if queued_len != 1 { "s" } else { "" };
-                    debug!(
-                        "queued {queued_len} sample{s} in {} ({} total, {inactive} inactive, {:?})",
-                        component.name(),
-                        total_samplers,
-                        size.0
-                    );
-                }
-            }
-        });
+        {
+            let (inactive, queued_len, total_samplers, size, label) = plan.summary;
+            let s = if queued_len != 1 { "s" } else { "" };
+            debug!(
+                "queued {queued_len} sample{s} in {label:?} ({total_samplers} total, {inactive} inactive, {:?})",
+                size.0
+            );
+        }
+
+        for assignment in plan.assignments {
+            let PlannedAssignment {
+                sample_entity,
+                player,
+                asset,
+                sample_effects,
+                sampler_entity,
+                sampler_index,
+                interrupted,
+            } = assignment;
+
+            let (sampler_entity, mut params, mut events, state, .., releasing) =
+                nodes.get_mut(sampler_entity)?;
+
+            // A sample that finished since its sampler was last polled
+            // completed normally, even though it's being replaced.
+            let interrupted_reason = if generations
+                .get(sampler_entity)
+                .is_ok_and(|g| g.finished(&params, &state.0))
+            {
+                super::CompletionReason::PlaybackComplete
+            } else {
+                super::CompletionReason::PlaybackInterrupted
+            };
+
+            let direction = settings
+                .get(sample_entity)
+                .map(|s| s.direction)
+                .unwrap_or_default();
+            events.push(SamplerNode::set_dyn_sample_event(
+                AssignedDirection::sample(asset, direction),
+            ));
+            commands
+                .entity(sampler_entity)
+                .insert(AssignedDirection(direction));
+            params.repeat_mode = player.repeat_mode;
 
-        if inactive_samplers.len() >= queued_samples.len() {
-            let mut inactive = inactive_samplers.iter();
+            if releasing {
+                commands.entity(sampler_entity).remove::<SamplerRelease>();
+            }
 
-            for (sample_entity, player, asset, sample_effects, _priority) in queued_samples {
-                let (sampler_entity, mut params, mut events, ..) =
-                    nodes.get_mut(*inactive.next().unwrap())?;
+            if (interrupted.is_some() || releasing) && steal_fade.0.0 > 0.0 {
+                // Fade out the stolen voice, start the new sample
+                // once silent, and then fade it in.
+                let start = time.now();
+                let midpoint = start + DurationSeconds(steal_fade.0.0 * 0.5);
+                let end = start + steal_fade.0;
 
-                events.push(SamplerNode::set_dyn_sample_event(asset.get()));
+                params.fade_param_at(Volume::SILENT, start, midpoint, &mut events, |s| {
+                    &mut s.volume
+                });
+                params.fade_param_at(player.volume, midpoint, end, &mut events, |s| &mut s.volume);
+                commands
+                    .entity(sampler_entity)
+                    .insert(DiffTimestamp(midpoint));
+            } else if releasing {
+                // Take over the release fade on the sampler's timeline.
+                events.schedule(time.now(), &*params, |s| s.volume = player.volume);
+            } else {
                 params.volume = player.volume;
-                params.repeat_mode = player.repeat_mode;
+            }
+
+            let effects_owner = match shared.get(sample_entity) {
+                Ok(shared) if !normalized_groups.insert(shared.0) => None,
+                Ok(shared) => Some(shared.0),
+                Err(_) => Some(sample_entity),
+            };
 
-                if normalize_effects(
-                    sample_entity,
+            if let Some(effects_owner) = effects_owner
+                && normalize_effects(
+                    effects_owner,
                     sample_effects,
                     pool_effects,
                     player,
                     pool_shape,
                     &mut effects,
                     &mut commands,
-                ) {
-                    continue;
-                }
+                )
+            {
+                continue;
+            }
+
+            if let Some(assignment) = interrupted {
+                // if the `Sampler` relationship is already present on either side,
+                // this will necessarily remove it
+                commands.trigger(PlaybackCompletion {
+                    entity: assignment,
+                    reason: interrupted_reason,
+                });
+            }
 
+            if let Some((_, history)) = &mut strategy {
                 commands
-                    .entity(sample_entity)
-                    .remove::<(QueuedSample, super::Sampler)>()
-                    .add_one_related::<SamplerOf>(sampler_entity);
+                    .entity(sampler_entity)
+                    .insert(history.record(sampler_index));
             }
 
-            continue;
+            // `QueuedSample` stays in place, so assignment only
+            // moves the sample into the `Sampler` archetype.
+            commands
+                .entity(sample_entity)
+                .remove::<super::Sampler>()
+                .add_one_related::<SamplerOf>(sampler_entity);
         }
+    }
 
-        // otherwise, sort the available samplers
-        let mut sampler_scores = Vec::new();
-        for (sampler_entity, params, _ev, state, assignment) in nodes.iter_many(samplers.iter()) {
-            let raw_score = calculate_raw_score(&state.0, params);
-            let has_assignment = assignment.is_some();
-
-            let active_data = assignment.and_then(|a| {
-                active_samples
-                    .get(a.0)
-                    .map(|s| (s.0.repeat_mode, *s.1))
-                    .ok()
-            });
+    Ok(())
+}
 
-            let (is_looping, priority) = match active_data {
-                Some((repeat, priority)) => (repeat != RepeatMode::PlayOnce, priority),
-                None => (false, SamplePriority(0)),
+/// Plan the sampler assignments for a single pool.
+#[expect(clippy::too_many_arguments)]
+fn plan_pool<'a>(
+    pool: Entity,
+    label: &PoolLabelContainer,
+    samplers: &PoolSamplers,
+    size: &PoolSize,
+    strategy: Option<(&AssignmentStrategy, &AssignmentHistory)>,
+    mut queued_samples: Vec<QueuedData<'a>>,
+    nodes: &Query<
+        (
+            Entity,
+            &mut SamplerNode,
+            &mut AudioEvents,
+            &AudioState<SamplerState>,
+            Option<&SamplerOf>,
+            Option<&LastAssigned>,
+            Has<SamplerRelease>,
+        ),
+        With<PoolSamplerOf>,
+    >,
+    active_samples: &Query<(&SamplePlayer, &SamplePriority)>,
+) -> PoolPlan<'a> {
+    // To suppress warnings when debug assertions are disabled.
+    #[cfg(not(debug_assertions))]
+    let _ = (size, label);
+
+    // rank each sampler according to the pool's assignment strategy
+    let ranks: HashMap<Entity, (usize, u64)> = samplers
+        .iter()
+        .enumerate()
+        .map(|(index, sampler)| {
+            let rank = match &strategy {
+                Some((strategy, history)) => strategy.rank(
+                    index,
+                    samplers.len(),
+                    history,
+                    nodes.get(sampler).ok().and_then(|n| n.5),
+                ),
+                None => index as u64,
             };
 
-            sampler_scores.push((
-                sampler_entity,
-                assignment.map(|s| s.0),
-                SamplerScore {
-                    priority,
-                    raw_score,
-                    has_assignment,
-                    is_looping,
-                },
-            ));
-        }
+            (sampler, (index, rank))
+        })
+        .collect();
 
-        sampler_scores.sort_by_key(|pair| pair.2);
+    let mut inactive_samplers: Vec<_> = samplers
+        .iter()
+        .filter(|s| nodes.get(*s).is_ok_and(|n| n.4.is_none() && !n.6))
+        .collect();
+    inactive_samplers.sort_by_key(|s| ranks[s].1);
 
-        // then sort the queued samples
-        queued_samples.sort_by_key(|s| {
-            (
-                core::cmp::Reverse(s.4),
-                s.1.repeat_mode == RepeatMode::PlayOnce,
-            )
-        });
+    let mut plan = PoolPlan {
+        pool,
+        assignments: Vec::new(),
+        #[cfg(debug_assertions)]
+        summary: (
+            inactive_samplers.len(),
+            queued_samples.len(),
+            samplers.len(),
+            size.clone(),
+            label.label,
+        ),
+    };
 
-        for ((sampler_entity, current_assignment, sampler_score), queued) in
-            sampler_scores.into_iter().zip(queued_samples)
-        {
-            let (sample_entity, player, asset, sample_effects, priority) = queued;
+    // if there is enough sampler availability in the pool,
+    // don't bother sorting samples by priority
+    if inactive_samplers.len() >= queued_samples.len() {
+        plan.assignments = queued_samples
+            .into_iter()
+            .zip(inactive_samplers)
+            .map(
+                |((sample_entity, player, asset, sample_effects, _), sampler_entity)| {
+                    PlannedAssignment {
+                        sample_entity,
+                        player,
+                        asset,
+                        sample_effects,
+                        sampler_entity,
+                        sampler_index: ranks[&sampler_entity].0,
+                        interrupted: None,
+                    }
+                },
+            )
+            .collect();
 
-            // Due to the sorting, if any queued sample has a lower priority then a currently playing sample,
-            // then every subsequent sample must also have a lower priority than its corresponding player.
-            if &sampler_score.priority > priority {
-                break;
-            }
+        return plan;
+    }
 
-            // We'll also skip over samples that won't loop
-            // when the occupied sampler is currently looping.
-            if sampler_score.is_looping && player.repeat_mode == RepeatMode::PlayOnce {
-                continue;
-            }
+    // otherwise, sort the available samplers
+    let mut sampler_scores = Vec::new();
+    for (sampler_entity, params, _ev, state, assignment, ..) in nodes.iter_many(samplers.iter()) {
+        let raw_score = calculate_raw_score(&state.0, params);
+        let has_assignment = assignment.is_some();
+
+        let active_data = assignment.and_then(|a| {
+            active_samples
+                .get(a.0)
+                .map(|s| (s.0.repeat_mode, *s.1))
+                .ok()
+        });
 
-            let (sampler_entity, mut params, mut events, ..) = nodes.get_mut(sampler_entity)?;
+        let (is_looping, priority) = match active_data {
+            Some((repeat, priority)) => (repeat != RepeatMode::PlayOnce, priority),
+            None => (false, SamplePriority(0)),
+        };
 
-            events.push(SamplerNode::set_dyn_sample_event(asset.get()));
-            params.volume = player.volume;
-            params.repeat_mode = player.repeat_mode;
+        sampler_scores.push((
+            sampler_entity,
+            assignment.map(|s| s.0),
+            SamplerScore {
+                priority,
+                raw_score,
+                has_assignment,
+                is_looping,
+            },
+        ));
+    }
 
-            if normalize_effects(
-                sample_entity,
-                sample_effects,
-                pool_effects,
-                player,
-                pool_shape,
-                &mut effects,
-                &mut commands,
-            ) {
-                continue;
-            }
+    sampler_scores.sort_by_key(|pair| (pair.2, ranks[&pair.0].1));
 
-            if let Some(assignment) = current_assignment {
-                // if the `Sampler` relationship is already present on either side,
-                // this will necessarily remove it
-                commands.trigger(PlaybackCompletion {
-                    entity: assignment,
-                    reason: super::CompletionReason::PlaybackInterrupted,
-                });
-            }
+    // then sort the queued samples
+    queued_samples.sort_by_key(|s| {
+        (
+            core::cmp::Reverse(s.4),
+            s.1.repeat_mode == RepeatMode::PlayOnce,
+        )
+    });
+
+    for ((sampler_entity, current_assignment, sampler_score), queued) in
+        sampler_scores.into_iter().zip(queued_samples)
+    {
+        let (sample_entity, player, asset, sample_effects, priority) = queued;
+
+        // Due to the sorting, if any queued sample has a lower priority then a currently playing sample,
+        // then every subsequent sample must also have a lower priority than its corresponding player.
+        if &sampler_score.priority > priority {
+            break;
+        }
 
-            commands
-                .entity(sample_entity)
-                .remove::<QueuedSample>()
-                .add_one_related::<SamplerOf>(sampler_entity);
+        // We'll also skip over samples that won't loop
+        // when the occupied sampler is currently looping.
+        if sampler_score.is_looping && player.repeat_mode == RepeatMode::PlayOnce {
+            continue;
         }
+
+        plan.assignments.push(PlannedAssignment {
+            sample_entity,
+            player,
+            asset,
+            sample_effects,
+            sampler_entity,
+            sampler_index: ranks[&sampler_entity].0,
+            interrupted: current_assignment,
+        });
     }
 
-    Ok(())
+    plan
 }
 
 pub(super) fn update_followers(
     samplers: Query<(Entity, &Children, &SamplerOf), Changed<SamplerOf>>,
     samples: Query<&SampleEffects>,
+    shared: Query<&SharedEffects>,
     mut commands: Commands,
 ) {
     for (sampler_entity, children, assignment) in &samplers {
         commands.entity(sampler_entity).insert(IgnoreDiffTimer);
 
-        let Ok(effects) = samples.get(assignment.get()) else {
+        let owner = shared
+            .get(assignment.get())
+            .map(SharedEffects::group)
+            .unwrap_or(assignment.get());
+        let Ok(effects) = samples.get(owner) else {
             continue;
         };
 
@@ -465,39 +682,48 @@ pub(super) fn update_followers(
     }
 }
 
-#[derive(Component)]
-pub(super) struct SkipTimer(Stopwatch);
+/// The time at which each queued sample became ready for playback.
+///
+/// Keeping this outside the ECS avoids inserting and removing
+/// a component for every sample that passes through the queue.
+#[derive(Resource, Default, Debug)]
+pub(super) struct QueueTimers(EntityHashMap<Duration>);
 
-pub(super) fn mark_skipped(
-    samples: Query<(Entity, &SamplePlayer), (With<QueuedSample>, Without<SkipTimer>)>,
+/// Skip any queued samples that have waited longer than their
+/// [`SampleQueueLifetime`].
+///
+/// A sample's lifetime only begins once its asset has loaded.
+pub(super) fn tick_queue(
+    samples: Query<(Entity, &SamplePlayer, &SampleQueueLifetime), Queued>,
     assets: Res<Assets<AudioSample>>,
-    mut commands: Commands,
-) {
-    for (sample, player) in &samples {
-        if assets.get(&player.sample).is_some() {
-            commands.entity(sample).insert(SkipTimer(Stopwatch::new()));
-        }
-    }
-}
-
-pub(super) fn tick_skipped(
-    mut samples: Query<
-        (Entity, &mut SkipTimer, &SampleQueueLifetime),
-        (With<SamplePlayer>, With<QueuedSample>),
-    >,
+    mut timers: ResMut<QueueTimers>,
     time: Res<Time>,
     mut commands: Commands,
 ) {
-    let delta = time.delta();
+    let now = time.elapsed();
 
-    for (sample_entity, mut timer, lifetime) in &mut samples {
-        if timer.0.tick(delta).elapsed() >= lifetime.0 {
-            debug!("skipping sample {:?} after {:?}", sample_entity, lifetime.0,);
+    // samples may have been assigned, despawned, or had their playback completed
+    if !timers.0.is_empty() {
+        timers.0.retain(|entity, _| samples.contains(*entity));
+    }
 
-            commands.trigger(PlaybackCompletion {
-                entity: sample_entity,
-                reason: crate::pool::CompletionReason::QueueLifetimeElapsed,
-            });
+    for (sample_entity, player, lifetime) in &samples {
+        match timers.0.get(&sample_entity) {
+            Some(start) => {
+                if now.saturating_sub(*start) >= lifetime.0 {
+                    debug!("skipping sample {:?} after {:?}", sample_entity, lifetime.0,);
+
+                    commands.trigger(PlaybackCompletion {
+                        entity: sample_entity,
+                        reason: crate::pool::CompletionReason::QueueLifetimeElapsed,
+                    });
+                }
+            }
+            None => {
+                if assets.get(&player.sample).is_some() {
+                    timers.0.insert(sample_entity, now);
+                }
+            }
         }
     }
 }
@@ -505,16 +731,17 @@ pub(super) fn tick_skipped(
 /// Assign the default pool label to a sample player that has no label.
 pub(super) fn assign_default(
     samples: Query<
-        (Entity, Option<&SampleEffects>),
+        (Entity, Option<&SampleEffects>, Option<&SharedEffects>),
         (With<SamplePlayer>, Without<PoolLabelContainer>),
     >,
+    groups: Query<&SampleEffects>,
     effects: Query<&EffectId>,
     // if there's no default pool, this probably shouldn't run
     default_pool: Single<Option<&SampleEffects>, With<super::SamplerPool<DefaultPool>>>,
     mut commands: Commands,
 ) {
-    for (sample, sample_effects) in samples.iter() {
-        match sample_effects {
+    for (sample, sample_effects, shared) in samples.iter() {
+        match SharedEffects::resolve(sample_effects, shared, &groups) {
             None => {
                 // clear default candidate
                 commands.entity(sample).insert(DefaultPool);
@@ -725,4 +952,41 @@ mod test {
             },
         );
     }
+
+    #[test]
+    fn test_queue_lifetime() {
+        #[derive(Component)]
+        struct Waiting;
+
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn((SamplerPool(DefaultPool), PoolSize(1..=1)));
+
+            commands.spawn((
+                SamplePlayer::new(server.load("caw.ogg")).looping(),
+                SamplePriority(1),
+            ));
+            commands.spawn((
+                Waiting,
+                SamplePlayer::new(server.load("caw.ogg")),
+                SampleQueueLifetime(Duration::ZERO),
+            ));
+        });
+
+        let start = std::time::Instant::now();
+        loop {
+            let waiting = run(&mut app, |q: Query<(), With<Waiting>>| q.iter().len());
+            if waiting == 0 {
+                break;
+            }
+
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+
+        app.update();
+        assert!(app.world().resource::<QueueTimers>().0.is_empty());
+    }
 }
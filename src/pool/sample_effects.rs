@@ -2,9 +2,15 @@
 
 use crate::utils::entity_set::{OrderedEntitySet, OrderedEntitySetIter};
 use bevy_ecs::{
+    entity::EntityCloner,
+    lifecycle::HookContext,
     prelude::*,
     query::{IterQueryData, QueryData, QueryFilter, QueryManyUniqueIter, ROQueryItem},
+    relationship::RelatedSpawner,
+    spawn::SpawnWith,
+    world::DeferredWorld,
 };
+use bevy_log::prelude::*;
 
 /// An effect applied to a sample player.
 ///
@@ -121,6 +127,62 @@ impl core::ops::Deref for SampleEffects {
     }
 }
 
+/// A name for an effect within a [`SampleEffects`] chain.
+///
+/// Chains with more than one effect of the same type, like
+/// two [`VolumeNode`]s, can't be addressed by type alone.
+/// Labels keep them distinct.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// # fn labels(mut commands: Commands, server: Res<AssetServer>) {
+/// commands.spawn((
+///     SamplePlayer::new(server.load("my_sample.wav")),
+///     sample_effects![
+///         (VolumeNode::default(), EffectLabel("pre")),
+///         FastLowpassNode::<2>::default(),
+///         (VolumeNode::default(), EffectLabel("post")),
+///     ],
+/// ));
+/// # }
+/// ```
+///
+/// See [`EffectsQuery::get_effect_labeled`].
+///
+/// [`VolumeNode`]: crate::prelude::VolumeNode
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct EffectLabel(pub &'static str);
+
+/// Find the single effect in `effects` labeled `label`.
+fn find_labeled(
+    effects: &SampleEffects,
+    label: EffectLabel,
+    labels: &Query<&EffectLabel>,
+) -> Result<Entity, EffectsQueryError> {
+    let entities: &[Entity] = effects;
+    let mut matches = entities
+        .iter()
+        .filter(|effect| labels.get(**effect).is_ok_and(|l| *l == label));
+
+    let effect = matches.next().ok_or(EffectsQueryError::MatchedNone)?;
+    if matches.next().is_some() {
+        return Err(EffectsQueryError::MatchedMultiple);
+    }
+
+    Ok(*effect)
+}
+
+/// Find the effect at `index` in `effects`.
+fn find_at(effects: &SampleEffects, index: usize) -> Result<Entity, EffectsQueryError> {
+    let entities: &[Entity] = effects;
+    entities
+        .get(index)
+        .copied()
+        .ok_or(EffectsQueryError::MatchedNone)
+}
+
 #[doc(hidden)]
 pub use bevy_ecs::recursive_spawn;
 
@@ -141,6 +203,318 @@ macro_rules! sample_effects {
     };
 }
 
+type EffectSpawner = Box<dyn FnOnce(&mut RelatedSpawner<EffectOf>) + Send + Sync>;
+
+/// A runtime builder for [`SampleEffects`].
+///
+/// [`sample_effects!`][crate::sample_effects] requires the full chain
+/// to be known up front. [`EffectsBuilder`] allows effects to be
+/// included conditionally, making it easy to compose chains at runtime.
+/// Effects are spawned in the order they're pushed.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// # fn builder(mut commands: Commands, server: Res<AssetServer>, underwater: bool) {
+/// commands.spawn((
+///     SamplePlayer::new(server.load("my_sample.wav")),
+///     EffectsBuilder::new()
+///         .push(VolumeNode::default())
+///         .push_if(underwater, FastLowpassNode::<2>::from_cutoff_hz(800.0))
+///         .build(),
+/// ));
+/// # }
+/// ```
+#[derive(Default)]
+pub struct EffectsBuilder {
+    effects: Vec<EffectSpawner>,
+}
+
+impl core::fmt::Debug for EffectsBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EffectsBuilder")
+            .field("effects", &self.effects.len())
+            .finish()
+    }
+}
+
+impl EffectsBuilder {
+    /// Create an empty [`EffectsBuilder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an effect to the chain.
+    pub fn push<B: Bundle>(mut self, effect: B) -> Self {
+        self.effects.push(Box::new(move |spawner| {
+            spawner.spawn(effect);
+        }));
+        self
+    }
+
+    /// Append an effect to the chain if `condition` is `true`.
+    pub fn push_if<B: Bundle>(self, condition: bool, effect: B) -> Self {
+        if condition { self.push(effect) } else { self }
+    }
+
+    /// Append an effect to the chain if it's `Some`.
+    pub fn push_some<B: Bundle>(self, effect: Option<B>) -> Self {
+        match effect {
+            Some(effect) => self.push(effect),
+            None => self,
+        }
+    }
+
+    /// The number of effects in the chain.
+    pub fn len(&self) -> usize {
+        self.effects.len()
+    }
+
+    /// Returns `true` if the chain contains no effects.
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// Produce a spawnable list of [`SampleEffects`].
+    ///
+    /// This is equivalent to the output of [`sample_effects!`][crate::sample_effects].
+    pub fn build(self) -> impl Bundle {
+        SampleEffects::spawn(SpawnWith(move |spawner: &mut RelatedSpawner<EffectOf>| {
+            for effect in self.effects {
+                effect(spawner);
+            }
+        }))
+    }
+}
+
+/// A nested chain of effects, flattened in place.
+///
+/// As an element of [`sample_effects!`][crate::sample_effects] or
+/// [`EffectsBuilder`], an [`EffectChain`] is replaced by its effects,
+/// in order, at its position in the chain. This lets common treatments
+/// be composed once and reused across many sounds.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// # fn chain(mut commands: Commands, server: Res<AssetServer>) {
+/// fn spatial_treatment() -> EffectChain {
+///     EffectChain::new(
+///         EffectsBuilder::new()
+///             .push(FastLowpassNode::<2>::default())
+///             .push(SpatialBasicNode::default()),
+///     )
+/// }
+///
+/// commands.spawn((
+///     SamplePlayer::new(server.load("footstep.wav")),
+///     sample_effects![VolumeNode::default(), spatial_treatment()],
+/// ));
+/// # }
+/// ```
+///
+/// Chains can also be cloned from a template entity's [`SampleEffects`]
+/// with [`EffectChain::from_template`]. The resulting effects are
+/// flattened before the sample is queued, so they determine
+/// the pool shape like any other effect.
+#[derive(Component)]
+#[component(on_insert = Self::on_insert_hook)]
+pub struct EffectChain(ChainSource);
+
+enum ChainSource {
+    Builder(EffectsBuilder),
+    Template(Entity),
+}
+
+impl core::fmt::Debug for EffectChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            ChainSource::Builder(builder) => f.debug_tuple("EffectChain").field(builder).finish(),
+            ChainSource::Template(entity) => f.debug_tuple("EffectChain").field(entity).finish(),
+        }
+    }
+}
+
+impl EffectChain {
+    /// Create a chain from an [`EffectsBuilder`].
+    pub fn new(builder: EffectsBuilder) -> Self {
+        Self(ChainSource::Builder(builder))
+    }
+
+    /// Create a chain by cloning the [`SampleEffects`] of `template`.
+    ///
+    /// The template entity is left untouched, so it may be reused.
+    pub fn from_template(template: Entity) -> Self {
+        Self(ChainSource::Template(template))
+    }
+
+    fn on_insert_hook(mut world: DeferredWorld, context: HookContext) {
+        let entity = context.entity;
+        world
+            .commands()
+            .queue(move |world: &mut World| Self::flatten(entity, world));
+    }
+
+    fn flatten(entity: Entity, world: &mut World) {
+        let Some(sample) = world.get::<EffectOf>(entity).map(|e| e.0) else {
+            return;
+        };
+        let Some(chain) = world.entity_mut(entity).take::<EffectChain>() else {
+            return;
+        };
+
+        let before: Vec<_> = world
+            .get::<SampleEffects>(sample)
+            .map(|e| e.iter().collect())
+            .unwrap_or_default();
+
+        let inner = match chain.0 {
+            ChainSource::Builder(builder) => {
+                world
+                    .entity_mut(sample)
+                    .with_related_entities::<EffectOf>(|spawner| {
+                        for effect in builder.effects {
+                            effect(spawner);
+                        }
+                    });
+
+                world
+                    .get::<SampleEffects>(sample)
+                    .map(|e| e.iter().filter(|e| !before.contains(e)).collect())
+                    .unwrap_or_default()
+            }
+            ChainSource::Template(template) => {
+                let Some(template) = world.get::<SampleEffects>(template) else {
+                    warn!("`EffectChain` template {template} has no `SampleEffects`.");
+                    world.despawn(entity);
+                    return;
+                };
+                let template: Vec<_> = template.iter().collect();
+
+                let mut cloner = EntityCloner::build_opt_out(world);
+                cloner.deny::<EffectOf>();
+                let mut cloner = cloner.finish();
+
+                template
+                    .into_iter()
+                    .map(|effect| cloner.spawn_clone(world, effect))
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        let mut flattened = Vec::with_capacity(before.len() + inner.len());
+        for effect in before {
+            if effect == entity {
+                flattened.extend_from_slice(&inner);
+            } else {
+                flattened.push(effect);
+            }
+        }
+
+        let current: Vec<_> = world
+            .get::<SampleEffects>(sample)
+            .map(|e| e.iter().collect())
+            .unwrap_or_default();
+
+        world
+            .entity_mut(sample)
+            .remove_related::<EffectOf>(&current)
+            .add_related::<EffectOf>(&flattened);
+        world.despawn(entity);
+    }
+}
+
+/// A [`SampleEffects`] chain shared by several samples.
+///
+/// Rather than each sample owning a copy of the same chain,
+/// samples with [`SharedEffects`] point to a single group entity
+/// whose [`SampleEffects`] serve as the baseline for all of them.
+/// Changing the group's effects affects every sample that uses it.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// # fn footsteps(mut commands: Commands, server: Res<AssetServer>) {
+/// let footsteps = commands
+///     .spawn(sample_effects![
+///         FastLowpassNode::<2>::from_cutoff_hz(2000.0),
+///         VolumeNode::default(),
+///     ])
+///     .id();
+///
+/// for _ in 0..4 {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("footstep.wav")),
+///         SharedEffects(footsteps),
+///     ));
+/// }
+/// # }
+/// ```
+///
+/// The group is reference counted through [`SharedEffectsUsers`].
+/// Once its last user is removed, the group and its effects are
+/// despawned, unless the group has [`RetainSharedEffects`].
+///
+/// [`SharedEffects`] takes precedence over a sample's own [`SampleEffects`].
+/// Since the group's chain is normalized to match the pool it's played in,
+/// all users of a group should be played in pools with the same shape.
+#[derive(Debug, Component)]
+#[relationship(relationship_target = SharedEffectsUsers)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct SharedEffects(pub Entity);
+
+/// The samples using a [`SharedEffects`] group.
+#[derive(Debug, Component)]
+#[relationship_target(relationship = SharedEffects)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct SharedEffectsUsers(Vec<Entity>);
+
+impl core::ops::Deref for SharedEffectsUsers {
+    type Target = [Entity];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Keep a [`SharedEffects`] group alive when it has no users.
+///
+/// This is useful for groups whose users come and go,
+/// like short-lived one-shot samples.
+#[derive(Debug, Default, Clone, Copy, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct RetainSharedEffects;
+
+impl SharedEffects {
+    /// The group entity holding the shared [`SampleEffects`].
+    pub fn group(&self) -> Entity {
+        self.0
+    }
+
+    /// Resolve a sample's effective chain, following its group if present.
+    pub(crate) fn resolve<'a>(
+        own: Option<&'a SampleEffects>,
+        shared: Option<&SharedEffects>,
+        groups: &'a Query<&SampleEffects>,
+    ) -> Option<&'a SampleEffects> {
+        match shared {
+            Some(shared) => groups.get(shared.0).ok(),
+            None => own,
+        }
+    }
+
+    /// Despawn groups once their last user is removed.
+    pub(crate) fn observe_release(
+        trigger: On<Remove, SharedEffectsUsers>,
+        retained: Query<(), With<RetainSharedEffects>>,
+        mut commands: Commands,
+    ) {
+        if !retained.contains(trigger.entity) {
+            commands.entity(trigger.entity).try_despawn();
+        }
+    }
+}
+
 /// Errors for effects queries.
 ///
 /// Since these queries require direct fetching with `get` and
@@ -264,6 +638,85 @@ where
         effects: &SampleEffects,
     ) -> Result<D::Item<'_, 's>, EffectsQueryError>;
 
+    /// Get the effect at `index` in the chain.
+    ///
+    /// An error is returned if there's no effect at `index`
+    /// or it doesn't match the query.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn log_first_volume(
+    ///     samples: Query<&SampleEffects, With<SamplePlayer>>,
+    ///     volume: Query<&VolumeNode>,
+    /// ) -> Result {
+    ///     for effects in samples {
+    ///         let volume = volume.get_effect_at(effects, 0)?.volume;
+    ///         info!("Volume: {volume:?}");
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// Samples queued in a pool have their effects reordered to match the
+    /// pool, so indices are only stable for chains that already match.
+    fn get_effect_at(
+        &self,
+        effects: &SampleEffects,
+        index: usize,
+    ) -> Result<ROQueryItem<'_, 's, D>, EffectsQueryError>;
+
+    /// Get a mutable reference to the effect at `index` in the chain.
+    ///
+    /// An error is returned if there's no effect at `index`
+    /// or it doesn't match the query.
+    fn get_effect_at_mut(
+        &mut self,
+        effects: &SampleEffects,
+        index: usize,
+    ) -> Result<D::Item<'_, 's>, EffectsQueryError>;
+
+    /// Get the single effect with the [`EffectLabel`] `label`.
+    ///
+    /// An error is returned if the label doesn't match exactly
+    /// one effect, or the effect doesn't match the query.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn duck_post_fader(
+    ///     samples: Query<&SampleEffects, With<SamplePlayer>>,
+    ///     mut volume: Query<&mut VolumeNode>,
+    ///     labels: Query<&EffectLabel>,
+    /// ) -> Result {
+    ///     for effects in samples {
+    ///         volume
+    ///             .get_effect_labeled_mut(effects, EffectLabel("post"), &labels)?
+    ///             .volume = Volume::Decibels(-12.0);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    fn get_effect_labeled(
+        &self,
+        effects: &SampleEffects,
+        label: EffectLabel,
+        labels: &Query<&EffectLabel>,
+    ) -> Result<ROQueryItem<'_, 's, D>, EffectsQueryError>;
+
+    /// Get a mutable reference to the single effect with the [`EffectLabel`] `label`.
+    ///
+    /// An error is returned if the label doesn't match exactly
+    /// one effect, or the effect doesn't match the query.
+    fn get_effect_labeled_mut(
+        &mut self,
+        effects: &SampleEffects,
+        label: EffectLabel,
+        labels: &Query<&EffectLabel>,
+    ) -> Result<D::Item<'_, 's>, EffectsQueryError>;
+
     /// Iterate over all effects entities that match the query.
     ///
     /// ```
@@ -342,6 +795,44 @@ where
             .ok_or(EffectsQueryError::MatchedNone)
     }
 
+    fn get_effect_at(
+        &self,
+        effects: &SampleEffects,
+        index: usize,
+    ) -> Result<ROQueryItem<'_, 's, D>, EffectsQueryError> {
+        self.get(find_at(effects, index)?)
+            .map_err(|_| EffectsQueryError::MatchedNone)
+    }
+
+    fn get_effect_at_mut(
+        &mut self,
+        effects: &SampleEffects,
+        index: usize,
+    ) -> Result<D::Item<'_, 's>, EffectsQueryError> {
+        self.get_mut(find_at(effects, index)?)
+            .map_err(|_| EffectsQueryError::MatchedNone)
+    }
+
+    fn get_effect_labeled(
+        &self,
+        effects: &SampleEffects,
+        label: EffectLabel,
+        labels: &Query<&EffectLabel>,
+    ) -> Result<ROQueryItem<'_, 's, D>, EffectsQueryError> {
+        self.get(find_labeled(effects, label, labels)?)
+            .map_err(|_| EffectsQueryError::MatchedNone)
+    }
+
+    fn get_effect_labeled_mut(
+        &mut self,
+        effects: &SampleEffects,
+        label: EffectLabel,
+        labels: &Query<&EffectLabel>,
+    ) -> Result<D::Item<'_, 's>, EffectsQueryError> {
+        self.get_mut(find_labeled(effects, label, labels)?)
+            .map_err(|_| EffectsQueryError::MatchedNone)
+    }
+
     fn iter_effects<'a>(
         &self,
         effects: &'a SampleEffects,
@@ -356,3 +847,98 @@ where
         self.iter_many_unique_mut(effects.iter())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        prelude::*,
+        test::{prepare_app, run},
+    };
+
+    #[test]
+    fn test_effect_chain() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            let template = commands
+                .spawn(sample_effects![
+                    FastLowpassNode::<2>::default(),
+                    EffectChain::new(EffectsBuilder::new().push(VolumePanNode::default())),
+                ])
+                .id();
+
+            commands.spawn(sample_effects![
+                VolumeNode::default(),
+                EffectChain::from_template(template),
+                (VolumeNode::default(), EffectLabel("post")),
+            ]);
+        });
+
+        run(
+            &mut app,
+            |samples: Query<&SampleEffects>,
+             chains: Query<(), With<EffectChain>>,
+             volume: Query<&VolumeNode>,
+             low_pass: Query<&FastLowpassNode>,
+             pan: Query<&VolumePanNode>,
+             labels: Query<&EffectLabel>| {
+                assert!(chains.is_empty());
+
+                let effects = samples.iter().find(|e| e.len() == 4).unwrap();
+                assert!(volume.get(effects[0]).is_ok());
+                assert!(low_pass.get(effects[1]).is_ok());
+                assert!(pan.get(effects[2]).is_ok());
+                assert_eq!(labels.get(effects[3]).ok(), Some(&EffectLabel("post")));
+            },
+        );
+    }
+
+    #[test]
+    fn test_effect_accessors() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn(sample_effects![
+                (
+                    VolumeNode {
+                        volume: Volume::Linear(0.25),
+                        ..Default::default()
+                    },
+                    EffectLabel("pre")
+                ),
+                (
+                    VolumeNode {
+                        volume: Volume::Linear(0.75),
+                        ..Default::default()
+                    },
+                    EffectLabel("post")
+                ),
+            ]);
+        });
+
+        run(
+            &mut app,
+            |effects: Single<&SampleEffects>,
+             volumes: Query<&VolumeNode>,
+             labels: Query<&EffectLabel>| {
+                assert!(matches!(
+                    volumes.get_effect(*effects),
+                    Err(EffectsQueryError::MatchedMultiple)
+                ));
+
+                assert_eq!(
+                    volumes.get_effect_at(*effects, 1).unwrap().volume,
+                    Volume::Linear(0.75)
+                );
+                assert!(volumes.get_effect_at(*effects, 2).is_err());
+
+                let pre = volumes
+                    .get_effect_labeled(*effects, EffectLabel("pre"), &labels)
+                    .unwrap();
+                assert_eq!(pre.volume, Volume::Linear(0.25));
+                assert!(
+                    volumes
+                        .get_effect_labeled(*effects, EffectLabel("missing"), &labels)
+                        .is_err()
+                );
+            },
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! Automatic voice scaling driven by [`DspLoad`].
+//!
+//! On weak hardware, a busy scene can push the audio thread past its
+//! time budget, producing audible glitches. Inserting the [`VoiceScaling`]
+//! resource enables a simple controller that trades voices for stability.
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use bevy_seedling::prelude::*;
+//! fn enable_scaling(mut commands: Commands) {
+//!     commands.insert_resource(VoiceScaling {
+//!         threshold: 0.7,
+//!         ..Default::default()
+//!     });
+//! }
+//! ```
+//!
+//! While the load exceeds [`VoiceScaling::threshold`], each pool's
+//! [`VoiceBudget`] is stepped down, capping its [`PoolSize`]. Any voices
+//! beyond the budget are [`Virtualized`], starting with the lowest
+//! [`SamplePriority`]. Once the load falls below [`VoiceScaling::recovery`],
+//! budgets are stepped back up toward each pool's original size, and
+//! virtualized voices resume in priority order.
+//!
+//! Removing [`VoiceScaling`] restores every pool's original size
+//! and resumes all virtualized voices.
+
+use super::{PoolSamplers, PoolSize, SamplerOf};
+use crate::{
+    context::DspLoad,
+    sample::{PlaybackSettings, SamplePriority},
+};
+use bevy_ecs::prelude::*;
+use bevy_time::{Real, Time};
+use core::{ops::RangeInclusive, time::Duration};
+
+/// Enables automatic voice scaling.
+///
+/// See the [module docs][self] for more information.
+#[derive(Resource, Debug, Clone)]
+pub struct VoiceScaling {
+    /// The [`DspLoad::usage`] above which voices are shed.
+    ///
+    /// Any reported underrun also sheds voices.
+    ///
+    /// By default, this is `0.75`.
+    pub threshold: f64,
+    /// The [`DspLoad::usage`] below which voices are restored.
+    ///
+    /// By default, this is `0.5`.
+    pub recovery: f64,
+    /// The fraction of a pool's budget added or removed in each step.
+    ///
+    /// By default, this is `0.25`.
+    pub step: f32,
+    /// The minimum time between steps.
+    ///
+    /// This gives the audio thread time to reflect each adjustment.
+    ///
+    /// By default, this is 250ms.
+    pub interval: Duration,
+    /// The fewest voices any pool will be scaled down to.
+    ///
+    /// By default, this is `1`.
+    pub min_voices: usize,
+}
+
+impl Default for VoiceScaling {
+    fn default() -> Self {
+        Self {
+            threshold: 0.75,
+            recovery: 0.5,
+            step: 0.25,
+            interval: Duration::from_millis(250),
+            min_voices: 1,
+        }
+    }
+}
+
+/// A pool's voice budget, managed by [`VoiceScaling`].
+///
+/// This is inserted on pools once scaling is enabled.
+#[derive(Component, Debug, Clone)]
+pub struct VoiceBudget {
+    original: RangeInclusive<usize>,
+    limit: usize,
+}
+
+impl VoiceBudget {
+    /// The maximum number of voices that may currently play.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// The pool's [`PoolSize`] before any scaling was applied.
+    pub fn original(&self) -> &RangeInclusive<usize> {
+        &self.original
+    }
+}
+
+/// A marker for samples paused by [`VoiceScaling`].
+///
+/// Virtualized samples retain their sampler and playhead,
+/// resuming once their pool's [`VoiceBudget`] allows.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct Virtualized;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Shrink,
+    Grow,
+}
+
+pub(super) fn scale_voices(
+    scaling: Res<VoiceScaling>,
+    load: Res<DspLoad>,
+    time: Res<Time<Real>>,
+    mut last_step: Local<Option<Duration>>,
+    mut pools: Query<(
+        Entity,
+        &PoolSamplers,
+        &mut PoolSize,
+        Option<&mut VoiceBudget>,
+    )>,
+    samplers: Query<&SamplerOf>,
+    mut samples: Query<(&SamplePriority, &mut PlaybackSettings, Has<Virtualized>)>,
+    mut commands: Commands,
+) {
+    let now = time.elapsed();
+    let due = last_step.is_none_or(|last| now.saturating_sub(last) >= scaling.interval);
+
+    let step = if !due {
+        None
+    } else if load.is_overloaded(scaling.threshold) {
+        Some(Step::Shrink)
+    } else if load.usage() < scaling.recovery {
+        Some(Step::Grow)
+    } else {
+        None
+    };
+
+    if step.is_some() {
+        *last_step = Some(now);
+    }
+
+    for (pool, pool_samplers, mut size, budget) in &mut pools {
+        let mut new_budget = None;
+        let budget = match budget {
+            Some(budget) => budget.into_inner(),
+            None => new_budget.insert(VoiceBudget {
+                original: size.0.clone(),
+                limit: *size.0.end(),
+            }),
+        };
+
+        let previous = budget.limit;
+        let amount = ((budget.limit.max(1) as f32 * scaling.step).ceil() as usize).max(1);
+        match step {
+            Some(Step::Shrink) => {
+                budget.limit = budget
+                    .limit
+                    .saturating_sub(amount)
+                    .max(scaling.min_voices)
+                    .min(*budget.original.end());
+            }
+            Some(Step::Grow) => {
+                budget.limit = (budget.limit + amount).min(*budget.original.end());
+            }
+            None => {}
+        }
+
+        if budget.limit != previous {
+            size.0 = (*budget.original.start()).min(budget.limit)..=budget.limit;
+        }
+
+        let limit = budget.limit;
+        if let Some(budget) = new_budget {
+            commands.entity(pool).insert(budget);
+        }
+
+        let mut playing = Vec::new();
+        let mut virtualized = Vec::new();
+        for assignment in samplers.iter_many(pool_samplers.iter()) {
+            let Ok((priority, settings, is_virtualized)) = samples.get(assignment.0) else {
+                continue;
+            };
+
+            if is_virtualized {
+                virtualized.push((assignment.0, *priority));
+            } else if *settings.play {
+                playing.push((assignment.0, *priority));
+            }
+        }
+
+        if playing.len() > limit {
+            playing.sort_by_key(|(_, priority)| *priority);
+
+            for (sample, _) in playing.iter().take(playing.len() - limit) {
+                if let Ok((_, mut settings, _)) = samples.get_mut(*sample) {
+                    settings.pause();
+                    commands.entity(*sample).insert(Virtualized);
+                }
+            }
+        } else if playing.len() < limit && !virtualized.is_empty() {
+            virtualized.sort_by_key(|(_, priority)| core::cmp::Reverse(*priority));
+
+            for (sample, _) in virtualized.iter().take(limit - playing.len()) {
+                if let Ok((_, mut settings, _)) = samples.get_mut(*sample) {
+                    settings.play();
+                    commands.entity(*sample).remove::<Virtualized>();
+                }
+            }
+        }
+    }
+}
+
+/// Undo all scaling once [`VoiceScaling`] is removed.
+pub(super) fn restore_voices(
+    mut pools: Query<(Entity, &VoiceBudget, &mut PoolSize)>,
+    mut samples: Query<(Entity, &mut PlaybackSettings), With<Virtualized>>,
+    mut commands: Commands,
+) {
+    for (pool, budget, mut size) in &mut pools {
+        size.0 = budget.original.clone();
+        commands.entity(pool).remove::<VoiceBudget>();
+    }
+
+    for (sample, mut settings) in &mut samples {
+        settings.play();
+        commands.entity(sample).remove::<Virtualized>();
+    }
+}
@@ -0,0 +1,75 @@
+//! Tracing instrumentation for `bevy_seedling`'s systems.
+//!
+//! With the `profiling` feature enabled, node acquisition, connections,
+//! pool queueing, diffing, and flushing are wrapped in `tracing` spans.
+//! Once per frame, the number of events queued and nodes added
+//! is emitted as a trace-level `tracing` event, so it stays out of
+//! normal logs. These appear in any `tracing` subscriber that
+//! records trace-level events, including Tracy and perfetto captures.
+
+#[cfg(feature = "profiling")]
+use bevy_app::prelude::*;
+#[cfg(feature = "profiling")]
+use bevy_ecs::prelude::*;
+#[cfg(feature = "profiling")]
+use bevy_platform::sync::atomic::{AtomicUsize, Ordering};
+
+/// Enter a `tracing` span for the rest of the enclosing scope.
+///
+/// Without the `profiling` feature, this expands to nothing.
+macro_rules! audio_span {
+    ($($args:tt)*) => {
+        #[cfg(feature = "profiling")]
+        let _span = bevy_log::info_span!($($args)*).entered();
+    };
+}
+
+pub(crate) use audio_span;
+
+#[cfg(feature = "profiling")]
+static EVENTS_QUEUED: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "profiling")]
+static NODES_ADDED: AtomicUsize = AtomicUsize::new(0);
+
+/// Record events sent to the audio context this frame.
+#[inline(always)]
+pub(crate) fn count_events_queued(count: usize) {
+    #[cfg(feature = "profiling")]
+    EVENTS_QUEUED.fetch_add(count, Ordering::Relaxed);
+
+    #[cfg(not(feature = "profiling"))]
+    let _ = count;
+}
+
+/// Record nodes added to the audio graph this frame.
+#[inline(always)]
+pub(crate) fn count_nodes_added(count: usize) {
+    #[cfg(feature = "profiling")]
+    NODES_ADDED.fetch_add(count, Ordering::Relaxed);
+
+    #[cfg(not(feature = "profiling"))]
+    let _ = count;
+}
+
+#[cfg(feature = "profiling")]
+pub(crate) struct ProfilingPlugin;
+
+#[cfg(feature = "profiling")]
+impl Plugin for ProfilingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Last, report_counters.after(crate::SeedlingSystems::Flush));
+    }
+}
+
+#[cfg(feature = "profiling")]
+fn report_counters() {
+    let events_queued = EVENTS_QUEUED.swap(0, Ordering::Relaxed);
+    let nodes_added = NODES_ADDED.swap(0, Ordering::Relaxed);
+
+    bevy_log::trace!(
+        target: "bevy_seedling::profiling",
+        events_queued,
+        nodes_added,
+        "audio frame"
+    );
+}
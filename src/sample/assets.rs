@@ -1,7 +1,14 @@
 use bevy_asset::Asset;
 use bevy_reflect::TypePath;
-use firewheel::{collector::ArcGc, sample_resource::SampleResource};
-use std::{num::NonZeroU32, sync::Arc};
+use firewheel::{
+    collector::ArcGc,
+    sample_resource::{InterleavedResourceF32, SampleResource, SampleResourceInfo},
+};
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    ops::Range,
+    sync::{Arc, OnceLock},
+};
 
 /// A type-erased audio sample.
 ///
@@ -14,10 +21,24 @@ use std::{num::NonZeroU32, sync::Arc};
 #[derive(Asset, TypePath, Clone)]
 pub struct AudioSample {
     sample: ArcGc<dyn SampleResource + Send + Sync>,
+    /// The reversed view of `sample`, shared between clones
+    /// so reversing doesn't allocate after the first time.
+    reversed: Arc<OnceLock<ArcGc<dyn SampleResource + Send + Sync>>>,
     original_sample_rate: NonZeroU32,
 }
 
 impl AudioSample {
+    fn from_resource(
+        sample: ArcGc<dyn SampleResource + Send + Sync>,
+        original_sample_rate: NonZeroU32,
+    ) -> Self {
+        Self {
+            sample,
+            reversed: Default::default(),
+            original_sample_rate,
+        }
+    }
+
     /// Create a new [`AudioSample`] from a [`SampleResource`] loaded into memory.
     ///
     /// If the sample resource has been resampled, `original_sample_rate` should represent
@@ -26,10 +47,60 @@ impl AudioSample {
         sample: S,
         original_sample_rate: NonZeroU32,
     ) -> Self {
-        Self {
-            sample: ArcGc::new_unsized(|| Arc::new(sample) as _),
+        Self::from_resource(
+            ArcGc::new_unsized(|| Arc::new(sample) as _),
             original_sample_rate,
-        }
+        )
+    }
+
+    /// Create a new [`AudioSample`] from raw, interleaved PCM data.
+    ///
+    /// This is useful for procedurally generated or network-received
+    /// audio, which can then be played through the usual [`SamplePlayer`]
+    /// and pool machinery.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// # use std::num::{NonZeroU32, NonZeroUsize};
+    /// fn play_tone(mut assets: ResMut<Assets<AudioSample>>, mut commands: Commands) {
+    ///     let sample_rate = 48000;
+    ///     let data: Vec<f32> = (0..sample_rate)
+    ///         .map(|i| (i as f32 * 440.0 * core::f32::consts::TAU / sample_rate as f32).sin())
+    ///         .collect();
+    ///
+    ///     let sample = AudioSample::from_pcm(
+    ///         data,
+    ///         NonZeroUsize::MIN,
+    ///         NonZeroU32::new(sample_rate as u32).unwrap(),
+    ///     );
+    ///
+    ///     commands.spawn(SamplePlayer::new(assets.add(sample)));
+    /// }
+    /// ```
+    ///
+    /// The data is not resampled, so `sample_rate` should match the
+    /// stream's [`SampleRate`][crate::context::SampleRate] for correct playback speed.
+    ///
+    /// [`SamplePlayer`]: crate::prelude::SamplePlayer
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `data` is not a multiple of `channels`.
+    pub fn from_pcm(data: Vec<f32>, channels: NonZeroUsize, sample_rate: NonZeroU32) -> Self {
+        assert!(
+            data.len() % channels.get() == 0,
+            "PCM data length must be a multiple of the channel count"
+        );
+
+        Self::new(
+            InterleavedResourceF32 {
+                data,
+                channels,
+                sample_rate: Some(sample_rate),
+            },
+            sample_rate,
+        )
     }
 
     /// Share the inner value.
@@ -37,6 +108,72 @@ impl AudioSample {
         self.sample.clone()
     }
 
+    /// Create a view of this sample that plays backwards.
+    ///
+    /// The underlying data is shared rather than copied, and the
+    /// reversed view is cached, so reversing a sample repeatedly is cheap.
+    /// Reversing the returned view yields the original resource.
+    pub fn reversed(&self) -> Self {
+        Self {
+            sample: self.get_reversed(),
+            reversed: Arc::new(OnceLock::from(self.sample.clone())),
+            original_sample_rate: self.original_sample_rate,
+        }
+    }
+
+    /// Share the inner value, reading back to front.
+    ///
+    /// The reversed view is created once and shared between
+    /// clones of this sample.
+    pub fn get_reversed(&self) -> ArcGc<dyn SampleResource + Send + Sync> {
+        self.reversed
+            .get_or_init(|| {
+                ArcGc::new_unsized(|| Arc::new(ReversedResource(self.sample.clone())) as _)
+            })
+            .clone()
+    }
+
+    /// Create a view of the frames in `frames`.
+    ///
+    /// The underlying data is shared rather than copied, so many
+    /// regions of a single large file are cheap. The range is clamped
+    /// to the sample's length.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn play_region(
+    ///     mut assets: ResMut<Assets<AudioSample>>,
+    ///     mut commands: Commands,
+    ///     sfx: Res<SfxHandle>,
+    /// ) {
+    ///     let Some(sample) = assets.get(&sfx.0) else {
+    ///         return;
+    ///     };
+    ///
+    ///     let region = assets.add(sample.region(48000..96000));
+    ///     commands.spawn(SamplePlayer::new(region));
+    /// }
+    /// # #[derive(Resource)]
+    /// # struct SfxHandle(Handle<AudioSample>);
+    /// ```
+    pub fn region(&self, frames: Range<u64>) -> Self {
+        let len = self.sample.len_frames();
+        let start = frames.start.min(len);
+        let end = frames.end.clamp(start, len);
+
+        Self::from_resource(
+            ArcGc::new_unsized(|| {
+                Arc::new(RegionResource {
+                    inner: self.sample.clone(),
+                    start,
+                    len: end - start,
+                }) as _
+            }),
+            self.original_sample_rate,
+        )
+    }
+
     /// Return the sample resource's original sample rate.
     ///
     /// If the resource has been resampled, this may return
@@ -48,23 +185,122 @@ impl AudioSample {
     }
 }
 
+/// A sample resource that reads its inner resource back to front.
+struct ReversedResource(ArcGc<dyn SampleResource + Send + Sync>);
+
+impl SampleResourceInfo for ReversedResource {
+    fn num_channels(&self) -> NonZeroUsize {
+        self.0.num_channels()
+    }
+
+    fn len_frames(&self) -> u64 {
+        self.0.len_frames()
+    }
+
+    fn sample_rate(&self) -> Option<NonZeroU32> {
+        self.0.sample_rate()
+    }
+}
+
+impl SampleResource for ReversedResource {
+    fn fill_buffers(
+        &self,
+        buffers: &mut [&mut [f32]],
+        buffer_range: Range<usize>,
+        start_frame: u64,
+    ) {
+        let len = self.0.len_frames();
+        let requested = buffer_range.len() as u64;
+
+        // Frames past the end of the resource are left silent.
+        let available = len.saturating_sub(start_frame).min(requested) as usize;
+        let filled = buffer_range.start..buffer_range.start + available;
+
+        for buffer in buffers.iter_mut() {
+            buffer[filled.end..buffer_range.end].fill(0.0);
+        }
+
+        if available == 0 {
+            return;
+        }
+
+        // Reading the mirrored range forwards and then reversing
+        // it in place yields the frames in backwards order.
+        let mirrored_start = len - start_frame - available as u64;
+        self.0.fill_buffers(buffers, filled.clone(), mirrored_start);
+
+        for buffer in buffers.iter_mut() {
+            buffer[filled.clone()].reverse();
+        }
+    }
+}
+
+/// A sample resource that reads a range of frames from its inner resource.
+struct RegionResource {
+    inner: ArcGc<dyn SampleResource + Send + Sync>,
+    start: u64,
+    len: u64,
+}
+
+impl SampleResourceInfo for RegionResource {
+    fn num_channels(&self) -> NonZeroUsize {
+        self.inner.num_channels()
+    }
+
+    fn len_frames(&self) -> u64 {
+        self.len
+    }
+
+    fn sample_rate(&self) -> Option<NonZeroU32> {
+        self.inner.sample_rate()
+    }
+}
+
+impl SampleResource for RegionResource {
+    fn fill_buffers(
+        &self,
+        buffers: &mut [&mut [f32]],
+        buffer_range: Range<usize>,
+        start_frame: u64,
+    ) {
+        let requested = buffer_range.len() as u64;
+
+        // Frames past the end of the region are left silent.
+        let available = self.len.saturating_sub(start_frame).min(requested) as usize;
+        let filled = buffer_range.start..buffer_range.start + available;
+
+        for buffer in buffers.iter_mut() {
+            buffer[filled.end..buffer_range.end].fill(0.0);
+        }
+
+        if available == 0 {
+            return;
+        }
+
+        self.inner
+            .fill_buffers(buffers, filled, self.start + start_frame);
+    }
+}
+
 #[cfg(feature = "symphonia")]
 impl From<firewheel::SymphoniumAudioF32> for AudioSample {
     fn from(source: firewheel::SymphoniumAudioF32) -> Self {
-        Self {
-            original_sample_rate: source.original_sample_rate(),
-            sample: ArcGc::new_unsized(|| Arc::new(source) as _),
-        }
+        let original_sample_rate = source.original_sample_rate();
+        Self::from_resource(
+            ArcGc::new_unsized(|| Arc::new(source) as _),
+            original_sample_rate,
+        )
     }
 }
 
 #[cfg(feature = "symphonia")]
 impl From<firewheel::SymphoniumAudio> for AudioSample {
     fn from(source: firewheel::SymphoniumAudio) -> Self {
-        Self {
-            original_sample_rate: source.original_sample_rate(),
-            sample: ArcGc::new_unsized(|| Arc::new(source) as _),
-        }
+        let original_sample_rate = source.original_sample_rate();
+        Self::from_resource(
+            ArcGc::new_unsized(|| Arc::new(source) as _),
+            original_sample_rate,
+        )
     }
 }
 
@@ -76,15 +312,87 @@ impl core::fmt::Debug for AudioSample {
     }
 }
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ramp(len: usize) -> AudioSample {
+        let data = (0..len).map(|i| i as f32).collect();
+        AudioSample::from_pcm(data, NonZeroUsize::MIN, NonZeroU32::new(48000).unwrap())
+    }
+
+    fn read(sample: &ArcGc<dyn SampleResource + Send + Sync>, start: u64, len: usize) -> Vec<f32> {
+        let mut buffer = vec![-1.0; len];
+        sample.fill_buffers(&mut [buffer.as_mut_slice()], 0..len, start);
+        buffer
+    }
+
+    #[test]
+    fn test_reversed_frames() {
+        let sample = ramp(5);
+        let reversed = sample.get_reversed();
+
+        assert_eq!(read(&reversed, 0, 5), [4.0, 3.0, 2.0, 1.0, 0.0]);
+        // Frames past the end are silent.
+        assert_eq!(read(&reversed, 3, 4), [1.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_reversed_region() {
+        let region = ramp(5).region(1..4);
+
+        assert_eq!(read(&region.get_reversed(), 0, 3), [3.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn test_reversed_is_cached() {
+        let sample = ramp(5);
+        let first = sample.get_reversed();
+
+        // Clones share the cache.
+        let second = sample.clone().get_reversed();
+        assert!(core::ptr::addr_eq(&*first, &*second));
+
+        // Reversing twice yields the original resource.
+        let original = sample.reversed().get_reversed();
+        assert!(core::ptr::addr_eq(&*original, &*sample.get()));
+    }
+
+    #[test]
+    fn test_region_seek() {
+        let sample = ramp(5);
+
+        // Seeking into a region reads the expected frames directly.
+        assert_eq!(read(&sample.region(1..4).get(), 2, 2), [3.0, 0.0]);
+    }
+}
+
 #[cfg(feature = "symphonia")]
 pub mod loader {
     use super::AudioSample;
     use bevy_app::prelude::*;
-    use bevy_asset::{AssetLoader, AssetServer};
+    use bevy_asset::{AssetLoader, AssetPath, AssetServer};
     use bevy_ecs::prelude::*;
+    use bevy_platform::collections::HashMap;
     use bevy_reflect::TypePath;
-    use symphonia::core::{codecs::registry::CodecRegistry, formats::probe::Probe};
-    use symphonium::{DecodeConfig, cache::SymphoniumCache};
+    use bevy_tasks::{AsyncComputeTaskPool, futures_lite::future};
+    use core::sync::atomic::{AtomicU64, Ordering};
+    use std::{
+        io::{Read, Seek},
+        num::NonZeroU32,
+        sync::{Arc, Mutex},
+    };
+    use symphonia::core::{
+        codecs::{audio::AudioDecoderOptions, registry::CodecRegistry},
+        errors::Error as SymphoniaError,
+        formats::{
+            FormatOptions, TrackType,
+            probe::{Hint, Probe},
+        },
+        io::{MediaSource, MediaSourceStream},
+        meta::MetadataOptions,
+    };
+    use symphonium::{DecodedAudioF32, cache::SymphoniumCache};
 
     pub struct SymphoniumLoaderPlugin;
 
@@ -99,7 +407,144 @@ pub mod loader {
                     .preregister_loader::<SampleLoader>(config.extensions());
             });
 
-            app.add_observer(init_loader);
+            app.init_resource::<SampleLoadProgress>()
+                .add_observer(init_loader);
+        }
+    }
+
+    /// Tracks the decoding progress of samples loaded with [`SampleLoader`].
+    ///
+    /// Decoding happens on the [`AsyncComputeTaskPool`], so large files
+    /// won't stall the asset IO pool. This resource reports how much of
+    /// each in-flight file has been decoded, which is useful for loading
+    /// screens with long music tracks.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::{prelude::*, sample::SampleLoadProgress};
+    /// fn loading_screen(progress: Res<SampleLoadProgress>) {
+    ///     if let Some(percent) = progress.get("music/theme.ogg") {
+    ///         info!("decoded {:.0}%", percent * 100.0);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Entries are removed once decoding completes.
+    #[derive(Resource, Default, Clone, Debug)]
+    pub struct SampleLoadProgress(Arc<Mutex<HashMap<AssetPath<'static>, Arc<DecodeProgress>>>>);
+
+    impl SampleLoadProgress {
+        /// Returns the decoding progress of the sample at `path` in the range `[0.0, 1.0]`.
+        ///
+        /// Returns `None` if the sample is not currently being decoded.
+        pub fn get<'a>(&self, path: impl Into<AssetPath<'a>>) -> Option<f32> {
+            let path = path.into();
+            let map = self.0.lock().ok()?;
+
+            map.get(&path).map(|progress| progress.fraction())
+        }
+
+        /// Iterate over the progress of all samples currently being decoded.
+        pub fn iter(&self) -> Vec<(AssetPath<'static>, f32)> {
+            let Ok(map) = self.0.lock() else {
+                return Vec::new();
+            };
+
+            map.iter()
+                .map(|(path, progress)| (path.clone(), progress.fraction()))
+                .collect()
+        }
+
+        fn track(
+            &self,
+            path: AssetPath<'static>,
+            total: u64,
+        ) -> (ProgressGuard, Arc<DecodeProgress>) {
+            let progress = Arc::new(DecodeProgress {
+                read: AtomicU64::new(0),
+                total,
+            });
+
+            if let Ok(mut map) = self.0.lock() {
+                map.insert(path.clone(), progress.clone());
+            }
+
+            let guard = ProgressGuard {
+                progress: self.clone(),
+                path,
+            };
+
+            (guard, progress)
+        }
+
+        fn finish(&self, path: &AssetPath<'static>) {
+            if let Ok(mut map) = self.0.lock() {
+                map.remove(path);
+            }
+        }
+    }
+
+    /// Removes a sample's entry from [`SampleLoadProgress`] when dropped.
+    ///
+    /// Loads can fail or be cancelled at any await point, so
+    /// the entry is cleaned up here rather than after decoding.
+    struct ProgressGuard {
+        progress: SampleLoadProgress,
+        path: AssetPath<'static>,
+    }
+
+    impl Drop for ProgressGuard {
+        fn drop(&mut self) {
+            self.progress.finish(&self.path);
+        }
+    }
+
+    #[derive(Debug)]
+    struct DecodeProgress {
+        read: AtomicU64,
+        total: u64,
+    }
+
+    impl DecodeProgress {
+        fn fraction(&self) -> f32 {
+            if self.total == 0 {
+                return 1.0;
+            }
+
+            (self.read.load(Ordering::Relaxed) as f64 / self.total as f64).min(1.0) as f32
+        }
+    }
+
+    /// An in-memory media source that reports how far the decoder has read.
+    struct ProgressSource {
+        cursor: std::io::Cursor<Vec<u8>>,
+        progress: Arc<DecodeProgress>,
+    }
+
+    impl Read for ProgressSource {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let read = self.cursor.read(buf)?;
+            self.progress
+                .read
+                .fetch_max(self.cursor.position(), Ordering::Relaxed);
+
+            Ok(read)
+        }
+    }
+
+    impl Seek for ProgressSource {
+        fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+            self.cursor.seek(pos)
+        }
+    }
+
+    impl MediaSource for ProgressSource {
+        fn is_seekable(&self) -> bool {
+            true
+        }
+
+        fn byte_len(&self) -> Option<u64> {
+            Some(self.progress.total)
         }
     }
 
@@ -221,12 +666,19 @@ pub mod loader {
     /// As a result, you may notice some latency when loading longer
     /// samples with low optimization levels.
     ///
+    /// Since compressed formats like Ogg Vorbis are fully decoded
+    /// during loading, seeking with [`PlayFrom::Seconds`] is constant-time
+    /// regardless of the sample's length or format; no seek table is needed.
+    ///
+    /// [`PlayFrom::Seconds`]: firewheel::nodes::sampler::PlayFrom::Seconds
+    ///
     /// The available containers and formats can be configured with
     /// this crate's feature flags and [`AudioLoaderConfig`].
     #[derive(TypePath, Debug)]
     pub struct SampleLoader {
         sample_rate: crate::context::SampleRate,
         config: &'static AudioLoaderConfig,
+        progress: SampleLoadProgress,
     }
 
     impl SampleLoader {
@@ -240,8 +692,16 @@ pub mod loader {
                 // we leak the config here to satisfy symphoium's `&'static` requirements
                 // NOTE: remove this when symphonium relaxes its lifetimes
                 config: Box::leak(Box::new(config)),
+                progress: SampleLoadProgress::default(),
             }
         }
+
+        /// Report decoding progress to `progress`.
+        ///
+        /// The default loader shares the [`SampleLoadProgress`] resource.
+        pub fn with_progress(self, progress: SampleLoadProgress) -> Self {
+            Self { progress, ..self }
+        }
     }
 
     /// Errors produced while loading samples.
@@ -249,7 +709,7 @@ pub mod loader {
     pub enum SampleLoaderError {
         /// An I/O error, such as missing files.
         StdIo(std::io::Error),
-        /// An error directly from `symphonium`.
+        /// An error directly from `symphonium` or `symphonia`.
         Symphonium(String),
     }
 
@@ -265,6 +725,12 @@ pub mod loader {
         }
     }
 
+    impl From<SymphoniaError> for SampleLoaderError {
+        fn from(value: SymphoniaError) -> Self {
+            Self::Symphonium(value.to_string())
+        }
+    }
+
     impl std::error::Error for SampleLoaderError {}
 
     impl std::fmt::Display for SampleLoaderError {
@@ -276,6 +742,99 @@ pub mod loader {
         }
     }
 
+    /// The number of packets decoded between yields.
+    ///
+    /// Yielding lets other tasks on the [`AsyncComputeTaskPool`]
+    /// make progress while long files decode.
+    const PACKETS_PER_CHUNK: usize = 64;
+
+    thread_local! {
+        static CACHE: SymphoniumCache = SymphoniumCache::new();
+    }
+
+    /// Decode the default audio track of `source`, yielding every [`PACKETS_PER_CHUNK`] packets.
+    ///
+    /// The decoded audio is resampled to `sample_rate` if necessary.
+    async fn decode_chunked(
+        source: Box<dyn MediaSource>,
+        hint: Hint,
+        config: &'static AudioLoaderConfig,
+        sample_rate: NonZeroU32,
+    ) -> Result<DecodedAudioF32, SampleLoaderError> {
+        let stream = MediaSourceStream::new(source, Default::default());
+        let mut format = config.probe.probe(
+            &hint,
+            stream,
+            FormatOptions::default(),
+            MetadataOptions::default(),
+        )?;
+
+        let missing_track = || SampleLoaderError::Symphonium("no audio track found".into());
+        let track = format
+            .default_track(TrackType::Audio)
+            .ok_or_else(missing_track)?;
+        let track_id = track.id;
+        let params = track
+            .codec_params
+            .as_ref()
+            .and_then(|params| params.audio())
+            .cloned()
+            .ok_or_else(missing_track)?;
+        let original_sample_rate = params.sample_rate.ok_or_else(|| {
+            SampleLoaderError::Symphonium("audio track has no sample rate".into())
+        })?;
+
+        let mut decoder = config
+            .codec_registry
+            .make_audio_decoder(&params, &AudioDecoderOptions::default())?;
+
+        let mut channels: Vec<Vec<f32>> = Vec::new();
+        let mut interleaved = Vec::new();
+
+        'decode: loop {
+            for _ in 0..PACKETS_PER_CHUNK {
+                let Some(packet) = format.next_packet()? else {
+                    break 'decode;
+                };
+
+                if packet.track_id() != track_id {
+                    continue;
+                }
+
+                let buffer = match decoder.decode(&packet) {
+                    Ok(buffer) => buffer,
+                    // Malformed packets are skipped rather than failing the whole load.
+                    Err(SymphoniaError::DecodeError(_)) => continue,
+                    Err(e) => return Err(e.into()),
+                };
+
+                let num_channels = buffer.spec().channels().count();
+                if channels.len() < num_channels {
+                    channels.resize_with(num_channels, Vec::new);
+                }
+
+                buffer.copy_to_vec_interleaved(&mut interleaved);
+                for frame in interleaved.chunks_exact(num_channels) {
+                    for (channel, sample) in channels.iter_mut().zip(frame) {
+                        channel.push(*sample);
+                    }
+                }
+            }
+
+            future::yield_now().await;
+        }
+
+        let decoded = DecodedAudioF32::new(channels, original_sample_rate, original_sample_rate);
+        if original_sample_rate == sample_rate.get() {
+            return Ok(decoded);
+        }
+
+        Ok(
+            CACHE
+                .with(|cache| symphonium::resample_f32(decoded, sample_rate.get(), Some(cache)))?,
+        )
+    }
+
     impl AssetLoader for SampleLoader {
         type Asset = AudioSample;
         type Settings = ();
@@ -287,32 +846,29 @@ pub mod loader {
             _settings: &Self::Settings,
             load_context: &mut bevy_asset::LoadContext<'_>,
         ) -> Result<Self::Asset, Self::Error> {
-            thread_local! {
-                static CACHE: SymphoniumCache = SymphoniumCache::new();
-            }
-
             let mut bytes = Vec::new();
             reader.read_to_end(&mut bytes).await?;
 
-            let mut hint = symphonia::core::formats::probe::Hint::new();
+            let path = load_context.asset_path().clone();
+            let (_guard, progress) = self.progress.track(path, bytes.len() as u64);
+
+            let mut hint = Hint::new();
             hint.with_extension(&load_context.path().to_string());
 
-            let probed = symphonium::probe_from_source(
-                Box::new(std::io::Cursor::new(bytes)),
-                Some(hint),
-                Some(&self.config.probe),
-            )?;
-            let source = CACHE.with(|cache| {
-                symphonium::decode_f32(
-                    probed,
-                    &DecodeConfig::default(),
-                    Some(self.sample_rate.get()),
-                    Some(cache),
-                    Some(&self.config.codec_registry),
-                )
-            })?;
+            let config = self.config;
+            let sample_rate = self.sample_rate.get();
+            let source = ProgressSource {
+                cursor: std::io::Cursor::new(bytes),
+                progress,
+            };
+
+            // Decoding large, compressed files can take a while, so we move
+            // it off the IO pool to avoid stalling other loads.
+            let decoded = AsyncComputeTaskPool::get()
+                .spawn(decode_chunked(Box::new(source), hint, config, sample_rate))
+                .await?;
 
-            Ok(firewheel::SymphoniumAudioF32(source).into())
+            Ok(firewheel::SymphoniumAudioF32(decoded).into())
         }
 
         fn extensions(&self) -> &[&str] {
@@ -329,11 +885,83 @@ pub mod loader {
             let config = world
                 .remove_resource::<AudioLoaderConfig>()
                 .ok_or("expected `AudioLoaderConfig` resource")?;
-            world
-                .resource::<AssetServer>()
-                .register_loader(SampleLoader::new(sample_rate.clone(), config));
+            let progress = world.resource::<SampleLoadProgress>().clone();
+            world.resource::<AssetServer>().register_loader(
+                SampleLoader::new(sample_rate.clone(), config).with_progress(progress),
+            );
 
             Ok(())
         });
     }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use firewheel::sample_resource::SampleResourceInfo;
+
+        #[test]
+        fn test_chunked_decode() {
+            let bytes = std::fs::read("assets/crow_ambience.ogg").unwrap();
+            let progress = SampleLoadProgress::default();
+            let (_guard, decode_progress) =
+                progress.track("crow_ambience.ogg".into(), bytes.len() as u64);
+
+            let source = ProgressSource {
+                cursor: std::io::Cursor::new(bytes),
+                progress: decode_progress,
+            };
+
+            let mut hint = Hint::new();
+            hint.with_extension("ogg");
+            let config = Box::leak(Box::new(AudioLoaderConfig::default()));
+
+            let mut decode = Box::pin(decode_chunked(
+                Box::new(source),
+                hint,
+                config,
+                NonZeroU32::new(48000).unwrap(),
+            ));
+
+            let mut yields = 0;
+            let decoded = loop {
+                match future::block_on(future::poll_once(&mut decode)) {
+                    Some(result) => break result.unwrap(),
+                    None => {
+                        yields += 1;
+
+                        // Progress is observable between chunks.
+                        let fraction = progress.get("crow_ambience.ogg").unwrap();
+                        assert!(fraction > 0.0 && fraction <= 1.0);
+                    }
+                }
+            };
+
+            assert!(yields > 0);
+
+            let sample: AudioSample = firewheel::SymphoniumAudioF32(decoded).into();
+            assert!(sample.get().len_frames() > 0);
+        }
+
+        #[test]
+        fn test_progress_guard() {
+            let progress = SampleLoadProgress::default();
+
+            let (guard, _) = progress.track("caw.ogg".into(), 1);
+            assert!(progress.get("caw.ogg").is_some());
+            drop(guard);
+            assert!(progress.get("caw.ogg").is_none());
+
+            // A load that's cancelled mid-decode still cleans up its entry.
+            let (guard, _) = progress.track("caw.ogg".into(), 1);
+            let mut load = Box::pin(async move {
+                let _guard = guard;
+                future::yield_now().await;
+            });
+
+            assert!(future::block_on(future::poll_once(&mut load)).is_none());
+            assert!(progress.get("caw.ogg").is_some());
+            drop(load);
+            assert!(progress.get("caw.ogg").is_none());
+        }
+    }
 }
@@ -0,0 +1,152 @@
+//! Keeping sounds alive after their parent despawns.
+
+use crate::{
+    SeedlingSystems,
+    pool::sample_effects::SampleEffects,
+    prelude::{AudioEvents, Volume, VolumeFade, VolumeNode},
+    time::{Audio, AudioTime},
+};
+use bevy_app::prelude::*;
+use bevy_ecs::{prelude::*, relationship::RelationshipTarget, world::DeferredWorld};
+use bevy_time::Time;
+use bevy_transform::prelude::*;
+use firewheel::clock::{DurationSeconds, InstantSeconds};
+
+pub(crate) struct DetachPlugin;
+
+impl Plugin for DetachPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(detach_children)
+            .add_systems(Last, despawn_faded.before(SeedlingSystems::Acquire));
+    }
+}
+
+/// Detach a sample player from its parent when the parent despawns.
+///
+/// By default, child entities are despawned along with their parent,
+/// cutting off any sound they're playing. With this component, the sample
+/// is instead re-parented to the global [`OrphanSounds`] container, where
+/// it plays out according to its [`OnComplete`][super::OnComplete] behavior.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn death_cry(enemy: Single<Entity>, server: Res<AssetServer>, mut commands: Commands) {
+///     commands.entity(*enemy).with_child((
+///         SamplePlayer::new(server.load("death_cry.wav")),
+///         DetachOnParentDespawn::default(),
+///     ));
+/// }
+/// ```
+///
+/// The sample keeps its last global position. If `fade` is set and the
+/// sample has a [`VolumeNode`] effect, the sample fades out over the given
+/// duration and is then despawned.
+#[derive(Debug, Component, Default, Clone)]
+pub struct DetachOnParentDespawn {
+    /// An optional fade-out applied once detached.
+    pub fade: Option<DurationSeconds>,
+}
+
+impl DetachOnParentDespawn {
+    /// Fade out over `duration` once detached.
+    pub fn fade(duration: DurationSeconds) -> Self {
+        Self {
+            fade: Some(duration),
+        }
+    }
+}
+
+/// The container for samples detached with [`DetachOnParentDespawn`].
+///
+/// This entity is spawned the first time a sample is detached.
+#[derive(Debug, Component, Default, Clone, Copy)]
+#[require(Transform)]
+pub struct OrphanSounds;
+
+/// A detached sample fading out before despawning.
+#[derive(Debug, Component)]
+struct OrphanFade(InstantSeconds);
+
+fn detach_children(trigger: On<Despawn, Children>, mut world: DeferredWorld) {
+    let parent = trigger.entity;
+    let Some(children) = world.get::<Children>(parent) else {
+        return;
+    };
+
+    let detached: Vec<_> = children
+        .iter()
+        .filter(|child| world.get::<DetachOnParentDespawn>(*child).is_some())
+        .collect();
+
+    if detached.is_empty() {
+        return;
+    }
+
+    // `Children` despawns its entities in a hook that runs after
+    // this observer, so we pull the detached entities out first.
+    if let Some(mut children) = world.get_mut::<Children>(parent) {
+        children
+            .collection_mut_risky()
+            .retain(|child| !detached.contains(child));
+    }
+
+    let mut commands = world.commands();
+    for child in detached {
+        commands.queue(move |world: &mut World| reparent(child, world));
+    }
+}
+
+fn reparent(child: Entity, world: &mut World) {
+    let Ok(entity) = world.get_entity(child) else {
+        return;
+    };
+    let global = entity.get::<GlobalTransform>().copied();
+
+    let orphans = world
+        .query_filtered::<Entity, With<OrphanSounds>>()
+        .iter(world)
+        .next();
+    let orphans = match orphans {
+        Some(orphans) => orphans,
+        None => world.spawn(OrphanSounds).id(),
+    };
+
+    let mut entity = world.entity_mut(child);
+    entity.insert(ChildOf(orphans));
+    if let Some(global) = global {
+        entity.insert(global.compute_transform());
+    }
+
+    let Some(fade) = entity.get::<DetachOnParentDespawn>().and_then(|d| d.fade) else {
+        return;
+    };
+
+    let Some(effects) = world.get::<SampleEffects>(child) else {
+        return;
+    };
+    let effects: Vec<_> = effects.iter().collect();
+
+    let end = world.resource::<Time<Audio>>().now() + fade;
+    let mut volumes = world.query::<(&VolumeNode, &mut AudioEvents)>();
+    let mut volumes = volumes.iter_many_mut(world, effects);
+    let Some((volume, mut events)) = volumes.fetch_next() else {
+        return;
+    };
+    volume.fade_to(Volume::SILENT, fade, &mut events);
+
+    world.entity_mut(child).insert(OrphanFade(end));
+}
+
+fn despawn_faded(
+    fading: Query<(Entity, &OrphanFade)>,
+    time: Res<Time<Audio>>,
+    mut commands: Commands,
+) {
+    let now = time.now();
+    for (entity, fade) in &fading {
+        if now >= fade.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
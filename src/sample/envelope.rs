@@ -0,0 +1,532 @@
+//! Volume envelopes driven by sample playback.
+
+use super::{AudioSample, PlaybackSettings, SamplePlayer, SampleQueue};
+use crate::{
+    SeedlingSystems,
+    context::SampleRate,
+    pool::{
+        Sampler, SamplerOf, SamplerRelease, Stopping,
+        sample_effects::{EffectOf, EffectsQuery, SampleEffects},
+    },
+    prelude::{AudioEvents, ParamFade, Volume, VolumeFade},
+    time::{Audio, AudioTime},
+};
+use bevy_app::prelude::*;
+use bevy_asset::Assets;
+use bevy_ecs::prelude::*;
+use bevy_time::Time;
+use firewheel::{
+    clock::{DurationSeconds, InstantSeconds},
+    diff::Notify,
+    nodes::{
+        sampler::{PlayFrom, RepeatMode, SamplerNode},
+        volume::VolumeNode,
+    },
+};
+
+pub(crate) struct EnvelopePlugin;
+
+impl Plugin for EnvelopePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(EnvelopeFollowsPlayback::observe_playback_start)
+            .add_observer(EnvelopeFollowsPlayback::observe_stop)
+            .add_observer(EnvelopeFollowsPlayback::observe_despawn)
+            .add_systems(
+                Last,
+                (
+                    EnvelopeFollowsPlayback::follow_gate,
+                    EnvelopeFollowsPlayback::release_before_end
+                        .run_if(resource_exists::<SampleRate>),
+                )
+                    .chain()
+                    .before(SeedlingSystems::Acquire),
+            );
+    }
+}
+
+/// An attack-hold-decay-sustain-release envelope that follows sample playback.
+///
+/// When a sample player with this component begins playback, the
+/// [`VolumeNode`] in its [`SampleEffects`] is faded in according to the
+/// attack, hold, and decay stages, settling at the sustain level.
+/// The [`VolumeNode`]'s own volume is left untouched.
+///
+/// The release stage is applied whenever playback ends:
+///
+/// - When playback is paused or stopped through [`PlaybackSettings`],
+///   the sampler keeps playing until the release completes.
+/// - When a non-looping sample nears its end, the release is timed
+///   to finish as the sample does.
+/// - When the sample is stopped with [`StopAll`] or [`StopWhere`],
+///   the stop fade lasts at least as long as the release.
+/// - When the sample player is despawned, its sampler plays out
+///   the release before it's freed.
+///
+/// [`StopAll`]: crate::prelude::StopAll
+/// [`StopWhere`]: crate::prelude::StopWhere
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn enveloped(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("my_sample.wav")),
+///         EnvelopeFollowsPlayback::new(
+///             DurationSeconds(0.01),
+///             DurationSeconds(0.05),
+///             DurationSeconds(0.2),
+///             Volume::Decibels(-6.0),
+///             DurationSeconds(0.5),
+///         ),
+///         sample_effects![VolumeNode::default()],
+///     ));
+/// }
+/// ```
+///
+/// If the sample player has no [`VolumeNode`] effect, or has more than one,
+/// the envelope is not applied.
+#[derive(Debug, Component, Clone)]
+#[require(PlaybackSettings)]
+#[component(immutable)]
+pub struct EnvelopeFollowsPlayback {
+    /// The time to rise from silence to [`EnvelopeFollowsPlayback::peak`].
+    pub attack: DurationSeconds,
+    /// The time to remain at the peak before decaying.
+    pub hold: DurationSeconds,
+    /// The time to fall from the peak to [`EnvelopeFollowsPlayback::sustain`].
+    pub decay: DurationSeconds,
+    /// The level held until playback is paused.
+    pub sustain: Volume,
+    /// The time to fall from the sustain level to silence once paused.
+    pub release: DurationSeconds,
+    /// The level reached at the end of the attack stage.
+    ///
+    /// Defaults to [`Volume::UNITY_GAIN`].
+    pub peak: Volume,
+}
+
+impl Default for EnvelopeFollowsPlayback {
+    fn default() -> Self {
+        Self::new(
+            DurationSeconds(0.005),
+            DurationSeconds(0.0),
+            DurationSeconds(0.0),
+            Volume::UNITY_GAIN,
+            DurationSeconds(0.05),
+        )
+    }
+}
+
+/// Tracks the playback gate for [`EnvelopeFollowsPlayback`].
+#[derive(Debug, Component)]
+struct EnvelopeGate {
+    play: Notify<bool>,
+    play_from: PlayFrom,
+    opened: InstantSeconds,
+    releasing: bool,
+}
+
+/// Shortly after a sampler is assigned, its shared state may
+/// still describe the previous sample.
+const STALE_PLAYHEAD: DurationSeconds = DurationSeconds(0.05);
+
+impl EnvelopeFollowsPlayback {
+    /// Construct a new [`EnvelopeFollowsPlayback`] with a peak of [`Volume::UNITY_GAIN`].
+    pub fn new(
+        attack: DurationSeconds,
+        hold: DurationSeconds,
+        decay: DurationSeconds,
+        sustain: Volume,
+        release: DurationSeconds,
+    ) -> Self {
+        Self {
+            attack,
+            hold,
+            decay,
+            sustain,
+            release,
+            peak: Volume::UNITY_GAIN,
+        }
+    }
+
+    /// Set the peak level.
+    pub fn with_peak(self, peak: Volume) -> Self {
+        Self { peak, ..self }
+    }
+
+    /// Apply the attack, hold, and decay stages starting at `now`.
+    fn trigger(&self, volume: &VolumeNode, events: &mut AudioEvents, now: InstantSeconds) {
+        let attack_end = now + self.attack;
+        let decay_start = attack_end + self.hold;
+        let decay_end = decay_start + self.decay;
+
+        let mut silent = volume.clone();
+        silent.volume = Volume::SILENT;
+
+        silent.fade_at(self.peak, now, attack_end, events);
+        volume.fade_at(self.sustain, decay_start, decay_end, events);
+    }
+
+    fn observe_playback_start(
+        trigger: On<Insert, Sampler>,
+        players: Query<(&Self, &PlaybackSettings, &SampleEffects), With<SamplePlayer>>,
+        mut volumes: Query<(&VolumeNode, &mut AudioEvents), With<EffectOf>>,
+        time: Res<Time<Audio>>,
+        mut commands: Commands,
+    ) {
+        let Ok((envelope, settings, effects)) = players.get(trigger.event_target()) else {
+            return;
+        };
+
+        let Ok((volume, mut events)) = volumes.get_effect_mut(effects) else {
+            return;
+        };
+
+        envelope.trigger(volume, &mut events, time.now());
+
+        commands
+            .entity(trigger.event_target())
+            .insert(EnvelopeGate {
+                play: settings.play.clone(),
+                play_from: settings.play_from,
+                opened: time.now(),
+                releasing: false,
+            });
+    }
+
+    fn follow_gate(
+        mut players: Query<
+            (
+                Entity,
+                &Self,
+                &mut PlaybackSettings,
+                &mut EnvelopeGate,
+                Option<&mut AudioEvents>,
+                &SampleEffects,
+            ),
+            (Changed<PlaybackSettings>, Without<EffectOf>),
+        >,
+        mut volumes: Query<(&VolumeNode, &mut AudioEvents), With<EffectOf>>,
+        time: Res<Time<Audio>>,
+        mut commands: Commands,
+    ) {
+        for (entity, envelope, mut settings, mut gate, events, effects) in &mut players {
+            let Ok((volume, mut volume_events)) = volumes.get_effect_mut(effects) else {
+                continue;
+            };
+
+            let now = time.now();
+            if *settings.play {
+                // Resuming after a release should retrigger the envelope.
+                if gate.releasing {
+                    envelope.trigger(volume, &mut volume_events, now);
+                    gate.opened = now;
+                }
+
+                gate.play = settings.play.clone();
+                gate.play_from = settings.play_from;
+                gate.releasing = false;
+                continue;
+            }
+
+            if gate.releasing {
+                continue;
+            }
+
+            // Restore the previous playback state so the
+            // sampler keeps playing throughout the release.
+            let play_from = settings.play_from;
+            let settings = settings.bypass_change_detection();
+            settings.play = gate.play.clone();
+            settings.play_from = gate.play_from;
+            gate.releasing = true;
+
+            let release_end = now + envelope.release;
+            volume.fade_at(Volume::SILENT, now, release_end, &mut volume_events);
+
+            // Then apply the pause or stop once silent.
+            let end_playback = |events: &mut AudioEvents| {
+                events.schedule(release_end, &*settings, |settings| {
+                    *settings.play = false;
+                    settings.play_from = play_from;
+                });
+            };
+
+            match events {
+                Some(mut events) => end_playback(&mut events),
+                None => {
+                    let mut events = AudioEvents::new(&time);
+                    end_playback(&mut events);
+                    commands.entity(entity).insert(events);
+                }
+            }
+        }
+    }
+
+    /// Time the release to finish along with non-looping samples.
+    fn release_before_end(
+        mut players: Query<
+            (
+                &Self,
+                &SamplePlayer,
+                &PlaybackSettings,
+                &Sampler,
+                &mut EnvelopeGate,
+                &SampleEffects,
+                Option<&SampleQueue>,
+            ),
+            Without<EffectOf>,
+        >,
+        mut volumes: Query<(&VolumeNode, &mut AudioEvents), With<EffectOf>>,
+        assets: Res<Assets<AudioSample>>,
+        sample_rate: Res<SampleRate>,
+        time: Res<Time<Audio>>,
+    ) {
+        let now = time.now();
+
+        for (envelope, player, settings, sampler, mut gate, effects, queue) in &mut players {
+            if gate.releasing
+                || !*settings.play
+                || player.repeat_mode != RepeatMode::PlayOnce
+                || now < gate.opened + STALE_PLAYHEAD
+            {
+                continue;
+            }
+
+            // Later items in a queue continue the same gate.
+            if queue.is_some_and(|q| q.iter().next().is_some()) {
+                continue;
+            }
+
+            let sample = queue
+                .and_then(SampleQueue::current)
+                .unwrap_or(&player.sample);
+            let (Some(asset), Some(playhead)) = (assets.get(sample), sampler.try_playhead_frames())
+            else {
+                continue;
+            };
+
+            let rate = sample_rate.get().get() as f64 * settings.speed;
+            if rate <= 0.0 {
+                continue;
+            }
+
+            let remaining = asset
+                .get()
+                .len_frames()
+                .saturating_sub(playhead.0.max(0) as u64);
+            let remaining = DurationSeconds(remaining as f64 / rate);
+            if remaining.0 > envelope.release.0 {
+                continue;
+            }
+
+            let Ok((volume, mut events)) = volumes.get_effect_mut(effects) else {
+                continue;
+            };
+
+            gate.releasing = true;
+            volume.fade_at(Volume::SILENT, now, now + remaining, &mut events);
+        }
+    }
+
+    /// Stretch declicked stops to cover the release.
+    fn observe_stop(trigger: On<Insert, Stopping>, mut players: Query<(&Self, &mut Stopping)>) {
+        let Ok((envelope, mut stopping)) = players.get_mut(trigger.event_target()) else {
+            return;
+        };
+
+        if stopping.end.is_none() && stopping.fade.0 < envelope.release.0 {
+            stopping.fade = envelope.release;
+        }
+    }
+
+    /// Keep a despawned player's sampler alive through the release.
+    fn observe_despawn(
+        trigger: On<Despawn, EnvelopeGate>,
+        players: Query<(&Self, &EnvelopeGate, &Sampler, Has<Stopping>)>,
+        mut samplers: Query<(&SamplerNode, &mut AudioEvents), With<SamplerOf>>,
+        time: Res<Time<Audio>>,
+        mut commands: Commands,
+    ) {
+        let Ok((envelope, gate, sampler, stopping)) = players.get(trigger.entity) else {
+            return;
+        };
+
+        // Stopped and completed samples are already silent.
+        if gate.releasing || stopping || !sampler.is_playing() || envelope.release.0 <= 0.0 {
+            return;
+        }
+
+        let Ok((node, mut events)) = samplers.get_mut(sampler.sampler()) else {
+            return;
+        };
+
+        let now = time.now();
+        let end = now + envelope.release;
+        node.fade_param_at(Volume::SILENT, now, end, &mut events, |s| &mut s.volume);
+
+        // This is applied before the assignment is removed.
+        commands
+            .entity(sampler.sampler())
+            .insert(SamplerRelease(end));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        pool::SamplerPool,
+        prelude::*,
+        sample_effects,
+        test::{prepare_app, run},
+    };
+    use std::time::Instant;
+
+    #[derive(PoolLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct TestPool;
+
+    fn envelope() -> EnvelopeFollowsPlayback {
+        EnvelopeFollowsPlayback::new(
+            DurationSeconds(0.5),
+            DurationSeconds(0.0),
+            DurationSeconds(0.5),
+            Volume::Linear(0.5),
+            DurationSeconds(0.25),
+        )
+    }
+
+    fn prepare_player() -> App {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn((
+                SamplerPool(TestPool),
+                sample_effects![VolumeNode::default()],
+            ));
+            commands.spawn((
+                TestPool,
+                SamplePlayer::new(server.load("caw.ogg")).looping(),
+                envelope(),
+                sample_effects![VolumeNode::default()],
+            ));
+        });
+
+        let start = Instant::now();
+        while !run(&mut app, |q: Query<&Sampler, With<EnvelopeGate>>| {
+            q.iter().any(Sampler::is_playing)
+        }) {
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+
+        app
+    }
+
+    #[test]
+    fn test_envelope_trigger() {
+        let mut app = prepare_player();
+
+        run(
+            &mut app,
+            |player: Single<&SampleEffects, With<SamplePlayer>>,
+             volumes: Query<(&VolumeNode, &AudioEvents), With<EffectOf>>,
+             time: Res<Time<Audio>>| {
+                let (volume, events) = volumes.get_effect(*player).unwrap();
+
+                // The user's volume is left alone.
+                assert_eq!(volume.volume, Volume::UNITY_GAIN);
+
+                let sustain = events.get_value_at(time.now() + DurationSeconds(2.0), volume);
+                assert_eq!(sustain.volume, Volume::Linear(0.5));
+            },
+        );
+    }
+
+    #[test]
+    fn test_envelope_release_on_pause() {
+        let mut app = prepare_player();
+
+        run(&mut app, |mut settings: Single<&mut PlaybackSettings>| {
+            settings.pause();
+        });
+        app.update();
+
+        run(
+            &mut app,
+            |player: Single<(&PlaybackSettings, &SampleEffects, &EnvelopeGate)>,
+             volumes: Query<(&VolumeNode, &AudioEvents), With<EffectOf>>,
+             time: Res<Time<Audio>>| {
+                let (settings, effects, gate) = player.into_inner();
+
+                // Playback continues until the release completes.
+                assert!(*settings.play);
+                assert!(gate.releasing);
+
+                let (volume, events) = volumes.get_effect(effects).unwrap();
+                let released = events.get_value_at(time.now() + DurationSeconds(0.3), volume);
+                assert_eq!(released.volume, Volume::SILENT);
+            },
+        );
+    }
+
+    #[test]
+    fn test_envelope_release_on_stop() {
+        let mut app = prepare_player();
+
+        run(&mut app, |mut commands: Commands| {
+            commands.stop_all();
+        });
+
+        let fade = run(&mut app, |stopping: Single<&Stopping>| stopping.fade);
+        assert_eq!(fade, DurationSeconds(0.25));
+    }
+
+    #[test]
+    fn test_envelope_release_on_despawn() {
+        let mut app = prepare_player();
+
+        let sampler = run(
+            &mut app,
+            |player: Single<(Entity, &Sampler)>, mut commands: Commands| {
+                let (player, sampler) = player.into_inner();
+                commands.entity(player).despawn();
+                sampler.sampler()
+            },
+        );
+        app.update();
+
+        let (releasing, playing) = run(
+            &mut app,
+            move |samplers: Query<(Has<SamplerRelease>, &SamplerNode)>| {
+                let (releasing, node) = samplers.get(sampler).unwrap();
+                (releasing, *node.play)
+            },
+        );
+        assert!(releasing);
+        assert!(playing);
+
+        let start = Instant::now();
+        loop {
+            let releasing = run(&mut app, move |samplers: Query<Has<SamplerRelease>>| {
+                samplers.get(sampler).unwrap()
+            });
+
+            if !releasing {
+                break;
+            }
+
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+
+        let playing = run(&mut app, move |samplers: Query<&SamplerNode>| {
+            *samplers.get(sampler).unwrap().play
+        });
+        assert!(!playing);
+    }
+}
@@ -2,26 +2,57 @@
 
 use crate::{
     prelude::{AudioEvents, Volume},
-    time::Audio,
+    time::{Audio, AudioTime},
 };
 use bevy_asset::Handle;
-use bevy_ecs::prelude::*;
-use bevy_math::FloatExt;
+use bevy_ecs::{lifecycle::HookContext, prelude::*, world::DeferredWorld};
+use bevy_math::{
+    FloatExt,
+    curve::{Curve, EaseFunction},
+};
 use firewheel::{
     clock::{DurationSeconds, InstantSeconds},
     diff::Notify,
     nodes::sampler::{PlayFrom, RepeatMode},
 };
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 mod assets;
+mod detach;
+mod envelope;
+mod play;
+mod sequence;
+#[cfg(feature = "sprites")]
+mod sprite;
+mod tape;
+#[cfg(feature = "validation")]
+mod validation;
 
 pub use assets::AudioSample;
+pub use detach::{DetachOnParentDespawn, OrphanSounds};
+pub use envelope::EnvelopeFollowsPlayback;
+pub use play::PlayCommands;
+pub use sequence::{SampleQueue, SampleQueueItem, SampleQueueItemComplete};
+pub use tape::{TapeStart, TapeStop};
+
+#[cfg(feature = "validation")]
+pub use validation::{
+    SampleAnalysis, SampleProblem, SampleValidation, SampleValidationPlugin, validate_sample,
+};
+
+#[cfg(feature = "sprites")]
+pub(crate) use sprite::AudioSpritePlugin;
+#[cfg(feature = "sprites")]
+pub use sprite::{AudioSpriteLoader, AudioSpriteLoaderError, AudioSpriteSheet, SpriteRegion};
+
+pub(crate) use detach::DetachPlugin;
+pub(crate) use envelope::EnvelopePlugin;
+pub(crate) use tape::TapePlugin;
 
 #[cfg(feature = "symphonia")]
 pub(crate) use assets::loader::SymphoniumLoaderPlugin;
 #[cfg(feature = "symphonia")]
-pub use assets::loader::{AudioLoaderConfig, SampleLoader, SampleLoaderError};
+pub use assets::loader::{AudioLoaderConfig, SampleLoadProgress, SampleLoader, SampleLoaderError};
 
 /// A component that queues sample playback.
 ///
@@ -160,6 +191,7 @@ pub use assets::loader::{AudioLoaderConfig, SampleLoader, SampleLoaderError};
 ///         play_from: PlayFrom::BEGINNING,
 ///         speed: 1.0,
 ///         on_complete: OnComplete::Despawn,
+///         direction: PlaybackDirection::Forward,
 ///     },
 ///     SamplePriority(0),
 ///     SampleQueueLifetime(std::time::Duration::from_millis(100)),
@@ -184,16 +216,18 @@ pub struct SamplePlayer {
     ///
     /// Defaults to [`RepeatMode::PlayOnce`].
     ///
-    /// The [`RepeatMode`] can only be configured once at the beginning of playback.
+    /// To change the [`RepeatMode`] during playback, trigger
+    /// [`SetRepeatMode`][crate::prelude::SetRepeatMode].
     pub repeat_mode: RepeatMode,
 
     /// Sets the volume of the sample.
     ///
     /// Defaults to [`Volume::UNITY_GAIN`].
     ///
-    /// This volume can only be configured once at the beginning of playback.
-    /// For dynamic volume, consider routing to buses or applying [`VolumeNode`]
-    /// as an effect.
+    /// To change this volume during playback, trigger
+    /// [`SetStaticVolume`][crate::prelude::SetStaticVolume].
+    /// For frequent or scheduled changes, consider routing to buses
+    /// or applying [`VolumeNode`] as an effect.
     ///
     /// [`VolumeNode`]: crate::prelude::VolumeNode
     pub volume: Volume,
@@ -238,7 +272,8 @@ impl SamplePlayer {
     /// }
     /// ```
     ///
-    /// Looping can only be configured once at the beginning of playback.
+    /// To change the [`RepeatMode`] during playback, trigger
+    /// [`SetRepeatMode`][crate::prelude::SetRepeatMode].
     pub fn looping(self) -> Self {
         Self {
             repeat_mode: RepeatMode::RepeatEndlessly,
@@ -258,9 +293,10 @@ impl SamplePlayer {
     /// }
     /// ```
     ///
-    /// This volume can only be configured once at the beginning of playback.
-    /// For dynamic volume, consider routing to buses or applying [`VolumeNode`]
-    /// as an effect.
+    /// To change this volume during playback, trigger
+    /// [`SetStaticVolume`][crate::prelude::SetStaticVolume].
+    /// For frequent or scheduled changes, consider routing to buses
+    /// or applying [`VolumeNode`] as an effect.
     ///
     /// [`VolumeNode`]: crate::prelude::VolumeNode
     pub fn with_volume(self, volume: Volume) -> Self {
@@ -268,16 +304,27 @@ impl SamplePlayer {
     }
 }
 
+/// Marks a [`SamplePlayer`] re-insertion that only changes its settings.
+///
+/// The sample's current playback, if any, is kept.
+#[derive(Debug, Component)]
+#[component(storage = "SparseSet")]
+pub(crate) struct PlayerUpdate;
+
 pub(super) fn observe_player_insert(
     player: On<Insert, SamplePlayer>,
+    updates: Query<(), With<PlayerUpdate>>,
     time: Res<bevy_time::Time<Audio>>,
     mut commands: Commands,
 ) {
-    commands
-        .entity(player.event_target())
-        // When re-inserting, the current playback if any should be stopped.
-        .remove::<crate::pool::Sampler>()
-        .insert_if_new(AudioEvents::new(&time));
+    let mut entity = commands.entity(player.event_target());
+
+    // When re-inserting, the current playback if any should be stopped.
+    if !updates.contains(player.event_target()) {
+        entity.remove::<crate::pool::Sampler>();
+    }
+
+    entity.insert_if_new(AudioEvents::new(&time));
 }
 
 /// Provide explicit priorities for samples.
@@ -303,6 +350,65 @@ pub(super) fn observe_player_insert(
 #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct SamplePriority(pub i32);
 
+/// Delay the start of a sample's playback.
+///
+/// The delay begins once the sample is assigned a sampler,
+/// so the time spent loading and queueing isn't counted.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// # use std::time::Duration;
+/// fn play_later(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("my_sample.wav")),
+///         StartDelay(Duration::from_millis(200)),
+///     ));
+/// }
+/// ```
+///
+/// This is equivalent to scheduling [`PlaybackSettings::play_at`]
+/// yourself. The component is removed once playback is scheduled.
+/// If the sample is paused when this is inserted, the delay is ignored.
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[component(immutable, on_insert = Self::on_insert_hook)]
+#[require(PlaybackSettings)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct StartDelay(pub Duration);
+
+impl StartDelay {
+    fn on_insert_hook(mut world: DeferredWorld, context: HookContext) {
+        let Some(mut settings) = world.get_mut::<PlaybackSettings>(context.entity) else {
+            return;
+        };
+
+        if *settings.play {
+            *settings.play = false;
+        } else {
+            world
+                .commands()
+                .entity(context.entity)
+                .remove::<StartDelay>();
+        }
+    }
+
+    pub(crate) fn observe_assignment(
+        trigger: On<Insert, crate::pool::Sampler>,
+        mut samples: Query<(&StartDelay, &PlaybackSettings, &mut AudioEvents)>,
+        time: Res<bevy_time::Time<Audio>>,
+        mut commands: Commands,
+    ) {
+        let Ok((delay, settings, mut events)) = samples.get_mut(trigger.entity) else {
+            return;
+        };
+
+        let start = time.delay(DurationSeconds(delay.0.as_secs_f64()));
+        settings.play_at(None, start, &mut events);
+
+        commands.entity(trigger.entity).remove::<StartDelay>();
+    }
+}
+
 /// The maximum duration of time that a sample will wait for an available sampler.
 ///
 /// The timer begins once the sample asset has loaded and after the sample player has been skipped
@@ -324,7 +430,7 @@ impl Default for SampleQueueLifetime {
 /// Determines what happens when a sample completes playback.
 ///
 /// This will not trigger for looping samples unless they are stopped.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub enum OnComplete {
     /// Preserve the entity and components, leaving them untouched.
@@ -337,6 +443,108 @@ pub enum OnComplete {
     /// common, this is the default.
     #[default]
     Despawn,
+    /// Run a [`CompletionTrigger`], then despawn the [`SamplePlayer`] entity.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// #[derive(Event, Clone)]
+    /// struct ExplosionFinished;
+    ///
+    /// fn explode(mut commands: Commands, server: Res<AssetServer>) {
+    ///     commands.spawn((
+    ///         SamplePlayer::new(server.load("explosion.wav")),
+    ///         PlaybackSettings::default()
+    ///             .with_on_complete(OnComplete::Trigger(CompletionTrigger::event(ExplosionFinished))),
+    ///     ));
+    /// }
+    ///
+    /// fn debris(
+    ///     _: On<ExplosionFinished>,
+    ///     mut commands: Commands,
+    ///     server: Res<AssetServer>,
+    /// ) {
+    ///     commands.spawn(SamplePlayer::new(server.load("debris.wav")));
+    /// }
+    /// ```
+    Trigger(#[cfg_attr(feature = "reflect", reflect(ignore))] CompletionTrigger),
+}
+
+/// A callback run when a sample completes with [`OnComplete::Trigger`].
+///
+/// The callback receives the [`SamplePlayer`] entity
+/// before it's despawned.
+#[derive(Clone)]
+pub struct CompletionTrigger(Arc<dyn Fn(Entity, &mut Commands) + Send + Sync>);
+
+impl CompletionTrigger {
+    /// Trigger a clone of `event` on completion.
+    pub fn event<E>(event: E) -> Self
+    where
+        E: Event + Clone,
+        for<'a> E::Trigger<'a>: Default,
+    {
+        Self::from_fn(move |_, commands| commands.trigger(event.clone()))
+    }
+
+    /// Run `f` with the [`SamplePlayer`] entity on completion.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// #[derive(EntityEvent)]
+    /// struct Finished(Entity);
+    ///
+    /// let trigger = CompletionTrigger::from_fn(|entity, commands| {
+    ///     commands.trigger(Finished(entity));
+    /// });
+    /// ```
+    pub fn from_fn<F>(f: F) -> Self
+    where
+        F: Fn(Entity, &mut Commands) + Send + Sync + 'static,
+    {
+        Self(Arc::new(f))
+    }
+
+    /// Run the callback for `entity`.
+    pub fn run(&self, entity: Entity, commands: &mut Commands) {
+        (self.0)(entity, commands)
+    }
+}
+
+impl Default for CompletionTrigger {
+    fn default() -> Self {
+        Self::from_fn(|_, _| {})
+    }
+}
+
+impl PartialEq for CompletionTrigger {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CompletionTrigger {}
+
+impl core::fmt::Debug for CompletionTrigger {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("CompletionTrigger").finish_non_exhaustive()
+    }
+}
+
+/// The direction in which a sample plays.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum PlaybackDirection {
+    /// Play the sample from start to end.
+    #[default]
+    Forward,
+    /// Play the sample from end to start.
+    ///
+    /// While reversed, positions like [`PlayFrom::Seconds`] and the
+    /// [`Sampler`][crate::pool::Sampler] playhead are measured
+    /// from the end of the sample.
+    Reverse,
 }
 
 /// Sample parameters that can change during playback.
@@ -390,6 +598,13 @@ pub struct PlaybackSettings {
 
     /// Determines this sample's behavior on playback completion.
     pub on_complete: OnComplete,
+
+    /// The direction of playback.
+    ///
+    /// Changing the direction during playback flips the
+    /// playhead, so the sample continues from the same point.
+    /// Looping and completion behave the same in either direction.
+    pub direction: PlaybackDirection,
 }
 
 impl PlaybackSettings {
@@ -419,6 +634,30 @@ impl PlaybackSettings {
         }
     }
 
+    /// Set the [`PlaybackDirection`].
+    pub fn with_direction(self, direction: PlaybackDirection) -> Self {
+        Self { direction, ..self }
+    }
+
+    /// Set [`PlaybackSettings::direction`] to [`PlaybackDirection::Reverse`].
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn rewind(mut commands: Commands, server: Res<AssetServer>) {
+    ///     commands.spawn((
+    ///         SamplePlayer::new(server.load("my_sample.wav")),
+    ///         PlaybackSettings::default().reversed(),
+    ///     ));
+    /// }
+    /// ```
+    pub fn reversed(self) -> Self {
+        Self {
+            direction: PlaybackDirection::Reverse,
+            ..self
+        }
+    }
+
     /// Set [`PlaybackSettings::on_complete`] to [`OnComplete::Preserve`].
     pub fn preserve(self) -> Self {
         Self {
@@ -507,6 +746,58 @@ impl PlaybackSettings {
         });
     }
 
+    /// Stop a sample at `time`, rewinding it to the beginning.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn stop(
+    ///     mut sample: Single<(&PlaybackSettings, &mut AudioEvents)>,
+    ///     time: Res<Time<Audio>>,
+    /// ) {
+    ///     let (settings, events) = &mut *sample;
+    ///
+    ///     // Stop exactly half a second from now.
+    ///     settings.stop_at(time.delay(DurationSeconds(0.5)), events);
+    /// }
+    /// ```
+    ///
+    /// Unlike [`StopAll`][crate::prelude::StopAll], this doesn't fade
+    /// out or complete the sample, so it can be played again later.
+    pub fn stop_at(&self, time: InstantSeconds, events: &mut AudioEvents) {
+        events.schedule(time, self, |settings| {
+            *settings.play = false;
+            settings.play_from = PlayFrom::BEGINNING;
+        });
+    }
+
+    /// Seek to `position` at `time` without changing
+    /// whether the sample is playing.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn skip_intro(
+    ///     mut sample: Single<(&PlaybackSettings, &mut AudioEvents)>,
+    ///     time: Res<Time<Audio>>,
+    /// ) {
+    ///     let (settings, events) = &mut *sample;
+    ///
+    ///     // Jump to the chorus two seconds from now.
+    ///     settings.seek_at(PlayFrom::Seconds(32.0), time.delay(DurationSeconds(2.0)), events);
+    /// }
+    /// ```
+    ///
+    /// The seek is abrupt. For a declicked seek, see [`SmoothSeek`].
+    pub fn seek_at(&self, position: PlayFrom, time: InstantSeconds, events: &mut AudioEvents) {
+        events.schedule(time, self, |settings| {
+            settings.play_from = position;
+            // Notify the sampler even if the value is unchanged.
+            let playing = *settings.play;
+            *settings.play = playing;
+        });
+    }
+
     /// Linearly interpolate a sample's speed from its current value to `speed`.
     ///
     /// The interpolation uses an approximation of the average just noticeable
@@ -572,6 +863,37 @@ impl PlaybackSettings {
         start: InstantSeconds,
         end: InstantSeconds,
         events: &mut AudioEvents,
+    ) {
+        self.speed_curve_at(speed, start, end, EaseFunction::Linear, events)
+    }
+
+    /// Interpolate a sample's speed from its value at `start` to `speed`
+    /// following `curve`.
+    ///
+    /// This is otherwise identical to [`PlaybackSettings::speed_at`].
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn slow_down(time: Res<Time<Audio>>, mut sample: Single<(&PlaybackSettings, &mut AudioEvents)>) {
+    ///     let (settings, events) = &mut *sample;
+    ///
+    ///     settings.speed_curve_at(
+    ///         0.25,
+    ///         time.now(),
+    ///         time.now() + DurationSeconds(0.5),
+    ///         EaseFunction::QuadraticOut,
+    ///         events,
+    ///     );
+    /// }
+    /// ```
+    pub fn speed_curve_at(
+        &self,
+        speed: f64,
+        start: InstantSeconds,
+        end: InstantSeconds,
+        curve: EaseFunction,
+        events: &mut AudioEvents,
     ) {
         let start_value = events.get_value_at(start, self);
         let mut end_value = start_value.clone();
@@ -589,9 +911,9 @@ impl PlaybackSettings {
             start_value,
             end_value,
             total_events,
-            |a, b, t| {
+            move |a, b, t| {
                 let mut output = a.clone();
-                output.speed = a.speed.lerp(b.speed, t as f64);
+                output.speed = a.speed.lerp(b.speed, curve.sample_clamped(t) as f64);
                 output
             },
         );
@@ -628,6 +950,28 @@ impl PlaybackSettings {
     pub fn pause(&mut self) {
         *self.play = false;
     }
+
+    /// Restart playback from the beginning.
+    ///
+    /// Unlike re-inserting [`SamplePlayer`], this replays
+    /// the sample on its currently assigned sampler.
+    ///
+    /// ```
+    /// # use bevy_seedling::prelude::*;
+    /// # use bevy::prelude::*;
+    /// fn retrigger(mut engine: Single<&mut PlaybackSettings>) {
+    ///     engine.restart();
+    /// }
+    /// ```
+    ///
+    /// This only affects samples that hold a sampler. To also
+    /// replay samples that have completed with [`OnComplete::Preserve`],
+    /// trigger [`Restart`][crate::prelude::Restart].
+    pub fn restart(&mut self) {
+        self.play_from = PlayFrom::BEGINNING;
+        // Notify the sampler even if the value is unchanged.
+        *self.play = true;
+    }
 }
 
 impl Default for PlaybackSettings {
@@ -637,6 +981,7 @@ impl Default for PlaybackSettings {
             play_from: PlayFrom::Resume,
             speed: 1.0,
             on_complete: OnComplete::Despawn,
+            direction: PlaybackDirection::Forward,
         }
     }
 }
@@ -678,14 +1023,81 @@ impl firewheel::diff::Patch for PlaybackSettings {
     }
 }
 
-/// A marker struct for entities that are waiting
-/// for asset loading and playback assignment.
+/// Seek within a playing sample with a short volume dip.
+///
+/// Seeking by setting [`PlaybackSettings::play_from`] directly
+/// while a sample is playing will usually produce a click.
+/// Inserting this component instead fades the sampler out, seeks,
+/// and fades back in, spending half of `fade` on each side.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn scrub(player: Single<Entity, With<SamplePlayer>>, mut commands: Commands) {
+///     commands.entity(*player).insert(
+///         SmoothSeek::new(PlayFrom::Seconds(12.5)).with_fade(DurationSeconds(0.05)),
+///     );
+/// }
+/// ```
+///
+/// If the sample hasn't been assigned a sampler yet, the seek is
+/// applied directly. This component is removed once the seek is scheduled.
+#[derive(Debug, Component, Clone)]
+#[component(immutable)]
+#[require(PlaybackSettings)]
+pub struct SmoothSeek {
+    /// The target position.
+    pub position: PlayFrom,
+    /// The total duration of the fade out and back in.
+    ///
+    /// Defaults to [`SmoothSeek::DEFAULT_FADE`].
+    pub fade: DurationSeconds,
+}
+
+impl SmoothSeek {
+    /// The default fade duration, which is just long enough to avoid clicks.
+    pub const DEFAULT_FADE: DurationSeconds = DurationSeconds(0.02);
+
+    /// Construct a new [`SmoothSeek`] to `position`.
+    pub fn new(position: PlayFrom) -> Self {
+        Self {
+            position,
+            fade: Self::DEFAULT_FADE,
+        }
+    }
+
+    /// Set the total fade duration.
+    pub fn with_fade(self, fade: DurationSeconds) -> Self {
+        Self { fade, ..self }
+    }
+}
+
+/// A marker struct for samples whose playback is managed by the sample queue.
+///
+/// This is inserted when a [`SamplePlayer`] is spawned and stays in place
+/// while the sample plays, so assigning a sampler doesn't move the entity
+/// between archetypes. A sample is waiting for asset loading and
+/// playback assignment while it has this marker but no
+/// [`Sampler`][crate::pool::Sampler]. The [`Queued`] filter matches
+/// exactly those samples.
+///
+/// The marker is removed once playback completes with [`OnComplete::Preserve`].
 #[derive(Debug, Component, Default)]
-#[component(storage = "SparseSet")]
 pub struct QueuedSample;
 
+/// A query filter for samples waiting in the queue.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::{prelude::*, sample::Queued};
+/// fn count_waiting(waiting: Query<(), (With<SamplePlayer>, Queued)>) {
+///     info!("{} samples are waiting", waiting.iter().len());
+/// }
+/// ```
+pub type Queued = (With<QueuedSample>, Without<crate::pool::Sampler>);
+
 #[cfg(feature = "rand")]
-pub use random::{PitchRngSource, RandomPitch};
+pub use random::{PitchRngSource, RandomPitch, RandomSeed};
 
 #[cfg(feature = "rand")]
 pub(crate) use random::RandomPlugin;
@@ -745,6 +1157,52 @@ mod random {
         pub fn new<T: rand::Rng + Send + Sync + 'static>(rng: T) -> Self {
             Self(Box::new(RandRng(rng)))
         }
+
+        /// Construct a deterministic [`PitchRngSource`] from `seed`.
+        ///
+        /// This is useful for replays and tests, where the same
+        /// sequence of pitches should be produced on every run.
+        ///
+        /// ```
+        /// # use bevy::prelude::*;
+        /// # use bevy_seedling::prelude::*;
+        /// fn plugin(app: &mut App) {
+        ///     app.insert_resource(PitchRngSource::from_seed(0xC0FFEE));
+        /// }
+        /// ```
+        pub fn from_seed(seed: u64) -> Self {
+            Self::new(SmallRng::seed_from_u64(seed))
+        }
+    }
+
+    /// Overrides the RNG source for a single entity's randomized components.
+    ///
+    /// Entities with this component derive their randomness from the seed
+    /// alone, rather than drawing from shared sources like [`PitchRngSource`].
+    /// This makes the result independent of spawn order.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn footstep(mut commands: Commands, server: Res<AssetServer>) {
+    ///     // This footstep will play at the same pitch every time.
+    ///     commands.spawn((
+    ///         SamplePlayer::new(server.load("footstep.wav")),
+    ///         RandomPitch::new(0.05),
+    ///         RandomSeed(42),
+    ///     ));
+    /// }
+    /// ```
+    #[derive(Debug, Component, Clone, Copy, PartialEq, Eq)]
+    #[component(immutable)]
+    #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+    pub struct RandomSeed(pub u64);
+
+    impl RandomSeed {
+        /// Construct an RNG from this seed.
+        fn rng(&self) -> SmallRng {
+            SmallRng::seed_from_u64(self.0)
+        }
     }
 
     /// A component that applies a random pitch to [`PlaybackSettings`] when spawned.
@@ -752,7 +1210,8 @@ mod random {
     /// This can be used for subtle sound variations, breaking up
     /// the monotony of repeated sounds like footsteps.
     ///
-    /// To control the RNG source, you can provide a custom [`PitchRngSource`] resource.
+    /// To control the RNG source, you can provide a custom [`PitchRngSource`] resource
+    /// or a per-entity [`RandomSeed`].
     #[derive(Debug, Component, Default, Clone)]
     #[require(PlaybackSettings)]
     #[component(immutable)]
@@ -780,13 +1239,15 @@ mod random {
         }
 
         fn apply(
-            mut samples: Query<(Entity, &mut PlaybackSettings, &Self)>,
+            mut samples: Query<(Entity, &mut PlaybackSettings, &Self, Option<&RandomSeed>)>,
             mut commands: Commands,
             mut rng: ResMut<PitchRngSource>,
         ) {
-            for (entity, mut settings, range) in samples.iter_mut() {
+            for (entity, mut settings, range, seed) in samples.iter_mut() {
                 let speed = if range.0.is_empty() {
                     range.0.start
+                } else if let Some(seed) = seed {
+                    RandRng(seed.rng()).gen_pitch(range.0.clone())
                 } else {
                     rng.0.gen_pitch(range.0.clone())
                 };
@@ -804,6 +1265,29 @@ mod test {
     use crate::prelude::*;
     use crate::test::{prepare_app, run};
     use bevy::prelude::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_sample_region() {
+        use firewheel::sample_resource::{SampleResource, SampleResourceInfo};
+        use std::num::{NonZeroU32, NonZeroUsize};
+
+        let sample = AudioSample::from_pcm(
+            (0..10).map(|i| i as f32).collect(),
+            NonZeroUsize::MIN,
+            NonZeroU32::new(48000).unwrap(),
+        );
+
+        let region = sample.region(2..5).get();
+        assert_eq!(region.len_frames(), 3);
+
+        let mut buffer = [1.0; 5];
+        region.fill_buffers(&mut [&mut buffer[..]], 0..5, 1);
+        assert_eq!(buffer, [3.0, 4.0, 0.0, 0.0, 0.0]);
+
+        // Out-of-bounds regions are clamped.
+        assert_eq!(sample.region(8..20).get().len_frames(), 2);
+    }
 
     #[test]
     fn test_reinsertion() {
@@ -846,4 +1330,137 @@ mod test {
 
         app.update();
     }
+
+    #[test]
+    fn test_start_delay() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((SamplerPool(DefaultPool), PoolSize(1..=1)));
+
+            commands
+                .spawn((VolumeNode::default(), MainBus))
+                .connect(AudioGraphOutput);
+        });
+
+        run(
+            &mut app,
+            |mut commands: Commands, server: Res<AssetServer>| {
+                commands.spawn((
+                    SamplePlayer::new(server.load("caw.ogg")),
+                    StartDelay(Duration::from_millis(200)),
+                ));
+            },
+        );
+
+        run(&mut app, |settings: Single<&PlaybackSettings>| {
+            assert!(!*settings.play);
+        });
+
+        loop {
+            let world = app.world_mut();
+            let mut q = world.query_filtered::<Entity, With<Sampler>>();
+            if q.iter(world).len() != 0 {
+                break;
+            }
+            app.update();
+        }
+
+        run(
+            &mut app,
+            |sample: Single<Has<StartDelay>, With<Sampler>>| {
+                assert!(!*sample);
+            },
+        );
+    }
+
+    #[test]
+    fn test_scheduled_transport() {
+        let mut app = prepare_app(|| ());
+
+        run(&mut app, |time: Res<Time<Audio>>| {
+            let settings = PlaybackSettings::default().with_playback(false);
+            let mut events = AudioEvents::new(&time);
+            let frame = |n: f64| InstantSeconds(n / 48_000.0);
+
+            settings.play_at(None, frame(48_000.0), &mut events);
+            settings.seek_at(PlayFrom::Seconds(2.0), frame(72_000.0), &mut events);
+            settings.pause_at(frame(96_000.0), &mut events);
+            settings.stop_at(frame(120_000.0), &mut events);
+
+            // Each change lands on exactly its frame.
+            let at = |n| events.get_value_at(frame(n), &settings);
+            assert!(!*at(47_999.0).play);
+            assert!(*at(48_000.0).play);
+
+            assert_eq!(at(71_999.0).play_from, PlayFrom::Resume);
+            assert_eq!(at(72_000.0).play_from, PlayFrom::Seconds(2.0));
+            assert!(*at(72_000.0).play);
+
+            assert!(*at(95_999.0).play);
+            assert!(!*at(96_000.0).play);
+
+            assert_eq!(at(119_999.0).play_from, PlayFrom::Seconds(2.0));
+            assert_eq!(at(120_000.0).play_from, PlayFrom::BEGINNING);
+            assert!(!*at(120_000.0).play);
+        });
+    }
+
+    #[test]
+    fn test_detach_on_parent_despawn() {
+        #[derive(Component)]
+        struct Parent;
+
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((
+                Parent,
+                Transform::from_xyz(4.0, 0.0, 0.0),
+                children![
+                    (
+                        PlaybackSettings::default().preserve(),
+                        DetachOnParentDespawn::default(),
+                        Transform::default(),
+                    ),
+                    PlaybackSettings::default().preserve(),
+                ],
+            ));
+        });
+
+        app.update();
+
+        run(
+            &mut app,
+            |parent: Single<Entity, With<Parent>>, mut commands: Commands| {
+                commands.entity(*parent).despawn();
+            },
+        );
+
+        app.update();
+
+        run(
+            &mut app,
+            |samples: Query<(&ChildOf, &Transform), With<PlaybackSettings>>,
+             orphans: Single<Entity, With<OrphanSounds>>| {
+                let (child_of, transform) = samples.single().unwrap();
+
+                assert_eq!(child_of.parent(), *orphans);
+                assert_eq!(transform.translation.x, 4.0);
+            },
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_seeded_pitch() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((RandomPitch::new(0.5), RandomSeed(7)));
+            commands.spawn((RandomPitch::new(0.5), RandomSeed(7)));
+        });
+
+        let speeds = run(&mut app, |q: Query<&PlaybackSettings>| {
+            q.iter().map(|s| s.speed).collect::<Vec<_>>()
+        });
+
+        assert_eq!(speeds.len(), 2);
+        assert_eq!(speeds[0], speeds[1]);
+        assert_ne!(speeds[0], 1.0);
+    }
 }
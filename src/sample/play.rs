@@ -0,0 +1,116 @@
+//! Convenience methods for one-shot playback.
+
+use super::{AudioSample, SamplePlayer};
+use crate::context::graph::{MusicPool, SpatialPool};
+use bevy_asset::Handle;
+use bevy_ecs::{prelude::*, system::EntityCommands, world::EntityWorldMut};
+use bevy_math::Vec3;
+use bevy_transform::prelude::Transform;
+
+/// Provides methods on [`Commands`] and [`World`] to quickly play samples.
+///
+/// Each method spawns a [`SamplePlayer`] into the pool
+/// appropriate for its use, returning the new entity for
+/// further configuration.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn play(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.play_sfx(server.load("caw.ogg"));
+///     commands.play_sfx_at(server.load("caw.ogg"), Vec3::new(5.0, 0.0, 0.0));
+///     commands.play_music(server.load("my_music.wav"));
+/// }
+/// ```
+pub trait PlayCommands {
+    /// The entity handle returned by each method.
+    type Entity<'a>
+    where
+        Self: 'a;
+
+    /// Play a one-shot sample in the [`DefaultPool`][crate::prelude::DefaultPool].
+    ///
+    /// The entity is despawned once playback completes.
+    fn play_sfx(&mut self, sample: Handle<AudioSample>) -> Self::Entity<'_>;
+
+    /// Play a one-shot sample at `position` in the [`SpatialPool`].
+    ///
+    /// The entity is despawned once playback completes.
+    /// Note that [`SpatialPool`] is only spawned by
+    /// [`AudioGraphTemplate::Game`][crate::prelude::AudioGraphTemplate::Game].
+    fn play_sfx_at(&mut self, sample: Handle<AudioSample>, position: Vec3) -> Self::Entity<'_>;
+
+    /// Play a looping sample in the [`MusicPool`].
+    ///
+    /// Note that [`MusicPool`] is only spawned by
+    /// [`AudioGraphTemplate::Game`][crate::prelude::AudioGraphTemplate::Game].
+    fn play_music(&mut self, sample: Handle<AudioSample>) -> Self::Entity<'_>;
+}
+
+impl PlayCommands for Commands<'_, '_> {
+    type Entity<'a>
+        = EntityCommands<'a>
+    where
+        Self: 'a;
+
+    fn play_sfx(&mut self, sample: Handle<AudioSample>) -> Self::Entity<'_> {
+        self.spawn(SamplePlayer::new(sample))
+    }
+
+    fn play_sfx_at(&mut self, sample: Handle<AudioSample>, position: Vec3) -> Self::Entity<'_> {
+        self.spawn((
+            SamplePlayer::new(sample),
+            SpatialPool,
+            Transform::from_translation(position),
+        ))
+    }
+
+    fn play_music(&mut self, sample: Handle<AudioSample>) -> Self::Entity<'_> {
+        self.spawn((SamplePlayer::new(sample).looping(), MusicPool))
+    }
+}
+
+impl PlayCommands for World {
+    type Entity<'a> = EntityWorldMut<'a>;
+
+    fn play_sfx(&mut self, sample: Handle<AudioSample>) -> Self::Entity<'_> {
+        self.spawn(SamplePlayer::new(sample))
+    }
+
+    fn play_sfx_at(&mut self, sample: Handle<AudioSample>, position: Vec3) -> Self::Entity<'_> {
+        self.spawn((
+            SamplePlayer::new(sample),
+            SpatialPool,
+            Transform::from_translation(position),
+        ))
+    }
+
+    fn play_music(&mut self, sample: Handle<AudioSample>) -> Self::Entity<'_> {
+        self.spawn((SamplePlayer::new(sample).looping(), MusicPool))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        prelude::*,
+        test::{prepare_app, run},
+    };
+    use firewheel::nodes::sampler::RepeatMode;
+
+    #[test]
+    fn test_play_music() {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            commands.play_music(server.load("caw.ogg"));
+        });
+
+        run(
+            &mut app,
+            |players: Query<&SamplePlayer, With<MusicPool>>| {
+                let player = players.single().unwrap();
+                assert_eq!(player.repeat_mode, RepeatMode::RepeatEndlessly);
+            },
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Back-to-back sample sequences.
+
+use super::AudioSample;
+use bevy_asset::Handle;
+use bevy_ecs::prelude::*;
+use firewheel::clock::DurationSeconds;
+use std::collections::VecDeque;
+
+/// A sequence of samples played back-to-back on a single sampler.
+///
+/// The [`SamplePlayer`][super::SamplePlayer]'s sample plays first, followed by
+/// each queued sample in order. Since the sequence reuses the same sampler
+/// assignment, it won't compete for space in its pool between items, making
+/// it well-suited for short, tightly coupled sounds.
+///
+/// A [`SampleQueueItemComplete`] event is triggered on the entity
+/// as each item finishes. [`PlaybackCompletion`][crate::prelude::PlaybackCompletion]
+/// is only triggered once the final item completes.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn reload(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("clip_out.wav")),
+///         SampleQueue::default()
+///             .then(server.load("clip_in.wav"))
+///             .then_after(DurationSeconds(0.15), server.load("slide.wav")),
+///     ));
+/// }
+/// ```
+///
+/// Looping samples never finish, so items queued after
+/// a looping sample will not play.
+#[derive(Debug, Default, Clone, Component)]
+pub struct SampleQueue {
+    pub(crate) items: VecDeque<SampleQueueItem>,
+    pub(crate) index: usize,
+    /// The queued item currently playing, if it isn't
+    /// the [`SamplePlayer`][super::SamplePlayer]'s sample.
+    pub(crate) current: Option<Handle<AudioSample>>,
+    /// The sampler's playback ID when the next item was scheduled.
+    pub(crate) starting: Option<u64>,
+}
+
+/// A sample in a [`SampleQueue`].
+#[derive(Debug, Clone)]
+pub struct SampleQueueItem {
+    /// The sample to play.
+    pub sample: Handle<AudioSample>,
+    /// The silence inserted between the previous item and this one.
+    pub gap: DurationSeconds,
+}
+
+impl SampleQueue {
+    /// Construct a new [`SampleQueue`] from an iterator of samples
+    /// with no gaps between them.
+    pub fn new(samples: impl IntoIterator<Item = Handle<AudioSample>>) -> Self {
+        samples
+            .into_iter()
+            .fold(Self::default(), |queue, sample| queue.then(sample))
+    }
+
+    /// Queue `sample` immediately after the previous item.
+    pub fn then(self, sample: Handle<AudioSample>) -> Self {
+        self.then_after(DurationSeconds(0.0), sample)
+    }
+
+    /// Queue `sample` to play `gap` seconds after the previous item.
+    pub fn then_after(mut self, gap: DurationSeconds, sample: Handle<AudioSample>) -> Self {
+        self.items.push_back(SampleQueueItem { sample, gap });
+        self
+    }
+
+    /// Append `sample` to a playing sequence.
+    pub fn push(&mut self, sample: Handle<AudioSample>, gap: DurationSeconds) {
+        self.items.push_back(SampleQueueItem { sample, gap });
+    }
+
+    /// Iterate over the samples that have yet to play.
+    pub fn iter(&self) -> impl Iterator<Item = &SampleQueueItem> {
+        self.items.iter()
+    }
+
+    /// The index of the currently playing item, where `0`
+    /// is the [`SamplePlayer`][super::SamplePlayer]'s original sample.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The queued sample currently playing.
+    ///
+    /// This is `None` while the [`SamplePlayer`][super::SamplePlayer]'s
+    /// original sample is playing.
+    pub fn current(&self) -> Option<&Handle<AudioSample>> {
+        self.current.as_ref()
+    }
+
+    /// Returns whether the sequence will continue after the current item.
+    pub(crate) fn is_pending(&self) -> bool {
+        self.starting.is_some() || !self.items.is_empty()
+    }
+}
+
+/// An event triggered on [`SampleQueue`] entities as each item completes.
+#[derive(Debug, EntityEvent)]
+pub struct SampleQueueItemComplete {
+    /// The [`SamplePlayer`][super::SamplePlayer] entity.
+    pub entity: Entity,
+    /// The index of the completed item, where `0` is the
+    /// [`SamplePlayer`][super::SamplePlayer]'s original sample.
+    pub index: usize,
+    /// The completed sample.
+    pub sample: Handle<AudioSample>,
+}
@@ -0,0 +1,270 @@
+//! Audio sprites: named regions of a single sample.
+
+use super::{AudioSample, SamplePlayer};
+use bevy_app::prelude::*;
+use bevy_asset::{
+    Asset, AssetApp, AssetEvent, AssetId, AssetLoader, Assets, Handle, LoadContext, uuid::Uuid,
+};
+use bevy_ecs::prelude::*;
+use bevy_platform::{collections::HashMap, hash::FixedHasher};
+use bevy_reflect::TypePath;
+use core::hash::{BuildHasher, Hash, Hasher};
+use firewheel::{clock::DurationSeconds, sample_resource::SampleResourceInfo};
+
+pub(crate) struct AudioSpritePlugin;
+
+impl Plugin for AudioSpritePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<AudioSpriteSheet>()
+            .init_asset_loader::<AudioSpriteLoader>()
+            .add_systems(PreUpdate, insert_sprites);
+    }
+}
+
+/// A named region within an [`AudioSpriteSheet`].
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize)]
+pub struct SpriteRegion {
+    /// The region's start within the source sample.
+    #[serde(with = "seconds")]
+    pub start: DurationSeconds,
+    /// The region's end within the source sample.
+    #[serde(with = "seconds")]
+    pub end: DurationSeconds,
+}
+
+mod seconds {
+    use firewheel::clock::DurationSeconds;
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DurationSeconds, D::Error> {
+        <f64 as serde::Deserialize>::deserialize(deserializer).map(DurationSeconds)
+    }
+}
+
+/// A single sample divided into named regions, or "audio sprites."
+///
+/// Packing many short sounds into one file can reduce load times and
+/// file handles, especially on the web. Sprite sheets are loaded from
+/// RON files with the `.sprites.ron` extension, where the source path
+/// is relative to the sheet.
+///
+/// ```ron
+/// (
+///     source: "sfx.ogg",
+///     regions: {
+///         "explosion_03": (start: 1.25, end: 2.0),
+///         "coin": (start: 2.0, end: 2.3),
+///     },
+/// )
+/// ```
+///
+/// Regions are played with [`SamplePlayer::sprite`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// #[derive(Resource)]
+/// struct Sfx(Handle<AudioSpriteSheet>);
+///
+/// fn load(server: Res<AssetServer>, mut commands: Commands) {
+///     commands.insert_resource(Sfx(server.load("sfx.sprites.ron")));
+/// }
+///
+/// fn explode(sfx: Res<Sfx>, mut commands: Commands) {
+///     commands.spawn(SamplePlayer::sprite(&sfx.0, "explosion_03"));
+/// }
+/// ```
+///
+/// Each region becomes available as its own [`AudioSample`] once the sheet
+/// and its source have loaded, sharing the source's data. Keep a handle to
+/// the sheet alive for as long as its sprites should be playable.
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct AudioSpriteSheet {
+    /// The source sample.
+    pub source: Handle<AudioSample>,
+    /// The sheet's named regions.
+    pub regions: HashMap<String, SpriteRegion>,
+}
+
+impl AudioSpriteSheet {
+    /// The handle for the sprite `name` in the sheet `sheet`.
+    ///
+    /// The handle is stable, so it can be created before the sheet has loaded.
+    pub fn sprite(sheet: impl Into<AssetId<AudioSpriteSheet>>, name: &str) -> Handle<AudioSample> {
+        Handle::Uuid(sprite_uuid(sheet.into(), name), Default::default())
+    }
+}
+
+fn sprite_uuid(sheet: AssetId<AudioSpriteSheet>, name: &str) -> Uuid {
+    // Fixed seeds keep the handle stable for the lifetime of the app.
+    let hash = |seed: u64| {
+        let mut hasher = FixedHasher.build_hasher();
+        seed.hash(&mut hasher);
+        sheet.hash(&mut hasher);
+        name.hash(&mut hasher);
+        hasher.finish()
+    };
+
+    Uuid::from_u64_pair(hash(0), hash(1))
+}
+
+impl SamplePlayer {
+    /// Construct a new [`SamplePlayer`] that plays the region `name`
+    /// of an [`AudioSpriteSheet`].
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn play_coin(mut commands: Commands, server: Res<AssetServer>) {
+    ///     let sheet: Handle<AudioSpriteSheet> = server.load("sfx.sprites.ron");
+    ///     commands.spawn(SamplePlayer::sprite(&sheet, "coin"));
+    /// #   commands.insert_resource(Sheet(sheet));
+    /// }
+    /// # #[derive(Resource)]
+    /// # struct Sheet(Handle<AudioSpriteSheet>);
+    /// ```
+    ///
+    /// Like any sample, the player waits in its pool
+    /// until the sprite is available.
+    pub fn sprite(sheet: &Handle<AudioSpriteSheet>, name: &str) -> Self {
+        Self::new(AudioSpriteSheet::sprite(sheet, name))
+    }
+}
+
+/// The RON representation of an [`AudioSpriteSheet`].
+#[derive(serde::Deserialize)]
+struct SpriteSheetFile {
+    source: String,
+    regions: std::collections::HashMap<String, SpriteRegion>,
+}
+
+/// Errors produced while loading an [`AudioSpriteSheet`].
+#[derive(Debug)]
+pub enum AudioSpriteLoaderError {
+    /// An error directly from the standard library.
+    StdIo(std::io::Error),
+    /// An error parsing the sheet.
+    Ron(String),
+    /// The source path could not be resolved.
+    Path(String),
+}
+
+impl From<std::io::Error> for AudioSpriteLoaderError {
+    fn from(value: std::io::Error) -> Self {
+        Self::StdIo(value)
+    }
+}
+
+impl From<ron::error::SpannedError> for AudioSpriteLoaderError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        Self::Ron(value.to_string())
+    }
+}
+
+impl std::error::Error for AudioSpriteLoaderError {}
+
+impl std::fmt::Display for AudioSpriteLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::StdIo(stdio) => stdio.fmt(f),
+            Self::Ron(ron) => f.write_str(ron),
+            Self::Path(path) => f.write_str(path),
+        }
+    }
+}
+
+/// A loader for [`AudioSpriteSheet`] RON files.
+#[derive(Debug, Default, TypePath)]
+pub struct AudioSpriteLoader;
+
+impl AssetLoader for AudioSpriteLoader {
+    type Asset = AudioSpriteSheet;
+    type Settings = ();
+    type Error = AudioSpriteLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn bevy_asset::io::Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let file: SpriteSheetFile = ron::de::from_bytes(&bytes)?;
+        let source = load_context
+            .asset_path()
+            .resolve_embed(&file.source)
+            .map_err(|e| AudioSpriteLoaderError::Path(e.to_string()))?;
+
+        Ok(AudioSpriteSheet {
+            source: load_context.load(source),
+            regions: file.regions.into_iter().collect(),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["sprites.ron"]
+    }
+}
+
+/// Insert or remove each sheet's sprites as the sheet changes.
+fn insert_sprites(
+    mut events: MessageReader<AssetEvent<AudioSpriteSheet>>,
+    sheets: Res<Assets<AudioSpriteSheet>>,
+    mut samples: ResMut<Assets<AudioSample>>,
+    mut inserted: Local<HashMap<AssetId<AudioSpriteSheet>, Vec<Uuid>>>,
+) {
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id }
+        | AssetEvent::Modified { id }
+        | AssetEvent::Removed { id }
+        | AssetEvent::Unused { id }) = *event
+        else {
+            continue;
+        };
+
+        for uuid in inserted.remove(&id).into_iter().flatten() {
+            samples.remove(uuid);
+        }
+
+        if matches!(
+            event,
+            AssetEvent::Removed { .. } | AssetEvent::Unused { .. }
+        ) {
+            continue;
+        }
+
+        let Some(sheet) = sheets.get(id) else {
+            continue;
+        };
+        let Some(source) = samples.get(&sheet.source) else {
+            continue;
+        };
+
+        let sample_rate = source
+            .get()
+            .sample_rate()
+            .unwrap_or(source.original_sample_rate())
+            .get() as f64;
+
+        let sprites: Vec<_> = sheet
+            .regions
+            .iter()
+            .map(|(name, region)| {
+                let start = (region.start.0.max(0.0) * sample_rate).round() as u64;
+                let end = (region.end.0.max(0.0) * sample_rate).round() as u64;
+
+                (sprite_uuid(id, name), source.region(start..end))
+            })
+            .collect();
+
+        let ids = inserted.entry(id).or_default();
+        for (uuid, sprite) in sprites {
+            // Inserting with a UUID can't fail.
+            let _ = samples.insert(uuid, sprite);
+            ids.push(uuid);
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! Tape-stop and spin-up effects built on speed timelines.
+
+use super::PlaybackSettings;
+use crate::{
+    SeedlingSystems,
+    pool::Stopping,
+    prelude::AudioEvents,
+    time::{Audio, AudioTime},
+};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_math::curve::EaseFunction;
+use bevy_time::Time;
+use firewheel::clock::DurationSeconds;
+
+pub(crate) struct TapePlugin;
+
+impl Plugin for TapePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Last,
+            (TapeStop::apply, TapeStart::apply).before(SeedlingSystems::Acquire),
+        );
+    }
+}
+
+/// Slow a sample down to a halt, like a tape machine losing power.
+///
+/// When inserted on a sample player, the playback speed is ramped from its
+/// current value to zero along `curve`, after which the sample is stopped.
+/// Like [`StopAll`][crate::prelude::StopAll], stopping releases the
+/// sample's sampler and runs its [`OnComplete`][crate::prelude::OnComplete].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn power_down(player: Single<Entity, With<SamplePlayer>>, mut commands: Commands) {
+///     commands
+///         .entity(*player)
+///         .insert(TapeStop::new(DurationSeconds(1.5)));
+/// }
+/// ```
+///
+/// This component is removed once the ramp has been scheduled.
+#[derive(Debug, Component, Clone)]
+#[require(PlaybackSettings)]
+#[component(immutable)]
+pub struct TapeStop {
+    /// How long it takes to come to a halt.
+    pub duration: DurationSeconds,
+    /// The shape of the ramp.
+    ///
+    /// Defaults to [`EaseFunction::QuadraticOut`].
+    pub curve: EaseFunction,
+}
+
+impl TapeStop {
+    /// Construct a new [`TapeStop`] with a [`EaseFunction::QuadraticOut`] curve.
+    pub fn new(duration: DurationSeconds) -> Self {
+        Self {
+            duration,
+            curve: EaseFunction::QuadraticOut,
+        }
+    }
+
+    /// Set the ramp's curve.
+    pub fn with_curve(self, curve: EaseFunction) -> Self {
+        Self { curve, ..self }
+    }
+
+    fn apply(
+        mut players: Query<(Entity, &Self, &PlaybackSettings, &mut AudioEvents)>,
+        time: Res<Time<Audio>>,
+        mut commands: Commands,
+    ) {
+        for (entity, stop, settings, mut events) in &mut players {
+            commands.entity(entity).remove::<Self>();
+
+            let start = time.now();
+            let end = start + stop.duration;
+
+            settings.speed_curve_at(0.0, start, end, stop.curve, &mut events);
+            commands.entity(entity).insert(Stopping {
+                fade: DurationSeconds(0.0),
+                end: Some(end),
+            });
+        }
+    }
+}
+
+/// Spin a sample up from a standstill, like a tape machine getting power.
+///
+/// When inserted on a sample player, playback begins at zero speed and ramps
+/// up to `speed` along `curve`.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn power_up(player: Single<Entity, With<SamplePlayer>>, mut commands: Commands) {
+///     commands
+///         .entity(*player)
+///         .insert(TapeStart::new(DurationSeconds(0.75)));
+/// }
+/// ```
+///
+/// This component is removed once the ramp has been scheduled.
+#[derive(Debug, Component, Clone)]
+#[require(PlaybackSettings)]
+#[component(immutable)]
+pub struct TapeStart {
+    /// How long it takes to reach `speed`.
+    pub duration: DurationSeconds,
+    /// The final playback speed.
+    ///
+    /// Defaults to `1.0`.
+    pub speed: f64,
+    /// The shape of the ramp.
+    ///
+    /// Defaults to [`EaseFunction::QuadraticIn`].
+    pub curve: EaseFunction,
+}
+
+impl TapeStart {
+    /// Construct a new [`TapeStart`] with a [`EaseFunction::QuadraticIn`] curve
+    /// that reaches normal speed.
+    pub fn new(duration: DurationSeconds) -> Self {
+        Self {
+            duration,
+            speed: 1.0,
+            curve: EaseFunction::QuadraticIn,
+        }
+    }
+
+    /// Set the final playback speed.
+    pub fn with_speed(self, speed: f64) -> Self {
+        Self { speed, ..self }
+    }
+
+    /// Set the ramp's curve.
+    pub fn with_curve(self, curve: EaseFunction) -> Self {
+        Self { curve, ..self }
+    }
+
+    fn apply(
+        mut players: Query<(Entity, &Self, &PlaybackSettings, &mut AudioEvents)>,
+        time: Res<Time<Audio>>,
+        mut commands: Commands,
+    ) {
+        for (entity, ramp, settings, mut events) in &mut players {
+            commands.entity(entity).remove::<Self>();
+
+            let start = time.now();
+            let end = start + ramp.duration;
+
+            events.schedule(start, settings, |settings| {
+                *settings.play = true;
+                settings.speed = 0.0;
+            });
+            settings.speed_curve_at(ramp.speed, start, end, ramp.curve, &mut events);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        pool::{CompletionReason, Sampler},
+        prelude::*,
+        test::{prepare_app, run},
+    };
+    use bevy::prelude::*;
+    use std::time::Instant;
+
+    #[derive(PoolLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct TestPool;
+
+    #[derive(Resource, Default)]
+    struct Completions(Vec<bool>);
+
+    fn prepare_player(settings: PlaybackSettings) -> App {
+        let mut app = prepare_app(move |mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn(SamplerPool(TestPool));
+            commands.spawn((
+                TestPool,
+                SamplePlayer::new(server.load("caw.ogg")).looping(),
+                settings.clone(),
+            ));
+        });
+
+        app.init_resource::<Completions>().add_observer(
+            |trigger: On<PlaybackCompletion>, mut completions: ResMut<Completions>| {
+                let stopped = matches!(trigger.reason, CompletionReason::PlaybackStopped);
+                completions.0.push(stopped);
+            },
+        );
+
+        let start = Instant::now();
+        while run(&mut app, |q: Query<(), With<Sampler>>| q.iter().len()) == 0 {
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+
+        app
+    }
+
+    fn tape_stop(app: &mut App) {
+        run(
+            app,
+            |player: Single<Entity, With<SamplePlayer>>, mut commands: Commands| {
+                commands
+                    .entity(*player)
+                    .insert(TapeStop::new(DurationSeconds(0.1)));
+            },
+        );
+
+        let start = Instant::now();
+        while app.world().resource::<Completions>().0.is_empty() {
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+    }
+
+    #[test]
+    fn test_tape_stop_completes() {
+        let mut app = prepare_player(PlaybackSettings::default());
+        tape_stop(&mut app);
+
+        assert_eq!(app.world().resource::<Completions>().0, [true]);
+
+        // The default `OnComplete` despawns the player.
+        app.update();
+        run(&mut app, |players: Query<(), With<SamplePlayer>>| {
+            assert_eq!(players.iter().len(), 0);
+        });
+    }
+
+    #[test]
+    fn test_tape_stop_releases_sampler() {
+        let mut app = prepare_player(PlaybackSettings::default().preserve());
+        tape_stop(&mut app);
+        app.update();
+
+        run(
+            &mut app,
+            |player: Single<Has<Sampler>, With<SamplePlayer>>| {
+                assert!(!*player);
+            },
+        );
+    }
+}
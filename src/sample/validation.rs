@@ -0,0 +1,409 @@
+//! Debug checks for common problems in audio assets.
+
+use super::{AudioSample, SamplePlayer};
+use crate::{context::SampleRate, pool::Sampler};
+use bevy_app::prelude::*;
+use bevy_asset::{AssetEvent, AssetId, AssetServer, Assets};
+use bevy_ecs::prelude::*;
+use bevy_log::warn;
+use bevy_platform::collections::HashSet;
+use firewheel::{
+    clock::DurationSeconds, nodes::sampler::SamplerConfig, sample_resource::SampleResourceInfo,
+};
+
+/// Reports common problems in loaded [`AudioSample`]s.
+///
+/// Each sample is analyzed once it loads, and any problems are logged as
+/// warnings alongside the sample's asset path. This is intended for audio
+/// QA during development, so it's only available with the `validation`
+/// feature, which is included in `dev`.
+///
+/// The checks include:
+/// - clipping,
+/// - a very high peak-to-RMS ratio,
+/// - a sample rate far from the device's,
+/// - excessive leading or trailing silence,
+/// - and mono samples played through stereo samplers.
+///
+/// Thresholds are configured with the [`SampleValidation`] resource.
+/// Since every frame of each sample is inspected, expect
+/// longer load times for large files.
+#[derive(Debug, Default)]
+pub struct SampleValidationPlugin;
+
+impl Plugin for SampleValidationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SampleValidation>()
+            .add_systems(PostUpdate, validate_samples)
+            .add_observer(validate_routing);
+    }
+}
+
+/// Thresholds for the [`SampleValidationPlugin`].
+#[derive(Resource, Debug, Clone)]
+pub struct SampleValidation {
+    /// The absolute amplitude at or above which a sample is considered clipped.
+    ///
+    /// By default, this is `0.999`.
+    pub clip_threshold: f32,
+    /// The number of consecutive clipped samples required to report clipping.
+    ///
+    /// By default, this is `3`.
+    pub clip_run: usize,
+    /// The maximum peak-to-RMS ratio in decibels.
+    ///
+    /// By default, this is `30.0`.
+    pub max_crest_db: f32,
+    /// The maximum ratio between a sample's original sample rate
+    /// and the device's, in either direction.
+    ///
+    /// By default, this is `2.0`.
+    pub max_rate_ratio: f64,
+    /// The amplitude below which audio is considered silent.
+    ///
+    /// By default, this is `0.001` (-60 dBFS).
+    pub silence_threshold: f32,
+    /// The maximum leading or trailing silence.
+    ///
+    /// By default, this is 0.5 seconds.
+    pub max_silence: DurationSeconds,
+}
+
+impl Default for SampleValidation {
+    fn default() -> Self {
+        Self {
+            clip_threshold: 0.999,
+            clip_run: 3,
+            max_crest_db: 30.0,
+            max_rate_ratio: 2.0,
+            silence_threshold: 0.001,
+            max_silence: DurationSeconds(0.5),
+        }
+    }
+}
+
+/// Measurements of a single sample.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SampleAnalysis {
+    /// The largest absolute amplitude.
+    pub peak: f32,
+    /// The RMS amplitude over all channels.
+    pub rms: f32,
+    /// The number of clipped runs.
+    pub clipped_runs: usize,
+    /// The number of silent frames at the start.
+    pub leading_silence: u64,
+    /// The number of silent frames at the end.
+    pub trailing_silence: u64,
+    /// The total number of frames.
+    pub frames: u64,
+}
+
+impl SampleAnalysis {
+    /// Analyze every frame of `sample`.
+    pub fn new(sample: &AudioSample, config: &SampleValidation) -> Self {
+        const BLOCK: usize = 4096;
+
+        let resource = sample.get();
+        let channels = resource.num_channels().get();
+        let frames = resource.len_frames();
+
+        let mut buffers = vec![vec![0.0; BLOCK]; channels];
+        let mut analysis = Self {
+            frames,
+            ..Default::default()
+        };
+
+        let mut sum_squares = 0.0;
+        let mut clip_lengths = vec![0; channels];
+        let mut first_sound = None;
+        let mut last_sound = None;
+
+        let mut start = 0;
+        while start < frames {
+            let len = (frames - start).min(BLOCK as u64) as usize;
+            let mut slices: Vec<&mut [f32]> = buffers.iter_mut().map(|b| &mut b[..]).collect();
+            resource.fill_buffers(&mut slices, 0..len, start);
+
+            for frame in 0..len {
+                let mut loudest = 0f32;
+
+                for (channel, buffer) in buffers.iter().enumerate() {
+                    let value = buffer[frame].abs();
+                    loudest = loudest.max(value);
+                    sum_squares += (value * value) as f64;
+
+                    if value >= config.clip_threshold {
+                        clip_lengths[channel] += 1;
+                        if clip_lengths[channel] == config.clip_run.max(1) {
+                            analysis.clipped_runs += 1;
+                        }
+                    } else {
+                        clip_lengths[channel] = 0;
+                    }
+                }
+
+                analysis.peak = analysis.peak.max(loudest);
+                if loudest >= config.silence_threshold {
+                    first_sound.get_or_insert(start + frame as u64);
+                    last_sound = Some(start + frame as u64);
+                }
+            }
+
+            start += len as u64;
+        }
+
+        let total = frames as f64 * channels as f64;
+        if total > 0.0 {
+            analysis.rms = (sum_squares / total).sqrt() as f32;
+        }
+
+        match (first_sound, last_sound) {
+            (Some(first), Some(last)) => {
+                analysis.leading_silence = first;
+                analysis.trailing_silence = frames - last - 1;
+            }
+            _ => {
+                analysis.leading_silence = frames;
+                analysis.trailing_silence = frames;
+            }
+        }
+
+        analysis
+    }
+
+    /// The peak-to-RMS ratio in decibels.
+    pub fn crest_db(&self) -> f32 {
+        if self.rms <= 0.0 {
+            return 0.0;
+        }
+
+        20.0 * (self.peak / self.rms).log10()
+    }
+}
+
+/// A problem detected by the [`SampleValidationPlugin`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SampleProblem {
+    /// The sample contains clipped runs.
+    Clipping {
+        /// The number of clipped runs.
+        runs: usize,
+    },
+    /// The sample's peak-to-RMS ratio is very high.
+    HighCrest {
+        /// The peak-to-RMS ratio in decibels.
+        crest_db: f32,
+    },
+    /// The sample's original rate is far from the device's.
+    SampleRate {
+        /// The sample's original rate.
+        sample: u32,
+        /// The device's rate.
+        device: u32,
+    },
+    /// The sample begins with a long silence.
+    LeadingSilence(DurationSeconds),
+    /// The sample ends with a long silence.
+    TrailingSilence(DurationSeconds),
+    /// A mono sample is played through a sampler with more channels.
+    MonoInStereo {
+        /// The sampler's channel count.
+        channels: u32,
+    },
+}
+
+impl core::fmt::Display for SampleProblem {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Clipping { runs } => write!(f, "clipping detected ({runs} runs)"),
+            Self::HighCrest { crest_db } => {
+                write!(f, "very high peak-to-RMS ratio ({crest_db:.1} dB)")
+            }
+            Self::SampleRate { sample, device } => write!(
+                f,
+                "sample rate of {sample} Hz is far from the device rate of {device} Hz"
+            ),
+            Self::LeadingSilence(duration) => {
+                write!(f, "{:.2}s of leading silence", duration.0)
+            }
+            Self::TrailingSilence(duration) => {
+                write!(f, "{:.2}s of trailing silence", duration.0)
+            }
+            Self::MonoInStereo { channels } => write!(
+                f,
+                "mono sample played through a {channels}-channel sampler; \
+                 consider a mono pool or spatial effects"
+            ),
+        }
+    }
+}
+
+/// Collect the problems found for `sample`.
+pub fn validate_sample(
+    sample: &AudioSample,
+    analysis: &SampleAnalysis,
+    device_rate: Option<u32>,
+    config: &SampleValidation,
+) -> Vec<SampleProblem> {
+    let mut problems = Vec::new();
+
+    if analysis.clipped_runs > 0 {
+        problems.push(SampleProblem::Clipping {
+            runs: analysis.clipped_runs,
+        });
+    }
+
+    let crest_db = analysis.crest_db();
+    if crest_db > config.max_crest_db {
+        problems.push(SampleProblem::HighCrest { crest_db });
+    }
+
+    let original = sample.original_sample_rate().get();
+    if let Some(device) = device_rate {
+        let ratio = original as f64 / device as f64;
+        let max = config.max_rate_ratio.max(1.0);
+
+        if ratio > max || ratio < 1.0 / max {
+            problems.push(SampleProblem::SampleRate {
+                sample: original,
+                device,
+            });
+        }
+    }
+
+    // Silence is measured after resampling, so use the current rate.
+    let rate = sample
+        .get()
+        .sample_rate()
+        .unwrap_or(sample.original_sample_rate())
+        .get() as f64;
+    let leading = DurationSeconds(analysis.leading_silence as f64 / rate);
+    let trailing = DurationSeconds(analysis.trailing_silence as f64 / rate);
+
+    if leading.0 > config.max_silence.0 {
+        problems.push(SampleProblem::LeadingSilence(leading));
+    }
+
+    // A fully silent sample only needs one report.
+    if trailing.0 > config.max_silence.0 && analysis.leading_silence < analysis.frames {
+        problems.push(SampleProblem::TrailingSilence(trailing));
+    }
+
+    problems
+}
+
+fn validate_samples(
+    mut events: MessageReader<AssetEvent<AudioSample>>,
+    samples: Res<Assets<AudioSample>>,
+    server: Res<AssetServer>,
+    config: Res<SampleValidation>,
+    sample_rate: Option<Res<SampleRate>>,
+) {
+    for event in events.read() {
+        let AssetEvent::LoadedWithDependencies { id } = *event else {
+            continue;
+        };
+
+        let Some(sample) = samples.get(id) else {
+            continue;
+        };
+
+        let analysis = SampleAnalysis::new(sample, &config);
+        let device_rate = sample_rate.as_ref().map(|r| r.get().get());
+
+        for problem in validate_sample(sample, &analysis, device_rate, &config) {
+            warn!("{}: {problem}", describe(id, &server));
+        }
+    }
+}
+
+fn validate_routing(
+    trigger: On<Insert, Sampler>,
+    players: Query<(&SamplePlayer, &Sampler)>,
+    samplers: Query<&SamplerConfig>,
+    samples: Res<Assets<AudioSample>>,
+    server: Res<AssetServer>,
+    mut reported: Local<HashSet<AssetId<AudioSample>>>,
+) {
+    let Ok((player, sampler)) = players.get(trigger.entity) else {
+        return;
+    };
+
+    let id = player.sample.id();
+    if reported.contains(&id) {
+        return;
+    }
+
+    let (Some(sample), Ok(config)) = (samples.get(id), samplers.get(sampler.sampler())) else {
+        return;
+    };
+
+    let channels = config.channels.get().get();
+    if sample.get().num_channels().get() == 1 && channels > 1 {
+        reported.insert(id);
+
+        let problem = SampleProblem::MonoInStereo { channels };
+        warn!("{}: {problem}", describe(id, &server));
+    }
+}
+
+fn describe(id: AssetId<AudioSample>, server: &AssetServer) -> String {
+    server
+        .get_path(id)
+        .map(|path| path.to_string())
+        .unwrap_or_else(|| format!("{id:?}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::num::{NonZeroU32, NonZeroUsize};
+
+    fn sample(data: Vec<f32>) -> AudioSample {
+        AudioSample::from_pcm(data, NonZeroUsize::MIN, NonZeroU32::new(1000).unwrap())
+    }
+
+    #[test]
+    fn test_clean_sample() {
+        let config = SampleValidation::default();
+        let sample = sample((0..1000).map(|i| (i as f32 * 0.1).sin() * 0.5).collect());
+
+        let analysis = SampleAnalysis::new(&sample, &config);
+        assert_eq!(analysis.clipped_runs, 0);
+        assert_eq!(analysis.leading_silence, 0);
+        assert!((analysis.peak - 0.5).abs() < 1e-3);
+
+        assert!(validate_sample(&sample, &analysis, Some(1000), &config).is_empty());
+    }
+
+    #[test]
+    fn test_sample_problems() {
+        let config = SampleValidation::default();
+
+        // 0.6s of silence, a clipped burst, then 0.6s of silence.
+        let mut data = vec![0.0; 600];
+        data.extend([1.0; 5]);
+        data.extend(vec![0.0; 600]);
+        let sample = sample(data);
+
+        let analysis = SampleAnalysis::new(&sample, &config);
+        assert_eq!(analysis.clipped_runs, 1);
+        assert_eq!(analysis.leading_silence, 600);
+        assert_eq!(analysis.trailing_silence, 600);
+
+        let problems = validate_sample(&sample, &analysis, Some(4000), &config);
+        assert_eq!(
+            problems,
+            [
+                SampleProblem::Clipping { runs: 1 },
+                SampleProblem::SampleRate {
+                    sample: 1000,
+                    device: 4000
+                },
+                SampleProblem::LeadingSilence(DurationSeconds(0.6)),
+                SampleProblem::TrailingSilence(DurationSeconds(0.6)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,464 @@
+//! A backend for typical audio settings menus.
+//!
+//! [`AudioSettingsPlugin`] provides the state and logic behind a
+//! settings screen, leaving only the UI widgets to your game.
+//!
+//! - Master, music, and sound effects volume are stored in [`AudioSettings`]
+//!   and mapped onto the buses of [`AudioGraphTemplate::Game`].
+//! - Output devices are listed in [`OutputDevices`] and selected with
+//!   [`SelectOutputDevice`] (with the `cpal` feature).
+//! - The stream's sample rate and latency are reported in [`StreamLatency`],
+//!   which is available even without this plugin.
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_seedling::prelude::*;
+//! fn main() {
+//!     App::new()
+//!         .add_plugins((DefaultPlugins, SeedlingPlugins, AudioSettingsPlugin))
+//!         .add_systems(Update, (volume_keys, show_music_volume))
+//!         .run();
+//! }
+//!
+//! fn volume_keys(keys: Res<ButtonInput<KeyCode>>, mut commands: Commands) {
+//!     if keys.just_pressed(KeyCode::ArrowUp) {
+//!         commands.trigger(AdjustVolume::new(VolumeChannel::Music, 0.1));
+//!     }
+//!
+//!     if keys.just_pressed(KeyCode::ArrowDown) {
+//!         commands.trigger(AdjustVolume::new(VolumeChannel::Music, -0.1));
+//!     }
+//! }
+//!
+//! fn show_music_volume(settings: Res<AudioSettings>) {
+//!     if settings.is_changed() {
+//!         let percent = settings.perceptual(VolumeChannel::Music) * 100.0;
+//!         info!("music: {}%", percent.round());
+//!     }
+//! }
+//! ```
+//!
+//! [`AudioGraphTemplate::Game`]: crate::prelude::AudioGraphTemplate::Game
+
+use crate::{
+    context::{AudioContext, StreamRestartEvent, StreamStartEvent, graph::MusicPool},
+    prelude::{MainBus, SamplerPool, SoundEffectsBus},
+    utils::perceptual_volume::PerceptualVolume,
+};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use core::num::NonZeroU32;
+use firewheel::{Volume, nodes::volume::VolumeNode};
+
+/// Provides the systems and events behind an audio settings menu.
+///
+/// See the [module docs][self] for more information.
+#[derive(Debug, Default)]
+pub struct AudioSettingsPlugin;
+
+impl Plugin for AudioSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AudioSettings>()
+            .init_resource::<OutputDevices>()
+            .add_systems(Last, apply_settings.before(crate::SeedlingSystems::Acquire))
+            .add_observer(observe_set_volume)
+            .add_observer(observe_adjust_volume);
+
+        #[cfg(feature = "cpal")]
+        app.add_systems(
+            PostStartup,
+            refresh_output_devices
+                .after(crate::prelude::SeedlingStartupSystems::StreamInitialization),
+        )
+        .add_observer(observe_refresh_devices)
+        .add_observer(observe_select_device);
+    }
+}
+
+/// Tracks the stream's [`StreamLatency`].
+///
+/// This is added by the [`SeedlingCorePlugin`][crate::SeedlingCorePlugin].
+pub(crate) struct StreamLatencyPlugin;
+
+impl Plugin for StreamLatencyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StreamLatency>()
+            .add_observer(update_latency::<StreamStartEvent>)
+            .add_observer(update_latency::<StreamRestartEvent>);
+    }
+}
+
+/// A user-facing volume control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum VolumeChannel {
+    /// The [`MainBus`].
+    Master,
+    /// The [`MusicPool`].
+    Music,
+    /// The [`SoundEffectsBus`].
+    Sfx,
+}
+
+/// The player's volume settings.
+///
+/// Whenever this resource changes, each channel's volume is applied
+/// to its [`VolumeNode`]. Since the volume is written directly, these
+/// nodes should be left to the settings rather than modulated elsewhere.
+#[derive(Resource, Debug, Clone)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct AudioSettings {
+    /// The master volume.
+    pub master: Volume,
+    /// The music volume.
+    pub music: Volume,
+    /// The sound effects volume.
+    pub sfx: Volume,
+    /// The largest perceptual volume [`AdjustVolume`] will reach.
+    ///
+    /// By default, this is `1.0`, or unity gain.
+    pub max_perceptual: f32,
+    /// The mapping between perceptual and actual volume.
+    #[cfg_attr(feature = "reflect", reflect(ignore))]
+    pub converter: PerceptualVolume,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: Volume::UNITY_GAIN,
+            music: Volume::UNITY_GAIN,
+            sfx: Volume::UNITY_GAIN,
+            max_perceptual: 1.0,
+            converter: PerceptualVolume::new(),
+        }
+    }
+}
+
+impl AudioSettings {
+    /// Get a channel's volume.
+    pub fn volume(&self, channel: VolumeChannel) -> Volume {
+        match channel {
+            VolumeChannel::Master => self.master,
+            VolumeChannel::Music => self.music,
+            VolumeChannel::Sfx => self.sfx,
+        }
+    }
+
+    /// Get a mutable reference to a channel's volume.
+    pub fn volume_mut(&mut self, channel: VolumeChannel) -> &mut Volume {
+        match channel {
+            VolumeChannel::Master => &mut self.master,
+            VolumeChannel::Music => &mut self.music,
+            VolumeChannel::Sfx => &mut self.sfx,
+        }
+    }
+
+    /// Get a channel's volume in the perceptual range,
+    /// suitable for sliders and percentages.
+    pub fn perceptual(&self, channel: VolumeChannel) -> f32 {
+        self.converter.volume_to_perceptual(self.volume(channel))
+    }
+
+    /// Set a channel's volume from the perceptual range.
+    ///
+    /// The value is clamped to [`AudioSettings::max_perceptual`].
+    pub fn set_perceptual(&mut self, channel: VolumeChannel, perceptual: f32) {
+        let perceptual = perceptual.clamp(0.0, self.max_perceptual);
+        *self.volume_mut(channel) = self.converter.perceptual_to_volume(perceptual);
+    }
+}
+
+/// Set a channel's volume.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SetVolume {
+    /// The target channel.
+    pub channel: VolumeChannel,
+    /// The new volume.
+    pub volume: Volume,
+}
+
+/// Nudge a channel's volume by a perceptual step.
+///
+/// This is convenient for `+` and `-` buttons.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AdjustVolume {
+    /// The target channel.
+    pub channel: VolumeChannel,
+    /// The perceptual step, where `0.1` is roughly ten percent.
+    pub step: f32,
+}
+
+impl AdjustVolume {
+    /// Construct a new [`AdjustVolume`].
+    pub fn new(channel: VolumeChannel, step: f32) -> Self {
+        Self { channel, step }
+    }
+}
+
+fn observe_set_volume(trigger: On<SetVolume>, mut settings: ResMut<AudioSettings>) {
+    *settings.volume_mut(trigger.channel) = trigger.volume;
+}
+
+fn observe_adjust_volume(trigger: On<AdjustVolume>, mut settings: ResMut<AudioSettings>) {
+    let perceptual = settings.perceptual(trigger.channel) + trigger.step;
+    settings.set_perceptual(trigger.channel, perceptual);
+}
+
+fn apply_settings(
+    settings: Res<AudioSettings>,
+    mut master: Query<&mut VolumeNode, With<MainBus>>,
+    mut music: Query<&mut VolumeNode, (With<SamplerPool<MusicPool>>, Without<MainBus>)>,
+    mut sfx: Query<
+        &mut VolumeNode,
+        (
+            With<SoundEffectsBus>,
+            Without<MainBus>,
+            Without<SamplerPool<MusicPool>>,
+        ),
+    >,
+) {
+    let changed = settings.is_changed();
+    let apply = |volume: Volume, mut node: Mut<VolumeNode>| {
+        if (changed || node.is_added()) && node.volume != volume {
+            node.volume = volume;
+        }
+    };
+
+    master.iter_mut().for_each(|n| apply(settings.master, n));
+    music.iter_mut().for_each(|n| apply(settings.music, n));
+    sfx.iter_mut().for_each(|n| apply(settings.sfx, n));
+}
+
+/// The current stream's sample rate, block size, and latency.
+///
+/// This is updated whenever the stream starts or restarts.
+/// If the values change mid-session, such as when switching
+/// to bluetooth headphones, a [`StreamLatencyChanged`] event is triggered.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct StreamLatency {
+    /// The stream's sample rate, once it's running.
+    pub sample_rate: Option<NonZeroU32>,
+    /// The stream's maximum block size, in frames.
+    pub block_frames: Option<NonZeroU32>,
+    /// The backend's reported input-to-output latency, in seconds.
+    pub input_to_output_seconds: Option<f64>,
+}
+
+impl StreamLatency {
+    /// The duration of a single block, in seconds.
+    ///
+    /// This is the minimum output latency the stream can achieve.
+    pub fn block_seconds(&self) -> Option<f64> {
+        let sample_rate = self.sample_rate?;
+        let block_frames = self.block_frames?;
+
+        Some(block_frames.get() as f64 / sample_rate.get() as f64)
+    }
+}
+
+/// An event triggered when the [`StreamLatency`] changes mid-session.
+///
+/// This is useful for re-calibrating latency-sensitive
+/// gameplay, like rhythm games.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn plugin(app: &mut App) {
+///     app.add_observer(|changed: On<StreamLatencyChanged>| {
+///         info!(
+///             "latency changed from {:?}s to {:?}s",
+///             changed.previous.input_to_output_seconds,
+///             changed.current.input_to_output_seconds,
+///         );
+///     });
+/// }
+/// ```
+///
+/// This isn't triggered when the stream first starts.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct StreamLatencyChanged {
+    /// The latency before the change.
+    pub previous: StreamLatency,
+    /// The latency after the change.
+    pub current: StreamLatency,
+}
+
+fn update_latency<E: Event>(
+    _: On<E>,
+    mut latency: ResMut<StreamLatency>,
+    mut context: ResMut<AudioContext>,
+    mut commands: Commands,
+) {
+    let info = context.try_with(|context| {
+        context.stream_info().map(|info| StreamLatency {
+            sample_rate: Some(info.sample_rate),
+            block_frames: Some(info.max_block_frames),
+            input_to_output_seconds: Some(info.input_to_output_latency_seconds),
+        })
+    });
+
+    let Ok(Some(current)) = info else {
+        return;
+    };
+
+    let previous = *latency;
+    if previous == current {
+        return;
+    }
+
+    *latency = current;
+    if previous.sample_rate.is_some() {
+        commands.trigger(StreamLatencyChanged { previous, current });
+    }
+}
+
+/// The available output devices.
+///
+/// This is populated after the stream is first initialized,
+/// and refreshed with [`RefreshOutputDevices`]. It's always
+/// empty without the `cpal` feature.
+#[derive(Resource, Debug, Default)]
+pub struct OutputDevices {
+    #[cfg(feature = "cpal")]
+    devices: Vec<crate::platform::cpal::DeviceInfo>,
+}
+
+#[cfg(feature = "cpal")]
+impl OutputDevices {
+    /// Iterate over the available devices.
+    pub fn iter(&self) -> impl Iterator<Item = &crate::platform::cpal::DeviceInfo> {
+        self.devices.iter()
+    }
+
+    /// Get the system's default device.
+    pub fn default_device(&self) -> Option<&crate::platform::cpal::DeviceInfo> {
+        self.devices.iter().find(|d| d.is_default)
+    }
+}
+
+/// Re-enumerate the available output devices.
+#[derive(Event, Debug, Default, Clone, Copy)]
+pub struct RefreshOutputDevices;
+
+/// Select an output device, restarting the stream.
+///
+/// This sets the output device of the
+/// [`AudioStreamConfig`][crate::prelude::AudioStreamConfig].
+#[cfg(feature = "cpal")]
+#[derive(Event, Debug, Clone)]
+pub struct SelectOutputDevice(pub crate::platform::cpal::DeviceInfo);
+
+#[cfg(feature = "cpal")]
+fn refresh_output_devices(mut devices: ResMut<OutputDevices>) {
+    let mut outputs = crate::platform::cpal::default_host_enumerator().output_devices();
+    outputs.sort_by(|a, b| a.name.cmp(&b.name));
+
+    devices.devices = outputs;
+}
+
+#[cfg(feature = "cpal")]
+fn observe_refresh_devices(_: On<RefreshOutputDevices>, devices: ResMut<OutputDevices>) {
+    refresh_output_devices(devices);
+}
+
+#[cfg(feature = "cpal")]
+fn observe_select_device(
+    trigger: On<SelectOutputDevice>,
+    stream: Option<ResMut<crate::platform::AudioStreamConfig<crate::platform::cpal::CpalConfig>>>,
+) {
+    if let Some(mut stream) = stream {
+        stream.0.output.device_id = Some(trigger.0.id.clone());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        prelude::*,
+        test::{prepare_app, run},
+    };
+
+    #[test]
+    fn test_settings_volume() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.spawn((MainBus, VolumeNode::default()));
+            commands.spawn((SoundEffectsBus, VolumeNode::default()));
+        });
+        app.init_resource::<AudioSettings>()
+            .add_systems(Last, apply_settings)
+            .add_observer(observe_set_volume)
+            .add_observer(observe_adjust_volume);
+
+        app.world_mut().trigger(SetVolume {
+            channel: VolumeChannel::Master,
+            volume: Volume::Linear(0.5),
+        });
+        app.world_mut()
+            .trigger(AdjustVolume::new(VolumeChannel::Sfx, -0.5));
+        app.update();
+
+        run(
+            &mut app,
+            |master: Single<&VolumeNode, With<MainBus>>,
+             sfx: Single<&VolumeNode, With<SoundEffectsBus>>,
+             settings: Res<AudioSettings>| {
+                assert_eq!(master.volume, Volume::Linear(0.5));
+                assert_eq!(sfx.volume, settings.sfx);
+                assert!((settings.perceptual(VolumeChannel::Sfx) - 0.5).abs() < 1e-4);
+            },
+        );
+
+        // Adjustments are clamped to the maximum.
+        app.world_mut()
+            .trigger(AdjustVolume::new(VolumeChannel::Sfx, 2.0));
+        app.update();
+
+        run(
+            &mut app,
+            |sfx: Single<&VolumeNode, With<SoundEffectsBus>>| {
+                assert_eq!(sfx.volume, Volume::Decibels(0.0));
+            },
+        );
+    }
+
+    #[test]
+    fn test_latency_changed() {
+        #[derive(Resource, Default)]
+        struct Changes(Vec<StreamLatencyChanged>);
+
+        let mut app = prepare_app(|| {});
+        app.init_resource::<Changes>().add_observer(
+            |changed: On<StreamLatencyChanged>, mut changes: ResMut<Changes>| {
+                changes.0.push(*changed);
+            },
+        );
+
+        let started = *app.world().resource::<StreamLatency>();
+        assert!(started.sample_rate.is_some());
+        assert!(app.world().resource::<Changes>().0.is_empty());
+
+        // Simulate a device with a different latency.
+        let stale = StreamLatency {
+            input_to_output_seconds: Some(0.25),
+            ..started
+        };
+        app.insert_resource(stale);
+
+        let rate = started.sample_rate.unwrap();
+        app.world_mut().trigger(StreamRestartEvent {
+            previous_rate: rate,
+            current_rate: rate,
+        });
+        app.update();
+
+        let changes = &app.world().resource::<Changes>().0;
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].previous, stale);
+        assert_eq!(changes[0].current, started);
+        assert_eq!(*app.world().resource::<StreamLatency>(), started);
+    }
+}
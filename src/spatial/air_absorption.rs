@@ -0,0 +1,136 @@
+//! Distance-driven low-pass filtering.
+
+use bevy_ecs::prelude::*;
+use firewheel::nodes::fast_filters::lowpass::FastLowpassNode;
+
+use super::{DefaultSpatialScale, EffectTransform, SpatialEmitters, SpatialScale};
+
+/// Simulates the absorption of high frequencies over distance.
+///
+/// When placed alongside a [`FastLowpassNode`], the filter's cutoff
+/// is driven by the distance between the emitter and its listener,
+/// so far-away sounds naturally dull.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn spawn_distant(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("thunder.wav")),
+///         Transform::from_xyz(150.0, 0.0, 0.0),
+///         sample_effects![
+///             SpatialBasicNode::default(),
+///             (FastLowpassNode::<2>::default(), AirAbsorption::default()),
+///         ],
+///     ));
+/// }
+/// ```
+///
+/// The cutoff is interpolated logarithmically between
+/// [`max_cutoff_hz`][Self::max_cutoff_hz] at
+/// [`start_distance`][Self::start_distance] and
+/// [`min_cutoff_hz`][Self::min_cutoff_hz] at
+/// [`end_distance`][Self::end_distance]. Distances are measured
+/// after applying [`SpatialScale`].
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct AirAbsorption {
+    /// The distance at which filtering begins.
+    ///
+    /// Defaults to `10.0`.
+    pub start_distance: f32,
+    /// The distance at which filtering reaches [`AirAbsorption::min_cutoff_hz`].
+    ///
+    /// Defaults to `200.0`.
+    pub end_distance: f32,
+    /// The cutoff at or below [`AirAbsorption::start_distance`].
+    ///
+    /// Defaults to `20_000.0`.
+    pub max_cutoff_hz: f32,
+    /// The cutoff at or beyond [`AirAbsorption::end_distance`].
+    ///
+    /// Defaults to `1_500.0`.
+    pub min_cutoff_hz: f32,
+    /// The exponent applied to the normalized distance.
+    ///
+    /// Values greater than `1.0` keep sounds bright for longer before
+    /// dulling quickly, while values less than `1.0` dull sounds sooner.
+    ///
+    /// Defaults to `1.0`.
+    pub curve: f32,
+}
+
+impl Default for AirAbsorption {
+    fn default() -> Self {
+        Self {
+            start_distance: 10.0,
+            end_distance: 200.0,
+            max_cutoff_hz: 20_000.0,
+            min_cutoff_hz: 1_500.0,
+            curve: 1.0,
+        }
+    }
+}
+
+impl AirAbsorption {
+    /// Calculate the cutoff frequency for an emitter `distance` units away.
+    pub fn cutoff_hz(&self, distance: f32) -> f32 {
+        let range = self.end_distance - self.start_distance;
+        let t = if range <= 0.0 {
+            if distance >= self.end_distance {
+                1.0
+            } else {
+                0.0
+            }
+        } else {
+            ((distance - self.start_distance) / range).clamp(0.0, 1.0)
+        };
+
+        let t = t.powf(self.curve.max(f32::EPSILON));
+        let ratio = self.min_cutoff_hz / self.max_cutoff_hz;
+
+        self.max_cutoff_hz * ratio.powf(t)
+    }
+}
+
+pub(super) fn update_air_absorption(
+    spatial_emitters: SpatialEmitters,
+    mut emitters: Query<(
+        Entity,
+        &mut FastLowpassNode,
+        &AirAbsorption,
+        Option<&SpatialScale>,
+        EffectTransform,
+    )>,
+    default_scale: Res<DefaultSpatialScale>,
+) {
+    for (entity, mut filter, absorption, scale, transform) in emitters.iter_mut() {
+        if let Some(offset) = spatial_emitters.offset(entity, transform) {
+            let scale = scale.map(|s| s.0).unwrap_or(default_scale.0);
+            let cutoff_hz = absorption.cutoff_hz((offset * scale).length());
+
+            if filter.cutoff_hz != cutoff_hz {
+                filter.cutoff_hz = cutoff_hz;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cutoff_curve() {
+        let absorption = AirAbsorption::default();
+
+        assert_eq!(absorption.cutoff_hz(0.0), 20_000.0);
+        assert_eq!(absorption.cutoff_hz(10.0), 20_000.0);
+        assert!((absorption.cutoff_hz(200.0) - 1_500.0).abs() < 1e-2);
+        assert!((absorption.cutoff_hz(1_000.0) - 1_500.0).abs() < 1e-2);
+
+        let midpoint = absorption.cutoff_hz(105.0);
+        let expected = (20_000f32 * 1_500.0).sqrt();
+        assert!((midpoint - expected).abs() < 1.0);
+    }
+}
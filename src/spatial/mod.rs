@@ -0,0 +1,793 @@
+//! Spatial audio components.
+//!
+//! To enable spatial audio, three conditions are required:
+//!
+//! 1. The spatial audio node, [`SpatialBasicNode`], must have
+//!    a transform.
+//! 2. The spatial listener entity must have a [`SpatialListener2D`]
+//!    or [`SpatialListener3D`].
+//! 3. The spatial listener entity must have a transform.
+//!
+//! Typically, you'll want to include a [`SpatialBasicNode`] as an effect.
+//!
+//! ```
+//! # use bevy_seedling::prelude::*;
+//! # use bevy::prelude::*;
+//! fn spawn_spatial(mut commands: Commands, server: Res<AssetServer>) {
+//!     // Spawn a player with a transform (1).
+//!     commands.spawn((
+//!         SamplePlayer::new(server.load("my_sample.wav")),
+//!         Transform::default(),
+//!         sample_effects![SpatialBasicNode::default()],
+//!     ));
+//!
+//!     // Then, spawn a listener (2), which automatically inserts
+//!     // a transform if it doesn't already exist (3).
+//!     commands.spawn(SpatialListener2D);
+//! }
+//! ```
+//!
+//! Multiple listeners are supported. By default, `bevy_seedling` will
+//! simply select the closest listener for distance calculations.
+//! For split-screen games, [`ListenerBlend`] and [`ListenerMask`]
+//! provide finer control, while [`ListenerOutput`] can route each
+//! listener to its own output.
+//!
+//! Emitters that shouldn't follow their transforms can be
+//! marked [`HeadLocked`] or positioned with [`ListenerRelative`].
+
+use bevy_app::prelude::*;
+use bevy_ecs::{prelude::*, query::QueryData, system::SystemParam};
+use bevy_math::prelude::*;
+use bevy_transform::prelude::*;
+use firewheel::nodes::spatial_basic::SpatialBasicNode;
+
+#[cfg(feature = "itd")]
+use crate::nodes::itd::ItdNode;
+use crate::{SeedlingSystems, pool::sample_effects::EffectOf};
+
+#[cfg(feature = "effects")]
+mod air_absorption;
+mod near_field;
+#[cfg(feature = "ui")]
+mod screen;
+mod split;
+
+#[cfg(feature = "effects")]
+pub use air_absorption::AirAbsorption;
+pub use near_field::NearField;
+#[cfg(feature = "ui")]
+pub use screen::{ScreenSource, ScreenSpacePan};
+pub use split::{ListenerBus, ListenerBusOf, ListenerOutput, ListenerPool, PerListener};
+
+pub(crate) struct SpatialPlugin;
+
+impl Plugin for SpatialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DefaultSpatialScale>()
+            .init_resource::<ListenerBlend>()
+            .add_systems(
+                Last,
+                (
+                    update_basic,
+                    #[cfg(feature = "itd")]
+                    update_itd,
+                    near_field::update_near_field,
+                    #[cfg(feature = "hrtf")]
+                    spatial_hrtf::update_hrtf,
+                    #[cfg(feature = "effects")]
+                    air_absorption::update_air_absorption,
+                    #[cfg(feature = "ui")]
+                    screen::update_screen_pan,
+                )
+                    .after(SeedlingSystems::Pool)
+                    .before(SeedlingSystems::Queue),
+            )
+            .add_systems(
+                Last,
+                (split::spawn_listener_pools, split::split_per_listener)
+                    .chain()
+                    .before(SeedlingSystems::Acquire),
+            );
+    }
+}
+
+/// A scaling factor applied to the distance between spatial listeners and emitters.
+///
+/// To override the [global spatial scaling][DefaultSpatialScale] for an entity,
+/// simply insert [`SpatialScale`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn set_scale(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("my_sample.wav")),
+///         Transform::default(),
+///         sample_effects![(SpatialBasicNode::default(), SpatialScale(Vec3::splat(0.25)))],
+///     ));
+/// }
+/// ```
+///
+/// By default, a spatial signal's amplitude will be cut in half at 10 units. Then,
+/// for each doubling in distance, the signal will be successively halved.
+///
+/// | Distance | Amplitude |
+/// | -------- | --------- |
+/// | 10       | -6dB      |
+/// | 20       | -12dB     |
+/// | 40       | -18dB     |
+/// | 80       | -24dB     |
+///
+/// When one unit corresponds to one meter, this is a good default. If
+/// your game's scale differs significantly, however, you may need
+/// to adjust the spatial scaling.
+///
+/// The distance between listeners and emitters is multiplied by this
+/// factor, so if a meter in your game corresponds to more than one unit, you
+/// should provide a spatial scale of less than one to compensate.
+#[derive(Component, Debug, Clone, Copy)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct SpatialScale(pub Vec3);
+
+impl Default for SpatialScale {
+    fn default() -> Self {
+        Self(Vec3::ONE)
+    }
+}
+
+/// The global default spatial scale.
+///
+/// For more details on spatial scaling, see [`SpatialScale`].
+///
+/// The default scaling is 1 in every direction, [`Vec3::ONE`].
+#[derive(Resource, Debug, Clone, Copy)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct DefaultSpatialScale(pub Vec3);
+
+impl Default for DefaultSpatialScale {
+    fn default() -> Self {
+        Self(Vec3::ONE)
+    }
+}
+
+/// A 2D spatial listener.
+///
+/// When this component is added to an entity with a transform,
+/// this transform is used to calculate spatial offsets for all
+/// emitters. An emitter is an entity with [`SpatialBasicNode`]
+/// and transform components.
+///
+/// Multiple listeners are supported. `bevy_seedling` will
+/// simply select the closest listener for distance
+/// calculations.
+#[derive(Debug, Default, Component)]
+#[require(Transform)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct SpatialListener2D;
+
+/// A 3D spatial listener.
+///
+/// When this component is added to an entity with a transform,
+/// this transform is used to calculate spatial offsets for all
+/// emitters. An emitter is an entity with [`SpatialBasicNode`]
+/// and transform components.
+///
+/// Multiple listeners are supported. `bevy_seedling` will
+/// simply select the closest listener for distance
+/// calculations.
+#[derive(Debug, Default, Component)]
+#[require(Transform)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct SpatialListener3D;
+
+/// Determines how emitters are spatialized when multiple listeners are present.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn split_screen(mut commands: Commands) {
+///     commands.insert_resource(ListenerBlend::WeightedAverage);
+///
+///     commands.spawn((SpatialListener2D, Transform::from_xyz(-100.0, 0.0, 0.0)));
+///     commands.spawn((SpatialListener2D, Transform::from_xyz(100.0, 0.0, 0.0)));
+/// }
+/// ```
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum ListenerBlend {
+    /// Spatialize relative to the closest listener.
+    ///
+    /// This is cheap and precise for a single listener, but
+    /// emitters will abruptly switch sides as the closest
+    /// listener changes.
+    #[default]
+    Closest,
+    /// Blend the offsets from every listener, weighted
+    /// by the inverse square of their distance.
+    ///
+    /// Nearby listeners dominate, while emitters equidistant
+    /// between listeners smoothly converge toward the center.
+    WeightedAverage,
+}
+
+/// Restricts which listeners an emitter is spatialized to.
+///
+/// An emitter is only heard by listeners whose masks share at least
+/// one bit with its own. Entities without a [`ListenerMask`] are
+/// treated as [`ListenerMask::ALL`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// const PLAYER_ONE: ListenerMask = ListenerMask(0b01);
+/// const PLAYER_TWO: ListenerMask = ListenerMask(0b10);
+///
+/// fn spawn(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((SpatialListener3D, PLAYER_ONE));
+///     commands.spawn((SpatialListener3D, PLAYER_TWO));
+///
+///     // Only player one hears their own footsteps.
+///     commands.spawn((
+///         SamplePlayer::new(server.load("footstep.wav")),
+///         Transform::default(),
+///         PLAYER_ONE,
+///         sample_effects![SpatialBasicNode::default()],
+///     ));
+/// }
+/// ```
+///
+/// On emitters, this can be placed on the
+/// [`SamplePlayer`][crate::prelude::SamplePlayer]
+/// or directly on its spatial effects. If an emitter matches
+/// no listeners, its spatial parameters are left unchanged.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct ListenerMask(pub u32);
+
+impl ListenerMask {
+    /// A mask matching every listener.
+    pub const ALL: Self = Self(u32::MAX);
+
+    /// Returns whether `self` and `other` share any bits.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl Default for ListenerMask {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// Spatializes an emitter relative to a single listener.
+///
+/// This overrides [`ListenerBlend`] and [`ListenerMask`]. If the
+/// listener doesn't exist, the emitter's spatial parameters are
+/// left unchanged.
+///
+/// This is inserted automatically on each copy of a [`PerListener`]
+/// sample, but it can also be used directly.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct ListenerTarget(pub Entity);
+
+/// A marker for emitters that should bypass spatialization.
+///
+/// Head-locked emitters are always heard as if they were
+/// positioned directly at the listener, regardless of their
+/// transform. This is useful for UI sounds or voice-over
+/// attached to entities that happen to have transforms.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn play_ui_sound(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("click.wav")),
+///         Transform::from_xyz(100.0, 0.0, 0.0),
+///         HeadLocked,
+///         sample_effects![SpatialBasicNode::default()],
+///     ));
+/// }
+/// ```
+///
+/// This can be placed on the [`SamplePlayer`][crate::prelude::SamplePlayer]
+/// or directly on its spatial effects.
+#[derive(Debug, Default, Clone, Copy, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct HeadLocked;
+
+/// Positions an emitter relative to the listener rather than in the world.
+///
+/// The offset is expressed in the listener's local space, where
+/// `+X` is right, `+Y` is up, and `-Z` is forward. The emitter's
+/// transform, if any, is ignored, so the sound stays fixed relative
+/// to the listener as it moves and rotates.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn play_radio(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("radio.wav")).looping(),
+///         // Just off the listener's left shoulder.
+///         ListenerRelative(Vec3::new(-0.5, 0.0, 0.5)),
+///         sample_effects![SpatialBasicNode::default()],
+///     ));
+/// }
+/// ```
+///
+/// Like other offsets, this is multiplied by the [`SpatialScale`].
+/// This can be placed on the [`SamplePlayer`][crate::prelude::SamplePlayer]
+/// or directly on its spatial effects. [`HeadLocked`] takes precedence
+/// if both are present.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Component)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct ListenerRelative(pub Vec3);
+
+#[derive(SystemParam)]
+struct SpatialListeners<'w, 's> {
+    listeners: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static GlobalTransform,
+            AnyOf<(&'static SpatialListener2D, &'static SpatialListener3D)>,
+            Option<&'static ListenerMask>,
+        ),
+    >,
+    blend: Res<'w, ListenerBlend>,
+}
+
+enum SpatialKind {
+    Listener2D,
+    Listener3D,
+}
+
+impl From<(Option<&'_ SpatialListener2D>, Option<&'_ SpatialListener3D>)> for SpatialKind {
+    fn from(value: (Option<&'_ SpatialListener2D>, Option<&'_ SpatialListener3D>)) -> Self {
+        match value {
+            (Some(_), None) => Self::Listener2D,
+            (None, Some(_)) => Self::Listener3D,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl SpatialListeners<'_, '_> {
+    /// Iterate over the listeners matching `mask`, along with
+    /// their squared distance to `emitter`.
+    fn candidates(
+        &self,
+        emitter: Vec3,
+        mask: ListenerMask,
+    ) -> impl Iterator<Item = (Transform, SpatialKind, f32)> {
+        self.listeners
+            .iter()
+            .filter(move |(.., listener_mask)| {
+                listener_mask.copied().unwrap_or_default().intersects(&mask)
+            })
+            .map(move |(_, transform, kind, _)| {
+                let transform = transform.compute_transform();
+                let kind = SpatialKind::from(kind);
+                let distance = match kind {
+                    // in a 2d context, we need to ignore the z component
+                    SpatialKind::Listener2D => {
+                        emitter.xy().distance_squared(transform.translation.xy())
+                    }
+                    SpatialKind::Listener3D => emitter.distance_squared(transform.translation),
+                };
+
+                (transform, kind, distance)
+            })
+    }
+
+    /// Fetch the nearest spatial listener, if any exist.
+    ///
+    /// This iterates over both 2D and 3D listeners.
+    fn nearest_listener(&self, emitter: Vec3) -> Option<(Transform, SpatialKind)> {
+        self.nearest_masked(emitter, ListenerMask::ALL)
+    }
+
+    /// Fetch the nearest spatial listener matching `mask`, if any exist.
+    fn nearest_masked(
+        &self,
+        emitter: Vec3,
+        mask: ListenerMask,
+    ) -> Option<(Transform, SpatialKind)> {
+        // This is linear over the number of listeners, but we
+        // expect there to be very few of these at any one time.
+        self.candidates(emitter, mask)
+            .min_by(|(.., a), (.., b)| a.total_cmp(b))
+            .map(|(transform, kind, ..)| (transform, kind))
+    }
+
+    /// Calculate the offset between `emitter` and a specific listener.
+    ///
+    /// This does not account for spatial scaling.
+    fn offset_to(&self, listener: Entity, emitter: Vec3) -> Option<Vec3> {
+        let (_, transform, kind, _) = self.listeners.get(listener).ok()?;
+        let transform = transform.compute_transform();
+
+        Some(local_offset(&transform, &SpatialKind::from(kind), emitter))
+    }
+
+    /// Calculate the offset between `emitter` and the listeners
+    /// matching `mask` according to the [`ListenerBlend`] mode.
+    ///
+    /// This does not account for spatial scaling.
+    fn calculate_offset(&self, emitter: Vec3, mask: ListenerMask) -> Option<Vec3> {
+        match *self.blend {
+            ListenerBlend::Closest => {
+                let (listener, kind) = self.nearest_masked(emitter, mask)?;
+                Some(local_offset(&listener, &kind, emitter))
+            }
+            ListenerBlend::WeightedAverage => {
+                let (sum, total_weight) = self.candidates(emitter, mask).fold(
+                    (Vec3::ZERO, 0f32),
+                    |(sum, total), (listener, kind, distance)| {
+                        let weight = 1.0 / distance.max(f32::EPSILON);
+                        (
+                            sum + local_offset(&listener, &kind, emitter) * weight,
+                            total + weight,
+                        )
+                    },
+                );
+
+                (total_weight > 0.0).then(|| sum / total_weight)
+            }
+        }
+    }
+}
+
+/// Calculate the offset of `emitter` in `listener`'s local space.
+fn local_offset(listener: &Transform, kind: &SpatialKind, emitter: Vec3) -> Vec3 {
+    let mut world_offset = emitter - listener.translation;
+
+    match kind {
+        SpatialKind::Listener2D => {
+            world_offset.z = 0.0;
+            let local_offset = listener.rotation.inverse() * world_offset;
+            Vec3::new(local_offset.x, 0.0, local_offset.y)
+        }
+        SpatialKind::Listener3D => listener.rotation.inverse() * world_offset,
+    }
+}
+
+type EffectTransform = AnyOf<(&'static GlobalTransform, &'static EffectOf)>;
+
+#[derive(SystemParam)]
+struct SpatialEmitters<'w, 's> {
+    listeners: SpatialListeners<'w, 's>,
+    transforms: Query<'w, 's, &'static GlobalTransform>,
+    overrides: Query<
+        'w,
+        's,
+        (
+            Has<HeadLocked>,
+            Option<&'static ListenerRelative>,
+            Option<&'static ListenerMask>,
+            Option<&'static ListenerTarget>,
+        ),
+    >,
+}
+
+impl SpatialEmitters<'_, '_> {
+    /// Calculate the listener-space offset of an emitter.
+    ///
+    /// [`HeadLocked`], [`ListenerRelative`], [`ListenerTarget`], and
+    /// [`ListenerMask`] are checked on the effect before its sample player. This does not account for spatial scaling.
+    fn offset(
+        &self,
+        effect: Entity,
+        effect_transform: <EffectTransform as QueryData>::Item<'_, '_>,
+    ) -> Option<Vec3> {
+        let parent = effect_transform.1.map(|p| p.0);
+        let mut mask = None;
+        let mut target = None;
+
+        for entity in core::iter::once(effect).chain(parent) {
+            match self.overrides.get(entity) {
+                Ok((true, ..)) => return Some(Vec3::ZERO),
+                Ok((false, Some(relative), ..)) => return Some(relative.0),
+                Ok((false, None, entity_mask, entity_target)) => {
+                    mask = mask.or(entity_mask.copied());
+                    target = target.or(entity_target.map(|t| t.0));
+                }
+                _ => {}
+            }
+        }
+
+        let emitter_pos = extract_effect_transform(effect_transform, &self.transforms)?;
+        match target {
+            Some(listener) => self.listeners.offset_to(listener, emitter_pos),
+            None => self
+                .listeners
+                .calculate_offset(emitter_pos, mask.unwrap_or_default()),
+        }
+    }
+}
+
+fn extract_effect_transform(
+    effect_transform: <EffectTransform as QueryData>::Item<'_, '_>,
+    transforms: &Query<&GlobalTransform>,
+) -> Option<Vec3> {
+    match effect_transform {
+        (Some(global), _) => Some(global.translation()),
+        (_, Some(parent)) => match transforms.get(parent.0) {
+            Ok(global) => Some(global.translation()),
+            Err(_) => None,
+        },
+        _ => unreachable!(),
+    }
+}
+
+fn update_basic(
+    spatial_emitters: SpatialEmitters,
+    mut emitters: Query<(
+        Entity,
+        &mut SpatialBasicNode,
+        Option<&SpatialScale>,
+        EffectTransform,
+    )>,
+    default_scale: Res<DefaultSpatialScale>,
+) {
+    for (entity, mut spatial, scale, transform) in emitters.iter_mut() {
+        if let Some(offset) = spatial_emitters.offset(entity, transform) {
+            let scale = scale.map(|s| s.0).unwrap_or(default_scale.0);
+            spatial.offset = (offset * scale).into();
+        }
+    }
+}
+
+#[cfg(feature = "itd")]
+fn update_itd(
+    spatial_emitters: SpatialEmitters,
+    mut emitters: Query<(Entity, &mut ItdNode, EffectTransform)>,
+) {
+    for (entity, mut spatial, transform) in emitters.iter_mut() {
+        if let Some(offset) = spatial_emitters.offset(entity, transform) {
+            spatial.direction = offset;
+        }
+    }
+}
+
+#[cfg(feature = "hrtf")]
+mod spatial_hrtf {
+    use super::*;
+    use crate::prelude::hrtf::HrtfNode;
+
+    pub(super) fn update_hrtf(
+        spatial_emitters: SpatialEmitters,
+        mut emitters: Query<(
+            Entity,
+            &mut HrtfNode,
+            Option<&SpatialScale>,
+            EffectTransform,
+        )>,
+        default_scale: Res<DefaultSpatialScale>,
+    ) {
+        for (entity, mut spatial, scale, transform) in emitters.iter_mut() {
+            if let Some(offset) = spatial_emitters.offset(entity, transform) {
+                let scale = scale.map(|s| s.0).unwrap_or(default_scale.0);
+                spatial.offset = offset * scale;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_asset::AssetServer;
+
+    use super::*;
+    use crate::{
+        node::follower::FollowerOf,
+        pool::Sampler,
+        prelude::*,
+        test::{prepare_app, run},
+    };
+
+    #[test]
+    fn test_closest() {
+        let positions = [Vec3::splat(5.0), Vec3::splat(4.0), Vec3::splat(6.0)]
+            .into_iter()
+            .map(Transform::from_translation)
+            .collect::<Vec<_>>();
+
+        let mut app = prepare_app({
+            let positions = positions.clone();
+            move |mut commands: Commands| {
+                for position in &positions {
+                    commands.spawn((SpatialListener3D, *position));
+                }
+            }
+        });
+
+        let closest = run(&mut app, |listeners: SpatialListeners| {
+            let emitter = Vec3::splat(0.0);
+            listeners.nearest_listener(emitter).unwrap()
+        });
+
+        assert_eq!(closest.0, positions[1]);
+    }
+
+    #[test]
+    fn test_empty() {
+        let positions = []
+            .into_iter()
+            .map(Transform::from_translation)
+            .collect::<Vec<_>>();
+
+        let mut app = prepare_app({
+            let positions = positions.clone();
+            move |mut commands: Commands| {
+                for position in &positions {
+                    commands.spawn((SpatialListener3D, *position));
+                }
+            }
+        });
+
+        let closest = run(&mut app, |listeners: SpatialListeners| {
+            let emitter = Vec3::splat(0.0);
+            listeners.nearest_listener(emitter)
+        });
+
+        assert!(closest.is_none());
+    }
+
+    #[test]
+    fn test_weighted_blend() {
+        let mut app = prepare_app(|mut commands: Commands| {
+            commands.insert_resource(ListenerBlend::WeightedAverage);
+            commands.spawn((
+                SpatialListener3D,
+                Transform::from_xyz(-10.0, 0.0, 0.0),
+                ListenerMask(0b01),
+            ));
+            commands.spawn((
+                SpatialListener3D,
+                Transform::from_xyz(10.0, 0.0, 0.0),
+                ListenerMask(0b10),
+            ));
+        });
+
+        let (blended, masked) = run(&mut app, |listeners: SpatialListeners| {
+            (
+                listeners.calculate_offset(Vec3::ZERO, ListenerMask::ALL),
+                listeners.calculate_offset(Vec3::ZERO, ListenerMask(0b01)),
+            )
+        });
+
+        assert_eq!(blended, Some(Vec3::ZERO));
+        assert_eq!(masked, Some(Vec3::new(10.0, 0.0, 0.0)));
+    }
+
+    #[derive(PoolLabel, PartialEq, Eq, Hash, Clone, Debug)]
+    struct TestPool;
+
+    /// Ensure that transform updates are propagated immediately when
+    /// queued in a pool.
+    #[test]
+    fn test_immediate_positioning() {
+        let position = Vec3::splat(3.0);
+        let mut app = prepare_app(move |mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn((
+                SamplerPool(TestPool),
+                sample_effects![SpatialBasicNode::default()],
+            ));
+
+            commands.spawn((SpatialListener3D, Transform::default()));
+
+            commands.spawn((
+                TestPool,
+                Transform::from_translation(position),
+                SamplePlayer::new(server.load("sine_440hz_1ms.wav")).looping(),
+            ));
+        });
+
+        loop {
+            let complete = run(
+                &mut app,
+                move |player: Query<&Sampler>,
+                      effect: Query<&SpatialBasicNode, With<FollowerOf>>| {
+                    if player.iter().len() == 1 {
+                        let effect: Vec3 = effect.single().unwrap().offset.into();
+                        assert_eq!(effect, position);
+                        true
+                    } else {
+                        false
+                    }
+                },
+            );
+
+            if complete {
+                break;
+            }
+
+            app.update();
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "itd")]
+    fn test_itd_effect() {
+        let position = Vec3::new(-4.0, 0.0, 0.0);
+        let mut app = prepare_app(move |mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn((SamplerPool(TestPool), sample_effects![ItdNode::default()]));
+
+            commands.spawn((SpatialListener3D, Transform::default()));
+
+            commands.spawn((
+                TestPool,
+                Transform::from_translation(position),
+                SamplePlayer::new(server.load("sine_440hz_1ms.wav")).looping(),
+            ));
+        });
+
+        loop {
+            let complete = run(
+                &mut app,
+                move |player: Query<&Sampler>, effect: Query<&ItdNode, With<FollowerOf>>| {
+                    if player.iter().len() == 1 {
+                        assert_eq!(effect.single().unwrap().direction, position);
+                        true
+                    } else {
+                        false
+                    }
+                },
+            );
+
+            if complete {
+                break;
+            }
+
+            app.update();
+        }
+    }
+
+    #[test]
+    fn test_listener_relative() {
+        let offset = Vec3::new(-1.0, 0.0, 2.0);
+        let mut app = prepare_app(move |mut commands: Commands, server: Res<AssetServer>| {
+            commands.spawn((
+                SamplerPool(TestPool),
+                sample_effects![SpatialBasicNode::default()],
+            ));
+
+            commands.spawn((SpatialListener3D, Transform::from_xyz(10.0, 0.0, 0.0)));
+
+            commands.spawn((
+                TestPool,
+                Transform::from_xyz(-50.0, 0.0, 0.0),
+                ListenerRelative(offset),
+                SamplePlayer::new(server.load("sine_440hz_1ms.wav")).looping(),
+            ));
+        });
+
+        loop {
+            let complete = run(
+                &mut app,
+                move |player: Query<&Sampler>,
+                      effect: Query<&SpatialBasicNode, With<FollowerOf>>| {
+                    if player.iter().len() == 1 {
+                        let effect: Vec3 = effect.single().unwrap().offset.into();
+                        assert_eq!(effect, offset);
+                        true
+                    } else {
+                        false
+                    }
+                },
+            );
+
+            if complete {
+                break;
+            }
+
+            app.update();
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Near-field emphasis for emitters close to the listener.
+
+use bevy_ecs::prelude::*;
+use firewheel::{Volume, nodes::volume::VolumeNode};
+
+use super::{DefaultSpatialScale, EffectTransform, SpatialEmitters, SpatialScale};
+
+/// Boosts emitters that come within a radius of the listener.
+///
+/// When placed alongside a [`VolumeNode`], the node's volume is
+/// driven by the emitter's distance, smoothly rising from unity
+/// at [`radius`][Self::radius] to [`boost`][Self::boost] at the
+/// listener's position. This helps close sounds, like a creature
+/// breathing over the player's shoulder, feel appropriately intimate.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn spawn_whisper(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("whisper.wav")),
+///         Transform::from_xyz(0.5, 0.0, 0.0),
+///         sample_effects![
+///             SpatialBasicNode::default(),
+///             (VolumeNode::default(), NearField::default()),
+///         ],
+///     ));
+/// }
+/// ```
+///
+/// Distances are measured after applying [`SpatialScale`].
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct NearField {
+    /// The distance within which emitters are boosted.
+    ///
+    /// Defaults to `2.0`.
+    pub radius: f32,
+    /// The boost applied when the emitter is at the listener's position.
+    ///
+    /// Defaults to 6 dB.
+    pub boost: Volume,
+}
+
+impl Default for NearField {
+    fn default() -> Self {
+        Self {
+            radius: 2.0,
+            boost: Volume::Decibels(6.0),
+        }
+    }
+}
+
+impl NearField {
+    /// Calculate the volume for an emitter `distance` units away.
+    pub fn volume(&self, distance: f32) -> Volume {
+        if self.radius <= 0.0 {
+            return Volume::UNITY_GAIN;
+        }
+
+        let t = (1.0 - distance / self.radius).clamp(0.0, 1.0);
+        // smoothstep to avoid an abrupt onset at the radius
+        let t = t * t * (3.0 - 2.0 * t);
+
+        Volume::Decibels(self.boost.decibels() * t)
+    }
+}
+
+pub(super) fn update_near_field(
+    spatial_emitters: SpatialEmitters,
+    mut emitters: Query<(
+        Entity,
+        &mut VolumeNode,
+        &NearField,
+        Option<&SpatialScale>,
+        EffectTransform,
+    )>,
+    default_scale: Res<DefaultSpatialScale>,
+) {
+    for (entity, mut node, near_field, scale, transform) in emitters.iter_mut() {
+        if let Some(offset) = spatial_emitters.offset(entity, transform) {
+            let scale = scale.map(|s| s.0).unwrap_or(default_scale.0);
+            let volume = near_field.volume((offset * scale).length());
+
+            if node.volume != volume {
+                node.volume = volume;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_near_field_volume() {
+        let near_field = NearField::default();
+
+        assert_eq!(near_field.volume(0.0), Volume::Decibels(6.0));
+        assert_eq!(near_field.volume(1.0), Volume::Decibels(3.0));
+        assert_eq!(near_field.volume(2.0), Volume::Decibels(0.0));
+        assert_eq!(near_field.volume(10.0), Volume::Decibels(0.0));
+    }
+}
@@ -0,0 +1,129 @@
+//! Panning derived from screen positions.
+
+use bevy_ecs::prelude::*;
+use bevy_math::prelude::*;
+use bevy_ui::{ComputedNode, UiGlobalTransform};
+use bevy_window::{PrimaryWindow, Window};
+use firewheel::nodes::volume_pan::VolumePanNode;
+
+/// Pans a [`VolumePanNode`] according to a position on screen.
+///
+/// This lets UI sounds, like inventory clicks and notifications,
+/// subtly follow their source without full spatialization.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn click(trigger: On<Pointer<Click>>, mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("click.wav")),
+///         sample_effects![(
+///             VolumePanNode::default(),
+///             ScreenSpacePan::node(trigger.event_target()),
+///         )],
+///     ));
+/// }
+/// ```
+///
+/// Positions are measured against the primary window's width.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct ScreenSpacePan {
+    /// The position to pan toward.
+    pub source: ScreenSource,
+    /// The proportion of the full pan range to use.
+    ///
+    /// Defaults to `0.5`, keeping the effect subtle.
+    pub spread: f32,
+}
+
+/// A position on screen for [`ScreenSpacePan`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub enum ScreenSource {
+    /// A position in logical pixels, measured from the window's top-left corner.
+    Position(Vec2),
+    /// The center of a UI node.
+    Node(Entity),
+}
+
+impl ScreenSpacePan {
+    /// Pan toward a position in logical pixels.
+    pub fn position(position: Vec2) -> Self {
+        Self {
+            source: ScreenSource::Position(position),
+            spread: 0.5,
+        }
+    }
+
+    /// Pan toward the center of a UI node.
+    pub fn node(node: Entity) -> Self {
+        Self {
+            source: ScreenSource::Node(node),
+            spread: 0.5,
+        }
+    }
+
+    /// Set the [`spread`][Self::spread].
+    pub fn with_spread(self, spread: f32) -> Self {
+        Self { spread, ..self }
+    }
+
+    /// Calculate the pan for a horizontal position `x`
+    /// within a screen `width` units wide.
+    pub fn pan(&self, x: f32, width: f32) -> f32 {
+        if width <= 0.0 {
+            return 0.0;
+        }
+
+        ((x / width) * 2.0 - 1.0).clamp(-1.0, 1.0) * self.spread.clamp(0.0, 1.0)
+    }
+}
+
+pub(super) fn update_screen_pan(
+    mut emitters: Query<(&mut VolumePanNode, &ScreenSpacePan)>,
+    nodes: Query<&UiGlobalTransform, With<ComputedNode>>,
+    window: Option<Single<&Window, With<PrimaryWindow>>>,
+) {
+    let Some(window) = window else {
+        return;
+    };
+
+    for (mut node, screen_pan) in emitters.iter_mut() {
+        // UI transforms are in physical pixels.
+        let (x, width) = match screen_pan.source {
+            ScreenSource::Position(position) => (position.x, window.width()),
+            ScreenSource::Node(entity) => {
+                let Ok(transform) = nodes.get(entity) else {
+                    continue;
+                };
+
+                (transform.translation.x, window.physical_width() as f32)
+            }
+        };
+
+        let pan = screen_pan.pan(x, width);
+        if node.pan != pan {
+            node.pan = pan;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_screen_pan() {
+        let pan = ScreenSpacePan::position(Vec2::ZERO);
+
+        assert_eq!(pan.pan(0.0, 100.0), -0.5);
+        assert_eq!(pan.pan(50.0, 100.0), 0.0);
+        assert_eq!(pan.pan(100.0, 100.0), 0.5);
+        assert_eq!(pan.pan(200.0, 100.0), 0.5);
+        assert_eq!(pan.pan(50.0, 0.0), 0.0);
+
+        let full = pan.with_spread(1.0);
+        assert_eq!(full.pan(0.0, 100.0), -1.0);
+    }
+}
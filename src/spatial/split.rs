@@ -0,0 +1,251 @@
+//! Per-listener output routing for split-screen audio.
+
+use bevy_ecs::prelude::*;
+use firewheel::nodes::spatial_basic::SpatialBasicNode;
+
+use super::{ListenerTarget, SpatialListener2D, SpatialListener3D};
+use crate::{
+    edge::{Connect, EdgeTarget},
+    pool::{MoveToPool, SamplerPool, label::PoolLabel},
+    sample::SamplePlayer,
+    sample_effects,
+};
+
+/// Routes a spatial listener to its own output.
+///
+/// When inserted on a [`SpatialListener2D`] or [`SpatialListener3D`],
+/// a dedicated [`SamplerPool`] labeled [`ListenerPool`] is spawned
+/// for the listener, spatializing relative to it alone and routing
+/// to `target`. Samples marked [`PerListener`] are then played once
+/// in each listener's pool.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn split_screen(mut commands: Commands) {
+///     // Each player hears their own mix on a separate bus.
+///     let left = commands.spawn(VolumeNode::default()).id();
+///     let right = commands.spawn(VolumeNode::default()).id();
+///
+///     commands.spawn((SpatialListener3D, ListenerOutput::new(left)));
+///     commands.spawn((SpatialListener3D, ListenerOutput::new(right)));
+/// }
+/// ```
+///
+/// The listener's pool includes a single [`SpatialBasicNode`] effect.
+/// It's spawned once, so changing the output afterwards has no effect;
+/// instead, reconnect the pool found through [`ListenerBus`].
+#[derive(Component, Debug, Clone)]
+pub struct ListenerOutput(pub EdgeTarget);
+
+impl ListenerOutput {
+    /// Construct a new [`ListenerOutput`].
+    pub fn new(target: impl Into<EdgeTarget>) -> Self {
+        Self(target.into())
+    }
+}
+
+/// The pool label for a listener's dedicated [`SamplerPool`].
+///
+/// See [`ListenerOutput`] for more details.
+#[derive(PoolLabel, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct ListenerPool(pub Entity);
+
+/// A relationship pointing from a listener's [`SamplerPool`]
+/// to the listener.
+#[derive(Debug, Component)]
+#[relationship(relationship_target = ListenerBus)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct ListenerBusOf(pub Entity);
+
+/// The [`SamplerPool`] spawned for a listener with a [`ListenerOutput`].
+///
+/// The pool is despawned along with its listener.
+#[derive(Debug, Component)]
+#[relationship_target(relationship = ListenerBusOf, linked_spawn)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct ListenerBus(Entity);
+
+impl ListenerBus {
+    /// Returns the listener's pool entity.
+    pub fn pool(&self) -> Entity {
+        self.0
+    }
+}
+
+/// Plays a sample once for each listener with a [`ListenerOutput`].
+///
+/// The original [`SamplePlayer`] is moved into the first listener's pool,
+/// while a copy is spawned alongside it for each additional listener.
+/// Every copy is spatialized relative to its own listener with [`ListenerTarget`].
+///
+/// Copies are independent entities that share the original's parent and
+/// transform, so each one completes on its own. Despawning the original
+/// leaves its copies playing, while despawning a shared parent stops them all.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn explosion(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         SamplePlayer::new(server.load("explosion.wav")),
+///         Transform::from_xyz(10.0, 0.0, 0.0),
+///         PerListener,
+///     ));
+/// }
+/// ```
+///
+/// Copies are made when the sample is first spawned, so later changes to the
+/// original's [`PlaybackSettings`][crate::prelude::PlaybackSettings] or
+/// effects are not reflected in its copies. If no listeners have a
+/// [`ListenerOutput`], the sample plays normally.
+#[derive(Component, Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct PerListener;
+
+pub(super) fn spawn_listener_pools(
+    listeners: Query<
+        (Entity, &ListenerOutput),
+        (
+            Or<(With<SpatialListener2D>, With<SpatialListener3D>)>,
+            Without<ListenerBus>,
+        ),
+    >,
+    mut commands: Commands,
+) {
+    for (listener, output) in &listeners {
+        commands
+            .spawn((
+                SamplerPool(ListenerPool(listener)),
+                sample_effects![SpatialBasicNode::default()],
+                ListenerBusOf(listener),
+            ))
+            .connect(output.0.clone());
+    }
+}
+
+pub(super) fn split_per_listener(
+    samples: Query<Entity, (With<PerListener>, With<SamplePlayer>)>,
+    listeners: Query<Entity, With<ListenerOutput>>,
+    mut commands: Commands,
+) {
+    for sample in &samples {
+        commands.entity(sample).remove::<PerListener>();
+
+        let mut listeners = listeners.iter();
+        let Some(first) = listeners.next() else {
+            continue;
+        };
+
+        for listener in listeners {
+            commands.queue(move |world: &mut World| -> Result {
+                let copy = world
+                    .get_entity_mut(sample)?
+                    .clone_and_spawn_with_opt_out(|builder| {
+                        builder.linked_cloning(true).deny::<PerListener>();
+                    });
+
+                let mut copy = world.entity_mut(copy);
+                copy.insert(ListenerTarget(listener));
+                MoveToPool::new(ListenerPool(listener)).apply(copy);
+
+                Ok(())
+            });
+        }
+
+        commands
+            .entity(sample)
+            .insert(ListenerTarget(first))
+            .queue(MoveToPool::new(ListenerPool(first)));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        pool::Sampler,
+        prelude::*,
+        test::{prepare_app, run},
+    };
+    use bevy::prelude::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_per_listener() {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            let left = commands.spawn(VolumeNode::default()).id();
+            let right = commands.spawn(VolumeNode::default()).id();
+
+            commands.spawn((SpatialListener3D, ListenerOutput::new(left)));
+            commands.spawn((SpatialListener3D, ListenerOutput::new(right)));
+
+            commands.spawn((
+                SamplePlayer::new(server.load("caw.ogg")).looping(),
+                Transform::default(),
+                PerListener,
+            ));
+        });
+
+        let start = Instant::now();
+        loop {
+            let targets = run(
+                &mut app,
+                |players: Query<&ListenerTarget, (With<SamplePlayer>, With<Sampler>)>| {
+                    players.iter().map(|t| t.0).collect::<Vec<_>>()
+                },
+            );
+
+            if targets.len() == 2 {
+                assert_ne!(targets[0], targets[1]);
+                break;
+            }
+
+            if start.elapsed().as_secs() > 5 {
+                panic!("test exceeded timeout");
+            }
+
+            app.update();
+        }
+    }
+
+    #[test]
+    fn test_independent_copies() {
+        let mut app = prepare_app(|mut commands: Commands, server: Res<AssetServer>| {
+            let left = commands.spawn(VolumeNode::default()).id();
+            let right = commands.spawn(VolumeNode::default()).id();
+
+            commands.spawn((SpatialListener3D, ListenerOutput::new(left)));
+            commands.spawn((SpatialListener3D, ListenerOutput::new(right)));
+
+            commands.spawn((
+                SamplePlayer::new(server.load("caw.ogg")).looping(),
+                Transform::from_xyz(4.0, 0.0, 0.0),
+                PerListener,
+            ));
+        });
+
+        app.update();
+
+        let players = run(
+            &mut app,
+            |players: Query<(Entity, &Transform, Has<ChildOf>), With<SamplePlayer>>| {
+                players.iter().collect::<Vec<_>>()
+            },
+        );
+        assert_eq!(players.len(), 2);
+        for (_, transform, has_parent) in &players {
+            assert_eq!(transform.translation.x, 4.0);
+            assert!(!has_parent);
+        }
+
+        app.world_mut().despawn(players[0].0);
+        app.update();
+
+        let remaining = run(&mut app, |players: Query<(), With<SamplePlayer>>| {
+            players.iter().len()
+        });
+        assert_eq!(remaining, 1);
+    }
+}
@@ -26,21 +26,38 @@
 //! information; it simply reads from the [`AudioContext`] once at the
 //! beginning of each frame in the [`First`] schedule. If you need more
 //! up-to-date timings, consider fetching the time in each system with [`AudioContext::now`].
+//!
+//! ## Monotonicity
+//!
+//! [`AudioTime::now`] never moves backwards. Small regressions in the audio
+//! clock, which can occur due to timing jitter on some platforms, are absorbed
+//! by holding the previous instant. Forward jumps, such as those caused by
+//! frame hitches, simply advance the clock.
+//!
+//! When the audio stream restarts, the audio context's clock may start over
+//! from zero. `Time<Audio>` continues from where it left off, offsetting
+//! its instants from the context's clock, and triggers an [`AudioClockResync`]
+//! event. Scheduled events are translated to the context's clock when they're
+//! sent to the audio thread, so they're unaffected by this offset. If you read
+//! [`AudioContext::now`] directly, use [`Audio::to_context`] to relate the two.
 
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 use bevy_time::{Time, TimeSystems};
-use firewheel::clock::{DurationSeconds, InstantSeconds};
+use firewheel::clock::{DurationSeconds, InstantMusical, InstantSeconds};
 use std::time::Duration;
 
-use crate::context::AudioContext;
+use crate::context::{AudioContext, StreamRestartEvent, StreamStartEvent};
 
 pub(crate) struct TimePlugin;
 
 impl Plugin for TimePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Time<Audio>>()
-            .add_systems(First, update_time.in_set(TimeSystems));
+            .add_systems(First, update_time.in_set(TimeSystems))
+            .add_observer(update_play_latency::<StreamStartEvent>)
+            .add_observer(update_play_latency::<StreamRestartEvent>)
+            .add_observer(mark_restart);
     }
 }
 
@@ -52,6 +69,13 @@ impl Plugin for TimePlugin {
 #[derive(Debug, Default)]
 pub struct Audio {
     instant: InstantSeconds,
+    play_latency: DurationSeconds,
+    /// The most recent reading of the audio context's clock.
+    reading: InstantSeconds,
+    /// The offset from the audio context's clock, accumulated over stream restarts.
+    offset: DurationSeconds,
+    /// Whether the stream restarted since the last reading.
+    restarted: bool,
 }
 
 impl Audio {
@@ -59,19 +83,149 @@ impl Audio {
     pub fn instant(&self) -> InstantSeconds {
         self.instant
     }
+
+    /// Get the stream's measured play latency.
+    ///
+    /// See [`AudioTime::play_latency`].
+    pub fn play_latency(&self) -> DurationSeconds {
+        self.play_latency
+    }
+
+    /// Convert an instant on this clock to the audio context's clock.
+    ///
+    /// The two clocks are identical until the audio stream restarts.
+    /// See the [module docs][crate::time#monotonicity] for more details.
+    pub fn to_context(&self, instant: InstantSeconds) -> InstantSeconds {
+        InstantSeconds(instant.0 - self.offset.0)
+    }
+
+    /// Apply a new reading of the audio context's clock.
+    fn apply_reading(time: &mut Time<Self>, now: InstantSeconds) -> Option<AudioClockResync> {
+        let last = time.context().reading;
+        let step = ClockStep::new(last, now, time.context().restarted);
+
+        match step {
+            ClockStep::Advance(delta) => {
+                time.advance_by(Duration::from_secs_f64(delta));
+                let clock = time.context_mut();
+                clock.reading = now;
+                clock.instant = InstantSeconds(now.0 + clock.offset.0);
+
+                None
+            }
+            ClockStep::Hold => {
+                time.advance_by(Duration::ZERO);
+
+                None
+            }
+            ClockStep::Resync => {
+                time.advance_by(Duration::ZERO);
+                let clock = time.context_mut();
+                clock.restarted = false;
+                clock.reading = now;
+                clock.offset = DurationSeconds(clock.instant.0 - now.0);
+
+                Some(AudioClockResync {
+                    previous: last,
+                    current: now,
+                })
+            }
+        }
+    }
 }
 
-fn update_time(mut time: ResMut<Time<Audio>>, context: Option<ResMut<AudioContext>>) {
+/// An event triggered when [`Time<Audio>`] resynchronizes after a stream restart.
+///
+/// Restarting the audio stream may reset the audio context's clock.
+/// `Time<Audio>` continues from where it left off, but the offset
+/// between it and [`AudioContext::now`] changes. Events in flight on the
+/// audio thread when the stream restarted may need to be rescheduled.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn plugin(app: &mut App) {
+///     app.add_observer(|resync: On<AudioClockResync>| {
+///         warn!(
+///             "audio context clock jumped from {:?} to {:?}",
+///             resync.previous, resync.current
+///         );
+///     });
+/// }
+/// ```
+#[derive(Event, Debug, Clone)]
+pub struct AudioClockResync {
+    /// The audio context's clock before the restart.
+    pub previous: InstantSeconds,
+    /// The audio context's clock after the restart.
+    pub current: InstantSeconds,
+}
+
+/// How the clock should respond to a new reading.
+#[derive(Debug, PartialEq)]
+enum ClockStep {
+    /// The clock advanced by the given number of seconds.
+    Advance(f64),
+    /// The clock moved backwards, or produced an invalid
+    /// reading, so the previous instant is held.
+    Hold,
+    /// The stream restarted, so the clock is resynchronized.
+    Resync,
+}
+
+impl ClockStep {
+    fn new(last: InstantSeconds, now: InstantSeconds, restarted: bool) -> Self {
+        let delta = now.0 - last.0;
+
+        if !delta.is_finite() || now.0 < 0.0 {
+            Self::Hold
+        } else if restarted {
+            Self::Resync
+        } else if delta < 0.0 {
+            Self::Hold
+        } else {
+            Self::Advance(delta)
+        }
+    }
+}
+
+fn mark_restart(_: On<StreamRestartEvent>, mut time: ResMut<Time<Audio>>) {
+    time.context_mut().restarted = true;
+}
+
+fn update_time(
+    mut time: ResMut<Time<Audio>>,
+    context: Option<ResMut<AudioContext>>,
+    mut commands: Commands,
+) {
     let Some(mut context) = context else {
         return;
     };
 
-    let last = time.context().instant;
-    let now = context.now();
-    let delta = (now.seconds.0 - last.0).max(0.0);
-    let delta = Duration::from_secs_f64(delta);
-    time.advance_by(delta);
-    time.context_mut().instant = now.seconds;
+    // A dead audio thread is reported by the context plugin.
+    let Ok(now) = context.try_with(|context| context.audio_clock_corrected().seconds) else {
+        return;
+    };
+
+    if let Some(resync) = Audio::apply_reading(&mut time, now) {
+        commands.trigger(resync);
+    }
+}
+
+fn update_play_latency<E: Event>(
+    _: On<E>,
+    mut time: ResMut<Time<Audio>>,
+    mut context: ResMut<AudioContext>,
+) {
+    let latency = context.try_with(|context| {
+        context
+            .stream_info()
+            .map(|info| info.input_to_output_latency_seconds)
+    });
+
+    if let Ok(Some(latency)) = latency {
+        time.context_mut().play_latency = DurationSeconds(latency.max(0.0));
+    }
 }
 
 /// An extension trait for `Time<Audio>`.
@@ -117,6 +271,26 @@ pub trait AudioTime {
     /// ```
     fn delay(&self, duration: DurationSeconds) -> InstantSeconds;
 
+    /// The stream's measured latency between scheduling
+    /// a sound and hearing it.
+    ///
+    /// This is reported by the audio backend when the stream
+    /// starts or restarts. It can be significant on some devices,
+    /// like bluetooth headphones, where it may exceed 100ms.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_seedling::prelude::*;
+    /// fn report(time: Res<Time<Audio>>) {
+    ///     info!("play latency: {:.1}ms", time.play_latency().0 * 1000.0);
+    /// }
+    /// ```
+    ///
+    /// To compensate automatically, see [`AudioEvents::with_latency_compensation`].
+    ///
+    /// [`AudioEvents::with_latency_compensation`]: crate::prelude::AudioEvents::with_latency_compensation
+    fn play_latency(&self) -> DurationSeconds;
+
     /// A frame's audio render range.
     ///
     /// This describes the time elapsed since the last frame from
@@ -133,10 +307,231 @@ impl AudioTime for Time<Audio> {
         self.now() + duration
     }
 
+    fn play_latency(&self) -> DurationSeconds {
+        self.context().play_latency()
+    }
+
     fn render_range(&self) -> core::ops::Range<InstantSeconds> {
         let now = self.context().instant();
         let last = self.delta_secs_f64();
 
-        InstantSeconds(now.0 - last)..now
+        InstantSeconds((now.0 - last).max(0.0))..now
+    }
+}
+
+/// A tempo source for musical scheduling.
+///
+/// [`Tempo`] maps musical positions, expressed in bars and beats,
+/// to instants on the audio clock. It's not inserted by default;
+/// insert it once you know when your music starts.
+///
+/// Bars and beats are counted from zero, so bar `0`, beat `0.0`
+/// is [`Tempo::origin`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_seedling::prelude::*;
+/// fn start_music(time: Res<Time<Audio>>, mut commands: Commands) {
+///     commands.insert_resource(Tempo::new(120.0, 4).starting_at(time.now()));
+/// }
+///
+/// fn accent(
+///     main: Single<(&VolumeNode, &mut AudioEvents), With<MainBus>>,
+///     tempo: Res<Tempo>,
+/// ) {
+///     let (volume, mut events) = main.into_inner();
+///
+///     // Duck on the third beat of bar 8, recovering over two beats.
+///     events.schedule_at_beat(&tempo, 8, 2.0, volume, |volume| {
+///         volume.volume = Volume::Decibels(-6.0);
+///     });
+///     volume.fade_over_beats(Volume::UNITY_GAIN, &tempo, (8, 2.5), 2.0, &mut events);
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct Tempo {
+    /// The tempo in beats per minute.
+    pub bpm: f64,
+    /// The number of beats in a bar.
+    pub beats_per_bar: u32,
+    /// The instant of bar `0`, beat `0.0`.
+    pub origin: InstantSeconds,
+}
+
+impl Tempo {
+    /// Create a new [`Tempo`] starting at the beginning of the audio clock.
+    pub fn new(bpm: f64, beats_per_bar: u32) -> Self {
+        Self {
+            bpm,
+            beats_per_bar,
+            origin: InstantSeconds(0.0),
+        }
+    }
+
+    /// Set the instant of bar `0`, beat `0.0`.
+    pub fn starting_at(self, origin: InstantSeconds) -> Self {
+        Self { origin, ..self }
+    }
+
+    /// The duration of `beats` beats.
+    pub fn beats(&self, beats: f64) -> DurationSeconds {
+        DurationSeconds(beats * 60.0 / self.bpm)
+    }
+
+    /// The duration of `bars` bars.
+    pub fn bars(&self, bars: f64) -> DurationSeconds {
+        self.beats(bars * self.beats_per_bar as f64)
+    }
+
+    /// The instant of `beat` within `bar`.
+    pub fn at(&self, bar: u32, beat: f64) -> InstantSeconds {
+        self.instant(InstantMusical(
+            bar as f64 * self.beats_per_bar as f64 + beat,
+        ))
+    }
+
+    /// Convert a musical instant, measured in beats from the origin, to the audio clock.
+    pub fn instant(&self, musical: InstantMusical) -> InstantSeconds {
+        self.origin + self.beats(musical.0)
+    }
+
+    /// Convert an instant on the audio clock to beats from the origin.
+    pub fn musical(&self, instant: InstantSeconds) -> InstantMusical {
+        InstantMusical((instant.0 - self.origin.0) * self.bpm / 60.0)
+    }
+
+    /// The first beat at or after `instant`.
+    ///
+    /// This is useful for quantizing events to the beat.
+    pub fn next_beat(&self, instant: InstantSeconds) -> InstantSeconds {
+        self.instant(InstantMusical(self.musical(instant).0.ceil()))
+    }
+
+    /// The first bar at or after `instant`.
+    pub fn next_bar(&self, instant: InstantSeconds) -> InstantSeconds {
+        let bar = (self.musical(instant).0 / self.beats_per_bar as f64).ceil();
+        self.instant(InstantMusical(bar * self.beats_per_bar as f64))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tempo() {
+        let tempo = Tempo::new(120.0, 4).starting_at(InstantSeconds(10.0));
+
+        assert_eq!(tempo.beats(1.0), DurationSeconds(0.5));
+        assert_eq!(tempo.bars(1.0), DurationSeconds(2.0));
+        assert_eq!(tempo.at(0, 0.0), InstantSeconds(10.0));
+        assert_eq!(tempo.at(2, 1.5), InstantSeconds(14.75));
+        assert_eq!(tempo.musical(InstantSeconds(14.75)), InstantMusical(9.5));
+        assert_eq!(tempo.next_beat(InstantSeconds(10.2)), InstantSeconds(10.5));
+        assert_eq!(tempo.next_bar(InstantSeconds(10.2)), InstantSeconds(12.0));
+        assert_eq!(tempo.next_bar(InstantSeconds(12.0)), InstantSeconds(12.0));
+    }
+
+    #[test]
+    fn test_latency_compensation() {
+        use crate::prelude::{AudioEvents, Volume, VolumeNode};
+
+        let mut time = Time::<Audio>::default();
+        time.context_mut().instant = InstantSeconds(10.0);
+        time.context_mut().play_latency = DurationSeconds(0.25);
+
+        let mut events = AudioEvents::new(&time);
+        let volume = VolumeNode::default();
+
+        events.with_latency_compensation(|events| {
+            events.schedule(InstantSeconds(11.0), &volume, |v| {
+                v.volume = Volume::Decibels(-6.0);
+            });
+            // Compensated events can't move into the past.
+            events.schedule(InstantSeconds(10.1), &volume, |v| {
+                v.volume = Volume::Decibels(-3.0);
+            });
+        });
+        events.schedule(InstantSeconds(12.0), &volume, |v| {
+            v.volume = Volume::SILENT;
+        });
+
+        let starts: Vec<_> = events.describe().events.iter().map(|e| e.start.0).collect();
+        assert_eq!(starts, [10.0, 10.75, 12.0]);
+    }
+
+    #[test]
+    fn test_advance() {
+        let step = ClockStep::new(InstantSeconds(1.0), InstantSeconds(1.016), false);
+        assert_eq!(step, ClockStep::Advance(1.016 - 1.0));
+    }
+
+    #[test]
+    fn test_regression_holds() {
+        let step = ClockStep::new(InstantSeconds(1.0), InstantSeconds(0.99), false);
+        assert_eq!(step, ClockStep::Hold);
+
+        // Without a restart, even large regressions are held.
+        let step = ClockStep::new(InstantSeconds(10.0), InstantSeconds(0.1), false);
+        assert_eq!(step, ClockStep::Hold);
+    }
+
+    #[test]
+    fn test_invalid_readings_hold() {
+        let last = InstantSeconds(1.0);
+
+        assert_eq!(
+            ClockStep::new(last, InstantSeconds(f64::NAN), false),
+            ClockStep::Hold
+        );
+        assert_eq!(
+            ClockStep::new(last, InstantSeconds(f64::INFINITY), true),
+            ClockStep::Hold
+        );
+        assert_eq!(
+            ClockStep::new(last, InstantSeconds(-0.5), false),
+            ClockStep::Hold
+        );
+    }
+
+    #[test]
+    fn test_forward_jumps_advance() {
+        // A long frame hitch isn't a discontinuity.
+        let step = ClockStep::new(InstantSeconds(1.0), InstantSeconds(4.0), false);
+        assert_eq!(step, ClockStep::Advance(3.0));
+    }
+
+    #[test]
+    fn test_restart_resyncs() {
+        let step = ClockStep::new(InstantSeconds(10.0), InstantSeconds(0.1), true);
+        assert_eq!(step, ClockStep::Resync);
+    }
+
+    #[test]
+    fn test_monotonic() {
+        let mut time = Time::<Audio>::default();
+        let readings = [
+            (0.01, false),
+            (0.02, false),
+            (0.015, false),
+            (1.5, false),
+            (0.05, true),
+            (0.04, false),
+            (0.1, false),
+        ];
+
+        let mut previous = time.now();
+        for (reading, restarted) in readings {
+            time.context_mut().restarted |= restarted;
+            Audio::apply_reading(&mut time, InstantSeconds(reading));
+
+            assert!(time.now() >= previous);
+            assert!(time.render_range().start.0 >= 0.0);
+            previous = time.now();
+        }
+
+        // The clock continues from the restart, offset from the context.
+        assert!((time.now().0 - 1.55).abs() < 1e-9);
+        assert!((time.context().to_context(time.now()).0 - 0.1).abs() < 1e-9);
     }
 }